@@ -40,6 +40,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: PUT,
         rate_limited: false,
+        rate_limit_category: Transaction,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/app/v1/transactions/:txn_id",
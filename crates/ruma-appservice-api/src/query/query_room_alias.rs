@@ -15,6 +15,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
+        rate_limit_category: AppserviceQuery,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/app/v1/rooms/:room_alias",
@@ -16,6 +16,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
+        rate_limit_category: AppserviceQuery,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/app/v1/thirdparty/protocol/:protocol",
@@ -7,6 +7,8 @@ pub mod check_registration_token_validity;
 pub mod deactivate;
 pub mod delete_3pid;
 pub mod get_3pids;
+#[cfg(feature = "unstable-msc2000")]
+pub mod get_password_policy;
 pub mod get_username_availability;
 pub mod register;
 pub mod request_3pid_management_token_via_email;
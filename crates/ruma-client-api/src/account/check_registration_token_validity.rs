@@ -15,6 +15,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: true,
+        rate_limit_category: Registration,
         authentication: None,
         history: {
             unstable => "/_matrix/client/unstable/org.matrix.msc3231/register/org.matrix.msc3231.login.registration_token/validity",
@@ -27,6 +28,7 @@ pub mod v1 {
     pub struct Request {
         /// The registration token to check the validity of.
         #[ruma_api(query)]
+        #[serde(rename = "token")]
         pub registration_token: String,
     }
 
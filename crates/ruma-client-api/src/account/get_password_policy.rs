@@ -0,0 +1,225 @@
+//! `GET /_matrix/client/*/password_policy`
+//!
+//! Get the password policy of the homeserver.
+
+pub mod v1 {
+    //! `/v1/` ([MSC2000])
+    //!
+    //! [MSC2000]: https://github.com/matrix-org/matrix-spec-proposals/pull/2000
+
+    use js_int::UInt;
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+    use serde::{Deserialize, Serialize};
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/client/unstable/org.matrix.msc2000/password_policy",
+            // 1.? => "/_matrix/client/v1/password_policy",
+        }
+    };
+
+    /// Request type for the `get_password_policy` endpoint.
+    #[request(error = crate::Error)]
+    #[derive(Default)]
+    pub struct Request {}
+
+    /// Response type for the `get_password_policy` endpoint.
+    #[response(error = crate::Error)]
+    #[derive(Default)]
+    pub struct Response {
+        /// The password policy of the homeserver.
+        #[ruma_api(body)]
+        pub policy: PasswordPolicy,
+    }
+
+    impl Request {
+        /// Creates an empty `Request`.
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given password policy.
+        pub fn new(policy: PasswordPolicy) -> Self {
+            Self { policy }
+        }
+    }
+
+    /// The rules that a new password must follow.
+    ///
+    /// Rules that are not set are not enforced.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct PasswordPolicy {
+        /// The minimum length of the password, in characters.
+        #[serde(
+            rename = "m.minimum_length",
+            alias = "org.matrix.msc2000.minimum_length",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub minimum_length: Option<UInt>,
+
+        /// Whether the password must contain at least one digit.
+        #[serde(
+            rename = "m.require_digit",
+            alias = "org.matrix.msc2000.require_digit",
+            default,
+            skip_serializing_if = "ruma_common::serde::is_default"
+        )]
+        pub require_digit: bool,
+
+        /// Whether the password must contain at least one symbol.
+        #[serde(
+            rename = "m.require_symbol",
+            alias = "org.matrix.msc2000.require_symbol",
+            default,
+            skip_serializing_if = "ruma_common::serde::is_default"
+        )]
+        pub require_symbol: bool,
+
+        /// Whether the password must contain at least one lowercase letter.
+        #[serde(
+            rename = "m.require_lowercase",
+            alias = "org.matrix.msc2000.require_lowercase",
+            default,
+            skip_serializing_if = "ruma_common::serde::is_default"
+        )]
+        pub require_lowercase: bool,
+
+        /// Whether the password must contain at least one uppercase letter.
+        #[serde(
+            rename = "m.require_uppercase",
+            alias = "org.matrix.msc2000.require_uppercase",
+            default,
+            skip_serializing_if = "ruma_common::serde::is_default"
+        )]
+        pub require_uppercase: bool,
+    }
+
+    impl PasswordPolicy {
+        /// Creates an empty `PasswordPolicy`, that accepts any password.
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    /// A rule of a [`PasswordPolicy`] that a password doesn't follow.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub enum PasswordPolicyViolation {
+        /// The password is shorter than the minimum length.
+        TooShort,
+
+        /// The password doesn't contain a digit.
+        NoDigit,
+
+        /// The password doesn't contain a symbol.
+        NoSymbol,
+
+        /// The password doesn't contain a lowercase letter.
+        NoLowercase,
+
+        /// The password doesn't contain an uppercase letter.
+        NoUppercase,
+    }
+
+    /// Check that the given password follows the given policy.
+    ///
+    /// This allows clients to reject a password before sending it to the homeserver, which still
+    /// enforces the policy.
+    ///
+    /// Returns all the rules that the password doesn't follow.
+    pub fn validate_password(
+        policy: &PasswordPolicy,
+        candidate: &str,
+    ) -> Result<(), Vec<PasswordPolicyViolation>> {
+        let mut violations = Vec::new();
+
+        if let Some(minimum_length) = policy.minimum_length {
+            if UInt::try_from(candidate.chars().count()).map_or(false, |len| len < minimum_length) {
+                violations.push(PasswordPolicyViolation::TooShort);
+            }
+        }
+
+        if policy.require_digit && !candidate.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PasswordPolicyViolation::NoDigit);
+        }
+
+        if policy.require_symbol
+            && !candidate.chars().any(|c| !c.is_alphanumeric() && !c.is_whitespace())
+        {
+            violations.push(PasswordPolicyViolation::NoSymbol);
+        }
+
+        if policy.require_lowercase && !candidate.chars().any(char::is_lowercase) {
+            violations.push(PasswordPolicyViolation::NoLowercase);
+        }
+
+        if policy.require_uppercase && !candidate.chars().any(char::is_uppercase) {
+            violations.push(PasswordPolicyViolation::NoUppercase);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use js_int::uint;
+        use serde_json::{from_value as from_json_value, json};
+
+        use super::{validate_password, PasswordPolicy, PasswordPolicyViolation};
+
+        #[test]
+        fn deserialize_policy() {
+            let policy: PasswordPolicy = from_json_value(json!({
+                "m.minimum_length": 8,
+                "m.require_digit": true,
+                "org.matrix.msc2000.require_symbol": true,
+            }))
+            .unwrap();
+
+            assert_eq!(policy.minimum_length, Some(uint!(8)));
+            assert!(policy.require_digit);
+            assert!(policy.require_symbol);
+            assert!(!policy.require_lowercase);
+            assert!(!policy.require_uppercase);
+        }
+
+        #[test]
+        fn validate() {
+            let mut policy = PasswordPolicy::new();
+            validate_password(&policy, "").unwrap();
+
+            policy.minimum_length = Some(uint!(8));
+            policy.require_digit = true;
+            policy.require_symbol = true;
+            policy.require_lowercase = true;
+            policy.require_uppercase = true;
+
+            validate_password(&policy, "Pässw0rd!").unwrap();
+            assert_eq!(
+                validate_password(&policy, "pässwörd").unwrap_err(),
+                [
+                    PasswordPolicyViolation::NoDigit,
+                    PasswordPolicyViolation::NoSymbol,
+                    PasswordPolicyViolation::NoUppercase,
+                ]
+            );
+            assert_eq!(
+                validate_password(&policy, "PÄ55!").unwrap_err(),
+                [PasswordPolicyViolation::TooShort, PasswordPolicyViolation::NoLowercase]
+            );
+        }
+    }
+}
@@ -15,6 +15,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: true,
+        rate_limit_category: Registration,
         authentication: None,
         history: {
             1.0 => "/_matrix/client/r0/register/available",
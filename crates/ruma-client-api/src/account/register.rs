@@ -22,7 +22,8 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
-        authentication: None,
+        rate_limit_category: Registration,
+        authentication: AppserviceToken,
         history: {
             1.0 => "/_matrix/client/r0/register",
             1.1 => "/_matrix/client/v3/register",
@@ -30,7 +31,7 @@ pub mod v3 {
     };
 
     /// Request type for the `register` endpoint.
-    #[request(error = UiaaResponse)]
+    #[request(error = UiaaResponse, builder)]
     #[derive(Default)]
     pub struct Request {
         /// The desired password for the account.
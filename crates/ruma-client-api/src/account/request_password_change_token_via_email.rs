@@ -18,6 +18,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: false,
+        rate_limit_category: ThirdPartyIdValidation,
         authentication: None,
         history: {
             1.0 => "/_matrix/client/r0/account/password/email/requestToken",
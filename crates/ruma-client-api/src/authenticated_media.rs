@@ -0,0 +1,6 @@
+//! Endpoints for the media repository that require authentication ([MSC3916]).
+//!
+//! [MSC3916]: https://github.com/matrix-org/matrix-spec-proposals/pull/3916
+
+pub mod get_content;
+pub mod get_content_thumbnail;
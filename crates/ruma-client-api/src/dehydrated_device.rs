@@ -0,0 +1,153 @@
+//! Endpoints for managing dehydrated devices ([MSC3814]).
+//!
+//! A dehydrated device is a device that is kept on the server, encrypted, while the user has no
+//! other device online, so it can receive room keys sent to the user.
+//!
+//! [MSC3814]: https://github.com/matrix-org/matrix-spec-proposals/pull/3814
+
+use ruma_common::serde::{from_raw_json_value, StringEnum};
+use serde::{de, Deserialize, Serialize};
+use serde_json::value::RawValue as RawJsonValue;
+
+use crate::PrivOwnedStr;
+
+pub mod delete_dehydrated_device;
+pub mod get_dehydrated_device;
+pub mod get_events;
+pub mod put_dehydrated_device;
+
+/// The data of a dehydrated device.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum DehydratedDeviceData {
+    /// The `org.matrix.msc3814.v1.olm` format.
+    V1(DehydratedDeviceV1),
+}
+
+impl DehydratedDeviceData {
+    /// The algorithm used to encrypt the data of the device.
+    pub fn algorithm(&self) -> DeviceDehydrationAlgorithm {
+        match self {
+            Self::V1(_) => DeviceDehydrationAlgorithm::V1,
+        }
+    }
+}
+
+impl From<DehydratedDeviceV1> for DehydratedDeviceData {
+    fn from(data: DehydratedDeviceV1) -> Self {
+        Self::V1(data)
+    }
+}
+
+impl Serialize for DehydratedDeviceData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(Serialize)]
+        struct Helper<'a> {
+            algorithm: DeviceDehydrationAlgorithm,
+            device_pickle: &'a str,
+        }
+
+        match self {
+            Self::V1(data) => {
+                Helper { algorithm: self.algorithm(), device_pickle: &data.device_pickle }
+                    .serialize(serializer)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DehydratedDeviceData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let json = Box::<RawJsonValue>::deserialize(deserializer)?;
+
+        #[derive(Deserialize)]
+        struct ExtractAlgorithm {
+            algorithm: DeviceDehydrationAlgorithm,
+        }
+
+        let ExtractAlgorithm { algorithm } = from_raw_json_value(&json)?;
+
+        match algorithm {
+            DeviceDehydrationAlgorithm::V1 => from_raw_json_value(&json).map(Self::V1),
+            _ => Err(de::Error::custom(format!(
+                "unsupported device dehydration algorithm: `{algorithm}`"
+            ))),
+        }
+    }
+}
+
+/// The data of a dehydrated device in the `org.matrix.msc3814.v1.olm` format.
+#[derive(Clone, Debug, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DehydratedDeviceV1 {
+    /// The pickle of the `Olm` account of the device, encrypted by the client.
+    pub device_pickle: String,
+}
+
+impl DehydratedDeviceV1 {
+    /// Creates a new `DehydratedDeviceV1` with the given encrypted pickle.
+    pub fn new(device_pickle: String) -> Self {
+        Self { device_pickle }
+    }
+}
+
+/// The algorithms used to encrypt the data of a dehydrated device.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, Debug, PartialEq, Eq, StringEnum)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum DeviceDehydrationAlgorithm {
+    /// The `org.matrix.msc3814.v1.olm` device dehydration algorithm.
+    #[ruma_enum(rename = "org.matrix.msc3814.v1.olm")]
+    V1,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{DehydratedDeviceData, DehydratedDeviceV1};
+
+    #[test]
+    fn serialize_device_data() {
+        let data: DehydratedDeviceData = DehydratedDeviceV1::new("pickle".to_owned()).into();
+
+        assert_eq!(
+            to_json_value(data).unwrap(),
+            json!({
+                "algorithm": "org.matrix.msc3814.v1.olm",
+                "device_pickle": "pickle",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_device_data() {
+        let json = json!({
+            "algorithm": "org.matrix.msc3814.v1.olm",
+            "device_pickle": "pickle",
+        });
+
+        assert_matches!(
+            from_json_value::<DehydratedDeviceData>(json),
+            Ok(DehydratedDeviceData::V1(DehydratedDeviceV1 { device_pickle })) => {
+                assert_eq!(device_pickle, "pickle");
+            }
+        );
+
+        let json = json!({
+            "algorithm": "org.example.unknown",
+            "device_pickle": "pickle",
+        });
+        from_json_value::<DehydratedDeviceData>(json).unwrap_err();
+    }
+}
@@ -0,0 +1,57 @@
+//! `GET /_matrix/client/*/dehydrated_device`
+//!
+//! Get the dehydrated device of the user, if any.
+
+pub mod unstable {
+    //! `/unstable/` ([MSC3814])
+    //!
+    //! [MSC3814]: https://github.com/matrix-org/matrix-spec-proposals/pull/3814
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+        serde::Raw,
+        OwnedDeviceId,
+    };
+
+    use crate::dehydrated_device::DehydratedDeviceData;
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: AccessToken,
+        history: {
+            unstable => "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device",
+        }
+    };
+
+    /// Request type for the `get_dehydrated_device` endpoint.
+    #[request(error = crate::Error)]
+    #[derive(Default)]
+    pub struct Request {}
+
+    /// Response type for the `get_dehydrated_device` endpoint.
+    #[response(error = crate::Error)]
+    pub struct Response {
+        /// The unique ID of the device.
+        pub device_id: OwnedDeviceId,
+
+        /// The data of the dehydrated device, containing the serialized and encrypted private
+        /// parts of the device keys.
+        pub device_data: Raw<DehydratedDeviceData>,
+    }
+
+    impl Request {
+        /// Creates a new empty `Request`.
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given device ID and device data.
+        pub fn new(device_id: OwnedDeviceId, device_data: Raw<DehydratedDeviceData>) -> Self {
+            Self { device_id, device_data }
+        }
+    }
+}
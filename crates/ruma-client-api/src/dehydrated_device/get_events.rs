@@ -0,0 +1,68 @@
+//! `POST /_matrix/client/*/dehydrated_device/{deviceId}/events`
+//!
+//! Get the to-device events that were sent to a dehydrated device.
+
+pub mod unstable {
+    //! `/unstable/` ([MSC3814])
+    //!
+    //! [MSC3814]: https://github.com/matrix-org/matrix-spec-proposals/pull/3814
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        events::AnyToDeviceEvent,
+        metadata,
+        serde::Raw,
+        OwnedDeviceId,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: POST,
+        rate_limited: false,
+        authentication: AccessToken,
+        history: {
+            unstable => "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device/:device_id/events",
+        }
+    };
+
+    /// Request type for the `get_events` endpoint.
+    #[request(error = crate::Error)]
+    pub struct Request {
+        /// The unique ID of the dehydrated device.
+        #[ruma_api(path)]
+        pub device_id: OwnedDeviceId,
+
+        /// A point in time to continue getting events from.
+        ///
+        /// Should be the `next_batch` field from the previous response, or `None` for the first
+        /// request.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub next_batch: Option<String>,
+    }
+
+    /// Response type for the `get_events` endpoint.
+    #[response(error = crate::Error)]
+    pub struct Response {
+        /// The batch token to supply in the `next_batch` parameter of the next call.
+        ///
+        /// If this is `None`, there are no more events.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub next_batch: Option<String>,
+
+        /// The to-device events sent to the dehydrated device.
+        pub events: Vec<Raw<AnyToDeviceEvent>>,
+    }
+
+    impl Request {
+        /// Creates a new `Request` with the given device ID.
+        pub fn new(device_id: OwnedDeviceId) -> Self {
+            Self { device_id, next_batch: None }
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given events.
+        pub fn new(events: Vec<Raw<AnyToDeviceEvent>>) -> Self {
+            Self { next_batch: None, events }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! `PUT /_matrix/client/*/dehydrated_device`
+//!
+//! Uploads a dehydrated device to the homeserver.
+
+pub mod unstable {
+    //! `/unstable/` ([MSC3814])
+    //!
+    //! [MSC3814]: https://github.com/matrix-org/matrix-spec-proposals/pull/3814
+
+    use std::collections::BTreeMap;
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        encryption::{DeviceKeys, OneTimeKey},
+        metadata,
+        serde::Raw,
+        OwnedDeviceId, OwnedDeviceKeyId,
+    };
+
+    use crate::dehydrated_device::DehydratedDeviceData;
+
+    const METADATA: Metadata = metadata! {
+        method: PUT,
+        rate_limited: false,
+        authentication: AccessToken,
+        history: {
+            unstable => "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device",
+        }
+    };
+
+    /// Request type for the `put_dehydrated_device` endpoint.
+    #[request(error = crate::Error)]
+    pub struct Request {
+        /// The unique ID of the device.
+        pub device_id: OwnedDeviceId,
+
+        /// The display name of the device.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub initial_device_display_name: Option<String>,
+
+        /// The data of the dehydrated device, containing the serialized and encrypted private
+        /// parts of the device keys.
+        pub device_data: Raw<DehydratedDeviceData>,
+
+        /// Identity keys for the dehydrated device.
+        pub device_keys: Raw<DeviceKeys>,
+
+        /// One-time public keys for "pre-key" messages.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub one_time_keys: BTreeMap<OwnedDeviceKeyId, Raw<OneTimeKey>>,
+
+        /// Fallback public keys for "pre-key" messages.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub fallback_keys: BTreeMap<OwnedDeviceKeyId, Raw<OneTimeKey>>,
+    }
+
+    /// Response type for the `put_dehydrated_device` endpoint.
+    #[response(error = crate::Error)]
+    pub struct Response {
+        /// The unique ID of the device.
+        pub device_id: OwnedDeviceId,
+    }
+
+    impl Request {
+        /// Creates a new `Request` with the given device ID, device data and device keys.
+        pub fn new(
+            device_id: OwnedDeviceId,
+            device_data: Raw<DehydratedDeviceData>,
+            device_keys: Raw<DeviceKeys>,
+        ) -> Self {
+            Self {
+                device_id,
+                initial_device_display_name: None,
+                device_data,
+                device_keys,
+                one_time_keys: Default::default(),
+                fallback_keys: Default::default(),
+            }
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given device ID.
+        pub fn new(device_id: OwnedDeviceId) -> Self {
+            Self { device_id }
+        }
+    }
+}
@@ -4,6 +4,8 @@
 //!
 //! Get discovery information about the domain.
 
+#[cfg(feature = "unstable-msc2965")]
+use ruma_common::OwnedDeviceId;
 use ruma_common::{
     api::{request, response, Metadata},
     metadata,
@@ -144,4 +146,151 @@ impl AuthenticationServerInfo {
     pub fn new(issuer: String, account: Option<String>) -> Self {
         Self { issuer, account }
     }
+
+    /// Build the URL to deep-link into the account management capabilities of the OIDC Provider
+    /// to perform the given action.
+    ///
+    /// Returns `None` if `account` is not set.
+    pub fn account_management_url(&self, action: &AccountManagementAction) -> Option<String> {
+        let account = self.account.as_deref()?;
+        let query = action.to_query();
+
+        // The query must be placed before the fragment, if any.
+        let (base, fragment) = match account.split_once('#') {
+            Some((base, fragment)) => (base, Some(fragment)),
+            None => (account, None),
+        };
+        let separator = match base.split_once('?') {
+            Some((_, "")) => "",
+            Some(_) => "&",
+            None => "?",
+        };
+
+        let mut url = format!("{base}{separator}{query}");
+        if let Some(fragment) = fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+
+        Some(url)
+    }
+}
+
+/// An action to perform with the account management capabilities of the OIDC Provider.
+#[cfg(feature = "unstable-msc2965")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum AccountManagementAction {
+    /// View the profile of the user.
+    Profile,
+
+    /// List the sessions of the user.
+    SessionsList,
+
+    /// View the details of a session.
+    SessionView {
+        /// The device ID of the session.
+        device_id: OwnedDeviceId,
+    },
+
+    /// End a session.
+    SessionEnd {
+        /// The device ID of the session.
+        device_id: OwnedDeviceId,
+    },
+}
+
+#[cfg(feature = "unstable-msc2965")]
+impl AccountManagementAction {
+    /// Parse the action from the query string of an account management URL.
+    pub fn from_query(query: &str) -> Result<Self, serde_html_form::de::Error> {
+        use serde::de::Error;
+
+        let AccountManagementQuery { action, device_id } = serde_html_form::from_str(query)?;
+        let device_id = || device_id.ok_or_else(|| Error::missing_field("device_id"));
+
+        Ok(match action.as_str() {
+            "profile" => Self::Profile,
+            "sessions_list" => Self::SessionsList,
+            "session_view" => Self::SessionView { device_id: device_id()? },
+            "session_end" => Self::SessionEnd { device_id: device_id()? },
+            _ => {
+                return Err(Error::unknown_variant(
+                    &action,
+                    &["profile", "sessions_list", "session_view", "session_end"],
+                ))
+            }
+        })
+    }
+
+    /// Serialize the action as the query string of an account management URL.
+    pub fn to_query(&self) -> String {
+        let (action, device_id) = match self {
+            Self::Profile => ("profile", None),
+            Self::SessionsList => ("sessions_list", None),
+            Self::SessionView { device_id } => ("session_view", Some(device_id.clone())),
+            Self::SessionEnd { device_id } => ("session_end", Some(device_id.clone())),
+        };
+
+        serde_html_form::to_string(AccountManagementQuery { action: action.to_owned(), device_id })
+            .expect("serializing a query with string values should not fail")
+    }
+}
+
+/// The query parameters of an account management URL.
+#[cfg(feature = "unstable-msc2965")]
+#[derive(Deserialize, Serialize)]
+struct AccountManagementQuery {
+    action: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_id: Option<OwnedDeviceId>,
+}
+
+#[cfg(all(test, feature = "unstable-msc2965"))]
+mod tests {
+    use assert_matches::assert_matches;
+    use ruma_common::device_id;
+
+    use super::{AccountManagementAction, AuthenticationServerInfo};
+
+    #[test]
+    fn account_management_url() {
+        let action =
+            AccountManagementAction::SessionEnd { device_id: device_id!("ABCD").to_owned() };
+
+        let info = AuthenticationServerInfo::new("https://auth.localhost/".to_owned(), None);
+        assert_matches!(info.account_management_url(&action), None);
+
+        let info = AuthenticationServerInfo::new(
+            "https://auth.localhost/".to_owned(),
+            Some("https://auth.localhost/account".to_owned()),
+        );
+        assert_eq!(
+            info.account_management_url(&action).unwrap(),
+            "https://auth.localhost/account?action=session_end&device_id=ABCD"
+        );
+
+        let info = AuthenticationServerInfo::new(
+            "https://auth.localhost/".to_owned(),
+            Some("https://auth.localhost/account?lang=en#/main".to_owned()),
+        );
+        assert_eq!(
+            info.account_management_url(&AccountManagementAction::Profile).unwrap(),
+            "https://auth.localhost/account?lang=en&action=profile#/main"
+        );
+    }
+
+    #[test]
+    fn action_from_query() {
+        assert_eq!(
+            AccountManagementAction::from_query("action=sessions_list").unwrap(),
+            AccountManagementAction::SessionsList
+        );
+        assert_eq!(
+            AccountManagementAction::from_query("action=session_view&device_id=ABCD").unwrap(),
+            AccountManagementAction::SessionView { device_id: device_id!("ABCD").to_owned() }
+        );
+        AccountManagementAction::from_query("action=session_end").unwrap_err();
+    }
 }
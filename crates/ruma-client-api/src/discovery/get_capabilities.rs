@@ -186,6 +186,43 @@ impl RoomVersionsCapability {
                 .map(|stability| *stability == RoomVersionStability::Stable)
                 .unwrap_or(false)
     }
+
+    /// How the given room version is supported by the server and by Ruma.
+    ///
+    /// Room versions are known by Ruma if [`RoomVersionId::rules()`] returns their rules, which
+    /// includes custom room versions whose rules were registered.
+    ///
+    /// Returns `None` if the server doesn't support the room version.
+    pub fn support(&self, version: &RoomVersionId) -> Option<RoomVersionSupport> {
+        let stability = self.available.get(version)?;
+
+        if version.rules().is_none() {
+            return Some(RoomVersionSupport::Unknown);
+        }
+
+        Some(match stability {
+            RoomVersionStability::Stable => RoomVersionSupport::Stable,
+            RoomVersionStability::Unstable => RoomVersionSupport::Unstable,
+            _ => RoomVersionSupport::Unknown,
+        })
+    }
+
+    /// Classify all the room versions supported by the server.
+    pub fn classify(&self) -> BTreeMap<RoomVersionId, RoomVersionSupport> {
+        self.available
+            .keys()
+            .filter_map(|version| Some((version.clone(), self.support(version)?)))
+            .collect()
+    }
+
+    /// The room versions that are supported as stable by the server and known by Ruma.
+    ///
+    /// These are the room versions that should be offered when creating or upgrading a room.
+    pub fn stable_versions(&self) -> impl Iterator<Item = &RoomVersionId> {
+        self.available
+            .keys()
+            .filter(|version| self.support(version) == Some(RoomVersionSupport::Stable))
+    }
 }
 
 impl Default for RoomVersionsCapability {
@@ -213,6 +250,20 @@ pub enum RoomVersionStability {
     _Custom(PrivOwnedStr),
 }
 
+/// How a room version is supported by the server and by Ruma.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RoomVersionSupport {
+    /// The server supports the room version as stable, and it is known by Ruma.
+    Stable,
+
+    /// The server supports the room version as unstable, and it is known by Ruma.
+    Unstable,
+
+    /// The room version is not known by Ruma, or the server uses an unknown stability for it.
+    Unknown,
+}
+
 /// Information about the `m.set_displayname` capability
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -297,9 +348,11 @@ mod tests {
     use std::borrow::Cow;
 
     use assert_matches::assert_matches;
+    use maplit::btreemap;
+    use ruma_common::{room_version_rules::RoomVersionRules, RoomVersionId};
     use serde_json::json;
 
-    use super::Capabilities;
+    use super::{Capabilities, RoomVersionStability, RoomVersionSupport, RoomVersionsCapability};
 
     #[test]
     fn capabilities_iter() -> serde_json::Result<()> {
@@ -340,4 +393,47 @@ mod tests {
         assert_matches!(caps_iter.next(), None);
         Ok(())
     }
+
+    #[test]
+    fn room_versions_support() {
+        let custom = RoomVersionId::try_from("org.example.custom").unwrap();
+        let room_versions = RoomVersionsCapability::new(
+            RoomVersionId::V9,
+            btreemap! {
+                RoomVersionId::V1 => RoomVersionStability::Stable,
+                RoomVersionId::V9 => RoomVersionStability::Stable,
+                RoomVersionId::V10 => RoomVersionStability::Unstable,
+                custom.clone() => RoomVersionStability::Stable,
+                RoomVersionId::V2 => RoomVersionStability::from("beta"),
+            },
+        );
+
+        assert_eq!(room_versions.support(&RoomVersionId::V9), Some(RoomVersionSupport::Stable));
+        assert_eq!(room_versions.support(&RoomVersionId::V10), Some(RoomVersionSupport::Unstable));
+        assert_eq!(room_versions.support(&RoomVersionId::V2), Some(RoomVersionSupport::Unknown));
+        assert_eq!(room_versions.support(&RoomVersionId::V3), None);
+        assert_eq!(room_versions.support(&custom), Some(RoomVersionSupport::Unknown));
+
+        assert_eq!(room_versions.classify().len(), 5);
+        assert_eq!(
+            room_versions.stable_versions().collect::<Vec<_>>(),
+            [&RoomVersionId::V1, &RoomVersionId::V9]
+        );
+    }
+
+    #[test]
+    fn registered_room_version_support() {
+        let registered = RoomVersionId::try_from("org.example.registered").unwrap();
+        let room_versions = RoomVersionsCapability::new(
+            RoomVersionId::V9,
+            btreemap! {
+                RoomVersionId::V9 => RoomVersionStability::Stable,
+                registered.clone() => RoomVersionStability::Unstable,
+            },
+        );
+        assert_eq!(room_versions.support(&registered), Some(RoomVersionSupport::Unknown));
+
+        RoomVersionRules::register_custom(registered.clone(), RoomVersionRules::V10).unwrap();
+        assert_eq!(room_versions.support(&registered), Some(RoomVersionSupport::Unstable));
+    }
 }
@@ -243,6 +243,8 @@ pub enum ErrorBody {
     Json(JsonValue),
 
     /// A response body that is not valid JSON.
+    ///
+    /// This is for example the case of the HTML error pages returned by some reverse proxies.
     #[non_exhaustive]
     NotJson {
         /// The raw bytes of the response body.
@@ -468,9 +470,12 @@ impl TryFrom<&AuthenticateError> for http::HeaderValue {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+    use ruma_common::api::{error::FromHttpResponseError, IncomingResponse};
     use serde_json::{from_value as from_json_value, json};
 
-    use super::{ErrorKind, StandardErrorBody};
+    use super::{Error, ErrorBody, ErrorKind, StandardErrorBody};
+    use crate::discovery::get_supported_versions;
 
     #[test]
     fn deserialize_forbidden() {
@@ -484,6 +489,28 @@ mod tests {
         assert_eq!(deserialized.message, "You are not authorized to ban users in this room.");
     }
 
+    #[test]
+    fn deserialize_html_error() {
+        let body = "<html><body><h1>502 Bad Gateway</h1></body></html>";
+        let response = http::Response::builder()
+            .status(http::StatusCode::BAD_GATEWAY)
+            .header(http::header::CONTENT_TYPE, "text/html")
+            .body(body)
+            .unwrap();
+
+        let error = get_supported_versions::Response::try_from_http_response(response).unwrap_err();
+        assert_matches!(
+            error,
+            FromHttpResponseError::Server(Error {
+                status_code: http::StatusCode::BAD_GATEWAY,
+                body: ErrorBody::NotJson { bytes, .. },
+                ..
+            }) => {
+                assert_eq!(bytes, body.as_bytes());
+            }
+        );
+    }
+
     #[cfg(feature = "unstable-msc2967")]
     #[test]
     fn custom_authenticate_error_sanity() {
@@ -0,0 +1,63 @@
+//! Example JSON for the endpoints of the client-server API, taken from the examples in the Matrix
+//! specification.
+//!
+//! There is one module per API module, with one fixture per endpoint. Every endpoint is covered,
+//! except the ones whose bodies are not JSON:
+//!
+//! * the endpoints that upload or download the raw bytes of a file, in [`media`](crate::media) and
+//!   `authenticated_media`,
+//! * the HTML fallback pages, [`session::login_fallback`](crate::session::login_fallback) and
+//!   [`uiaa::get_uiaa_fallback_page`](crate::uiaa::get_uiaa_fallback_page),
+//! * the SSO redirects, [`session::sso_login`](crate::session::sso_login) and
+//!   [`session::sso_login_with_provider`](crate::session::sso_login_with_provider).
+//!
+//! Use them with [`ruma_common::assert_round_trip!`] to check that the (de)serialization of the
+//! endpoints doesn't regress:
+//!
+//! ```
+//! use ruma_client_api::{fixtures, membership};
+//!
+//! ruma_common::assert_round_trip!(
+//!     membership::joined_rooms::v3,
+//!     fixtures::membership::JOINED_ROOMS
+//! );
+//! ```
+
+pub mod account;
+pub mod alias;
+pub mod appservice;
+pub mod backup;
+pub mod config;
+pub mod context;
+#[cfg(feature = "unstable-msc3814")]
+pub mod dehydrated_device;
+pub mod device;
+pub mod directory;
+pub mod discovery;
+pub mod filter;
+pub mod keys;
+pub mod knock;
+pub mod media;
+pub mod membership;
+pub mod message;
+pub mod presence;
+pub mod profile;
+pub mod push;
+pub mod read_marker;
+pub mod receipt;
+pub mod redact;
+pub mod relations;
+pub mod room;
+pub mod search;
+pub mod server;
+pub mod session;
+pub mod space;
+pub mod state;
+pub mod sync;
+pub mod tag;
+pub mod thirdparty;
+pub mod threads;
+pub mod to_device;
+pub mod typing;
+pub mod user_directory;
+pub mod voip;
@@ -0,0 +1,285 @@
+//! Example JSON for the endpoints of [`account`](crate::account).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`account::add_3pid::v3`](crate::account::add_3pid::v3)
+pub const ADD_3PID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/add",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "01234567890ABCDEF",
+                "type": "example.type.foo"
+            },
+            "client_secret": "d0nt-T3ll",
+            "sid": "abc123987"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`account::bind_3pid::v3`](crate::account::bind_3pid::v3)
+pub const BIND_3PID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/bind",
+    request_body: Some(
+        r#"{
+            "client_secret": "d0nt-T3ll",
+            "id_access_token": "abc123_OpaqueString",
+            "id_server": "example.org",
+            "sid": "abc123987"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`account::change_password::v3`](crate::account::change_password::v3)
+pub const CHANGE_PASSWORD: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/password",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "01234567890ABCDEF",
+                "type": "example.type.foo"
+            },
+            "logout_devices": false,
+            "new_password": "ihatebananas"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`account::check_registration_token_validity::v1`](crate::account::check_registration_token_validity::v1)
+pub const CHECK_REGISTRATION_TOKEN_VALIDITY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/register/m.login.registration_token/validity?token=fBVFdqVE",
+    request_body: None,
+    response_body: r#"{ "valid": true }"#,
+};
+
+/// [`account::deactivate::v3`](crate::account::deactivate::v3)
+pub const DEACTIVATE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/deactivate",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "01234567890ABCDEF",
+                "type": "example.type.foo"
+            },
+            "id_server": "example.org"
+        }"#,
+    ),
+    response_body: r#"{ "id_server_unbind_result": "success" }"#,
+};
+
+/// [`account::delete_3pid::v3`](crate::account::delete_3pid::v3)
+pub const DELETE_3PID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/delete",
+    request_body: Some(
+        r#"{
+            "address": "example@example.org",
+            "id_server": "example.org",
+            "medium": "email"
+        }"#,
+    ),
+    response_body: r#"{ "id_server_unbind_result": "success" }"#,
+};
+
+/// [`account::get_3pids::v3`](crate::account::get_3pids::v3)
+pub const GET_3PIDS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid",
+    request_body: None,
+    response_body: r#"{
+        "threepids": [
+            {
+                "added_at": 1535336848756,
+                "address": "monkey@banana.island",
+                "medium": "email",
+                "validated_at": 1535176800000
+            }
+        ]
+    }"#,
+};
+
+/// [`account::get_password_policy::v1`](crate::account::get_password_policy::v1)
+#[cfg(feature = "unstable-msc2000")]
+pub const GET_PASSWORD_POLICY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc2000/password_policy",
+    request_body: None,
+    response_body: r#"{
+        "m.minimum_length": 8,
+        "m.require_digit": true,
+        "m.require_symbol": true
+    }"#,
+};
+
+/// [`account::get_username_availability::v3`](crate::account::get_username_availability::v3)
+pub const GET_USERNAME_AVAILABILITY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/register/available?username=my_cool_localpart",
+    request_body: None,
+    response_body: r#"{ "available": true }"#,
+};
+
+/// [`account::register::v3`](crate::account::register::v3)
+pub const REGISTER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/register",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "xxxxx",
+                "type": "example.type.foo"
+            },
+            "device_id": "GHTYAJCE",
+            "initial_device_display_name": "Jungle Phone",
+            "password": "ilovebananas",
+            "username": "cheeky_monkey"
+        }"#,
+    ),
+    response_body: r#"{
+        "access_token": "abc123",
+        "device_id": "GHTYAJCE",
+        "user_id": "@cheeky_monkey:matrix.org"
+    }"#,
+};
+
+/// [`account::request_3pid_management_token_via_email::v3`](crate::account::request_3pid_management_token_via_email::v3)
+pub const REQUEST_3PID_MANAGEMENT_TOKEN_VIA_EMAIL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/email/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "email": "alice@example.org",
+            "next_link": "https://example.org/congratulations.html",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::request_3pid_management_token_via_msisdn::v3`](crate::account::request_3pid_management_token_via_msisdn::v3)
+pub const REQUEST_3PID_MANAGEMENT_TOKEN_VIA_MSISDN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/msisdn/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "country": "GB",
+            "next_link": "https://example.org/congratulations.html",
+            "phone_number": "07700900001",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::request_openid_token::v3`](crate::account::request_openid_token::v3)
+pub const REQUEST_OPENID_TOKEN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user/%40alice%3Aexample.com/openid/request_token",
+    request_body: None,
+    response_body: r#"{
+        "access_token": "SomeT0kenHere",
+        "expires_in": 3600,
+        "matrix_server_name": "example.com",
+        "token_type": "Bearer"
+    }"#,
+};
+
+/// [`account::request_password_change_token_via_email::v3`](crate::account::request_password_change_token_via_email::v3)
+pub const REQUEST_PASSWORD_CHANGE_TOKEN_VIA_EMAIL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/password/email/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "email": "alice@example.org",
+            "next_link": "https://example.org/congratulations.html",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::request_password_change_token_via_msisdn::v3`](crate::account::request_password_change_token_via_msisdn::v3)
+pub const REQUEST_PASSWORD_CHANGE_TOKEN_VIA_MSISDN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/password/msisdn/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "country": "GB",
+            "next_link": "https://example.org/congratulations.html",
+            "phone_number": "07700900001",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::request_registration_token_via_email::v3`](crate::account::request_registration_token_via_email::v3)
+pub const REQUEST_REGISTRATION_TOKEN_VIA_EMAIL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/register/email/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "email": "alice@example.org",
+            "next_link": "https://example.org/congratulations.html",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::request_registration_token_via_msisdn::v3`](crate::account::request_registration_token_via_msisdn::v3)
+pub const REQUEST_REGISTRATION_TOKEN_VIA_MSISDN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/register/msisdn/requestToken",
+    request_body: Some(
+        r#"{
+            "client_secret": "monkeys_are_GREAT",
+            "country": "GB",
+            "next_link": "https://example.org/congratulations.html",
+            "phone_number": "07700900001",
+            "send_attempt": 1
+        }"#,
+    ),
+    response_body: r#"{
+        "sid": "123abc",
+        "submit_url": "https://example.org/path/to/submitToken"
+    }"#,
+};
+
+/// [`account::unbind_3pid::v3`](crate::account::unbind_3pid::v3)
+pub const UNBIND_3PID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/3pid/unbind",
+    request_body: Some(
+        r#"{
+            "address": "example@example.org",
+            "id_server": "example.org",
+            "medium": "email"
+        }"#,
+    ),
+    response_body: r#"{ "id_server_unbind_result": "success" }"#,
+};
+
+/// [`account::whoami::v3`](crate::account::whoami::v3)
+pub const WHOAMI: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/account/whoami",
+    request_body: None,
+    response_body: r#"{
+        "device_id": "ABC1234",
+        "user_id": "@joe:example.org"
+    }"#,
+};
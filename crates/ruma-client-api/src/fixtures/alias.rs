@@ -0,0 +1,27 @@
+//! Example JSON for the endpoints of [`alias`](crate::alias).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`alias::create_alias::v3`](crate::alias::create_alias::v3)
+pub const CREATE_ALIAS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/room/%23monkeys%3Aexample.org",
+    request_body: Some(r#"{ "room_id": "!abnjk1jdasj98:capuchin.example.org" }"#),
+    response_body: "{}",
+};
+
+/// [`alias::delete_alias::v3`](crate::alias::delete_alias::v3)
+pub const DELETE_ALIAS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/room/%23monkeys%3Aexample.org",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`alias::get_alias::v3`](crate::alias::get_alias::v3)
+pub const GET_ALIAS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/room/%23monkeys%3Aexample.org",
+    request_body: None,
+    response_body: r#"{
+        "room_id": "!abnjk1jdasj98:capuchin.example.org",
+        "servers": ["capuchin.example.org", "ferrets.example.org"]
+    }"#,
+};
@@ -0,0 +1,10 @@
+//! Example JSON for the endpoints of [`appservice`](crate::appservice).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`appservice::set_room_visibility::v3`](crate::appservice::set_room_visibility::v3)
+pub const SET_ROOM_VISIBILITY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/list/appservice/irc/%21somewhere%3Aexample.org",
+    request_body: Some(r#"{ "visibility": "public" }"#),
+    response_body: "{}",
+};
@@ -0,0 +1,253 @@
+//! Example JSON for the endpoints of [`backup`](crate::backup).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`backup::add_backup_keys::v3`](crate::backup::add_backup_keys::v3)
+pub const ADD_BACKUP_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys?version=1",
+    request_body: Some(
+        r#"{
+            "rooms": {
+                "!room:example.org": {
+                    "sessions": {
+                        "sessionid1": {
+                            "first_message_index": 1,
+                            "forwarded_count": 0,
+                            "is_verified": true,
+                            "session_data": {
+                                "ciphertext": "base64+ciphertext+of+JSON+data",
+                                "ephemeral": "base64+ephemeral+key",
+                                "mac": "base64+mac+of+ciphertext"
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::add_backup_keys_for_room::v3`](crate::backup::add_backup_keys_for_room::v3)
+pub const ADD_BACKUP_KEYS_FOR_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org?version=1",
+    request_body: Some(
+        r#"{
+            "sessions": {
+                "sessionid1": {
+                    "first_message_index": 1,
+                    "forwarded_count": 0,
+                    "is_verified": true,
+                    "session_data": {
+                        "ciphertext": "base64+ciphertext+of+JSON+data",
+                        "ephemeral": "base64+ephemeral+key",
+                        "mac": "base64+mac+of+ciphertext"
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::add_backup_keys_for_session::v3`](crate::backup::add_backup_keys_for_session::v3)
+pub const ADD_BACKUP_KEYS_FOR_SESSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org/sessionid1?version=1",
+    request_body: Some(
+        r#"{
+            "first_message_index": 1,
+            "forwarded_count": 0,
+            "is_verified": true,
+            "session_data": {
+                "ciphertext": "base64+ciphertext+of+JSON+data",
+                "ephemeral": "base64+ephemeral+key",
+                "mac": "base64+mac+of+ciphertext"
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::create_backup_version::v3`](crate::backup::create_backup_version::v3)
+pub const CREATE_BACKUP_VERSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/version",
+    request_body: Some(
+        r#"{
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": {
+                "public_key": "abcdefg",
+                "signatures": {
+                    "@alice:example.org": {
+                        "ed25519:deviceid": "signature"
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{ "version": "1" }"#,
+};
+
+/// [`backup::delete_backup_keys::v3`](crate::backup::delete_backup_keys::v3)
+pub const DELETE_BACKUP_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys?version=1",
+    request_body: None,
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::delete_backup_keys_for_room::v3`](crate::backup::delete_backup_keys_for_room::v3)
+pub const DELETE_BACKUP_KEYS_FOR_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org?version=1",
+    request_body: None,
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::delete_backup_keys_for_session::v3`](crate::backup::delete_backup_keys_for_session::v3)
+pub const DELETE_BACKUP_KEYS_FOR_SESSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org/sessionid1?version=1",
+    request_body: None,
+    response_body: r#"{
+        "count": 10,
+        "etag": "abcdefg"
+    }"#,
+};
+
+/// [`backup::delete_backup_version::v3`](crate::backup::delete_backup_version::v3)
+pub const DELETE_BACKUP_VERSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/version/1",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`backup::get_backup_info::v3`](crate::backup::get_backup_info::v3)
+pub const GET_BACKUP_INFO: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/version/1",
+    request_body: None,
+    response_body: r#"{
+        "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+        "auth_data": {
+            "public_key": "abcdefg",
+            "signatures": {
+                "@alice:example.org": {
+                    "ed25519:deviceid": "signature"
+                }
+            }
+        },
+        "count": 42,
+        "etag": "anopaquestring",
+        "version": "1"
+    }"#,
+};
+
+/// [`backup::get_backup_keys::v3`](crate::backup::get_backup_keys::v3)
+pub const GET_BACKUP_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys?version=1",
+    request_body: None,
+    response_body: r#"{
+        "rooms": {
+            "!room:example.org": {
+                "sessions": {
+                    "sessionid1": {
+                        "first_message_index": 1,
+                        "forwarded_count": 0,
+                        "is_verified": true,
+                        "session_data": {
+                            "ciphertext": "base64+ciphertext+of+JSON+data",
+                            "ephemeral": "base64+ephemeral+key",
+                            "mac": "base64+mac+of+ciphertext"
+                        }
+                    }
+                }
+            }
+        }
+    }"#,
+};
+
+/// [`backup::get_backup_keys_for_room::v3`](crate::backup::get_backup_keys_for_room::v3)
+pub const GET_BACKUP_KEYS_FOR_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org?version=1",
+    request_body: None,
+    response_body: r#"{
+        "sessions": {
+            "sessionid1": {
+                "first_message_index": 1,
+                "forwarded_count": 0,
+                "is_verified": true,
+                "session_data": {
+                    "ciphertext": "base64+ciphertext+of+JSON+data",
+                    "ephemeral": "base64+ephemeral+key",
+                    "mac": "base64+mac+of+ciphertext"
+                }
+            }
+        }
+    }"#,
+};
+
+/// [`backup::get_backup_keys_for_session::v3`](crate::backup::get_backup_keys_for_session::v3)
+pub const GET_BACKUP_KEYS_FOR_SESSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/keys/%21room%3Aexample.org/sessionid1?version=1",
+    request_body: None,
+    response_body: r#"{
+        "first_message_index": 1,
+        "forwarded_count": 0,
+        "is_verified": true,
+        "session_data": {
+            "ciphertext": "base64+ciphertext+of+JSON+data",
+            "ephemeral": "base64+ephemeral+key",
+            "mac": "base64+mac+of+ciphertext"
+        }
+    }"#,
+};
+
+/// [`backup::get_latest_backup_info::v3`](crate::backup::get_latest_backup_info::v3)
+pub const GET_LATEST_BACKUP_INFO: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/version",
+    request_body: None,
+    response_body: r#"{
+        "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+        "auth_data": {
+            "public_key": "abcdefg",
+            "signatures": {
+                "@alice:example.org": {
+                    "ed25519:deviceid": "signature"
+                }
+            }
+        },
+        "count": 42,
+        "etag": "anopaquestring",
+        "version": "1"
+    }"#,
+};
+
+/// [`backup::update_backup_version::v3`](crate::backup::update_backup_version::v3)
+pub const UPDATE_BACKUP_VERSION: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/room_keys/version/1",
+    request_body: Some(
+        r#"{
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": {
+                "public_key": "abcdefg",
+                "signatures": {
+                    "@alice:example.org": {
+                        "ed25519:deviceid": "signature"
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: "{}",
+};
@@ -0,0 +1,33 @@
+//! Example JSON for the endpoints of [`config`](crate::config).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`config::get_global_account_data::v3`](crate::config::get_global_account_data::v3)
+pub const GET_GLOBAL_ACCOUNT_DATA: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/user/%40alice%3Aexample.com/account_data/org.example.custom.config",
+    request_body: None,
+    response_body: r#"{ "custom_account_data_key": "custom_config_value" }"#,
+};
+
+/// [`config::get_room_account_data::v3`](crate::config::get_room_account_data::v3)
+pub const GET_ROOM_ACCOUNT_DATA: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user/%40alice%3Aexample.com/rooms/%21726s6s6q%3Aexample.com/account_data/org.example.custom.room.config",
+    request_body: None,
+    response_body: r#"{ "custom_account_data_key": "custom_config_value" }"#,
+};
+
+/// [`config::set_global_account_data::v3`](crate::config::set_global_account_data::v3)
+pub const SET_GLOBAL_ACCOUNT_DATA: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/user/%40alice%3Aexample.com/account_data/org.example.custom.config",
+    request_body: Some(r#"{ "custom_account_data_key": "custom_config_value" }"#),
+    response_body: "{}",
+};
+
+/// [`config::set_room_account_data::v3`](crate::config::set_room_account_data::v3)
+pub const SET_ROOM_ACCOUNT_DATA: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user/%40alice%3Aexample.com/rooms/%21726s6s6q%3Aexample.com/account_data/org.example.custom.room.config",
+    request_body: Some(r#"{ "custom_account_data_key": "custom_config_value" }"#),
+    response_body: "{}",
+};
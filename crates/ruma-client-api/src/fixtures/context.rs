@@ -0,0 +1,88 @@
+//! Example JSON for the endpoints of [`context`](crate::context).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`context::get_context::v3`](crate::context::get_context::v3)
+pub const GET_CONTEXT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/context/%24143273582443PhrSn%3Aexample.org?limit=3",
+    request_body: None,
+    response_body: r#"{
+        "end": "t29-57_2_0_2",
+        "event": {
+            "content": {
+                "body": "filename.jpg",
+                "info": {
+                    "h": 398,
+                    "mimetype": "image/jpeg",
+                    "size": 31037,
+                    "w": 394
+                },
+                "msgtype": "m.image",
+                "url": "mxc://example.org/JWEIFJgwEIhweiWJE"
+            },
+            "event_id": "$f3h4d129462ha:example.com",
+            "origin_server_ts": 1432735824653,
+            "room_id": "!636q39766251:example.com",
+            "sender": "@example:example.org",
+            "type": "m.room.message",
+            "unsigned": { "age": 1234 }
+        },
+        "events_after": [
+            {
+                "content": {
+                    "body": "This is an example text message",
+                    "format": "org.matrix.custom.html",
+                    "formatted_body": "<b>This is an example text message</b>",
+                    "msgtype": "m.text"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "events_before": [
+            {
+                "content": {
+                    "body": "something-important.doc",
+                    "filename": "something-important.doc",
+                    "info": {
+                        "mimetype": "application/msword",
+                        "size": 46144
+                    },
+                    "msgtype": "m.file",
+                    "url": "mxc://example.org/FHyPlCeYUSFFxlgbQYZmoEoe"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "start": "t27-54_2_0_2",
+        "state": [
+            {
+                "content": {
+                    "creator": "@example:example.org",
+                    "m.federate": true,
+                    "predecessor": {
+                        "event_id": "$something:example.org",
+                        "room_id": "!oldroom:example.org"
+                    },
+                    "room_version": "1"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "state_key": "",
+                "type": "m.room.create",
+                "unsigned": { "age": 1234 }
+            }
+        ]
+    }"#,
+};
@@ -0,0 +1,78 @@
+//! Example JSON for the endpoints of [`dehydrated_device`](crate::dehydrated_device).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`dehydrated_device::delete_dehydrated_device::unstable`](crate::dehydrated_device::delete_dehydrated_device::unstable)
+pub const DELETE_DEHYDRATED_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device",
+    request_body: None,
+    response_body: r#"{ "device_id": "ABCDEFG" }"#,
+};
+
+/// [`dehydrated_device::get_dehydrated_device::unstable`](crate::dehydrated_device::get_dehydrated_device::unstable)
+pub const GET_DEHYDRATED_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device",
+    request_body: None,
+    response_body: r#"{
+        "device_data": {
+            "algorithm": "org.matrix.msc3814.v1.olm",
+            "device_pickle": "encrypted dehydrated device"
+        },
+        "device_id": "ABCDEFG"
+    }"#,
+};
+
+/// [`dehydrated_device::get_events::unstable`](crate::dehydrated_device::get_events::unstable)
+pub const GET_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device/ABCDEFG/events",
+    request_body: Some(r#"{ "next_batch": "token from previous call" }"#),
+    response_body: r#"{
+        "events": [
+            {
+                "content": {
+                    "algorithm": "m.olm.v1.curve25519-aes-sha2",
+                    "ciphertext": {
+                        "7qZcfnBmbEGzxxaWfBjElJuvn7BZx+lSz/SvFrDF/z8": {
+                            "body": "AwogGJJzMhf/S3GQFXAOrCZ3iKyGU5ZScVtjI0KypTYrW...",
+                            "type": 0
+                        }
+                    },
+                    "sender_key": "Szl29ksW/L8yZGWAX+8dY1XyFi+i5wm+DRhTGkbMiwU"
+                },
+                "sender": "@alice:example.com",
+                "type": "m.room.encrypted"
+            }
+        ],
+        "next_batch": "token to obtain next events"
+    }"#,
+};
+
+/// [`dehydrated_device::put_dehydrated_device::unstable`](crate::dehydrated_device::put_dehydrated_device::unstable)
+pub const PUT_DEHYDRATED_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3814.v1/dehydrated_device",
+    request_body: Some(
+        r#"{
+            "device_data": {
+                "algorithm": "org.matrix.msc3814.v1.olm",
+                "device_pickle": "encrypted dehydrated device"
+            },
+            "device_id": "ABCDEFG",
+            "device_keys": {
+                "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+                "device_id": "ABCDEFG",
+                "keys": {
+                    "curve25519:ABCDEFG": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+                    "ed25519:ABCDEFG": "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:ABCDEFG": "dSO80A01XiigH3uBiDVx/EjzaoycHcjq9lfQX0uWsqxl2giMIiSPR8a4d291W1ihKJL/a+myXS367WT6NAIcBA"
+                    }
+                },
+                "user_id": "@alice:example.com"
+            },
+            "initial_device_display_name": "Dehydrated device"
+        }"#,
+    ),
+    response_body: r#"{ "device_id": "ABCDEFG" }"#,
+};
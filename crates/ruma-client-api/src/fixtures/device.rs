@@ -0,0 +1,69 @@
+//! Example JSON for the endpoints of [`device`](crate::device).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`device::delete_device::v3`](crate::device::delete_device::v3)
+pub const DELETE_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/devices/QBUAZIFURK",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "xxxxx",
+                "type": "example.type.foo"
+            }
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`device::delete_devices::v3`](crate::device::delete_devices::v3)
+pub const DELETE_DEVICES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/delete_devices",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "xxxxx",
+                "type": "example.type.foo"
+            },
+            "devices": ["QBUAZIFURK", "AUIECTSRND"]
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`device::get_device::v3`](crate::device::get_device::v3)
+pub const GET_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/devices/QBUAZIFURK",
+    request_body: None,
+    response_body: r#"{
+        "device_id": "QBUAZIFURK",
+        "display_name": "android",
+        "last_seen_ip": "1.2.3.4",
+        "last_seen_ts": 1474491775024
+    }"#,
+};
+
+/// [`device::get_devices::v3`](crate::device::get_devices::v3)
+pub const GET_DEVICES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/devices",
+    request_body: None,
+    response_body: r#"{
+        "devices": [
+            {
+                "device_id": "QBUAZIFURK",
+                "display_name": "android",
+                "last_seen_ip": "1.2.3.4",
+                "last_seen_ts": 1474491775024
+            }
+        ]
+    }"#,
+};
+
+/// [`device::update_device::v3`](crate::device::update_device::v3)
+pub const UPDATE_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/devices/QBUAZIFURK",
+    request_body: Some(r#"{ "display_name": "My other phone" }"#),
+    response_body: "{}",
+};
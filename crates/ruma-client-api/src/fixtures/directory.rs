@@ -0,0 +1,74 @@
+//! Example JSON for the endpoints of [`directory`](crate::directory).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`directory::get_public_rooms::v3`](crate::directory::get_public_rooms::v3)
+pub const GET_PUBLIC_ROOMS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/publicRooms?limit=10&server=matrix.org",
+    request_body: None,
+    response_body: r##"{
+        "chunk": [
+            {
+                "avatar_url": "mxc://bleecker.street/CHEDDARandBRIE",
+                "canonical_alias": "#murrays:cheese.bar",
+                "guest_can_join": false,
+                "name": "CHEESE",
+                "num_joined_members": 37,
+                "room_id": "!ol19s:bleecker.street",
+                "room_type": "m.space",
+                "topic": "Tasty tasty cheese",
+                "world_readable": true
+            }
+        ],
+        "next_batch": "p190q",
+        "prev_batch": "p1902",
+        "total_room_count_estimate": 115
+    }"##,
+};
+
+/// [`directory::get_public_rooms_filtered::v3`](crate::directory::get_public_rooms_filtered::v3)
+pub const GET_PUBLIC_ROOMS_FILTERED: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/publicRooms?server=matrix.org",
+    request_body: Some(
+        r#"{
+            "filter": {
+                "generic_search_term": "foo",
+                "room_types": [null, "m.space"]
+            },
+            "limit": 10,
+            "third_party_instance_id": "irc"
+        }"#,
+    ),
+    response_body: r##"{
+        "chunk": [
+            {
+                "avatar_url": "mxc://bleecker.street/CHEDDARandBRIE",
+                "canonical_alias": "#murrays:cheese.bar",
+                "guest_can_join": false,
+                "join_rule": "knock",
+                "name": "CHEESE",
+                "num_joined_members": 37,
+                "room_id": "!ol19s:bleecker.street",
+                "topic": "Tasty tasty cheese",
+                "world_readable": true
+            }
+        ],
+        "next_batch": "p190q",
+        "prev_batch": "p1902",
+        "total_room_count_estimate": 115
+    }"##,
+};
+
+/// [`directory::get_room_visibility::v3`](crate::directory::get_room_visibility::v3)
+pub const GET_ROOM_VISIBILITY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/list/room/%21ol19s%3Ableecker.street",
+    request_body: None,
+    response_body: r#"{ "visibility": "public" }"#,
+};
+
+/// [`directory::set_room_visibility::v3`](crate::directory::set_room_visibility::v3)
+pub const SET_ROOM_VISIBILITY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/directory/list/room/%21ol19s%3Ableecker.street",
+    request_body: Some(r#"{ "visibility": "public" }"#),
+    response_body: "{}",
+};
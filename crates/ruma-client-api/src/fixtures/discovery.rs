@@ -0,0 +1,54 @@
+//! Example JSON for the endpoints of [`discovery`](crate::discovery).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`discovery::discover_homeserver`](crate::discovery::discover_homeserver)
+pub const DISCOVER_HOMESERVER: EndpointFixture = EndpointFixture {
+    request_path: "/.well-known/matrix/client",
+    request_body: None,
+    response_body: r#"{
+        "m.homeserver": {
+            "base_url": "https://matrix.example.com"
+        },
+        "m.identity_server": {
+            "base_url": "https://identity.example.com"
+        }
+    }"#,
+};
+
+/// [`discovery::get_capabilities::v3`](crate::discovery::get_capabilities::v3)
+pub const GET_CAPABILITIES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/capabilities",
+    request_body: None,
+    response_body: r#"{
+        "capabilities": {
+            "com.example.custom.ratelimit": {
+                "max_requests_per_hour": 600
+            },
+            "m.change_password": {
+                "enabled": false
+            },
+            "m.room_versions": {
+                "available": {
+                    "1": "stable",
+                    "2": "stable",
+                    "3": "unstable",
+                    "test-version": "unstable"
+                },
+                "default": "1"
+            }
+        }
+    }"#,
+};
+
+/// [`discovery::get_supported_versions`](crate::discovery::get_supported_versions)
+pub const GET_SUPPORTED_VERSIONS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/versions",
+    request_body: None,
+    response_body: r#"{
+        "unstable_features": {
+            "org.example.my_feature": true
+        },
+        "versions": ["r0.0.1", "v1.1"]
+    }"#,
+};
@@ -0,0 +1,67 @@
+//! Example JSON for the endpoints of [`filter`](crate::filter).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`filter::create_filter::v3`](crate::filter::create_filter::v3)
+pub const CREATE_FILTER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user/%40alice%3Aexample.com/filter",
+    request_body: Some(
+        r#"{
+            "event_fields": ["type", "content", "sender"],
+            "presence": {
+                "not_senders": ["@alice:example.com"],
+                "types": ["m.presence"]
+            },
+            "room": {
+                "ephemeral": {
+                    "not_rooms": ["!726s6s6q:example.com"],
+                    "not_senders": ["@spam:example.com"],
+                    "types": ["m.receipt", "m.typing"]
+                },
+                "state": {
+                    "not_rooms": ["!726s6s6q:example.com"],
+                    "types": ["m.room.*"]
+                },
+                "timeline": {
+                    "limit": 10,
+                    "not_rooms": ["!726s6s6q:example.com"],
+                    "not_senders": ["@spam:example.com"],
+                    "types": ["m.room.message"]
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{ "filter_id": "66696p746572" }"#,
+};
+
+/// [`filter::get_filter::v3`](crate::filter::get_filter::v3)
+pub const GET_FILTER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user/%40alice%3Aexample.com/filter/66696p746572",
+    request_body: None,
+    response_body: r#"{
+        "event_fields": ["type", "content", "sender"],
+        "event_format": "federation",
+        "presence": {
+            "not_senders": ["@alice:example.com"],
+            "types": ["m.presence"]
+        },
+        "room": {
+            "ephemeral": {
+                "not_rooms": ["!726s6s6q:example.com"],
+                "not_senders": ["@spam:example.com"],
+                "types": ["m.receipt", "m.typing"]
+            },
+            "state": {
+                "lazy_load_members": true,
+                "not_rooms": ["!726s6s6q:example.com"],
+                "types": ["m.room.*"]
+            },
+            "timeline": {
+                "limit": 10,
+                "not_rooms": ["!726s6s6q:example.com"],
+                "not_senders": ["@spam:example.com"],
+                "types": ["m.room.message"]
+            }
+        }
+    }"#,
+};
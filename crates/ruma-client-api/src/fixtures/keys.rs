@@ -0,0 +1,250 @@
+//! Example JSON for the endpoints of [`keys`](crate::keys).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`keys::claim_keys::v3`](crate::keys::claim_keys::v3)
+pub const CLAIM_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/claim",
+    request_body: Some(
+        r#"{
+            "one_time_keys": {
+                "@alice:example.com": {
+                    "JLAFKJWSCS": "signed_curve25519"
+                }
+            },
+            "timeout": 10000
+        }"#,
+    ),
+    response_body: r#"{
+        "failures": {},
+        "one_time_keys": {
+            "@alice:example.com": {
+                "JLAFKJWSCS": {
+                    "signed_curve25519:AAAAHg": {
+                        "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+                        "signatures": {
+                            "@alice:example.com": {
+                                "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }"#,
+};
+
+/// [`keys::get_key_changes::v3`](crate::keys::get_key_changes::v3)
+pub const GET_KEY_CHANGES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/changes?from=s72594_4483_1934&to=s75689_5632_2435",
+    request_body: None,
+    response_body: r#"{
+        "changed": ["@alice:example.com", "@bob:example.org"],
+        "left": ["@clara:example.com", "@doug:example.org"]
+    }"#,
+};
+
+/// [`keys::get_keys::v3`](crate::keys::get_keys::v3)
+pub const GET_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/query",
+    request_body: Some(
+        r#"{
+            "device_keys": {
+                "@alice:example.com": []
+            },
+            "timeout": 10000
+        }"#,
+    ),
+    response_body: r#"{
+        "device_keys": {
+            "@alice:example.com": {
+                "JLAFKJWSCS": {
+                    "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+                    "device_id": "JLAFKJWSCS",
+                    "keys": {
+                        "curve25519:JLAFKJWSCS": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+                        "ed25519:JLAFKJWSCS": "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI"
+                    },
+                    "signatures": {
+                        "@alice:example.com": {
+                            "ed25519:JLAFKJWSCS": "dSO80A01XiigH3uBiDVx/EjzaoycHcjq9lfQX0uWsqxl2giMIiSPR8a4d291W1ihKJL/a+myXS367WT6NAIcBA"
+                        }
+                    },
+                    "unsigned": {
+                        "device_display_name": "Alice's mobile phone"
+                    },
+                    "user_id": "@alice:example.com"
+                }
+            }
+        },
+        "master_keys": {
+            "@alice:example.com": {
+                "keys": {
+                    "ed25519:base64+master+public+key": "base64+master+public+key"
+                },
+                "usage": ["master"],
+                "user_id": "@alice:example.com"
+            }
+        },
+        "self_signing_keys": {
+            "@alice:example.com": {
+                "keys": {
+                    "ed25519:base64+self+signing+public+key": "base64+self+signing+master+public+key"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:base64+master+public+key": "signature+of+self+signing+key"
+                    }
+                },
+                "usage": ["self_signing"],
+                "user_id": "@alice:example.com"
+            }
+        },
+        "user_signing_keys": {
+            "@alice:example.com": {
+                "keys": {
+                    "ed25519:base64+user+signing+public+key": "base64+user+signing+master+public+key"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:base64+master+public+key": "signature+of+user+signing+key"
+                    }
+                },
+                "usage": ["user_signing"],
+                "user_id": "@alice:example.com"
+            }
+        }
+    }"#,
+};
+
+/// [`keys::upload_keys::v3`](crate::keys::upload_keys::v3)
+pub const UPLOAD_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/upload",
+    request_body: Some(
+        r#"{
+            "device_keys": {
+                "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+                "device_id": "JLAFKJWSCS",
+                "keys": {
+                    "curve25519:JLAFKJWSCS": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+                    "ed25519:JLAFKJWSCS": "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:JLAFKJWSCS": "dSO80A01XiigH3uBiDVx/EjzaoycHcjq9lfQX0uWsqxl2giMIiSPR8a4d291W1ihKJL/a+myXS367WT6NAIcBA"
+                    }
+                },
+                "user_id": "@alice:example.com"
+            },
+            "fallback_keys": {
+                "signed_curve25519:AAAAGj": {
+                    "fallback": true,
+                    "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+                    "signatures": {
+                        "@alice:example.com": {
+                            "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw"
+                        }
+                    }
+                }
+            },
+            "one_time_keys": {
+                "curve25519:AAAAAQ": "/qyvZvwjiTxGdGU0RCguDCLeR+nmsb3FfNG3/Ve4vU8",
+                "signed_curve25519:AAAAHg": {
+                    "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+                    "signatures": {
+                        "@alice:example.com": {
+                            "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw"
+                        }
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "one_time_key_counts": {
+            "curve25519": 10,
+            "signed_curve25519": 20
+        }
+    }"#,
+};
+
+/// [`keys::upload_signatures::v3`](crate::keys::upload_signatures::v3)
+pub const UPLOAD_SIGNATURES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/signatures/upload",
+    request_body: Some(
+        r#"{
+            "@alice:example.com": {
+                "HIJKLMN": {
+                    "algorithms": ["m.olm.v1.curve25519-aes-sha256", "m.megolm.v1.aes-sha"],
+                    "device_id": "HIJKLMN",
+                    "keys": {
+                        "curve25519:HIJKLMN": "base64+curve25519+key",
+                        "ed25519:HIJKLMN": "base64+ed25519+key"
+                    },
+                    "signatures": {
+                        "@alice:example.com": {
+                            "ed25519:base64+self+signing+public+key": "base64+signature+of+HIJKLMN"
+                        }
+                    },
+                    "user_id": "@alice:example.com"
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "failures": {
+            "@alice:example.com": {
+                "HIJKLMN": {
+                    "errcode": "M_INVALID_SIGNATURE",
+                    "error": "Invalid signature"
+                }
+            }
+        }
+    }"#,
+};
+
+/// [`keys::upload_signing_keys::v3`](crate::keys::upload_signing_keys::v3)
+pub const UPLOAD_SIGNING_KEYS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/keys/device_signing/upload",
+    request_body: Some(
+        r#"{
+            "auth": {
+                "example_credential": "verypoorsharedsecret",
+                "session": "xxxxx",
+                "type": "example.type.foo"
+            },
+            "master_key": {
+                "keys": {
+                    "ed25519:base64+master+public+key": "base64+master+public+key"
+                },
+                "usage": ["master"],
+                "user_id": "@alice:example.com"
+            },
+            "self_signing_key": {
+                "keys": {
+                    "ed25519:base64+self+signing+public+key": "base64+self+signing+master+public+key"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:base64+master+public+key": "signature+of+self+signing+key"
+                    }
+                },
+                "usage": ["self_signing"],
+                "user_id": "@alice:example.com"
+            },
+            "user_signing_key": {
+                "keys": {
+                    "ed25519:base64+user+signing+public+key": "base64+user+signing+master+public+key"
+                },
+                "signatures": {
+                    "@alice:example.com": {
+                        "ed25519:base64+master+public+key": "signature+of+user+signing+key"
+                    }
+                },
+                "usage": ["user_signing"],
+                "user_id": "@alice:example.com"
+            }
+        }"#,
+    ),
+    response_body: "{}",
+};
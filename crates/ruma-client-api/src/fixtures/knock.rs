@@ -0,0 +1,10 @@
+//! Example JSON for the endpoints of [`knock`](crate::knock).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`knock::knock_room::v3`](crate::knock::knock_room::v3)
+pub const KNOCK_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/knock/%23monkeys%3Amatrix.org?server_name=matrix.org&server_name=elsewhere.ca",
+    request_body: Some(r#"{ "reason": "Looking for support" }"#),
+    response_body: r#"{ "room_id": "!d415324:example.org" }"#,
+};
@@ -0,0 +1,39 @@
+//! Example JSON for the endpoints of [`media`](crate::media).
+//!
+//! The endpoints that upload or download the raw bytes of a file are not covered, since their
+//! bodies are not JSON.
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`media::create_mxc_uri::unstable`](crate::media::create_mxc_uri::unstable)
+#[cfg(feature = "unstable-msc2246")]
+pub const CREATE_MXC_URI: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/media/unstable/fi.mau.msc2246/create",
+    request_body: None,
+    response_body: r#"{
+        "content_uri": "mxc://example.com/AQwafuaFswefuhsfAFAgsw",
+        "unused_expires_at": 1647257217083
+    }"#,
+};
+
+/// [`media::get_media_config::v3`](crate::media::get_media_config::v3)
+pub const GET_MEDIA_CONFIG: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/media/v3/config",
+    request_body: None,
+    response_body: r#"{ "m.upload.size": 50000000 }"#,
+};
+
+/// [`media::get_media_preview::v3`](crate::media::get_media_preview::v3)
+pub const GET_MEDIA_PREVIEW: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/media/v3/preview_url?url=https%3A%2F%2Fmatrix.org&ts=1510610716656",
+    request_body: None,
+    response_body: r#"{
+        "matrix:image:size": 102400,
+        "og:description": "This is a really cool blog post from matrix.org",
+        "og:image": "mxc://example.com/ascERGshawAWawugaAcauga",
+        "og:image:height": 48,
+        "og:image:type": "image/png",
+        "og:image:width": 48,
+        "og:title": "Matrix Blog Post"
+    }"#,
+};
@@ -0,0 +1,148 @@
+//! Example JSON for the endpoints of [`membership`](crate::membership).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`membership::ban_user::v3`](crate::membership::ban_user::v3)
+pub const BAN_USER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21e42d8c%3Amatrix.org/ban",
+    request_body: Some(
+        r#"{
+            "reason": "Telling unfunny jokes",
+            "user_id": "@cheeky_monkey:matrix.org"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`membership::forget_room::v3`](crate::membership::forget_room::v3)
+pub const FORGET_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21au1ba7o%3Amatrix.org/forget",
+    request_body: Some("{}"),
+    response_body: "{}",
+};
+
+/// [`membership::get_member_events::v3`](crate::membership::get_member_events::v3)
+pub const GET_MEMBER_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/members?at=t27-54_2_0_2&not_membership=leave",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "avatar_url": "mxc://example.org/SEsfnsuifSDFSSEF",
+                    "displayname": "Alice Margatroid",
+                    "membership": "join",
+                    "reason": "Looking for support"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "state_key": "@alice:example.org",
+                "type": "m.room.member",
+                "unsigned": { "age": 1234 }
+            }
+        ]
+    }"#,
+};
+
+/// [`membership::invite_user::v3`](crate::membership::invite_user::v3)
+pub const INVITE_USER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21d415324%3Aexample.org/invite",
+    request_body: Some(
+        r#"{
+            "reason": "Welcome to the team!",
+            "user_id": "@cheeky_monkey:matrix.org"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`membership::join_room_by_id::v3`](crate::membership::join_room_by_id::v3)
+pub const JOIN_ROOM_BY_ID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21d415324%3Aexample.org/join",
+    request_body: Some(r#"{ "reason": "Looking for support" }"#),
+    response_body: r#"{ "room_id": "!d415324:example.org" }"#,
+};
+
+/// [`membership::join_room_by_id_or_alias::v3`](crate::membership::join_room_by_id_or_alias::v3)
+pub const JOIN_ROOM_BY_ID_OR_ALIAS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/join/%23monkeys%3Amatrix.org?server_name=matrix.org&server_name=elsewhere.ca",
+    request_body: Some(
+        r#"{
+            "reason": "Looking for support",
+            "third_party_signed": {
+                "mxid": "@bob:example.org",
+                "sender": "@alice:example.org",
+                "signatures": {
+                    "example.org": {
+                        "ed25519:0": "some9signature"
+                    }
+                },
+                "token": "random8nonce"
+            }
+        }"#,
+    ),
+    response_body: r#"{ "room_id": "!d415324:example.org" }"#,
+};
+
+/// [`membership::joined_members::v3`](crate::membership::joined_members::v3)
+pub const JOINED_MEMBERS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/joined_members",
+    request_body: None,
+    response_body: r#"{
+        "joined": {
+            "@bar:example.com": {
+                "avatar_url": "mxc://riot.ovh/printErCATzZijQsSDWorRaK",
+                "display_name": "Bar"
+            }
+        }
+    }"#,
+};
+
+/// [`membership::joined_rooms::v3`](crate::membership::joined_rooms::v3)
+pub const JOINED_ROOMS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/joined_rooms",
+    request_body: None,
+    response_body: r#"{ "joined_rooms": ["!foo:example.com"] }"#,
+};
+
+/// [`membership::kick_user::v3`](crate::membership::kick_user::v3)
+pub const KICK_USER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21e42d8c%3Amatrix.org/kick",
+    request_body: Some(
+        r#"{
+            "reason": "Telling unfunny jokes",
+            "user_id": "@cheeky_monkey:matrix.org"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`membership::leave_room::v3`](crate::membership::leave_room::v3)
+pub const LEAVE_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21nkl290a%3Amatrix.org/leave",
+    request_body: Some(r#"{ "reason": "Saying farewell - thanks for the support!" }"#),
+    response_body: "{}",
+};
+
+/// [`membership::mutual_rooms::unstable`](crate::membership::mutual_rooms::unstable)
+#[cfg(feature = "unstable-msc2666")]
+pub const MUTUAL_ROOMS: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/unstable/uk.half-shot.msc2666/user/mutual_rooms/%40bob%3Aexample.com",
+    request_body: None,
+    response_body: r#"{ "joined": ["!OGEhHVWSdvArJzumhm:matrix.org", "!HYlSnuBHTxUPgyZPKC:half-shot.uk"] }"#,
+};
+
+/// [`membership::unban_user::v3`](crate::membership::unban_user::v3)
+pub const UNBAN_USER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21e42d8c%3Amatrix.org/unban",
+    request_body: Some(
+        r#"{
+            "reason": "They've been banned long enough",
+            "user_id": "@cheeky_monkey:matrix.org"
+        }"#,
+    ),
+    response_body: "{}",
+};
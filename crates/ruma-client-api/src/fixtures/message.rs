@@ -0,0 +1,53 @@
+//! Example JSON for the endpoints of [`message`](crate::message).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`message::get_message_events::v3`](crate::message::get_message_events::v3)
+pub const GET_MESSAGE_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/messages?from=s345_678_333&dir=b&limit=3",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "body": "This is an example text message",
+                    "format": "org.matrix.custom.html",
+                    "formatted_body": "<b>This is an example text message</b>",
+                    "msgtype": "m.text"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": { "age": 1234 }
+            },
+            {
+                "content": {
+                    "name": "The room name"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "state_key": "",
+                "type": "m.room.name",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "end": "t47409-4357353_219380_26003_2265",
+        "start": "t47429-4392820_219380_26003_2265"
+    }"#,
+};
+
+/// [`message::send_message_event::v3`](crate::message::send_message_event::v3)
+pub const SEND_MESSAGE_EVENT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/send/m.room.message/35",
+    request_body: Some(
+        r#"{
+            "body": "hello",
+            "msgtype": "m.text"
+        }"#,
+    ),
+    response_body: r#"{ "event_id": "$YUwRidLecu:example.com" }"#,
+};
@@ -0,0 +1,25 @@
+//! Example JSON for the endpoints of [`presence`](crate::presence).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`presence::get_presence::v3`](crate::presence::get_presence::v3)
+pub const GET_PRESENCE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/presence/%40alice%3Aexample.com/status",
+    request_body: None,
+    response_body: r#"{
+        "last_active_ago": 420845,
+        "presence": "unavailable"
+    }"#,
+};
+
+/// [`presence::set_presence::v3`](crate::presence::set_presence::v3)
+pub const SET_PRESENCE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/presence/%40alice%3Aexample.com/status",
+    request_body: Some(
+        r#"{
+            "presence": "online",
+            "status_msg": "I am here."
+        }"#,
+    ),
+    response_body: "{}",
+};
@@ -0,0 +1,41 @@
+//! Example JSON for the endpoints of [`profile`](crate::profile).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`profile::get_avatar_url::v3`](crate::profile::get_avatar_url::v3)
+pub const GET_AVATAR_URL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/profile/%40alice%3Aexample.com/avatar_url",
+    request_body: None,
+    response_body: r#"{ "avatar_url": "mxc://matrix.org/SDGdghriugerRg" }"#,
+};
+
+/// [`profile::get_display_name::v3`](crate::profile::get_display_name::v3)
+pub const GET_DISPLAY_NAME: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/profile/%40alice%3Aexample.com/displayname",
+    request_body: None,
+    response_body: r#"{ "displayname": "Alice Margatroid" }"#,
+};
+
+/// [`profile::get_profile::v3`](crate::profile::get_profile::v3)
+pub const GET_PROFILE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/profile/%40alice%3Aexample.com",
+    request_body: None,
+    response_body: r#"{
+        "avatar_url": "mxc://matrix.org/SDGdghriugerRg",
+        "displayname": "Alice Margatroid"
+    }"#,
+};
+
+/// [`profile::set_avatar_url::v3`](crate::profile::set_avatar_url::v3)
+pub const SET_AVATAR_URL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/profile/%40alice%3Aexample.com/avatar_url",
+    request_body: Some(r#"{ "avatar_url": "mxc://matrix.org/wefh34uihSDRGhw34" }"#),
+    response_body: "{}",
+};
+
+/// [`profile::set_display_name::v3`](crate::profile::set_display_name::v3)
+pub const SET_DISPLAY_NAME: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/profile/%40alice%3Aexample.com/displayname",
+    request_body: Some(r#"{ "displayname": "Alice Margatroid" }"#),
+    response_body: "{}",
+};
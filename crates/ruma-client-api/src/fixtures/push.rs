@@ -0,0 +1,241 @@
+//! Example JSON for the endpoints of [`push`](crate::push).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`push::delete_pushrule::v3`](crate::push::delete_pushrule::v3)
+pub const DELETE_PUSHRULE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`push::get_notifications::v3`](crate::push::get_notifications::v3)
+pub const GET_NOTIFICATIONS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/notifications?from=xyz&limit=10&only=highlight",
+    request_body: None,
+    response_body: r#"{
+        "next_token": "abcdef",
+        "notifications": [
+            {
+                "actions": ["notify"],
+                "event": {
+                    "content": {
+                        "body": "This is an example text message",
+                        "format": "org.matrix.custom.html",
+                        "formatted_body": "<b>This is an example text message</b>",
+                        "msgtype": "m.text"
+                    },
+                    "event_id": "$143273582443PhrSn:example.org",
+                    "origin_server_ts": 1432735824653,
+                    "sender": "@example:example.org",
+                    "type": "m.room.message",
+                    "unsigned": { "age": 1234 }
+                },
+                "profile_tag": "hcbvkzxhcvb",
+                "read": true,
+                "room_id": "!abcdefg:example.com",
+                "ts": 1475508881945
+            }
+        ]
+    }"#,
+};
+
+/// [`push::get_pushers::v3`](crate::push::get_pushers::v3)
+pub const GET_PUSHERS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushers",
+    request_body: None,
+    response_body: r#"{
+        "pushers": [
+            {
+                "app_display_name": "Appy McAppface",
+                "app_id": "face.mcapp.appy.prod",
+                "data": {
+                    "url": "https://example.com/_matrix/push/v1/notify"
+                },
+                "device_display_name": "Alice's Phone",
+                "kind": "http",
+                "lang": "en-US",
+                "profile_tag": "xyz",
+                "pushkey": "Xp/MzCt8/9DcSNE9cuiaoT5Ac55job3TdLSSmtmYl4A="
+            }
+        ]
+    }"#,
+};
+
+/// [`push::get_pushrule::v3`](crate::push::get_pushrule::v3)
+pub const GET_PUSHRULE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake",
+    request_body: None,
+    response_body: r#"{
+        "actions": [],
+        "default": false,
+        "enabled": true,
+        "pattern": "cake*lie",
+        "rule_id": "nocake"
+    }"#,
+};
+
+/// [`push::get_pushrule_actions::v3`](crate::push::get_pushrule_actions::v3)
+pub const GET_PUSHRULE_ACTIONS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake/actions",
+    request_body: None,
+    response_body: r#"{ "actions": ["notify"] }"#,
+};
+
+/// [`push::get_pushrule_enabled::v3`](crate::push::get_pushrule_enabled::v3)
+pub const GET_PUSHRULE_ENABLED: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake/enabled",
+    request_body: None,
+    response_body: r#"{ "enabled": true }"#,
+};
+
+/// [`push::get_pushrules_all::v3`](crate::push::get_pushrules_all::v3)
+pub const GET_PUSHRULES_ALL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/",
+    request_body: None,
+    response_body: r#"{
+        "global": {
+            "content": [
+                {
+                    "actions": [
+                        "notify",
+                        { "set_tweak": "sound", "value": "default" },
+                        { "set_tweak": "highlight" }
+                    ],
+                    "default": true,
+                    "enabled": true,
+                    "pattern": "alice",
+                    "rule_id": ".m.rule.contains_user_name"
+                }
+            ],
+            "override": [
+                {
+                    "actions": [],
+                    "conditions": [],
+                    "default": true,
+                    "enabled": false,
+                    "rule_id": ".m.rule.master"
+                }
+            ],
+            "room": [],
+            "sender": [],
+            "underride": [
+                {
+                    "actions": [
+                        "notify",
+                        { "set_tweak": "highlight", "value": false }
+                    ],
+                    "conditions": [
+                        {
+                            "key": "type",
+                            "kind": "event_match",
+                            "pattern": "m.room.message"
+                        }
+                    ],
+                    "default": true,
+                    "enabled": true,
+                    "rule_id": ".m.rule.message"
+                }
+            ]
+        }
+    }"#,
+};
+
+/// [`push::get_pushrules_global_scope::v3`](crate::push::get_pushrules_global_scope::v3)
+pub const GET_PUSHRULES_GLOBAL_SCOPE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/",
+    request_body: None,
+    response_body: r#"{
+        "content": [
+            {
+                "actions": [
+                    "notify",
+                    { "set_tweak": "sound", "value": "default" },
+                    { "set_tweak": "highlight" }
+                ],
+                "default": true,
+                "enabled": true,
+                "pattern": "alice",
+                "rule_id": ".m.rule.contains_user_name"
+            }
+        ],
+        "override": [
+            {
+                "actions": [],
+                "conditions": [],
+                "default": true,
+                "enabled": false,
+                "rule_id": ".m.rule.master"
+            }
+        ],
+        "room": [],
+        "sender": [],
+        "underride": [
+            {
+                "actions": [
+                    "notify",
+                    { "set_tweak": "highlight", "value": false }
+                ],
+                "conditions": [
+                    {
+                        "key": "type",
+                        "kind": "event_match",
+                        "pattern": "m.room.message"
+                    }
+                ],
+                "default": true,
+                "enabled": true,
+                "rule_id": ".m.rule.message"
+            }
+        ]
+    }"#,
+};
+
+/// [`push::set_pusher::v3`](crate::push::set_pusher::v3)
+pub const SET_PUSHER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushers/set",
+    request_body: Some(
+        r#"{
+            "app_display_name": "Mat Rix",
+            "app_id": "com.example.app.ios",
+            "append": true,
+            "data": {
+                "format": "event_id_only",
+                "url": "https://push-gateway.location.here/_matrix/push/v1/notify"
+            },
+            "device_display_name": "iPhone 9",
+            "kind": "http",
+            "lang": "en",
+            "profile_tag": "xxyyzz",
+            "pushkey": "APA91bHPRgkF3JUikC4ENAHEeMrd41Zxv3hVZjC9KtT8OvPVGJ-hQMRKRrZuJAEcl7B338qju59zJMjw2DELjzEvxwYv7hH5Ynpc1ODQ0aT4U4OFEeco8ohsN5PjL1iC2dNtk2BAokeMCg2ZXKqpc8FXKmhX94kIxQ"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`push::set_pushrule::v3`](crate::push::set_pushrule::v3)
+pub const SET_PUSHRULE: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/pushrules/global/content/nocake?before=someRuleId&after=anotherRuleId",
+    request_body: Some(
+        r#"{
+            "actions": ["notify"],
+            "pattern": "cake*lie"
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`push::set_pushrule_actions::v3`](crate::push::set_pushrule_actions::v3)
+pub const SET_PUSHRULE_ACTIONS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake/actions",
+    request_body: Some(r#"{ "actions": ["notify", { "set_tweak": "highlight" }] }"#),
+    response_body: "{}",
+};
+
+/// [`push::set_pushrule_enabled::v3`](crate::push::set_pushrule_enabled::v3)
+pub const SET_PUSHRULE_ENABLED: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/pushrules/global/content/nocake/enabled",
+    request_body: Some(r#"{ "enabled": true }"#),
+    response_body: "{}",
+};
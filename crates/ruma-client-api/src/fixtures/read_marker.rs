@@ -0,0 +1,16 @@
+//! Example JSON for the endpoints of [`read_marker`](crate::read_marker).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`read_marker::set_read_marker::v3`](crate::read_marker::set_read_marker::v3)
+pub const SET_READ_MARKER: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21somewhere%3Aexample.org/read_markers",
+    request_body: Some(
+        r#"{
+            "m.fully_read": "$somewhere:example.org",
+            "m.read": "$elsewhere:example.org",
+            "m.read.private": "$elsewhere:example.org"
+        }"#,
+    ),
+    response_body: "{}",
+};
@@ -0,0 +1,10 @@
+//! Example JSON for the endpoints of [`receipt`](crate::receipt).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`receipt::create_receipt::v3`](crate::receipt::create_receipt::v3)
+pub const CREATE_RECEIPT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21wefuh21ffskAqwe%3Aexample.org/receipt/m.read/%241924376522eioj%3Aexample.org",
+    request_body: Some(r#"{ "thread_id": "main" }"#),
+    response_body: "{}",
+};
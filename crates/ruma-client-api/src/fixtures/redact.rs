@@ -0,0 +1,10 @@
+//! Example JSON for the endpoints of [`redact`](crate::redact).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`redact::redact_event::v3`](crate::redact::redact_event::v3)
+pub const REDACT_EVENT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21637q39766251%3Aexample.com/redact/%24bai2ogh3ie2ieLu%3Aexample.com/37",
+    request_body: Some(r#"{ "reason": "Indecent material" }"#),
+    response_body: r#"{ "event_id": "$YUwQidLecu:example.com" }"#,
+};
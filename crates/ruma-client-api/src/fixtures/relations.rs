@@ -0,0 +1,83 @@
+//! Example JSON for the endpoints of [`relations`](crate::relations).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`relations::get_relating_events::v1`](crate::relations::get_relating_events::v1)
+pub const GET_RELATING_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/rooms/%21636q39766251%3Aexample.com/relations/%24asfDuShaf7Gafaw%3Aexample.com?from=page1_token&dir=f&limit=3",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "m.relates_to": {
+                        "event_id": "$asfDuShaf7Gafaw:example.com",
+                        "rel_type": "org.example.my_relation"
+                    }
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "next_batch": "page2_token",
+        "prev_batch": "page1_token"
+    }"#,
+};
+
+/// [`relations::get_relating_events_with_rel_type::v1`](crate::relations::get_relating_events_with_rel_type::v1)
+pub const GET_RELATING_EVENTS_WITH_REL_TYPE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/rooms/%21636q39766251%3Aexample.com/relations/%24asfDuShaf7Gafaw%3Aexample.com/m.thread?from=page1_token&limit=3",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "body": "A reply in the thread",
+                    "m.relates_to": {
+                        "event_id": "$asfDuShaf7Gafaw:example.com",
+                        "rel_type": "m.thread"
+                    },
+                    "msgtype": "m.text"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "next_batch": "page2_token",
+        "prev_batch": "page1_token"
+    }"#,
+};
+
+/// [`relations::get_relating_events_with_rel_type_and_event_type::v1`](crate::relations::get_relating_events_with_rel_type_and_event_type::v1)
+pub const GET_RELATING_EVENTS_WITH_REL_TYPE_AND_EVENT_TYPE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/rooms/%21636q39766251%3Aexample.com/relations/%24asfDuShaf7Gafaw%3Aexample.com/m.annotation/m.reaction?limit=3",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "m.relates_to": {
+                        "event_id": "$asfDuShaf7Gafaw:example.com",
+                        "key": "👍",
+                        "rel_type": "m.annotation"
+                    }
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.reaction",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "next_batch": "page2_token"
+    }"#,
+};
@@ -0,0 +1,129 @@
+//! Example JSON for the endpoints of [`room`](crate::room).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`room::aliases::v3`](crate::room::aliases::v3)
+pub const ALIASES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/aliases",
+    request_body: None,
+    response_body: r##"{ "aliases": ["#somewhere:example.com", "#another:example.com", "#hat_trick:example.com"] }"##,
+};
+
+/// [`room::create_room::v3`](crate::room::create_room::v3)
+pub const CREATE_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/createRoom",
+    request_body: Some(
+        r#"{
+            "creation_content": {
+                "m.federate": false
+            },
+            "name": "The Grand Duke Pub",
+            "preset": "public_chat",
+            "room_alias_name": "thepub",
+            "topic": "All about happy hour"
+        }"#,
+    ),
+    response_body: r#"{ "room_id": "!sefiuhWgwghwWgh:example.com" }"#,
+};
+
+/// [`room::get_event_by_timestamp::unstable`](crate::room::get_event_by_timestamp::unstable)
+#[cfg(feature = "unstable-msc3030")]
+pub const GET_EVENT_BY_TIMESTAMP: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3030/rooms/%21636q39766251%3Aexample.com/timestamp_to_event?ts=1432684800000&dir=f",
+    request_body: None,
+    response_body: r#"{
+        "event_id": "$143273582443PhrSn:example.org",
+        "origin_server_ts": 1432735824653
+    }"#,
+};
+
+/// [`room::get_room_event::v3`](crate::room::get_room_event::v3)
+pub const GET_ROOM_EVENT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/event/%24asfDuShaf7Gafaw%3Amatrix.org",
+    request_body: None,
+    response_body: r#"{
+        "content": {
+            "body": "This is an example text message",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "<b>This is an example text message</b>",
+            "msgtype": "m.text"
+        },
+        "event_id": "$asfDuShaf7Gafaw:matrix.org",
+        "origin_server_ts": 1432735824653,
+        "room_id": "!636q39766251:example.com",
+        "sender": "@example:example.org",
+        "type": "m.room.message",
+        "unsigned": { "age": 1234 }
+    }"#,
+};
+
+/// [`room::get_room_initial_sync::v3`](crate::room::get_room_initial_sync::v3)
+pub const GET_ROOM_INITIAL_SYNC: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/initialSync",
+    request_body: None,
+    response_body: r#"{
+        "account_data": [
+            {
+                "content": {
+                    "tags": {
+                        "work": { "order": 1 }
+                    }
+                },
+                "type": "m.tag"
+            }
+        ],
+        "membership": "join",
+        "messages": {
+            "chunk": [
+                {
+                    "content": {
+                        "body": "This is an example text message",
+                        "msgtype": "m.text"
+                    },
+                    "event_id": "$143273582443PhrSn:example.org",
+                    "origin_server_ts": 1432735824653,
+                    "room_id": "!636q39766251:example.com",
+                    "sender": "@example:example.org",
+                    "type": "m.room.message",
+                    "unsigned": { "age": 1234 }
+                }
+            ],
+            "end": "s3456_9_0",
+            "start": "t44-3453_9_0"
+        },
+        "room_id": "!636q39766251:example.com",
+        "state": [
+            {
+                "content": { "join_rule": "public" },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "state_key": "",
+                "type": "m.room.join_rules",
+                "unsigned": { "age": 1234 }
+            }
+        ],
+        "visibility": "private"
+    }"#,
+};
+
+/// [`room::report_content::v3`](crate::room::report_content::v3)
+pub const REPORT_CONTENT: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/rooms/%21637q39766251%3Aexample.com/report/%24something%3Aexample.org",
+    request_body: Some(
+        r#"{
+            "reason": "this makes me sad",
+            "score": -100
+        }"#,
+    ),
+    response_body: "{}",
+};
+
+/// [`room::upgrade_room::v3`](crate::room::upgrade_room::v3)
+pub const UPGRADE_ROOM: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21oldroom%3Aexample.org/upgrade",
+    request_body: Some(r#"{ "new_version": "2" }"#),
+    response_body: r#"{ "replacement_room": "!newroom:example.org" }"#,
+};
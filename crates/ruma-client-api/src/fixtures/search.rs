@@ -0,0 +1,69 @@
+//! Example JSON for the endpoints of [`search`](crate::search).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`search::search_events::v3`](crate::search::search_events::v3)
+pub const SEARCH_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/search?next_batch=YWxsCgpOb25lLDM1ODcwOA",
+    request_body: Some(
+        r#"{
+            "search_categories": {
+                "room_events": {
+                    "event_context": {
+                        "after_limit": 4,
+                        "before_limit": 4,
+                        "include_profile": true
+                    },
+                    "filter": {
+                        "limit": 10
+                    },
+                    "groupings": {
+                        "group_by": [
+                            { "key": "room_id" }
+                        ]
+                    },
+                    "keys": ["content.body"],
+                    "order_by": "recent",
+                    "search_term": "martians and men"
+                }
+            }
+        }"#,
+    ),
+    response_body: r#"{
+        "search_categories": {
+            "room_events": {
+                "count": 1224,
+                "groups": {
+                    "room_id": {
+                        "!qPewotXpIctQySfjSy:localhost": {
+                            "next_batch": "BdgFsdfHSf-dsFD",
+                            "order": 1,
+                            "results": ["$144429830826TWwbB:localhost"]
+                        }
+                    }
+                },
+                "highlights": ["martians", "men"],
+                "next_batch": "5FdgFsd234dfgsdfFD",
+                "results": [
+                    {
+                        "rank": 0,
+                        "result": {
+                            "content": {
+                                "body": "This is an example text message",
+                                "format": "org.matrix.custom.html",
+                                "formatted_body": "<b>This is an example text message</b>",
+                                "msgtype": "m.text"
+                            },
+                            "event_id": "$144429830826TWwbB:localhost",
+                            "origin_server_ts": 1432735824653,
+                            "room_id": "!qPewotXpIctQySfjSy:localhost",
+                            "sender": "@example:example.org",
+                            "type": "m.room.message",
+                            "unsigned": { "age": 1234 }
+                        }
+                    }
+                ]
+            }
+        }
+    }"#,
+};
@@ -0,0 +1,32 @@
+//! Example JSON for the endpoints of [`server`](crate::server).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`server::get_user_info::v3`](crate::server::get_user_info::v3)
+pub const GET_USER_INFO: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/admin/whois/%40peter%3Arabbit.rocks",
+    request_body: None,
+    response_body: r#"{
+        "devices": {
+            "teapot": {
+                "sessions": [
+                    {
+                        "connections": [
+                            {
+                                "ip": "127.0.0.1",
+                                "last_seen": 1411996332123,
+                                "user_agent": "curl/7.31.0-DEV"
+                            },
+                            {
+                                "ip": "10.0.0.2",
+                                "last_seen": 1411996332123,
+                                "user_agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_10_0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/38.0.2125.104 Safari/537.36"
+                            }
+                        ]
+                    }
+                ]
+            }
+        },
+        "user_id": "@peter:rabbit.rocks"
+    }"#,
+};
@@ -0,0 +1,74 @@
+//! Example JSON for the endpoints of [`session`](crate::session).
+//!
+//! The login fallback page and the SSO redirects are not covered, since their responses are not
+//! JSON.
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`session::get_login_types::v3`](crate::session::get_login_types::v3)
+pub const GET_LOGIN_TYPES: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/login",
+    request_body: None,
+    response_body: r#"{
+        "flows": [
+            { "type": "m.login.password" },
+            { "type": "m.login.token" }
+        ]
+    }"#,
+};
+
+/// [`session::login::v3`](crate::session::login::v3)
+pub const LOGIN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/login",
+    request_body: Some(
+        r#"{
+            "identifier": {
+                "type": "m.id.user",
+                "user": "cheeky_monkey"
+            },
+            "initial_device_display_name": "Jungle Phone",
+            "password": "ilovebananas",
+            "type": "m.login.password"
+        }"#,
+    ),
+    response_body: r#"{
+        "access_token": "abc123",
+        "device_id": "GHTYAJCE",
+        "expires_in_ms": 60000,
+        "refresh_token": "def456",
+        "user_id": "@cheeky_monkey:matrix.org",
+        "well_known": {
+            "m.homeserver": {
+                "base_url": "https://example.org"
+            },
+            "m.identity_server": {
+                "base_url": "https://id.example.org"
+            }
+        }
+    }"#,
+};
+
+/// [`session::logout::v3`](crate::session::logout::v3)
+pub const LOGOUT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/logout",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`session::logout_all::v3`](crate::session::logout_all::v3)
+pub const LOGOUT_ALL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/logout/all",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`session::refresh_token::v3`](crate::session::refresh_token::v3)
+pub const REFRESH_TOKEN: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/refresh",
+    request_body: Some(r#"{ "refresh_token": "some_token" }"#),
+    response_body: r#"{
+        "access_token": "a_new_token",
+        "expires_in_ms": 60000,
+        "refresh_token": "another_new_token"
+    }"#,
+};
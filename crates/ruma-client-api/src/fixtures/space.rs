@@ -0,0 +1,37 @@
+//! Example JSON for the endpoints of [`space`](crate::space).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`space::get_hierarchy::v1`](crate::space::get_hierarchy::v1)
+pub const GET_HIERARCHY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/rooms/%21space%3Aexample.org/hierarchy?from=next_batch_token&limit=10&max_depth=2&suggested_only=true",
+    request_body: None,
+    response_body: r##"{
+        "next_batch": "next_batch_token",
+        "rooms": [
+            {
+                "avatar_url": "mxc://example.org/abcdef",
+                "canonical_alias": "#general:example.org",
+                "children_state": [
+                    {
+                        "content": {
+                            "via": ["example.org"]
+                        },
+                        "origin_server_ts": 1629413349153,
+                        "sender": "@alice:example.org",
+                        "state_key": "!a:example.org",
+                        "type": "m.space.child"
+                    }
+                ],
+                "guest_can_join": false,
+                "join_rule": "knock",
+                "name": "The First Space",
+                "num_joined_members": 42,
+                "room_id": "!space:example.org",
+                "room_type": "m.space",
+                "topic": "No other spaces were created first, ever",
+                "world_readable": true
+            }
+        ]
+    }"##,
+};
@@ -0,0 +1,57 @@
+//! Example JSON for the endpoints of [`state`](crate::state).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`state::get_state_events::v3`](crate::state::get_state_events::v3)
+pub const GET_STATE_EVENTS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/state",
+    request_body: None,
+    response_body: r#"[
+        {
+            "content": {
+                "join_rule": "public"
+            },
+            "event_id": "$143273582443PhrSn:example.org",
+            "origin_server_ts": 1432735824653,
+            "room_id": "!636q39766251:example.com",
+            "sender": "@example:example.org",
+            "state_key": "",
+            "type": "m.room.join_rules",
+            "unsigned": { "age": 1234 }
+        },
+        {
+            "content": {
+                "avatar_url": "mxc://example.org/SEsfnsuifSDFSSEF",
+                "displayname": "Alice Margatroid",
+                "membership": "join"
+            },
+            "event_id": "$143273582443PhrSn:example.org",
+            "origin_server_ts": 1432735824653,
+            "room_id": "!636q39766251:example.com",
+            "sender": "@example:example.org",
+            "state_key": "@alice:example.org",
+            "type": "m.room.member",
+            "unsigned": { "age": 1234 }
+        }
+    ]"#,
+};
+
+/// [`state::get_state_events_for_key::v3`](crate::state::get_state_events_for_key::v3)
+pub const GET_STATE_EVENTS_FOR_KEY: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/state/m.room.name/",
+    request_body: None,
+    response_body: r#"{ "name": "Example room name" }"#,
+};
+
+/// [`state::send_state_event::v3`](crate::state::send_state_event::v3)
+pub const SEND_STATE_EVENT: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/rooms/%21636q39766251%3Aexample.com/state/m.room.member/%40alice%3Aexample.com",
+    request_body: Some(
+        r#"{
+            "avatar_url": "mxc://localhost/SEsfnsuifSDFSSEF",
+            "displayname": "Alice Margatroid",
+            "membership": "join"
+        }"#,
+    ),
+    response_body: r#"{ "event_id": "$YUwRidLecu:example.com" }"#,
+};
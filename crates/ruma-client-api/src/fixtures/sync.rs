@@ -0,0 +1,283 @@
+//! Example JSON for the endpoints of [`sync`](crate::sync).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`sync::sync_events::v3`](crate::sync::sync_events::v3)
+pub const SYNC_EVENTS_V3: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/sync?filter=66696p746572&since=s72594_4483_1934&full_state=true&set_presence=offline&timeout=30000",
+    request_body: None,
+    response_body: r#"{
+        "account_data": {
+            "events": [
+                {
+                    "content": {
+                        "custom_config_key": "custom_config_value"
+                    },
+                    "type": "org.example.custom.config"
+                }
+            ]
+        },
+        "device_lists": {
+            "changed": ["@alice:example.com"],
+            "left": ["@bob:example.com"]
+        },
+        "device_one_time_keys_count": {
+            "signed_curve25519": 20
+        },
+        "device_unused_fallback_key_types": ["signed_curve25519"],
+        "next_batch": "s72595_4483_1934",
+        "presence": {
+            "events": [
+                {
+                    "content": {
+                        "avatar_url": "mxc://localhost/wefuiwegh8742w",
+                        "currently_active": false,
+                        "last_active_ago": 2478593,
+                        "presence": "online",
+                        "status_msg": "Making cupcakes"
+                    },
+                    "sender": "@example:localhost",
+                    "type": "m.presence"
+                }
+            ]
+        },
+        "rooms": {
+            "invite": {
+                "!696r7674:example.com": {
+                    "invite_state": {
+                        "events": [
+                            {
+                                "content": {
+                                    "name": "My Room Name"
+                                },
+                                "sender": "@alice:example.com",
+                                "state_key": "",
+                                "type": "m.room.name"
+                            },
+                            {
+                                "content": {
+                                    "membership": "invite"
+                                },
+                                "sender": "@alice:example.com",
+                                "state_key": "@bob:example.com",
+                                "type": "m.room.member"
+                            }
+                        ]
+                    }
+                }
+            },
+            "join": {
+                "!726s6s6q:example.com": {
+                    "account_data": {
+                        "events": [
+                            {
+                                "content": {
+                                    "tags": {
+                                        "u.work": {
+                                            "order": 0.9
+                                        }
+                                    }
+                                },
+                                "type": "m.tag"
+                            }
+                        ]
+                    },
+                    "ephemeral": {
+                        "events": [
+                            {
+                                "content": {
+                                    "user_ids": ["@alice:matrix.org", "@bob:example.com"]
+                                },
+                                "type": "m.typing"
+                            }
+                        ]
+                    },
+                    "state": {
+                        "events": [
+                            {
+                                "content": {
+                                    "avatar_url": "mxc://example.org/SEsfnsuifSDFSSEF",
+                                    "displayname": "Alice Margatroid",
+                                    "membership": "join"
+                                },
+                                "event_id": "$143273582443PhrSn:example.org",
+                                "origin_server_ts": 1432735824653,
+                                "sender": "@example:example.org",
+                                "state_key": "@alice:example.org",
+                                "type": "m.room.member",
+                                "unsigned": { "age": 1234 }
+                            }
+                        ]
+                    },
+                    "summary": {
+                        "m.heroes": ["@alice:example.com", "@bob:example.com"],
+                        "m.invited_member_count": 0,
+                        "m.joined_member_count": 2
+                    },
+                    "timeline": {
+                        "events": [
+                            {
+                                "content": {
+                                    "body": "This is an example text message",
+                                    "format": "org.matrix.custom.html",
+                                    "formatted_body": "<b>This is an example text message</b>",
+                                    "msgtype": "m.text"
+                                },
+                                "event_id": "$143273582443PhrSn:example.org",
+                                "origin_server_ts": 1432735824653,
+                                "sender": "@example:example.org",
+                                "type": "m.room.message",
+                                "unsigned": { "age": 1234 }
+                            }
+                        ],
+                        "limited": true,
+                        "prev_batch": "t34-23535_0_0"
+                    },
+                    "unread_notifications": {
+                        "highlight_count": 1,
+                        "notification_count": 5
+                    },
+                    "unread_thread_notifications": {
+                        "$threadroot": {
+                            "highlight_count": 3,
+                            "notification_count": 6
+                        }
+                    }
+                }
+            },
+            "knock": {
+                "!223asd456:example.com": {
+                    "knock_state": {
+                        "events": [
+                            {
+                                "content": {
+                                    "name": "My Room Name"
+                                },
+                                "sender": "@alice:example.com",
+                                "state_key": "",
+                                "type": "m.room.name"
+                            }
+                        ]
+                    }
+                }
+            },
+            "leave": {
+                "!lvbsf3245:example.com": {
+                    "timeline": {
+                        "events": [
+                            {
+                                "content": {
+                                    "membership": "leave"
+                                },
+                                "event_id": "$143273582443PhrSn:example.org",
+                                "origin_server_ts": 1432735824653,
+                                "sender": "@example:example.org",
+                                "state_key": "@example:example.org",
+                                "type": "m.room.member"
+                            }
+                        ]
+                    }
+                }
+            }
+        },
+        "to_device": {
+            "events": [
+                {
+                    "content": {
+                        "action": "request",
+                        "request_id": "1495474790150.19",
+                        "requesting_device_id": "RJYKSTBOIE"
+                    },
+                    "sender": "@alice:example.com",
+                    "type": "m.room_key_request"
+                }
+            ]
+        }
+    }"#,
+};
+
+/// [`sync::sync_events::v4`](crate::sync::sync_events::v4)
+#[cfg(feature = "unstable-msc3575")]
+pub const SYNC_EVENTS_V4: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/unstable/org.matrix.msc3575/sync?pos=12345&timeout=30000",
+    request_body: Some(
+        r#"{
+            "extensions": {
+                "to_device": {
+                    "enabled": true,
+                    "since": "some_token"
+                }
+            },
+            "lists": [
+                {
+                    "ranges": [[0, 99]],
+                    "required_state": [["m.room.topic", ""], ["m.room.avatar", ""]],
+                    "sort": ["by_notification_level", "by_recency", "by_name"],
+                    "timeline_limit": 10
+                }
+            ],
+            "room_subscriptions": {
+                "!sub1:bar": {
+                    "required_state": [["*", "*"]],
+                    "timeline_limit": 50
+                }
+            },
+            "txn_id": "f3ds63cw",
+            "unsubscribe_rooms": ["!sub3:bar"]
+        }"#,
+    ),
+    response_body: r#"{
+        "lists": [
+            {
+                "count": 1337,
+                "ops": [
+                    {
+                        "op": "SYNC",
+                        "range": [0, 1],
+                        "room_ids": ["!foo:bar", "!bar:bar"]
+                    },
+                    {
+                        "index": 3,
+                        "op": "INSERT",
+                        "room_id": "!baz:bar"
+                    }
+                ]
+            }
+        ],
+        "pos": "s58_224_0_13_10_1_1_16_0_1",
+        "rooms": {
+            "!foo:bar": {
+                "highlight_count": 1,
+                "initial": true,
+                "joined_count": 2,
+                "name": "The Foobar Room",
+                "notification_count": 3,
+                "prev_batch": "t111_222_333",
+                "required_state": [
+                    {
+                        "content": {
+                            "topic": "Foo the bars"
+                        },
+                        "event_id": "$143273582443PhrSn:example.org",
+                        "origin_server_ts": 1432735824653,
+                        "sender": "@example:example.org",
+                        "state_key": "",
+                        "type": "m.room.topic"
+                    }
+                ],
+                "timeline": [
+                    {
+                        "content": {
+                            "body": "This is an example text message",
+                            "msgtype": "m.text"
+                        },
+                        "event_id": "$143273582443PhrSn:example.org",
+                        "origin_server_ts": 1432735824653,
+                        "sender": "@example:example.org",
+                        "type": "m.room.message"
+                    }
+                ]
+            }
+        }
+    }"#,
+};
@@ -0,0 +1,33 @@
+//! Example JSON for the endpoints of [`tag`](crate::tag).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`tag::create_tag::v3`](crate::tag::create_tag::v3)
+pub const CREATE_TAG: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/user/%40alice%3Aexample.com/rooms/%21726s6s6q%3Aexample.com/tags/u.work",
+    request_body: Some(r#"{ "order": 0.25 }"#),
+    response_body: "{}",
+};
+
+/// [`tag::delete_tag::v3`](crate::tag::delete_tag::v3)
+pub const DELETE_TAG: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/user/%40alice%3Aexample.com/rooms/%21726s6s6q%3Aexample.com/tags/u.work",
+    request_body: None,
+    response_body: "{}",
+};
+
+/// [`tag::get_tags::v3`](crate::tag::get_tags::v3)
+pub const GET_TAGS: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/user/%40alice%3Aexample.com/rooms/%21726s6s6q%3Aexample.com/tags",
+    request_body: None,
+    response_body: r#"{
+        "tags": {
+            "m.favourite": { "order": 0.1 },
+            "u.Customers": {},
+            "u.Work": { "order": 0.7 }
+        }
+    }"#,
+};
@@ -0,0 +1,122 @@
+//! Example JSON for the endpoints of [`thirdparty`](crate::thirdparty).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`thirdparty::get_location_for_protocol::v3`](crate::thirdparty::get_location_for_protocol::v3)
+pub const GET_LOCATION_FOR_PROTOCOL: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/thirdparty/location/irc?channel=%23foo&network=irc.example.org",
+    request_body: None,
+    response_body: r##"[
+        {
+            "alias": "#freenode_#matrix:matrix.org",
+            "fields": {
+                "channel": "#matrix",
+                "network": "freenode"
+            },
+            "protocol": "irc"
+        }
+    ]"##,
+};
+
+/// [`thirdparty::get_location_for_room_alias::v3`](crate::thirdparty::get_location_for_room_alias::v3)
+pub const GET_LOCATION_FOR_ROOM_ALIAS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/thirdparty/location?alias=%23freenode_%23matrix%3Amatrix.org",
+    request_body: None,
+    response_body: r##"[
+        {
+            "alias": "#freenode_#matrix:matrix.org",
+            "fields": {
+                "channel": "#matrix",
+                "network": "freenode"
+            },
+            "protocol": "irc"
+        }
+    ]"##,
+};
+
+/// [`thirdparty::get_protocol::v3`](crate::thirdparty::get_protocol::v3)
+pub const GET_PROTOCOL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/thirdparty/protocol/irc",
+    request_body: None,
+    response_body: r##"{
+        "field_types": {
+            "channel": {
+                "placeholder": "#foobar",
+                "regexp": "#[^\\s]+"
+            },
+            "network": {
+                "placeholder": "irc.example.org",
+                "regexp": "([a-z0-9]+\\.)*[a-z0-9]+"
+            },
+            "nickname": {
+                "placeholder": "username",
+                "regexp": "[^\\s#]+"
+            }
+        },
+        "icon": "mxc://example.org/aBcDeFgH",
+        "instances": [],
+        "location_fields": ["network", "channel"],
+        "user_fields": ["network", "nickname"]
+    }"##,
+};
+
+/// [`thirdparty::get_protocols::v3`](crate::thirdparty::get_protocols::v3)
+pub const GET_PROTOCOLS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/thirdparty/protocols",
+    request_body: None,
+    response_body: r##"{
+        "irc": {
+            "field_types": {
+                "channel": {
+                    "placeholder": "#foobar",
+                    "regexp": "#[^\\s]+"
+                },
+                "network": {
+                    "placeholder": "irc.example.org",
+                    "regexp": "([a-z0-9]+\\.)*[a-z0-9]+"
+                },
+                "nickname": {
+                    "placeholder": "username",
+                    "regexp": "[^\\s#]+"
+                }
+            },
+            "icon": "mxc://example.org/aBcDeFgH",
+            "instances": [],
+            "location_fields": ["network", "channel"],
+            "user_fields": ["network", "nickname"]
+        }
+    }"##,
+};
+
+/// [`thirdparty::get_user_for_protocol::v3`](crate::thirdparty::get_user_for_protocol::v3)
+pub const GET_USER_FOR_PROTOCOL: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/thirdparty/user/irc?network=freenode&nickname=jim",
+    request_body: None,
+    response_body: r#"[
+        {
+            "fields": {
+                "network": "freenode",
+                "nickname": "jim"
+            },
+            "protocol": "irc",
+            "userid": "@_gitter_jim:matrix.org"
+        }
+    ]"#,
+};
+
+/// [`thirdparty::get_user_for_user_id::v3`](crate::thirdparty::get_user_for_user_id::v3)
+pub const GET_USER_FOR_USER_ID: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/thirdparty/user?userid=%40_gitter_jim%3Amatrix.org",
+    request_body: None,
+    response_body: r#"[
+        {
+            "fields": {
+                "network": "freenode",
+                "nickname": "jim"
+            },
+            "protocol": "irc",
+            "userid": "@_gitter_jim:matrix.org"
+        }
+    ]"#,
+};
@@ -0,0 +1,50 @@
+//! Example JSON for the endpoints of [`threads`](crate::threads).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`threads::get_threads::v1`](crate::threads::get_threads::v1)
+pub const GET_THREADS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v1/rooms/%21636q39766251%3Aexample.com/threads?from=page1_token&include=participated&limit=10",
+    request_body: None,
+    response_body: r#"{
+        "chunk": [
+            {
+                "content": {
+                    "body": "This is an example text message",
+                    "format": "org.matrix.custom.html",
+                    "formatted_body": "<b>This is an example text message</b>",
+                    "msgtype": "m.text"
+                },
+                "event_id": "$143273582443PhrSn:example.org",
+                "origin_server_ts": 1432735824653,
+                "room_id": "!636q39766251:example.com",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+                "unsigned": {
+                    "m.relations": {
+                        "m.thread": {
+                            "count": 7,
+                            "current_user_participated": true,
+                            "latest_event": {
+                                "content": {
+                                    "body": "Woo! Threads!",
+                                    "m.relates_to": {
+                                        "event_id": "$143273582443PhrSn:example.org",
+                                        "rel_type": "m.thread"
+                                    },
+                                    "msgtype": "m.text"
+                                },
+                                "event_id": "$latestevent:example.org",
+                                "origin_server_ts": 1432735824654,
+                                "room_id": "!636q39766251:example.com",
+                                "sender": "@alice:example.org",
+                                "type": "m.room.message"
+                            }
+                        }
+                    }
+                }
+            }
+        ],
+        "next_batch": "page2_token"
+    }"#,
+};
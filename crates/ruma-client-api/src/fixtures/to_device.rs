@@ -0,0 +1,20 @@
+//! Example JSON for the endpoints of [`to_device`](crate::to_device).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`to_device::send_event_to_device::v3`](crate::to_device::send_event_to_device::v3)
+pub const SEND_EVENT_TO_DEVICE: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/sendToDevice/m.new_device/35",
+    request_body: Some(
+        r#"{
+            "messages": {
+                "@alice:example.com": {
+                    "TLLBEANAAG": {
+                        "example_content_key": "value"
+                    }
+                }
+            }
+        }"#,
+    ),
+    response_body: "{}",
+};
@@ -0,0 +1,16 @@
+//! Example JSON for the endpoints of [`typing`](crate::typing).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`typing::create_typing_event::v3`](crate::typing::create_typing_event::v3)
+pub const CREATE_TYPING_EVENT: EndpointFixture = EndpointFixture {
+    request_path:
+        "/_matrix/client/v3/rooms/%21wefuh21ffskfuh345%3Aexample.com/typing/%40alice%3Aexample.com",
+    request_body: Some(
+        r#"{
+            "timeout": 30000,
+            "typing": true
+        }"#,
+    ),
+    response_body: "{}",
+};
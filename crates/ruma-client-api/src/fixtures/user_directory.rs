@@ -0,0 +1,24 @@
+//! Example JSON for the endpoints of [`user_directory`](crate::user_directory).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`user_directory::search_users::v3`](crate::user_directory::search_users::v3)
+pub const SEARCH_USERS: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/user_directory/search",
+    request_body: Some(
+        r#"{
+            "limit": 5,
+            "search_term": "foo"
+        }"#,
+    ),
+    response_body: r#"{
+        "limited": false,
+        "results": [
+            {
+                "avatar_url": "mxc://bar.com/foo",
+                "display_name": "Foo",
+                "user_id": "@foo:bar.com"
+            }
+        ]
+    }"#,
+};
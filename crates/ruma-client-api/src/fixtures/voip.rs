@@ -0,0 +1,19 @@
+//! Example JSON for the endpoints of [`voip`](crate::voip).
+
+use ruma_common::api::test_utils::EndpointFixture;
+
+/// [`voip::get_turn_server_info::v3`](crate::voip::get_turn_server_info::v3)
+pub const GET_TURN_SERVER_INFO: EndpointFixture = EndpointFixture {
+    request_path: "/_matrix/client/v3/voip/turnServer",
+    request_body: None,
+    response_body: r#"{
+        "password": "JlKfBy1QwLrO20385QyAtEyIv0=",
+        "ttl": 86400,
+        "uris": [
+            "turn:turn.example.com:3478?transport=udp",
+            "turn:10.20.30.40:3478?transport=tcp",
+            "turns:10.20.30.40:443?transport=tcp"
+        ],
+        "username": "1443779631:@user:example.com"
+    }"#,
+};
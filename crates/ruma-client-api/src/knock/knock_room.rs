@@ -15,6 +15,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
+        rate_limit_category: RoomJoin,
         authentication: AccessToken,
         history: {
             unstable => "/_matrix/client/unstable/xyz.amorgan.knock/knock/:room_id_or_alias",
@@ -12,14 +12,20 @@
 pub mod account;
 pub mod alias;
 pub mod appservice;
+#[cfg(feature = "unstable-msc3916")]
+pub mod authenticated_media;
 pub mod backup;
 pub mod config;
 pub mod context;
+#[cfg(feature = "unstable-msc3814")]
+pub mod dehydrated_device;
 pub mod device;
 pub mod directory;
 pub mod discovery;
 pub mod error;
 pub mod filter;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 pub mod http_headers;
 pub mod keys;
 pub mod knock;
@@ -16,6 +16,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
+        rate_limit_category: MediaUpload,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/media/r0/upload",
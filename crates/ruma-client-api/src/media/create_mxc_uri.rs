@@ -16,6 +16,7 @@ pub mod unstable {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
+        rate_limit_category: MediaUpload,
         authentication: AccessToken,
         history: {
             unstable => "/_matrix/media/unstable/fi.mau.msc2246/create",
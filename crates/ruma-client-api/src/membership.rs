@@ -14,9 +14,12 @@ pub mod leave_room;
 pub mod mutual_rooms;
 pub mod unban_user;
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, error::Error, fmt};
 
-use ruma_common::{thirdparty::Medium, OwnedServerName, OwnedServerSigningKeyId, OwnedUserId};
+use ruma_common::{
+    thirdparty::Medium, IdParseError, OwnedServerName, OwnedServerSigningKeyId, OwnedUserId,
+    ServerName,
+};
 use serde::{Deserialize, Serialize};
 
 /// A signature of an `m.third_party_invite` token to prove that this user owns a third party
@@ -52,8 +55,13 @@ impl ThirdPartySigned {
 
 /// Represents third party IDs to invite to the room.
 ///
-/// To create an instance of this type, first create a `Invite3pidInit` and convert it via
-/// `Invite3pid::from` / `.into()`.
+/// The homeserver forwards the `medium` and `address` to the identity server in a [store
+/// invitation request], which is why they must be valid for the medium.
+///
+/// To create an instance of this type, use [`Invite3pidBuilder`], which validates the third party
+/// identifier, or create a `Invite3pidInit` and convert it via `Invite3pid::from` / `.into()`.
+///
+/// [store invitation request]: https://docs.rs/ruma-identity-service-api/latest/ruma_identity_service_api/invitation/store_invitation/v2/struct.Request.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct Invite3pid {
@@ -96,3 +104,162 @@ impl From<Invite3pidInit> for Invite3pid {
         Self { id_server, id_access_token, medium, address }
     }
 }
+
+impl Invite3pid {
+    /// Parses `id_server` as a server name.
+    ///
+    /// This is the identity server that the homeserver asks to store the invite, and whose public
+    /// keys are used to sign the `m.room.third_party_invite` event and to verify the signature
+    /// when the invitee joins the room.
+    pub fn id_server_name(&self) -> Result<&ServerName, IdParseError> {
+        self.id_server.as_str().try_into()
+    }
+}
+
+/// A builder for [`Invite3pid`] that validates the third party identifier.
+#[derive(Debug)]
+pub struct Invite3pidBuilder {
+    id_server: String,
+    id_access_token: String,
+    third_party_id: Option<BuilderThirdPartyId>,
+}
+
+#[derive(Debug)]
+enum BuilderThirdPartyId {
+    Email(String),
+    Msisdn(String),
+}
+
+impl Invite3pidBuilder {
+    /// Creates a new `Invite3pidBuilder` with the given identity server and access token.
+    pub fn new(id_server: String, id_access_token: String) -> Self {
+        Self { id_server, id_access_token, third_party_id: None }
+    }
+
+    /// Invite the given email address.
+    ///
+    /// The address is lowercased when the `Invite3pid` is built.
+    pub fn email(self, address: String) -> Self {
+        Self { third_party_id: Some(BuilderThirdPartyId::Email(address)), ..self }
+    }
+
+    /// Invite the given phone number.
+    ///
+    /// The phone number must be in the international format without the leading `+`, so it
+    /// must only contain digits.
+    pub fn msisdn(self, address: String) -> Self {
+        Self { third_party_id: Some(BuilderThirdPartyId::Msisdn(address)), ..self }
+    }
+
+    /// Validate the third party identifier and build the [`Invite3pid`].
+    pub fn build(self) -> Result<Invite3pid, Invite3pidError> {
+        let (medium, address) = match self.third_party_id {
+            Some(BuilderThirdPartyId::Email(address)) => {
+                match address.split_once('@') {
+                    Some((local, domain))
+                        if !local.is_empty() && !domain.is_empty() && !domain.contains('@') => {}
+                    _ => return Err(Invite3pidError::InvalidEmail),
+                }
+
+                (Medium::Email, address.to_lowercase())
+            }
+            Some(BuilderThirdPartyId::Msisdn(address)) => {
+                if address.is_empty() || !address.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(Invite3pidError::InvalidMsisdn);
+                }
+
+                (Medium::Msisdn, address)
+            }
+            None => return Err(Invite3pidError::MissingAddress),
+        };
+
+        Ok(Invite3pid {
+            id_server: self.id_server,
+            id_access_token: self.id_access_token,
+            medium,
+            address,
+        })
+    }
+}
+
+/// An error that happens when [`Invite3pidBuilder`] fails to build an `Invite3pid`.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum Invite3pidError {
+    /// No email address or phone number was set.
+    MissingAddress,
+
+    /// The email address is invalid.
+    InvalidEmail,
+
+    /// The phone number contains characters that are not digits.
+    InvalidMsisdn,
+}
+
+impl fmt::Display for Invite3pidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAddress => write!(f, "No third party identifier was set."),
+            Self::InvalidEmail => write!(f, "Invalid email address."),
+            Self::InvalidMsisdn => write!(f, "Phone number must only contain digits."),
+        }
+    }
+}
+
+impl Error for Invite3pidError {}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use ruma_common::{server_name, thirdparty::Medium};
+
+    use super::{Invite3pid, Invite3pidBuilder, Invite3pidError, Invite3pidInit};
+
+    #[test]
+    fn invite_3pid_id_server_name() {
+        let invite: Invite3pid = Invite3pidInit {
+            id_server: "identity.localhost:8090".to_owned(),
+            id_access_token: "abcdefghijklmnop".to_owned(),
+            medium: Medium::Email,
+            address: "alice@localhost".to_owned(),
+        }
+        .into();
+        assert_eq!(invite.id_server_name().unwrap(), server_name!("identity.localhost:8090"));
+
+        let invite = Invite3pid { id_server: "https://identity.localhost".to_owned(), ..invite };
+        invite.id_server_name().unwrap_err();
+    }
+
+    #[test]
+    fn invite_3pid_builder() {
+        let builder = || {
+            Invite3pidBuilder::new("identity.localhost".to_owned(), "abcdefghijklmnop".to_owned())
+        };
+
+        let invite = builder().email("Alice@LocalHost".to_owned()).build().unwrap();
+        assert_eq!(invite.medium, Medium::Email);
+        assert_eq!(invite.address, "alice@localhost");
+
+        let invite = builder().msisdn("447700900000".to_owned()).build().unwrap();
+        assert_eq!(invite.medium, Medium::Msisdn);
+        assert_eq!(invite.address, "447700900000");
+
+        assert_matches!(builder().build(), Err(Invite3pidError::MissingAddress));
+        assert_matches!(
+            builder().email("alice".to_owned()).build(),
+            Err(Invite3pidError::InvalidEmail)
+        );
+        assert_matches!(
+            builder().email("@localhost".to_owned()).build(),
+            Err(Invite3pidError::InvalidEmail)
+        );
+        assert_matches!(
+            builder().msisdn("+44 7700 900000".to_owned()).build(),
+            Err(Invite3pidError::InvalidMsisdn)
+        );
+        assert_matches!(
+            builder().msisdn(String::new()).build(),
+            Err(Invite3pidError::InvalidMsisdn)
+        );
+    }
+}
@@ -23,6 +23,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
+        rate_limit_category: Invite,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/invite",
@@ -10,7 +10,7 @@ pub mod v3 {
     use js_int::{uint, UInt};
     use ruma_common::{
         api::{request, response, Direction, Metadata},
-        events::{AnyStateEvent, AnyTimelineEvent},
+        events::{AnyStateEvent, AnyTimelineEvent, BundledRelations},
         metadata,
         serde::Raw,
         OwnedRoomId,
@@ -159,6 +159,17 @@ pub mod v3 {
         pub fn new() -> Self {
             Default::default()
         }
+
+        /// The [bundled aggregations] of the events in `chunk`, in the same order.
+        ///
+        /// This gives access to the summary of the thread of events that are thread roots.
+        ///
+        /// [bundled aggregations]: https://spec.matrix.org/v1.5/client-server-api/#aggregations
+        pub fn bundled_relations(
+            &self,
+        ) -> impl Iterator<Item = serde_json::Result<BundledRelations>> + '_ {
+            self.chunk.iter().map(BundledRelations::from_raw_event)
+        }
     }
 
     fn default_limit() -> UInt {
@@ -176,9 +187,11 @@ pub mod v3 {
         use ruma_common::{
             api::{Direction, MatrixVersion, OutgoingRequest, SendAccessToken},
             room_id,
+            serde::Raw,
         };
+        use serde_json::json;
 
-        use super::Request;
+        use super::{Request, Response};
         use crate::filter::{LazyLoadOptions, RoomEventFilter};
 
         #[test]
@@ -243,5 +256,57 @@ pub mod v3 {
                 .unwrap();
             assert_eq!("from=token&to=token2&dir=b&limit=0", request.uri().query().unwrap(),);
         }
+
+        #[test]
+        fn bundled_thread_summaries() {
+            let thread_root = json!({
+                "content": { "body": "Thread root", "msgtype": "m.text" },
+                "event_id": "$root:example.org",
+                "origin_server_ts": 1,
+                "room_id": "!roomid:example.org",
+                "sender": "@alice:example.org",
+                "type": "m.room.message",
+                "unsigned": {
+                    "m.relations": {
+                        "m.thread": {
+                            "latest_event": {
+                                "content": { "body": "Reply", "msgtype": "m.text" },
+                                "event_id": "$reply:example.org",
+                                "origin_server_ts": 2,
+                                "room_id": "!roomid:example.org",
+                                "sender": "@bob:example.org",
+                                "type": "m.room.message",
+                            },
+                            "count": 7,
+                            "current_user_participated": true,
+                        },
+                    },
+                },
+            });
+            let other = json!({
+                "content": { "body": "Hello", "msgtype": "m.text" },
+                "event_id": "$other:example.org",
+                "origin_server_ts": 3,
+                "room_id": "!roomid:example.org",
+                "sender": "@alice:example.org",
+                "type": "m.room.message",
+            });
+
+            let mut response = Response::new();
+            response.chunk =
+                vec![Raw::new(&thread_root).unwrap().cast(), Raw::new(&other).unwrap().cast()];
+
+            let relations = response.bundled_relations().collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(relations.len(), 2);
+
+            let thread = relations[0].thread.as_ref().unwrap();
+            assert_eq!(thread.count, uint!(7));
+            assert!(thread.current_user_participated);
+            assert_eq!(
+                thread.latest_event.get_field::<String>("event_id").unwrap().as_deref(),
+                Some("$reply:example.org")
+            );
+            assert!(relations[1].is_empty());
+        }
     }
 }
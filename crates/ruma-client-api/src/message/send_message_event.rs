@@ -19,6 +19,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: PUT,
         rate_limited: false,
+        rate_limit_category: RoomSend,
         authentication: AccessToken,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/send/:event_type/:txn_id",
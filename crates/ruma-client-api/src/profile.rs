@@ -1,7 +1,166 @@
 //! Endpoints for user profiles.
 
+use std::num::NonZeroUsize;
+
+use ruma_common::{
+    events::room::member::{MembershipState, RoomMemberEventContent},
+    OwnedMxcUri, OwnedRoomId, UserId,
+};
+
+use crate::state::send_state_event;
+
 pub mod get_avatar_url;
 pub mod get_display_name;
 pub mod get_profile;
 pub mod set_avatar_url;
 pub mod set_display_name;
+
+/// A change of the global profile of a user, to propagate to their `m.room.member` events.
+///
+/// The homeserver usually propagates changes made with [`set_display_name`] and
+/// [`set_avatar_url`] to the rooms joined by the user, but clients that keep a per-room profile in
+/// sync, or that only update some rooms, need to send the updated member events themselves.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ProfileUpdate {
+    /// The new display name, if it changed.
+    ///
+    /// `Some(None)` removes the display name.
+    pub displayname: Option<Option<String>>,
+
+    /// The new avatar URL, if it changed.
+    ///
+    /// `Some(None)` removes the avatar.
+    pub avatar_url: Option<Option<OwnedMxcUri>>,
+}
+
+impl ProfileUpdate {
+    /// Creates an empty `ProfileUpdate`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a `ProfileUpdate` that changes the display name.
+    pub fn displayname(displayname: Option<String>) -> Self {
+        Self { displayname: Some(displayname), avatar_url: None }
+    }
+
+    /// Creates a `ProfileUpdate` that changes the avatar URL.
+    pub fn avatar_url(avatar_url: Option<OwnedMxcUri>) -> Self {
+        Self { displayname: None, avatar_url: Some(avatar_url) }
+    }
+
+    /// Whether this update doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.displayname.is_none() && self.avatar_url.is_none()
+    }
+
+    /// Apply this update to the given member event content.
+    ///
+    /// Returns `true` if the content changed.
+    pub fn apply(&self, content: &mut RoomMemberEventContent) -> bool {
+        let mut changed = false;
+
+        if let Some(displayname) = &self.displayname {
+            if content.displayname != *displayname {
+                content.displayname = displayname.clone();
+                changed = true;
+            }
+        }
+
+        if let Some(avatar_url) = &self.avatar_url {
+            if content.avatar_url != *avatar_url {
+                content.avatar_url = avatar_url.clone();
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Generate the requests to update the `m.room.member` event of the given user in the given
+    /// rooms, split in chunks of at most `chunk_size` requests.
+    ///
+    /// `rooms` contains the current content of the member event of the user in each room. Rooms
+    /// where the user is not joined, or where the member event is already up to date, are
+    /// skipped.
+    ///
+    /// Clients should wait between chunks to avoid being rate-limited by the homeserver.
+    ///
+    /// # Errors
+    ///
+    /// Since the request stores the content in serialized form, this function can fail if the
+    /// serialization of a `RoomMemberEventContent` fails.
+    pub fn member_update_requests(
+        &self,
+        user_id: &UserId,
+        rooms: impl IntoIterator<Item = (OwnedRoomId, RoomMemberEventContent)>,
+        chunk_size: NonZeroUsize,
+    ) -> serde_json::Result<Vec<Vec<send_state_event::v3::Request>>> {
+        let mut chunks: Vec<Vec<_>> = Vec::new();
+
+        for (room_id, mut content) in rooms {
+            if content.membership != MembershipState::Join || !self.apply(&mut content) {
+                continue;
+            }
+
+            let request = send_state_event::v3::Request::new(room_id, user_id, &content)?;
+
+            match chunks.last_mut() {
+                Some(chunk) if chunk.len() < chunk_size.get() => chunk.push(request),
+                _ => chunks.push(vec![request]),
+            }
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use ruma_common::{
+        events::room::member::{MembershipState, RoomMemberEventContent},
+        room_id, user_id,
+    };
+
+    use super::ProfileUpdate;
+
+    #[test]
+    fn member_update_requests() {
+        let user_id = user_id!("@alice:example.org");
+        let joined = RoomMemberEventContent::new(MembershipState::Join);
+        let mut up_to_date = joined.clone();
+        up_to_date.displayname = Some("Alice".to_owned());
+
+        let rooms = vec![
+            (room_id!("!a:example.org").to_owned(), joined.clone()),
+            (
+                room_id!("!b:example.org").to_owned(),
+                RoomMemberEventContent::new(MembershipState::Leave),
+            ),
+            (room_id!("!c:example.org").to_owned(), up_to_date),
+            (room_id!("!d:example.org").to_owned(), joined.clone()),
+            (room_id!("!e:example.org").to_owned(), joined),
+        ];
+
+        let chunks = ProfileUpdate::displayname(Some("Alice".to_owned()))
+            .member_update_requests(user_id, rooms, NonZeroUsize::new(2).unwrap())
+            .unwrap();
+
+        let room_ids: Vec<Vec<_>> = chunks
+            .iter()
+            .map(|chunk| chunk.iter().map(|request| request.room_id.as_str()).collect())
+            .collect();
+        assert_eq!(room_ids, [vec!["!a:example.org", "!d:example.org"], vec!["!e:example.org"]]);
+
+        let request = &chunks[0][0];
+        assert_eq!(request.state_key, "@alice:example.org");
+        assert_eq!(request.event_type.to_string(), "m.room.member");
+
+        let content = request.body.deserialize_as::<RoomMemberEventContent>().unwrap();
+        assert_eq!(content.displayname.as_deref(), Some("Alice"));
+        assert_eq!(content.membership, MembershipState::Join);
+    }
+}
@@ -280,9 +280,17 @@ impl PusherIds {
 }
 
 /// Information for an email pusher.
-#[derive(Clone, Debug, Default)]
+///
+/// Email pushers don't have a URL, the address to send emails to is the pushkey of the pusher.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-pub struct EmailPusherData;
+pub struct EmailPusherData {
+    /// The brand to use in the emails, instead of the default one of the homeserver.
+    // Not specified, but supported by Synapse.
+    #[cfg(feature = "unstable-unspecified")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+}
 
 impl EmailPusherData {
     /// Creates a new empty `EmailPusherData`.
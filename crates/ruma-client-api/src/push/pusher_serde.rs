@@ -1,8 +1,8 @@
-use ruma_common::serde::{from_raw_json_value, JsonObject};
+use ruma_common::serde::from_raw_json_value;
 use serde::{de, ser::SerializeStruct, Deserialize, Serialize};
 use serde_json::value::RawValue as RawJsonValue;
 
-use super::{EmailPusherData, Pusher, PusherIds, PusherKind};
+use super::{Pusher, PusherIds, PusherKind};
 
 #[derive(Debug, Deserialize)]
 struct PusherDeHelper {
@@ -41,9 +41,9 @@ impl Serialize for PusherKind {
                 st.serialize_field("kind", &"http")?;
                 st.serialize_field("data", data)?;
             }
-            PusherKind::Email(_) => {
+            PusherKind::Email(data) => {
                 st.serialize_field("kind", &"email")?;
-                st.serialize_field("data", &JsonObject::new())?;
+                st.serialize_field("data", data)?;
             }
             PusherKind::_Custom(custom) => {
                 st.serialize_field("kind", &custom.kind)?;
@@ -71,7 +71,7 @@ impl<'de> Deserialize<'de> for PusherKind {
 
         match kind.as_ref() {
             "http" => from_raw_json_value(&data).map(Self::Http),
-            "email" => Ok(Self::Email(EmailPusherData)),
+            "email" => from_raw_json_value(&data).map(Self::Email),
             _ => from_raw_json_value(&json).map(Self::_Custom),
         }
     }
@@ -139,6 +139,24 @@ mod tests {
         assert_matches!(from_json_value(json).unwrap(), PusherKind::Email(_));
     }
 
+    #[cfg(feature = "unstable-unspecified")]
+    #[test]
+    fn email_brand_serde() {
+        let mut data = EmailPusherData::new();
+        data.brand = Some("Ruma".to_owned());
+        let json = json!({
+            "kind": "email",
+            "data": {
+                "brand": "Ruma",
+            },
+        });
+
+        assert_eq!(to_json_value(PusherKind::Email(data)).unwrap(), json);
+
+        let data = assert_matches!(from_json_value(json).unwrap(), PusherKind::Email(data) => data);
+        assert_eq!(data.brand.as_deref(), Some("Ruma"));
+    }
+
     #[test]
     fn deserialize_http() {
         let json = json!({
@@ -5,6 +5,7 @@ pub mod create_room;
 #[cfg(feature = "unstable-msc3030")]
 pub mod get_event_by_timestamp;
 pub mod get_room_event;
+pub mod get_room_initial_sync;
 pub mod report_content;
 pub mod upgrade_room;
 
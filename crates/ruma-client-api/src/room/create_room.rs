@@ -37,7 +37,7 @@ pub mod v3 {
     };
 
     /// Request type for the `create_room` endpoint.
-    #[request(error = crate::Error)]
+    #[request(error = crate::Error, builder)]
     #[derive(Default)]
     pub struct Request {
         /// Extra keys to be added to the content of the `m.room.create`.
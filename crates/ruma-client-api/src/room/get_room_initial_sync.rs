@@ -0,0 +1,116 @@
+//! `GET /_matrix/client/*/rooms/{roomId}/initialSync`
+//!
+//! Get a snapshot of the state and the latest messages of a room.
+//!
+//! This endpoint is deprecated in favor of `/sync`, but is still used to preview rooms the user is
+//! not joined to, if their history is world-readable.
+
+pub mod v3 {
+    //! `/v3/` ([spec])
+    //!
+    //! [spec]: https://spec.matrix.org/v1.4/client-server-api/#get_matrixclientv3roomsroomidinitialsync
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        events::{
+            room::member::MembershipState, AnyRoomAccountDataEvent, AnyStateEvent, AnyTimelineEvent,
+        },
+        metadata,
+        serde::Raw,
+        OwnedRoomId,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use crate::room::Visibility;
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: AccessToken,
+        history: {
+            1.0 => "/_matrix/client/r0/rooms/:room_id/initialSync",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/initialSync",
+        }
+    };
+
+    /// Request type for the `get_room_initial_sync` endpoint.
+    #[request(error = crate::Error)]
+    pub struct Request {
+        /// The room to get the snapshot of.
+        #[ruma_api(path)]
+        pub room_id: OwnedRoomId,
+    }
+
+    /// Response type for the `get_room_initial_sync` endpoint.
+    #[response(error = crate::Error)]
+    pub struct Response {
+        /// The ID of this room.
+        pub room_id: OwnedRoomId,
+
+        /// The user's membership state in this room, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub membership: Option<MembershipState>,
+
+        /// The latest events of the room, in chronological order.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub messages: Option<PaginationChunk>,
+
+        /// The current state of the room.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub state: Vec<Raw<AnyStateEvent>>,
+
+        /// Whether this room is visible in the room directory.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub visibility: Option<Visibility>,
+
+        /// The private data that this user has attached to this room.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub account_data: Vec<Raw<AnyRoomAccountDataEvent>>,
+    }
+
+    impl Request {
+        /// Creates a new `Request` with the given room ID.
+        pub fn new(room_id: OwnedRoomId) -> Self {
+            Self { room_id }
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given room ID.
+        pub fn new(room_id: OwnedRoomId) -> Self {
+            Self {
+                room_id,
+                membership: None,
+                messages: None,
+                state: Vec::new(),
+                visibility: None,
+                account_data: Vec::new(),
+            }
+        }
+    }
+
+    /// A chunk of the timeline of a room.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct PaginationChunk {
+        /// The events, in chronological order.
+        pub chunk: Vec<Raw<AnyTimelineEvent>>,
+
+        /// A token which correlates to the start of `chunk`.
+        ///
+        /// It can be passed to `/messages` to retrieve earlier events.
+        pub start: String,
+
+        /// A token which correlates to the end of `chunk`.
+        ///
+        /// It can be passed to `/messages` to retrieve later events.
+        pub end: String,
+    }
+
+    impl PaginationChunk {
+        /// Creates a new `PaginationChunk` with the given events and tokens.
+        pub fn new(chunk: Vec<Raw<AnyTimelineEvent>>, start: String, end: String) -> Self {
+            Self { chunk, start, end }
+        }
+    }
+}
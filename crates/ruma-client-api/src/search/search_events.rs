@@ -32,7 +32,7 @@ pub mod v3 {
     };
 
     /// Request type for the `search` endpoint.
-    #[request(error = crate::Error)]
+    #[request(error = crate::Error, builder)]
     pub struct Request {
         /// The point to return events from.
         ///
@@ -428,7 +428,7 @@ pub mod v3 {
     #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
     pub struct SearchResult {
         /// Context for result, if requested.
-        #[serde(skip_serializing_if = "EventContextResult::is_empty")]
+        #[serde(default, skip_serializing_if = "EventContextResult::is_empty")]
         pub context: EventContextResult,
 
         /// A number that describes how closely this result matches the search.
@@ -489,6 +489,7 @@ pub mod v3 {
     /// Represents either a room or user ID for returning grouped search results.
     #[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
     #[allow(clippy::exhaustive_enums)]
+    #[serde(untagged)]
     pub enum OwnedRoomIdOrUserId {
         /// Represents a room ID.
         RoomId(OwnedRoomId),
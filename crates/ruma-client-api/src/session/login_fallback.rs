@@ -5,9 +5,10 @@
 //! [spec]: https://spec.matrix.org/v1.4/client-server-api/#login-fallback
 
 use ruma_common::{
-    api::{request, response, Metadata},
-    metadata, OwnedDeviceId,
+    api::{request, response, MatrixVersion, Metadata},
+    metadata, DeviceId, OwnedDeviceId,
 };
+use serde::Serialize;
 
 const METADATA: Metadata = metadata! {
     method: GET,
@@ -51,6 +52,32 @@ impl Request {
     ) -> Self {
         Self { device_id, initial_device_display_name }
     }
+
+    /// The URL of the login fallback page on the homeserver with the given base URL.
+    ///
+    /// Clients that don't support any of the login flows of the homeserver can open this page in
+    /// a web browser or a webview to let the user log in.
+    pub fn fallback_url(&self, homeserver_url: &str) -> String {
+        let query = serde_html_form::to_string(FallbackQuery {
+            device_id: self.device_id.as_deref(),
+            initial_device_display_name: self.initial_device_display_name.as_deref(),
+        })
+        .expect("serializing a query with string values should not fail");
+
+        METADATA
+            .make_endpoint_url(&[MatrixVersion::V1_0], homeserver_url, &[], &query)
+            .expect("the login fallback has a stable path since Matrix 1.0")
+    }
+}
+
+/// The query parameters of the login fallback URL.
+#[derive(Serialize)]
+struct FallbackQuery<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_id: Option<&'a DeviceId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initial_device_display_name: Option<&'a str>,
 }
 
 impl Response {
@@ -59,3 +86,23 @@ impl Response {
         Self { body }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::device_id;
+
+    use super::Request;
+
+    #[test]
+    fn fallback_url() {
+        assert_eq!(
+            Request::default().fallback_url("https://homeserver.tld/"),
+            "https://homeserver.tld/_matrix/static/client/login/"
+        );
+        assert_eq!(
+            Request::new(Some(device_id!("ABCD").to_owned()), Some("My Phone".to_owned()))
+                .fallback_url("https://homeserver.tld"),
+            "https://homeserver.tld/_matrix/static/client/login/?device_id=ABCD&initial_device_display_name=My+Phone"
+        );
+    }
+}
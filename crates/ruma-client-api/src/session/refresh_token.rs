@@ -35,6 +35,7 @@ pub mod v3 {
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: true,
+        rate_limit_category: Login,
         authentication: None,
         history: {
             unstable => "/_matrix/client/unstable/org.matrix.msc2918/refresh",
@@ -5,11 +5,16 @@ pub mod v3 {
     //!
     //! [spec]: https://spec.matrix.org/v1.4/client-server-api/#get_matrixclientv3loginssoredirect
 
+    use std::fmt;
+
     use http::header::LOCATION;
     use ruma_common::{
-        api::{request, response, Metadata},
+        api::{error::IntoHttpError, request, response, MatrixVersion, Metadata},
         metadata,
     };
+    use serde::{Deserialize, Serialize};
+
+    use crate::session::login::v3::Token;
 
     const METADATA: Metadata = metadata! {
         method: GET,
@@ -44,6 +49,28 @@ pub mod v3 {
         pub fn new(redirect_url: String) -> Self {
             Self { redirect_url }
         }
+
+        /// The URL of this endpoint on the homeserver with the given base URL.
+        ///
+        /// Clients can open this URL in a web browser or a webview to let the user log in with
+        /// SSO. The homeserver then redirects the user to `redirect_url` with the query parameters
+        /// described by [`SsoCallback`].
+        pub fn sso_url(
+            &self,
+            homeserver_url: &str,
+            considering_versions: &[MatrixVersion],
+        ) -> Result<String, IntoHttpError> {
+            let query =
+                serde_html_form::to_string(RedirectQuery { redirect_url: &self.redirect_url })?;
+            METADATA.make_endpoint_url(considering_versions, homeserver_url, &[], &query)
+        }
+    }
+
+    /// The query parameters of the URL of this endpoint.
+    #[derive(Serialize)]
+    struct RedirectQuery<'a> {
+        #[serde(rename = "redirectUrl")]
+        redirect_url: &'a str,
     }
 
     impl Response {
@@ -53,11 +80,80 @@ pub mod v3 {
         }
     }
 
+    /// The query parameters added by the homeserver to the `redirect_url` of the request, once the
+    /// user completed the authentication with the SSO identity provider.
+    ///
+    /// The login token can be used to log in with [`Token`].
+    #[derive(Clone, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct SsoCallback {
+        /// The token to log in with.
+        #[serde(rename = "loginToken")]
+        pub login_token: String,
+    }
+
+    impl SsoCallback {
+        /// Creates a new `SsoCallback` with the given login token.
+        pub fn new(login_token: String) -> Self {
+            Self { login_token }
+        }
+
+        /// Parse the callback from the query string of the URL the user was redirected to.
+        pub fn from_query(query: &str) -> Result<Self, serde_html_form::de::Error> {
+            serde_html_form::from_str(query)
+        }
+
+        /// Parse the callback from the full URL the user was redirected to.
+        pub fn from_url(url: &str) -> Result<Self, serde_html_form::de::Error> {
+            let url = url.split_once('#').map_or(url, |(url, _)| url);
+            let query = url.split_once('?').map_or("", |(_, query)| query);
+            Self::from_query(query)
+        }
+
+        /// Build the URL the user is redirected to, from the `redirect_url` of the request.
+        pub fn callback_url(&self, redirect_url: &str) -> String {
+            let query = serde_html_form::to_string(self)
+                .expect("serializing a query with string values should not fail");
+
+            // The query must be placed before the fragment, if any.
+            let (base, fragment) = match redirect_url.split_once('#') {
+                Some((base, fragment)) => (base, Some(fragment)),
+                None => (redirect_url, None),
+            };
+            let separator = match base.split_once('?') {
+                Some((_, "")) => "",
+                Some(_) => "&",
+                None => "?",
+            };
+
+            let mut url = format!("{base}{separator}{query}");
+            if let Some(fragment) = fragment {
+                url.push('#');
+                url.push_str(fragment);
+            }
+
+            url
+        }
+    }
+
+    impl fmt::Debug for SsoCallback {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Self { login_token: _ } = self;
+            f.debug_struct("SsoCallback").finish_non_exhaustive()
+        }
+    }
+
+    impl From<SsoCallback> for Token {
+        fn from(callback: SsoCallback) -> Self {
+            Token::new(callback.login_token)
+        }
+    }
+
     #[cfg(all(test, feature = "client"))]
     mod tests {
         use ruma_common::api::{MatrixVersion, OutgoingRequest, SendAccessToken};
 
-        use super::Request;
+        use super::{Request, SsoCallback};
 
         #[test]
         fn serialize_sso_login_request_uri() {
@@ -75,5 +171,32 @@ pub mod v3 {
             "https://homeserver.tld/_matrix/client/v3/login/sso/redirect?redirectUrl=https%3A%2F%2Fexample.com%2Fsso"
         );
         }
+
+        #[test]
+        fn sso_url() {
+            let req = Request::new("https://example.com/sso".to_owned());
+
+            assert_eq!(
+                req.sso_url("https://homeserver.tld/", &[MatrixVersion::V1_0]).unwrap(),
+                "https://homeserver.tld/_matrix/client/r0/login/sso/redirect?redirectUrl=https%3A%2F%2Fexample.com%2Fsso"
+            );
+        }
+
+        #[test]
+        fn sso_callback() {
+            let callback = SsoCallback::from_url("https://example.com/sso?loginToken=abc%2Fdef#top")
+                .unwrap();
+            assert_eq!(callback.login_token, "abc/def");
+            SsoCallback::from_url("https://example.com/sso?error=denied").unwrap_err();
+
+            assert_eq!(
+                callback.callback_url("https://example.com/sso?lang=en#top"),
+                "https://example.com/sso?lang=en&loginToken=abc%2Fdef#top"
+            );
+            assert_eq!(
+                callback.callback_url("https://example.com/sso"),
+                "https://example.com/sso?loginToken=abc%2Fdef"
+            );
+        }
     }
 }
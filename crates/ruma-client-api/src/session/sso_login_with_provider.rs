@@ -9,9 +9,10 @@ pub mod v3 {
 
     use http::header::LOCATION;
     use ruma_common::{
-        api::{request, response, Metadata},
+        api::{error::IntoHttpError, request, response, MatrixVersion, Metadata},
         metadata,
     };
+    use serde::Serialize;
 
     const METADATA: Metadata = metadata! {
         method: GET,
@@ -50,6 +51,33 @@ pub mod v3 {
         pub fn new(idp_id: String, redirect_url: String) -> Self {
             Self { idp_id, redirect_url }
         }
+
+        /// The URL of this endpoint on the homeserver with the given base URL.
+        ///
+        /// Clients can open this URL in a web browser or a webview to let the user log in with
+        /// SSO. The homeserver then redirects the user to `redirect_url` with the query parameters
+        /// described by [`SsoCallback`](crate::session::sso_login::v3::SsoCallback).
+        pub fn sso_url(
+            &self,
+            homeserver_url: &str,
+            considering_versions: &[MatrixVersion],
+        ) -> Result<String, IntoHttpError> {
+            let query =
+                serde_html_form::to_string(RedirectQuery { redirect_url: &self.redirect_url })?;
+            METADATA.make_endpoint_url(
+                considering_versions,
+                homeserver_url,
+                &[&self.idp_id],
+                &query,
+            )
+        }
+    }
+
+    /// The query parameters of the URL of this endpoint.
+    #[derive(Serialize)]
+    struct RedirectQuery<'a> {
+        #[serde(rename = "redirectUrl")]
+        redirect_url: &'a str,
     }
 
     impl Response {
@@ -83,5 +111,20 @@ pub mod v3 {
             "https://homeserver.tld/_matrix/client/v3/login/sso/redirect/provider?redirectUrl=https%3A%2F%2Fexample.com%2Fsso"
         );
         }
+
+        #[test]
+        fn sso_url() {
+            let req = Request::new("provider".to_owned(), "https://example.com/sso".to_owned());
+            let url = req.sso_url("https://homeserver.tld", &[MatrixVersion::V1_1]).unwrap();
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://homeserver.tld",
+                    SendAccessToken::None,
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+
+            assert_eq!(url, http_req.uri().to_string());
+        }
     }
 }
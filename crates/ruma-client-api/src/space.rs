@@ -79,6 +79,20 @@ pub struct SpaceHierarchyRoomsChunk {
     pub children_state: Vec<Raw<HierarchySpaceChildEvent>>,
 }
 
+impl SpaceHierarchyRoomsChunk {
+    /// Whether this room is a space.
+    pub fn is_space(&self) -> bool {
+        self.room_type == Some(RoomType::Space)
+    }
+
+    /// Whether the user can join this room without being invited.
+    ///
+    /// See [`SpaceRoomJoinRule::is_joinable`].
+    pub fn is_joinable(&self) -> bool {
+        self.join_rule.is_joinable()
+    }
+}
+
 /// Initial set of mandatory fields of `SpaceHierarchyRoomsChunk`.
 ///
 /// This struct will not be updated even if additional fields are added to
@@ -177,3 +191,45 @@ pub enum SpaceRoomJoinRule {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+impl SpaceRoomJoinRule {
+    /// Whether a user can join a room with this join rule without being invited.
+    ///
+    /// This is the case for public rooms, and for restricted rooms since the server only returns
+    /// the restricted rooms that the user is allowed to join.
+    pub fn is_joinable(&self) -> bool {
+        matches!(self, Self::Public | Self::Restricted | Self::KnockRestricted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_common::{room::RoomType, room_id};
+
+    use super::{SpaceHierarchyRoomsChunk, SpaceHierarchyRoomsChunkInit, SpaceRoomJoinRule};
+
+    #[test]
+    fn chunk_filters() {
+        let mut chunk = SpaceHierarchyRoomsChunk::from(SpaceHierarchyRoomsChunkInit {
+            num_joined_members: uint!(5),
+            room_id: room_id!("!space:localhost").to_owned(),
+            world_readable: false,
+            guest_can_join: false,
+            join_rule: SpaceRoomJoinRule::Invite,
+            children_state: Vec::new(),
+        });
+        assert!(!chunk.is_space());
+        assert!(!chunk.is_joinable());
+
+        chunk.room_type = Some(RoomType::Space);
+        chunk.join_rule = SpaceRoomJoinRule::Restricted;
+        assert!(chunk.is_space());
+        assert!(chunk.is_joinable());
+
+        chunk.room_type = Some(RoomType::from("org.example.custom"));
+        chunk.join_rule = SpaceRoomJoinRule::Knock;
+        assert!(!chunk.is_space());
+        assert!(!chunk.is_joinable());
+    }
+}
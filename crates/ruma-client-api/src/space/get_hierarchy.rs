@@ -7,6 +7,8 @@ pub mod v1 {
     //!
     //! [spec]: https://spec.matrix.org/v1.4/client-server-api/#get_matrixclientv1roomsroomidhierarchy
 
+    use std::collections::BTreeSet;
+
     use js_int::UInt;
     use ruma_common::{
         api::{request, response, Metadata},
@@ -85,5 +87,78 @@ pub mod v1 {
         pub fn new() -> Self {
             Default::default()
         }
+
+        /// The rooms of this response that are spaces.
+        pub fn spaces(&self) -> impl Iterator<Item = &SpaceHierarchyRoomsChunk> {
+            self.rooms.iter().filter(|room| room.is_space())
+        }
+
+        /// The rooms of this response that the user can join without being invited.
+        pub fn joinable_rooms(&self) -> impl Iterator<Item = &SpaceHierarchyRoomsChunk> {
+            self.rooms.iter().filter(|room| room.is_joinable())
+        }
+
+        /// Remove the rooms that were already returned in a previous page.
+        ///
+        /// `seen` contains the IDs of the rooms of the previous pages, the IDs of the remaining
+        /// rooms are added to it. A room that appears several times in this response is only
+        /// kept once.
+        pub fn dedup_rooms(&mut self, seen: &mut BTreeSet<OwnedRoomId>) {
+            self.rooms.retain(|room| seen.insert(room.room_id.clone()));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeSet;
+
+        use js_int::uint;
+        use ruma_common::{room::RoomType, room_id, OwnedRoomId};
+
+        use super::Response;
+        use crate::space::{
+            SpaceHierarchyRoomsChunk, SpaceHierarchyRoomsChunkInit, SpaceRoomJoinRule,
+        };
+
+        fn chunk(room_id: OwnedRoomId, is_space: bool) -> SpaceHierarchyRoomsChunk {
+            let mut chunk = SpaceHierarchyRoomsChunk::from(SpaceHierarchyRoomsChunkInit {
+                num_joined_members: uint!(1),
+                room_id,
+                world_readable: false,
+                guest_can_join: false,
+                join_rule: SpaceRoomJoinRule::Public,
+                children_state: Vec::new(),
+            });
+            if is_space {
+                chunk.room_type = Some(RoomType::Space);
+            }
+            chunk
+        }
+
+        #[test]
+        fn filter_and_dedup_pages() {
+            let mut seen = BTreeSet::new();
+
+            let mut first_page = Response::new();
+            first_page.rooms = vec![
+                chunk(room_id!("!space:localhost").to_owned(), true),
+                chunk(room_id!("!a:localhost").to_owned(), false),
+            ];
+            first_page.dedup_rooms(&mut seen);
+            assert_eq!(first_page.rooms.len(), 2);
+            assert_eq!(first_page.spaces().count(), 1);
+            assert_eq!(first_page.joinable_rooms().count(), 2);
+
+            let mut second_page = Response::new();
+            second_page.rooms = vec![
+                chunk(room_id!("!a:localhost").to_owned(), false),
+                chunk(room_id!("!b:localhost").to_owned(), false),
+                chunk(room_id!("!b:localhost").to_owned(), false),
+            ];
+            second_page.dedup_rooms(&mut seen);
+            assert_eq!(second_page.rooms.len(), 1);
+            assert_eq!(second_page.rooms[0].room_id, "!b:localhost");
+            assert_eq!(seen.len(), 3);
+        }
     }
 }
@@ -2,8 +2,10 @@
 //!
 //! Get all new events from all rooms since the last sync or a given point in time.
 
+use std::collections::BTreeMap;
+
 use js_int::UInt;
-use ruma_common::OwnedUserId;
+use ruma_common::{events::AnyToDeviceEvent, serde::Raw, DeviceKeyAlgorithm, OwnedUserId};
 use serde::{self, Deserialize, Serialize};
 
 pub mod v3;
@@ -62,3 +64,62 @@ impl DeviceLists {
         self.changed.is_empty() && self.left.is_empty()
     }
 }
+
+/// Messages sent directly between devices.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ToDevice {
+    /// A list of to-device events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Raw<AnyToDeviceEvent>>,
+}
+
+impl ToDevice {
+    /// Creates an empty `ToDevice`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns true if there are no to-device events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Information on E2EE device updates and one-time keys.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct E2EE {
+    /// Information on E2EE device updates.
+    ///
+    /// Only present on an incremental sync.
+    #[serde(default, skip_serializing_if = "DeviceLists::is_empty")]
+    pub device_lists: DeviceLists,
+
+    /// For each key algorithm, the number of unclaimed one-time keys
+    /// currently held on the server for a device.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub device_one_time_keys_count: BTreeMap<DeviceKeyAlgorithm, UInt>,
+
+    /// For each key algorithm, the number of unclaimed one-time keys
+    /// currently held on the server for a device.
+    ///
+    /// The presence of this field indicates that the server supports
+    /// fallback keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_unused_fallback_key_types: Option<Vec<DeviceKeyAlgorithm>>,
+}
+
+impl E2EE {
+    /// Creates an empty `E2EE`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns true if there are no E2EE updates.
+    pub fn is_empty(&self) -> bool {
+        self.device_lists.is_empty()
+            && self.device_one_time_keys_count.is_empty()
+            && self.device_unused_fallback_key_types.is_none()
+    }
+}
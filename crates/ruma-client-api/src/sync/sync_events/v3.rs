@@ -4,14 +4,14 @@
 
 use std::{collections::BTreeMap, time::Duration};
 
-use super::{DeviceLists, UnreadNotificationsCount};
+use super::{DeviceLists, ToDevice, UnreadNotificationsCount};
 use js_int::UInt;
 use ruma_common::{
     api::{request, response, Metadata},
     events::{
         presence::PresenceEvent, AnyGlobalAccountDataEvent, AnyRoomAccountDataEvent,
         AnyStrippedStateEvent, AnySyncEphemeralRoomEvent, AnySyncStateEvent, AnySyncTimelineEvent,
-        AnyToDeviceEvent,
+        BundledRelations,
     },
     metadata,
     presence::PresenceState,
@@ -363,6 +363,17 @@ impl Timeline {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// The [bundled aggregations] of the events in `events`, in the same order.
+    ///
+    /// This gives access to the summary of the thread of events that are thread roots.
+    ///
+    /// [bundled aggregations]: https://spec.matrix.org/v1.5/client-server-api/#aggregations
+    pub fn bundled_relations(
+        &self,
+    ) -> impl Iterator<Item = serde_json::Result<BundledRelations>> + '_ {
+        self.events.iter().map(BundledRelations::from_raw_event)
+    }
 }
 
 /// State events in the room.
@@ -572,27 +583,6 @@ impl Presence {
     }
 }
 
-/// Messages sent directly between devices.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
-#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-pub struct ToDevice {
-    /// A list of to-device events.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub events: Vec<Raw<AnyToDeviceEvent>>,
-}
-
-impl ToDevice {
-    /// Creates an empty `ToDevice`.
-    pub fn new() -> Self {
-        Default::default()
-    }
-
-    /// Returns true if there are no to-device events.
-    pub fn is_empty(&self) -> bool {
-        self.events.is_empty()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use assign::assign;
@@ -6,7 +6,7 @@
 
 use std::{collections::BTreeMap, time::Duration};
 
-use super::{DeviceLists, UnreadNotificationsCount};
+use super::{UnreadNotificationsCount, E2EE};
 use js_int::UInt;
 use ruma_common::{
     api::{request, response, Metadata},
@@ -16,7 +16,7 @@ use ruma_common::{
     },
     metadata,
     serde::{duration::opt_ms, Raw},
-    DeviceKeyAlgorithm, OwnedRoomId,
+    OwnedRoomId,
 };
 use serde::{Deserialize, Serialize};
 
@@ -300,9 +300,11 @@ pub struct SyncOp {
     pub op: SlidingOp,
 
     /// The range this list update applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub range: Option<(UInt, UInt)>,
 
     /// Or the specific index the update applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub index: Option<UInt>,
 
     /// The list of room_ids updates to apply.
@@ -310,6 +312,7 @@ pub struct SyncOp {
     pub room_ids: Vec<OwnedRoomId>,
 
     /// On insert and delete we are only receiving exactly one room_id.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub room_id: Option<OwnedRoomId>,
 }
 
@@ -472,32 +475,6 @@ pub struct E2EEConfig {
     pub enabled: Option<bool>,
 }
 
-/// E2EE extension response data.
-///
-/// According to [MSC3884](https://github.com/matrix-org/matrix-spec-proposals/pull/3884).
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-pub struct E2EE {
-    /// Information on E2EE device updates.
-    ///
-    /// Only present on an incremental sync.
-    #[serde(default, skip_serializing_if = "DeviceLists::is_empty")]
-    pub device_lists: DeviceLists,
-
-    /// For each key algorithm, the number of unclaimed one-time keys
-    /// currently held on the server for a device.
-    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-    pub device_one_time_keys_count: BTreeMap<DeviceKeyAlgorithm, UInt>,
-
-    /// For each key algorithm, the number of unclaimed one-time keys
-    /// currently held on the server for a device.
-    ///
-    /// The presence of this field indicates that the server supports
-    /// fallback keys.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub device_unused_fallback_key_types: Option<Vec<DeviceKeyAlgorithm>>,
-}
-
 /// Account-data extension configuration.
 ///
 /// Not yet part of the spec proposal. Taken from the reference implementation
@@ -0,0 +1,421 @@
+#![cfg(feature = "fixtures")]
+
+#[cfg(feature = "unstable-msc3814")]
+use ruma_client_api::dehydrated_device;
+use ruma_client_api::{
+    account, alias, appservice, backup, config, context, device, directory, discovery, filter,
+    fixtures, keys, knock, media, membership, message, presence, profile, push, read_marker,
+    receipt, redact, relations, room, search, server, session, space, state, sync, tag, thirdparty,
+    threads, to_device, typing, user_directory, voip,
+};
+use ruma_common::assert_round_trip;
+
+#[test]
+fn account() {
+    use fixtures::account as f;
+
+    assert_round_trip!(account::add_3pid::v3, f::ADD_3PID);
+    assert_round_trip!(account::bind_3pid::v3, f::BIND_3PID);
+    assert_round_trip!(account::change_password::v3, f::CHANGE_PASSWORD);
+    assert_round_trip!(
+        account::check_registration_token_validity::v1,
+        f::CHECK_REGISTRATION_TOKEN_VALIDITY
+    );
+    assert_round_trip!(account::deactivate::v3, f::DEACTIVATE);
+    assert_round_trip!(account::delete_3pid::v3, f::DELETE_3PID);
+    assert_round_trip!(account::get_3pids::v3, f::GET_3PIDS);
+    #[cfg(feature = "unstable-msc2000")]
+    assert_round_trip!(account::get_password_policy::v1, f::GET_PASSWORD_POLICY);
+    assert_round_trip!(account::get_username_availability::v3, f::GET_USERNAME_AVAILABILITY);
+    assert_round_trip!(account::register::v3, f::REGISTER);
+    assert_round_trip!(
+        account::request_3pid_management_token_via_email::v3,
+        f::REQUEST_3PID_MANAGEMENT_TOKEN_VIA_EMAIL
+    );
+    assert_round_trip!(
+        account::request_3pid_management_token_via_msisdn::v3,
+        f::REQUEST_3PID_MANAGEMENT_TOKEN_VIA_MSISDN
+    );
+    assert_round_trip!(account::request_openid_token::v3, f::REQUEST_OPENID_TOKEN);
+    assert_round_trip!(
+        account::request_password_change_token_via_email::v3,
+        f::REQUEST_PASSWORD_CHANGE_TOKEN_VIA_EMAIL
+    );
+    assert_round_trip!(
+        account::request_password_change_token_via_msisdn::v3,
+        f::REQUEST_PASSWORD_CHANGE_TOKEN_VIA_MSISDN
+    );
+    assert_round_trip!(
+        account::request_registration_token_via_email::v3,
+        f::REQUEST_REGISTRATION_TOKEN_VIA_EMAIL
+    );
+    assert_round_trip!(
+        account::request_registration_token_via_msisdn::v3,
+        f::REQUEST_REGISTRATION_TOKEN_VIA_MSISDN
+    );
+    assert_round_trip!(account::unbind_3pid::v3, f::UNBIND_3PID);
+    assert_round_trip!(account::whoami::v3, f::WHOAMI);
+}
+
+#[test]
+fn alias() {
+    use fixtures::alias as f;
+
+    assert_round_trip!(alias::create_alias::v3, f::CREATE_ALIAS);
+    assert_round_trip!(alias::delete_alias::v3, f::DELETE_ALIAS);
+    assert_round_trip!(alias::get_alias::v3, f::GET_ALIAS);
+}
+
+#[test]
+fn appservice() {
+    use fixtures::appservice as f;
+
+    assert_round_trip!(appservice::set_room_visibility::v3, f::SET_ROOM_VISIBILITY);
+}
+
+#[test]
+fn backup() {
+    use fixtures::backup as f;
+
+    assert_round_trip!(backup::add_backup_keys::v3, f::ADD_BACKUP_KEYS);
+    assert_round_trip!(backup::add_backup_keys_for_room::v3, f::ADD_BACKUP_KEYS_FOR_ROOM);
+    assert_round_trip!(backup::add_backup_keys_for_session::v3, f::ADD_BACKUP_KEYS_FOR_SESSION);
+    assert_round_trip!(backup::create_backup_version::v3, f::CREATE_BACKUP_VERSION);
+    assert_round_trip!(backup::delete_backup_keys::v3, f::DELETE_BACKUP_KEYS);
+    assert_round_trip!(backup::delete_backup_keys_for_room::v3, f::DELETE_BACKUP_KEYS_FOR_ROOM);
+    assert_round_trip!(
+        backup::delete_backup_keys_for_session::v3,
+        f::DELETE_BACKUP_KEYS_FOR_SESSION
+    );
+    assert_round_trip!(backup::delete_backup_version::v3, f::DELETE_BACKUP_VERSION);
+    assert_round_trip!(backup::get_backup_info::v3, f::GET_BACKUP_INFO);
+    assert_round_trip!(backup::get_backup_keys::v3, f::GET_BACKUP_KEYS);
+    assert_round_trip!(backup::get_backup_keys_for_room::v3, f::GET_BACKUP_KEYS_FOR_ROOM);
+    assert_round_trip!(backup::get_backup_keys_for_session::v3, f::GET_BACKUP_KEYS_FOR_SESSION);
+    assert_round_trip!(backup::get_latest_backup_info::v3, f::GET_LATEST_BACKUP_INFO);
+    assert_round_trip!(backup::update_backup_version::v3, f::UPDATE_BACKUP_VERSION);
+}
+
+#[test]
+fn config() {
+    use fixtures::config as f;
+
+    assert_round_trip!(config::get_global_account_data::v3, f::GET_GLOBAL_ACCOUNT_DATA);
+    assert_round_trip!(config::get_room_account_data::v3, f::GET_ROOM_ACCOUNT_DATA);
+    assert_round_trip!(config::set_global_account_data::v3, f::SET_GLOBAL_ACCOUNT_DATA);
+    assert_round_trip!(config::set_room_account_data::v3, f::SET_ROOM_ACCOUNT_DATA);
+}
+
+#[test]
+fn context() {
+    use fixtures::context as f;
+
+    assert_round_trip!(context::get_context::v3, f::GET_CONTEXT);
+}
+
+#[test]
+#[cfg(feature = "unstable-msc3814")]
+fn dehydrated_device() {
+    use fixtures::dehydrated_device as f;
+
+    assert_round_trip!(
+        dehydrated_device::delete_dehydrated_device::unstable,
+        f::DELETE_DEHYDRATED_DEVICE
+    );
+    assert_round_trip!(
+        dehydrated_device::get_dehydrated_device::unstable,
+        f::GET_DEHYDRATED_DEVICE
+    );
+    assert_round_trip!(dehydrated_device::get_events::unstable, f::GET_EVENTS);
+    assert_round_trip!(
+        dehydrated_device::put_dehydrated_device::unstable,
+        f::PUT_DEHYDRATED_DEVICE
+    );
+}
+
+#[test]
+fn device() {
+    use fixtures::device as f;
+
+    assert_round_trip!(device::delete_device::v3, f::DELETE_DEVICE);
+    assert_round_trip!(device::delete_devices::v3, f::DELETE_DEVICES);
+    assert_round_trip!(device::get_device::v3, f::GET_DEVICE);
+    assert_round_trip!(device::get_devices::v3, f::GET_DEVICES);
+    assert_round_trip!(device::update_device::v3, f::UPDATE_DEVICE);
+}
+
+#[test]
+fn directory() {
+    use fixtures::directory as f;
+
+    assert_round_trip!(directory::get_public_rooms::v3, f::GET_PUBLIC_ROOMS);
+    assert_round_trip!(directory::get_public_rooms_filtered::v3, f::GET_PUBLIC_ROOMS_FILTERED);
+    assert_round_trip!(directory::get_room_visibility::v3, f::GET_ROOM_VISIBILITY);
+    assert_round_trip!(directory::set_room_visibility::v3, f::SET_ROOM_VISIBILITY);
+}
+
+#[test]
+fn discovery() {
+    use fixtures::discovery as f;
+
+    assert_round_trip!(discovery::discover_homeserver, f::DISCOVER_HOMESERVER);
+    assert_round_trip!(discovery::get_capabilities::v3, f::GET_CAPABILITIES);
+    assert_round_trip!(discovery::get_supported_versions, f::GET_SUPPORTED_VERSIONS);
+}
+
+#[test]
+fn filter() {
+    use fixtures::filter as f;
+
+    assert_round_trip!(filter::create_filter::v3, f::CREATE_FILTER);
+    assert_round_trip!(filter::get_filter::v3, f::GET_FILTER);
+}
+
+#[test]
+fn keys() {
+    use fixtures::keys as f;
+
+    assert_round_trip!(keys::claim_keys::v3, f::CLAIM_KEYS);
+    assert_round_trip!(keys::get_key_changes::v3, f::GET_KEY_CHANGES);
+    assert_round_trip!(keys::get_keys::v3, f::GET_KEYS);
+    assert_round_trip!(keys::upload_keys::v3, f::UPLOAD_KEYS);
+    assert_round_trip!(keys::upload_signatures::v3, f::UPLOAD_SIGNATURES);
+    assert_round_trip!(keys::upload_signing_keys::v3, f::UPLOAD_SIGNING_KEYS);
+}
+
+#[test]
+fn knock() {
+    use fixtures::knock as f;
+
+    assert_round_trip!(knock::knock_room::v3, f::KNOCK_ROOM);
+}
+
+#[test]
+fn media() {
+    use fixtures::media as f;
+
+    #[cfg(feature = "unstable-msc2246")]
+    assert_round_trip!(media::create_mxc_uri::unstable, f::CREATE_MXC_URI);
+    assert_round_trip!(media::get_media_config::v3, f::GET_MEDIA_CONFIG);
+    assert_round_trip!(media::get_media_preview::v3, f::GET_MEDIA_PREVIEW);
+}
+
+#[test]
+fn membership() {
+    use fixtures::membership as f;
+
+    assert_round_trip!(membership::ban_user::v3, f::BAN_USER);
+    assert_round_trip!(membership::forget_room::v3, f::FORGET_ROOM);
+    assert_round_trip!(membership::get_member_events::v3, f::GET_MEMBER_EVENTS);
+    assert_round_trip!(membership::invite_user::v3, f::INVITE_USER);
+    assert_round_trip!(membership::join_room_by_id::v3, f::JOIN_ROOM_BY_ID);
+    assert_round_trip!(membership::join_room_by_id_or_alias::v3, f::JOIN_ROOM_BY_ID_OR_ALIAS);
+    assert_round_trip!(membership::joined_members::v3, f::JOINED_MEMBERS);
+    assert_round_trip!(membership::joined_rooms::v3, f::JOINED_ROOMS);
+    assert_round_trip!(membership::kick_user::v3, f::KICK_USER);
+    assert_round_trip!(membership::leave_room::v3, f::LEAVE_ROOM);
+    #[cfg(feature = "unstable-msc2666")]
+    assert_round_trip!(membership::mutual_rooms::unstable, f::MUTUAL_ROOMS);
+    assert_round_trip!(membership::unban_user::v3, f::UNBAN_USER);
+}
+
+#[test]
+fn message() {
+    use fixtures::message as f;
+
+    assert_round_trip!(message::get_message_events::v3, f::GET_MESSAGE_EVENTS);
+    assert_round_trip!(message::send_message_event::v3, f::SEND_MESSAGE_EVENT);
+}
+
+#[test]
+fn presence() {
+    use fixtures::presence as f;
+
+    assert_round_trip!(presence::get_presence::v3, f::GET_PRESENCE);
+    assert_round_trip!(presence::set_presence::v3, f::SET_PRESENCE);
+}
+
+#[test]
+fn profile() {
+    use fixtures::profile as f;
+
+    assert_round_trip!(profile::get_avatar_url::v3, f::GET_AVATAR_URL);
+    assert_round_trip!(profile::get_display_name::v3, f::GET_DISPLAY_NAME);
+    assert_round_trip!(profile::get_profile::v3, f::GET_PROFILE);
+    assert_round_trip!(profile::set_avatar_url::v3, f::SET_AVATAR_URL);
+    assert_round_trip!(profile::set_display_name::v3, f::SET_DISPLAY_NAME);
+}
+
+#[test]
+fn push() {
+    use fixtures::push as f;
+
+    assert_round_trip!(push::delete_pushrule::v3, f::DELETE_PUSHRULE);
+    assert_round_trip!(push::get_notifications::v3, f::GET_NOTIFICATIONS);
+    assert_round_trip!(push::get_pushers::v3, f::GET_PUSHERS);
+    assert_round_trip!(push::get_pushrule::v3, f::GET_PUSHRULE);
+    assert_round_trip!(push::get_pushrule_actions::v3, f::GET_PUSHRULE_ACTIONS);
+    assert_round_trip!(push::get_pushrule_enabled::v3, f::GET_PUSHRULE_ENABLED);
+    assert_round_trip!(push::get_pushrules_all::v3, f::GET_PUSHRULES_ALL);
+    assert_round_trip!(push::get_pushrules_global_scope::v3, f::GET_PUSHRULES_GLOBAL_SCOPE);
+    assert_round_trip!(push::set_pusher::v3, f::SET_PUSHER);
+    assert_round_trip!(push::set_pushrule::v3, f::SET_PUSHRULE);
+    assert_round_trip!(push::set_pushrule_actions::v3, f::SET_PUSHRULE_ACTIONS);
+    assert_round_trip!(push::set_pushrule_enabled::v3, f::SET_PUSHRULE_ENABLED);
+}
+
+#[test]
+fn read_marker() {
+    use fixtures::read_marker as f;
+
+    assert_round_trip!(read_marker::set_read_marker::v3, f::SET_READ_MARKER);
+}
+
+#[test]
+fn receipt() {
+    use fixtures::receipt as f;
+
+    assert_round_trip!(receipt::create_receipt::v3, f::CREATE_RECEIPT);
+}
+
+#[test]
+fn redact() {
+    use fixtures::redact as f;
+
+    assert_round_trip!(redact::redact_event::v3, f::REDACT_EVENT);
+}
+
+#[test]
+fn relations() {
+    use fixtures::relations as f;
+
+    assert_round_trip!(relations::get_relating_events::v1, f::GET_RELATING_EVENTS);
+    assert_round_trip!(
+        relations::get_relating_events_with_rel_type::v1,
+        f::GET_RELATING_EVENTS_WITH_REL_TYPE
+    );
+    assert_round_trip!(
+        relations::get_relating_events_with_rel_type_and_event_type::v1,
+        f::GET_RELATING_EVENTS_WITH_REL_TYPE_AND_EVENT_TYPE
+    );
+}
+
+#[test]
+fn room() {
+    use fixtures::room as f;
+
+    assert_round_trip!(room::aliases::v3, f::ALIASES);
+    assert_round_trip!(room::create_room::v3, f::CREATE_ROOM);
+    #[cfg(feature = "unstable-msc3030")]
+    assert_round_trip!(room::get_event_by_timestamp::unstable, f::GET_EVENT_BY_TIMESTAMP);
+    assert_round_trip!(room::get_room_event::v3, f::GET_ROOM_EVENT);
+    assert_round_trip!(room::get_room_initial_sync::v3, f::GET_ROOM_INITIAL_SYNC);
+    assert_round_trip!(room::report_content::v3, f::REPORT_CONTENT);
+    assert_round_trip!(room::upgrade_room::v3, f::UPGRADE_ROOM);
+}
+
+#[test]
+fn search() {
+    use fixtures::search as f;
+
+    assert_round_trip!(search::search_events::v3, f::SEARCH_EVENTS);
+}
+
+#[test]
+fn server() {
+    use fixtures::server as f;
+
+    assert_round_trip!(server::get_user_info::v3, f::GET_USER_INFO);
+}
+
+#[test]
+fn session() {
+    use fixtures::session as f;
+
+    assert_round_trip!(session::get_login_types::v3, f::GET_LOGIN_TYPES);
+    assert_round_trip!(session::login::v3, f::LOGIN);
+    assert_round_trip!(session::logout::v3, f::LOGOUT);
+    assert_round_trip!(session::logout_all::v3, f::LOGOUT_ALL);
+    assert_round_trip!(session::refresh_token::v3, f::REFRESH_TOKEN);
+}
+
+#[test]
+fn space() {
+    use fixtures::space as f;
+
+    assert_round_trip!(space::get_hierarchy::v1, f::GET_HIERARCHY);
+}
+
+#[test]
+fn state() {
+    use fixtures::state as f;
+
+    assert_round_trip!(state::get_state_events::v3, f::GET_STATE_EVENTS);
+    assert_round_trip!(state::get_state_events_for_key::v3, f::GET_STATE_EVENTS_FOR_KEY);
+    assert_round_trip!(state::send_state_event::v3, f::SEND_STATE_EVENT);
+}
+
+#[test]
+fn tag() {
+    use fixtures::tag as f;
+
+    assert_round_trip!(tag::create_tag::v3, f::CREATE_TAG);
+    assert_round_trip!(tag::delete_tag::v3, f::DELETE_TAG);
+    assert_round_trip!(tag::get_tags::v3, f::GET_TAGS);
+}
+
+#[test]
+fn sync() {
+    use fixtures::sync as f;
+
+    assert_round_trip!(sync::sync_events::v3, f::SYNC_EVENTS_V3);
+    #[cfg(feature = "unstable-msc3575")]
+    assert_round_trip!(sync::sync_events::v4, f::SYNC_EVENTS_V4);
+}
+
+#[test]
+fn thirdparty() {
+    use fixtures::thirdparty as f;
+
+    assert_round_trip!(thirdparty::get_location_for_protocol::v3, f::GET_LOCATION_FOR_PROTOCOL);
+    assert_round_trip!(thirdparty::get_location_for_room_alias::v3, f::GET_LOCATION_FOR_ROOM_ALIAS);
+    assert_round_trip!(thirdparty::get_protocol::v3, f::GET_PROTOCOL);
+    assert_round_trip!(thirdparty::get_protocols::v3, f::GET_PROTOCOLS);
+    assert_round_trip!(thirdparty::get_user_for_protocol::v3, f::GET_USER_FOR_PROTOCOL);
+    assert_round_trip!(thirdparty::get_user_for_user_id::v3, f::GET_USER_FOR_USER_ID);
+}
+
+#[test]
+fn threads() {
+    use fixtures::threads as f;
+
+    assert_round_trip!(threads::get_threads::v1, f::GET_THREADS);
+}
+
+#[test]
+fn to_device() {
+    use fixtures::to_device as f;
+
+    assert_round_trip!(to_device::send_event_to_device::v3, f::SEND_EVENT_TO_DEVICE);
+}
+
+#[test]
+fn typing() {
+    use fixtures::typing as f;
+
+    assert_round_trip!(typing::create_typing_event::v3, f::CREATE_TYPING_EVENT);
+}
+
+#[test]
+fn user_directory() {
+    use fixtures::user_directory as f;
+
+    assert_round_trip!(user_directory::search_users::v3, f::SEARCH_USERS);
+}
+
+#[test]
+fn voip() {
+    use fixtures::voip as f;
+
+    assert_round_trip!(voip::get_turn_server_info::v3, f::GET_TURN_SERVER_INFO);
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use ruma_appservice_api::{
+    event::push_events,
+    query::{query_room_alias, query_user_id},
+};
+use ruma_common::{
+    api::{
+        error::{FromHttpResponseError, MatrixError},
+        MatrixVersion, OutgoingRequest, SendAccessToken,
+    },
+    events::AnyTimelineEvent,
+    serde::Raw,
+    OwnedTransactionId, RoomAliasId, UserId,
+};
+
+use crate::{send_customized_request, Error, HttpClient, ResponseResult};
+
+/// A client for the Matrix application service API, used by homeservers to send requests to an
+/// application service.
+///
+/// The `hs_token` of the application service's registration is sent with every request that
+/// requires authentication.
+#[derive(Clone, Debug)]
+pub struct AppserviceClient<C>(Arc<AppserviceClientData<C>>);
+
+/// Data contained in AppserviceClient's Arc
+#[derive(Debug)]
+struct AppserviceClientData<C> {
+    /// The URL of the application service.
+    url: String,
+
+    /// The token used by the homeserver to authenticate with the application service.
+    hs_token: String,
+
+    /// The underlying HTTP client.
+    http_client: C,
+}
+
+impl<C> AppserviceClient<C> {
+    /// Creates a new client for the application service at the given URL.
+    ///
+    /// `url` and `hs_token` are the `url` and `hs_token` of the application service's
+    /// registration.
+    pub fn new(http_client: C, url: String, hs_token: String) -> Self {
+        Self(Arc::new(AppserviceClientData { url, hs_token, http_client }))
+    }
+
+    /// The URL of the application service.
+    pub fn url(&self) -> &str {
+        &self.0.url
+    }
+}
+
+impl<C: HttpClient> AppserviceClient<C> {
+    /// Makes a request to a Matrix API endpoint of the application service.
+    pub async fn send_request<R: OutgoingRequest>(&self, request: R) -> ResponseResult<C, R> {
+        send_customized_request(
+            &self.0.http_client,
+            &self.0.url,
+            SendAccessToken::IfRequired(&self.0.hs_token),
+            &[MatrixVersion::V1_0],
+            request,
+            |_| Ok(()),
+        )
+        .await
+    }
+
+    /// Push a transaction of events to the application service.
+    ///
+    /// The same `txn_id` must be used when retrying to send the same transaction.
+    pub async fn push_events(
+        &self,
+        txn_id: OwnedTransactionId,
+        events: Vec<Raw<AnyTimelineEvent>>,
+    ) -> Result<(), Error<C::Error, MatrixError>> {
+        self.send_request(push_events::v1::Request::new(txn_id, events)).await?;
+        Ok(())
+    }
+
+    /// Query the application service for the existence of the given user.
+    ///
+    /// Returns `false` if the application service responded that the user doesn't exist.
+    pub async fn query_user_id(
+        &self,
+        user_id: &UserId,
+    ) -> Result<bool, Error<C::Error, MatrixError>> {
+        let response = self.send_request(query_user_id::v1::Request::new(user_id.to_owned())).await;
+        exists(response)
+    }
+
+    /// Query the application service for the existence of the given room alias.
+    ///
+    /// Returns `false` if the application service responded that the room alias doesn't exist.
+    pub async fn query_room_alias(
+        &self,
+        room_alias: &RoomAliasId,
+    ) -> Result<bool, Error<C::Error, MatrixError>> {
+        let response =
+            self.send_request(query_room_alias::v1::Request::new(room_alias.to_owned())).await;
+        exists(response)
+    }
+}
+
+/// Convert the response of a query endpoint to whether the queried entity exists.
+fn exists<T, E>(response: Result<T, Error<E, MatrixError>>) -> Result<bool, Error<E, MatrixError>> {
+    match response {
+        Ok(_) => Ok(true),
+        Err(Error::FromHttpResponse(FromHttpResponseError::Server(error)))
+            if error.status_code == http::StatusCode::NOT_FOUND =>
+        {
+            Ok(false)
+        }
+        Err(error) => Err(error),
+    }
+}
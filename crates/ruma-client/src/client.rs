@@ -5,17 +5,24 @@ use std::{
 
 use assign::assign;
 use async_stream::try_stream;
+use bytes::Bytes;
 use futures_core::stream::Stream;
+use http::Method;
 use ruma_client_api::{
     account::register::{self, RegistrationKind},
+    alias::get_alias,
+    discovery::discover_homeserver,
     session::login::{self, v3::LoginInfo},
     sync::sync_events,
     uiaa::UserIdentifier,
 };
 use ruma_common::{
-    api::{MatrixVersion, OutgoingRequest, SendAccessToken},
+    api::{
+        error::FromHttpResponseError, IncomingResponse, MatrixVersion, OutgoingRequest,
+        SendAccessToken,
+    },
     presence::PresenceState,
-    DeviceId, UserId,
+    DeviceId, OwnedServerName, RoomAliasId, ServerName, UserId,
 };
 
 use crate::{
@@ -23,8 +30,11 @@ use crate::{
 };
 
 mod builder;
+mod coalesce;
+mod media;
 
 pub use self::builder::ClientBuilder;
+use self::coalesce::{InFlightRequests, JoinedRequest};
 
 /// A client for the Matrix client-server API.
 #[derive(Clone, Debug)]
@@ -44,6 +54,9 @@ struct ClientData<C> {
 
     /// The (known) Matrix versions the homeserver supports.
     supported_matrix_versions: Vec<MatrixVersion>,
+
+    /// The `GET` requests that are being sent, if identical requests should be coalesced.
+    in_flight_requests: Option<InFlightRequests>,
 }
 
 impl Client<()> {
@@ -69,6 +82,9 @@ impl<C: HttpClient> Client<C> {
     }
 
     /// Makes a request to a Matrix API endpoint including additional URL parameters.
+    ///
+    /// If the client was built with [`ClientBuilder::coalesce_requests()`] and this is a `GET`
+    /// request, the response of an identical request that is already being sent is reused.
     pub async fn send_customized_request<R, F>(
         &self,
         request: R,
@@ -84,6 +100,19 @@ impl<C: HttpClient> Client<C> {
             None => SendAccessToken::None,
         };
 
+        if let Some(in_flight_requests) = &self.0.in_flight_requests {
+            if R::METADATA.method == Method::GET {
+                return self
+                    .send_coalesced_request(
+                        in_flight_requests,
+                        send_access_token,
+                        request,
+                        customize,
+                    )
+                    .await;
+            }
+        }
+
         send_customized_request(
             &self.0.http_client,
             &self.0.homeserver_url,
@@ -95,6 +124,66 @@ impl<C: HttpClient> Client<C> {
         .await
     }
 
+    /// Sends a request, or waits for the response of an identical request that is already being
+    /// sent.
+    async fn send_coalesced_request<R, F>(
+        &self,
+        in_flight_requests: &InFlightRequests,
+        send_access_token: SendAccessToken<'_>,
+        request: R,
+        customize: F,
+    ) -> ResponseResult<C, R>
+    where
+        R: OutgoingRequest,
+        F: FnOnce(&mut http::Request<C::RequestBody>) -> Result<(), ResponseError<C, R>>,
+    {
+        let mut http_request = request
+            .try_into_http_request(
+                &self.0.homeserver_url,
+                send_access_token,
+                &self.0.supported_matrix_versions,
+            )
+            .map_err(ResponseError::<C, R>::from)?;
+        customize(&mut http_request)?;
+
+        let joined_request = in_flight_requests.join(&http_request);
+        let http_response = match joined_request {
+            JoinedRequest::Leader(leader) => {
+                let http_response = self
+                    .0
+                    .http_client
+                    .send_http_request(http_request)
+                    .await
+                    .map_err(Error::Response)?;
+                let http_response =
+                    Arc::new(http_response.map(|body| Bytes::copy_from_slice(body.as_ref())));
+                leader.finish(Arc::clone(&http_response));
+                http_response
+            }
+            JoinedRequest::Follower(response) => match response.await {
+                Some(http_response) => http_response,
+                // The identical request failed, send this one to get its own error.
+                None => {
+                    let http_response = self
+                        .0
+                        .http_client
+                        .send_http_request(http_request)
+                        .await
+                        .map_err(Error::Response)?;
+                    return Ok(R::IncomingResponse::try_from_http_response(http_response)?);
+                }
+            },
+        };
+
+        // The response can be shared with other requests, so deserialize a copy of it.
+        let mut shared_response = http::Response::new(http_response.body().clone());
+        *shared_response.status_mut() = http_response.status();
+        *shared_response.version_mut() = http_response.version();
+        *shared_response.headers_mut() = http_response.headers().clone();
+
+        Ok(R::IncomingResponse::try_from_http_response(shared_response)?)
+    }
+
     /// Makes a request to a Matrix API endpoint as a virtual user.
     ///
     /// This method is meant to be used by application services when interacting with the
@@ -174,6 +263,82 @@ impl<C: HttpClient> Client<C> {
         Ok(response)
     }
 
+    /// Resolve the given room alias to a room ID and a list of servers that are aware of the room.
+    ///
+    /// The homeserver of this client is asked first. If it doesn't know the alias, the server of
+    /// the alias and then the given `servers` are asked, in this order, until one of them knows
+    /// the alias. The client-server API of these servers is discovered with their
+    /// `/.well-known/matrix/client` file, falling back to `https://{server_name}`.
+    ///
+    /// Returns the error of the homeserver of this client if none of the servers know the alias.
+    pub async fn resolve_room_alias(
+        &self,
+        room_alias: &RoomAliasId,
+        servers: &[OwnedServerName],
+    ) -> Result<get_alias::v3::Response, Error<C::Error, ruma_client_api::Error>> {
+        let request = || get_alias::v3::Request::new(room_alias.to_owned());
+
+        let error = match self.send_request(request()).await {
+            Err(Error::FromHttpResponse(FromHttpResponseError::Server(error)))
+                if error.status_code == http::StatusCode::NOT_FOUND =>
+            {
+                Error::FromHttpResponse(FromHttpResponseError::Server(error))
+            }
+            result => return result,
+        };
+
+        let mut candidates: Vec<&ServerName> = vec![room_alias.server_name()];
+        for server_name in servers {
+            if !candidates.contains(&&**server_name) {
+                candidates.push(server_name);
+            }
+        }
+
+        for server_name in candidates {
+            let homeserver_url = self.discover_homeserver_url(server_name).await;
+            let versions = &self.0.supported_matrix_versions;
+
+            if let Ok(response) = self.send_request_to(&homeserver_url, versions, request()).await {
+                return Ok(response);
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Get the URL of the client-server API of the given server from its
+    /// `/.well-known/matrix/client` file, falling back to `https://{server_name}`.
+    async fn discover_homeserver_url(&self, server_name: &ServerName) -> String {
+        let server_url = format!("https://{server_name}");
+
+        self.send_request_to(
+            &server_url,
+            &[MatrixVersion::V1_0],
+            discover_homeserver::Request::new(),
+        )
+        .await
+        .map(|response| response.homeserver.base_url.trim_end_matches('/').to_owned())
+        .unwrap_or(server_url)
+    }
+
+    /// Makes an unauthenticated request to a Matrix API endpoint of the given server.
+    async fn send_request_to<R: OutgoingRequest>(
+        &self,
+        homeserver_url: &str,
+        for_versions: &[MatrixVersion],
+        request: R,
+    ) -> ResponseResult<C, R> {
+        send_customized_request(
+            &self.0.http_client,
+            homeserver_url,
+            SendAccessToken::None,
+            for_versions,
+            request,
+            |_| Ok(()),
+        )
+        .await
+    }
+
     /// Convenience method that represents repeated calls to the sync_events endpoint as a stream.
     ///
     /// # Example:
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 use ruma_client_api::discovery::get_supported_versions;
 use ruma_common::api::{MatrixVersion, SendAccessToken};
 
-use super::{Client, ClientData};
+use super::{Client, ClientData, InFlightRequests};
 use crate::{DefaultConstructibleHttpClient, Error, HttpClient, HttpClientExt};
 
 /// A [`Client`] builder.
@@ -13,11 +13,17 @@ pub struct ClientBuilder {
     homeserver_url: Option<String>,
     access_token: Option<String>,
     supported_matrix_versions: Option<Vec<MatrixVersion>>,
+    coalesce_requests: bool,
 }
 
 impl ClientBuilder {
     pub(super) fn new() -> Self {
-        Self { homeserver_url: None, access_token: None, supported_matrix_versions: None }
+        Self {
+            homeserver_url: None,
+            access_token: None,
+            supported_matrix_versions: None,
+            coalesce_requests: false,
+        }
     }
 
     /// Set the homeserver URL.
@@ -42,6 +48,21 @@ impl ClientBuilder {
         Self { supported_matrix_versions: Some(versions), ..self }
     }
 
+    /// Coalesce identical `GET` requests that are sent at the same time.
+    ///
+    /// When enabled, a `GET` request with the same URL and headers, including the access token, as
+    /// a request that is already being sent is not sent again, it gets the response of the first
+    /// request instead. This avoids sending the
+    /// same request many times when identical lookups are made concurrently, like when a bot gets
+    /// the profile of the same user for several events.
+    ///
+    /// If the first request fails, the identical requests are sent separately.
+    ///
+    /// Disabled by default.
+    pub fn coalesce_requests(self, coalesce_requests: bool) -> Self {
+        Self { coalesce_requests, ..self }
+    }
+
     /// Finish building the [`Client`].
     ///
     /// Uses [`DefaultConstructibleHttpClient::default()`] to create an HTTP client instance.
@@ -90,6 +111,7 @@ impl ClientBuilder {
             http_client,
             access_token: Mutex::new(self.access_token),
             supported_matrix_versions,
+            in_flight_requests: self.coalesce_requests.then(InFlightRequests::new),
         })))
     }
 }
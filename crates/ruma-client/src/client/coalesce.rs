@@ -0,0 +1,236 @@
+//! Coalescing of identical in-flight requests.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use bytes::Bytes;
+
+/// The key of a request: its HTTP method, its URL and its headers.
+///
+/// The headers are part of the key so requests with different access tokens, or with headers
+/// added when customizing the request, are not coalesced.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    url: String,
+    headers: Vec<(String, Vec<u8>)>,
+}
+
+impl RequestKey {
+    fn new<B>(request: &http::Request<B>) -> Self {
+        Self {
+            method: request.method().as_str().to_owned(),
+            url: request.uri().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.as_str().to_owned(), value.as_bytes().to_owned()))
+                .collect(),
+        }
+    }
+}
+
+/// The shared state of a request that is being sent.
+type SharedRequest = Arc<Mutex<InFlightRequest>>;
+
+/// The requests that are currently being sent, by method, URL and headers.
+pub(super) struct InFlightRequests {
+    requests: Mutex<BTreeMap<RequestKey, SharedRequest>>,
+}
+
+impl InFlightRequests {
+    pub(super) fn new() -> Self {
+        Self { requests: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Join the given request.
+    ///
+    /// If the same request is already being sent, returns a future that resolves to its response.
+    /// Otherwise, the caller is responsible for sending the request and sharing its response with
+    /// the returned [`RequestLeader`].
+    pub(super) fn join<B>(&self, request: &http::Request<B>) -> JoinedRequest<'_> {
+        let key = RequestKey::new(request);
+        let mut requests = self.requests.lock().expect("in-flight requests mutex was poisoned");
+
+        if let Some(request) = requests.get(&key) {
+            return JoinedRequest::Follower(WaitForResponse(Arc::clone(request)));
+        }
+
+        let request = Arc::new(Mutex::new(InFlightRequest { response: None, wakers: Vec::new() }));
+        requests.insert(key.clone(), Arc::clone(&request));
+
+        JoinedRequest::Leader(RequestLeader { requests: self, key, request })
+    }
+}
+
+impl fmt::Debug for InFlightRequests {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requests = self.requests.lock().expect("in-flight requests mutex was poisoned");
+        f.debug_struct("InFlightRequests").field("count", &requests.len()).finish()
+    }
+}
+
+/// The state of a request that is being sent.
+struct InFlightRequest {
+    /// The response of the request.
+    ///
+    /// `None` while the request is being sent, `Some(None)` if the request failed or was
+    /// cancelled.
+    response: Option<Option<Arc<http::Response<Bytes>>>>,
+
+    /// The wakers of the tasks waiting for the response.
+    wakers: Vec<Waker>,
+}
+
+/// A request that was joined with [`InFlightRequests::join()`].
+pub(super) enum JoinedRequest<'a> {
+    /// The request is not being sent yet, the caller must send it.
+    Leader(RequestLeader<'a>),
+
+    /// The request is already being sent.
+    Follower(WaitForResponse),
+}
+
+/// The sender of a request that other identical requests are waiting for.
+///
+/// If this is dropped before the response is shared, the waiting requests are notified that they
+/// need to be sent separately.
+pub(super) struct RequestLeader<'a> {
+    requests: &'a InFlightRequests,
+    key: RequestKey,
+    request: SharedRequest,
+}
+
+impl RequestLeader<'_> {
+    /// Share the response of the request with the waiting requests.
+    pub(super) fn finish(self, response: Arc<http::Response<Bytes>>) {
+        self.request.lock().expect("in-flight request mutex was poisoned").response =
+            Some(Some(response));
+    }
+}
+
+impl Drop for RequestLeader<'_> {
+    fn drop(&mut self) {
+        {
+            let mut requests =
+                self.requests.requests.lock().expect("in-flight requests mutex was poisoned");
+            if requests.get(&self.key).map_or(false, |request| Arc::ptr_eq(request, &self.request))
+            {
+                requests.remove(&self.key);
+            }
+        }
+
+        let mut request = self.request.lock().expect("in-flight request mutex was poisoned");
+        request.response.get_or_insert(None);
+
+        for waker in request.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// A future that resolves to the response of an identical request that is being sent.
+///
+/// Resolves to `None` if that request failed or was cancelled.
+pub(super) struct WaitForResponse(SharedRequest);
+
+impl Future for WaitForResponse {
+    type Output = Option<Arc<http::Response<Bytes>>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut request = self.0.lock().expect("in-flight request mutex was poisoned");
+
+        match &request.response {
+            Some(response) => Poll::Ready(response.clone()),
+            None => {
+                if !request.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    request.wakers.push(cx.waker().clone());
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use futures_lite::future::{block_on, poll_once};
+    use http::header::AUTHORIZATION;
+
+    use super::{InFlightRequests, JoinedRequest};
+
+    fn request(access_token: &str) -> http::Request<()> {
+        http::Request::get(
+            "https://matrix.localhost/_matrix/client/v3/profile/%40alice%3Alocalhost",
+        )
+        .header(AUTHORIZATION, format!("Bearer {access_token}"))
+        .body(())
+        .unwrap()
+    }
+
+    fn is_empty(in_flight_requests: &InFlightRequests) -> bool {
+        in_flight_requests.requests.lock().unwrap().is_empty()
+    }
+
+    #[test]
+    fn followers_receive_leader_response() {
+        let in_flight_requests = InFlightRequests::new();
+
+        let leader = match in_flight_requests.join(&request("token")) {
+            JoinedRequest::Leader(leader) => leader,
+            JoinedRequest::Follower(_) => panic!("first request should be the leader"),
+        };
+        let mut follower = match in_flight_requests.join(&request("token")) {
+            JoinedRequest::Follower(follower) => follower,
+            JoinedRequest::Leader(_) => panic!("identical request should be a follower"),
+        };
+
+        assert!(block_on(poll_once(&mut follower)).is_none());
+
+        let response = Arc::new(http::Response::new(Bytes::from_static(b"{}")));
+        leader.finish(Arc::clone(&response));
+        assert!(is_empty(&in_flight_requests));
+
+        let follower_response = block_on(follower).unwrap();
+        assert!(Arc::ptr_eq(&follower_response, &response));
+
+        // Once the response was shared, the request is sent again.
+        assert!(matches!(in_flight_requests.join(&request("token")), JoinedRequest::Leader(_)));
+    }
+
+    #[test]
+    fn followers_resend_after_leader_is_dropped() {
+        let in_flight_requests = InFlightRequests::new();
+
+        let leader = in_flight_requests.join(&request("token"));
+        assert!(matches!(leader, JoinedRequest::Leader(_)));
+        let follower = match in_flight_requests.join(&request("token")) {
+            JoinedRequest::Follower(follower) => follower,
+            JoinedRequest::Leader(_) => panic!("identical request should be a follower"),
+        };
+
+        // The leader failed to get a response.
+        drop(leader);
+        assert!(is_empty(&in_flight_requests));
+
+        assert!(block_on(follower).is_none());
+    }
+
+    #[test]
+    fn different_access_tokens_are_not_coalesced() {
+        let in_flight_requests = InFlightRequests::new();
+
+        let _alice = in_flight_requests.join(&request("alice_token"));
+        assert!(matches!(in_flight_requests.join(&request("bob_token")), JoinedRequest::Leader(_)));
+    }
+}
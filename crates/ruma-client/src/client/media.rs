@@ -0,0 +1,237 @@
+//! Convenience methods for the media repository.
+
+use std::path::Path;
+
+use assign::assign;
+use js_int::UInt;
+#[cfg(feature = "unstable-msc3916")]
+use ruma_client_api::authenticated_media;
+use ruma_client_api::media::{
+    create_content, get_content,
+    get_content_thumbnail::{self, v3::Method},
+};
+#[cfg(feature = "unstable-msc3916")]
+use ruma_common::api::error::FromHttpResponseError;
+use ruma_common::MxcUri;
+
+use super::Client;
+use crate::{Error, HttpClient};
+
+impl<C: HttpClient> Client<C> {
+    /// Upload a file to the media repository of the homeserver.
+    ///
+    /// If `content_type` is `None`, it is inferred from the extension of `filename`, and defaults
+    /// to `application/octet-stream`.
+    pub async fn upload_media(
+        &self,
+        file: Vec<u8>,
+        content_type: Option<&str>,
+        filename: Option<&str>,
+    ) -> Result<create_content::v3::Response, Error<C::Error, ruma_client_api::Error>> {
+        let content_type = content_type.unwrap_or_else(|| infer_content_type(filename));
+
+        self.send_request(assign!(create_content::v3::Request::new(file), {
+            filename: filename.map(ToOwned::to_owned),
+            content_type: Some(content_type.to_owned()),
+        }))
+        .await
+    }
+
+    /// Download a file from the media repository of the homeserver.
+    ///
+    /// With the `unstable-msc3916` feature, if this client has an access token, the authenticated
+    /// media endpoint is used, falling back to the unauthenticated endpoint if the homeserver
+    /// doesn't support it.
+    pub async fn download_media(
+        &self,
+        uri: &MxcUri,
+    ) -> Result<get_content::v3::Response, Error<C::Error, ruma_client_api::Error>> {
+        let request = get_content::v3::Request::from_url(uri).map_err(Error::InvalidMxcUri)?;
+
+        #[cfg(feature = "unstable-msc3916")]
+        if self.access_token().is_some() {
+            let authenticated_request = authenticated_media::get_content::unstable::Request::new(
+                request.media_id.clone(),
+                request.server_name.clone(),
+            );
+
+            match self.send_request(authenticated_request).await {
+                Ok(response) => {
+                    return Ok(assign!(get_content::v3::Response::new(response.file), {
+                        content_type: response.content_type,
+                        content_disposition: response.content_disposition,
+                    }));
+                }
+                Err(error) if !is_unsupported_endpoint(&error) => return Err(error),
+                Err(_) => {}
+            }
+        }
+
+        self.send_request(request).await
+    }
+
+    /// Download a thumbnail of a file from the media repository of the homeserver.
+    ///
+    /// The actual size of the thumbnail may not match the given `width` and `height`.
+    ///
+    /// With the `unstable-msc3916` feature, if this client has an access token, the authenticated
+    /// media endpoint is used, falling back to the unauthenticated endpoint if the homeserver
+    /// doesn't support it.
+    pub async fn download_thumbnail(
+        &self,
+        uri: &MxcUri,
+        width: UInt,
+        height: UInt,
+        method: Option<Method>,
+    ) -> Result<get_content_thumbnail::v3::Response, Error<C::Error, ruma_client_api::Error>> {
+        let request = assign!(
+            get_content_thumbnail::v3::Request::from_url(uri, width, height)
+                .map_err(Error::InvalidMxcUri)?,
+            { method }
+        );
+
+        #[cfg(feature = "unstable-msc3916")]
+        if self.access_token().is_some() {
+            let authenticated_request = assign!(
+                authenticated_media::get_content_thumbnail::unstable::Request::new(
+                    request.media_id.clone(),
+                    request.server_name.clone(),
+                    width,
+                    height,
+                ),
+                { method: request.method.clone() }
+            );
+
+            match self.send_request(authenticated_request).await {
+                Ok(response) => {
+                    return Ok(assign!(get_content_thumbnail::v3::Response::new(response.file), {
+                        content_type: response.content_type,
+                    }));
+                }
+                Err(error) if !is_unsupported_endpoint(&error) => return Err(error),
+                Err(_) => {}
+            }
+        }
+
+        self.send_request(request).await
+    }
+}
+
+/// Whether the given error means that the homeserver doesn't support the endpoint.
+#[cfg(feature = "unstable-msc3916")]
+fn is_unsupported_endpoint<E>(error: &Error<E, ruma_client_api::Error>) -> bool {
+    use http::StatusCode;
+    use ruma_client_api::error::{ErrorBody, ErrorKind};
+
+    match error {
+        Error::FromHttpResponse(FromHttpResponseError::Server(error)) => match &error.body {
+            ErrorBody::Standard { kind, .. } => *kind == ErrorKind::Unrecognized,
+            _ => {
+                matches!(error.status_code, StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED)
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Infer the content type of a file from the extension of its name.
+fn infer_content_type(filename: Option<&str>) -> &'static str {
+    let extension = filename
+        .and_then(|filename| Path::new(filename).extension())
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("ogg" | "oga" | "opus") => "audio/ogg",
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") => "audio/mp4",
+        Some("wav") => "audio/wav",
+        Some("pdf") => "application/pdf",
+        Some("json") => "application/json",
+        Some("zip") => "application/zip",
+        Some("txt") => "text/plain",
+        Some("html" | "htm") => "text/html",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::infer_content_type;
+
+    #[test]
+    fn infer_content_type_from_extension() {
+        assert_eq!(infer_content_type(Some("image.png")), "image/png");
+        assert_eq!(infer_content_type(Some("photo.jpeg")), "image/jpeg");
+        assert_eq!(infer_content_type(Some("archive.tar.zip")), "application/zip");
+        assert_eq!(infer_content_type(Some("/tmp/voice.opus")), "audio/ogg");
+    }
+
+    #[test]
+    fn infer_content_type_ignores_case() {
+        assert_eq!(infer_content_type(Some("IMAGE.PNG")), "image/png");
+        assert_eq!(infer_content_type(Some("Notes.Txt")), "text/plain");
+    }
+
+    #[test]
+    fn infer_content_type_defaults_to_octet_stream() {
+        assert_eq!(infer_content_type(None), "application/octet-stream");
+        assert_eq!(infer_content_type(Some("README")), "application/octet-stream");
+        assert_eq!(infer_content_type(Some(".png")), "application/octet-stream");
+        assert_eq!(infer_content_type(Some("data.unknown")), "application/octet-stream");
+    }
+
+    #[cfg(feature = "unstable-msc3916")]
+    mod unsupported_endpoint {
+        use http::StatusCode;
+        use ruma_common::api::{error::FromHttpResponseError, EndpointError};
+
+        use super::super::is_unsupported_endpoint;
+        use crate::Error;
+
+        fn server_error(status: StatusCode, body: &str) -> Error<(), ruma_client_api::Error> {
+            let response = http::Response::builder().status(status).body(body).unwrap();
+            Error::FromHttpResponse(FromHttpResponseError::Server(
+                ruma_client_api::Error::from_http_response(response),
+            ))
+        }
+
+        #[test]
+        fn unrecognized_errcode_is_unsupported() {
+            let body = r#"{ "errcode": "M_UNRECOGNIZED", "error": "Unrecognized request" }"#;
+            assert!(is_unsupported_endpoint(&server_error(StatusCode::BAD_REQUEST, body)));
+            assert!(is_unsupported_endpoint(&server_error(StatusCode::NOT_FOUND, body)));
+        }
+
+        #[test]
+        fn missing_route_is_unsupported() {
+            assert!(is_unsupported_endpoint(&server_error(StatusCode::NOT_FOUND, "Not found")));
+            assert!(is_unsupported_endpoint(&server_error(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "Method not allowed"
+            )));
+        }
+
+        #[test]
+        fn other_errors_are_not_unsupported() {
+            let not_found = r#"{ "errcode": "M_NOT_FOUND", "error": "Media not found" }"#;
+            assert!(!is_unsupported_endpoint(&server_error(StatusCode::NOT_FOUND, not_found)));
+
+            let forbidden = r#"{ "errcode": "M_FORBIDDEN", "error": "Access denied" }"#;
+            assert!(!is_unsupported_endpoint(&server_error(StatusCode::FORBIDDEN, forbidden)));
+
+            assert!(!is_unsupported_endpoint(&server_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error"
+            )));
+            assert!(!is_unsupported_endpoint(&Error::<(), _>::AuthenticationRequired));
+        }
+    }
+}
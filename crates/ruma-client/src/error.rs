@@ -2,7 +2,10 @@
 
 use std::fmt::{self, Debug, Display, Formatter};
 
-use ruma_common::api::error::{FromHttpResponseError, IntoHttpError};
+use ruma_common::{
+    api::error::{FromHttpResponseError, IntoHttpError},
+    IdParseError,
+};
 
 /// An error that can occur during client operations.
 #[derive(Debug)]
@@ -22,6 +25,9 @@ pub enum Error<E, F> {
 
     /// Converting the HTTP response to one of ruma's types failed.
     FromHttpResponse(FromHttpResponseError<F>),
+
+    /// The given MXC URI is invalid.
+    InvalidMxcUri(IdParseError),
 }
 
 impl<E: Display, F: Display> Display for Error<E, F> {
@@ -34,6 +40,7 @@ impl<E: Display, F: Display> Display for Error<E, F> {
             Self::Url(err) => write!(f, "Invalid URL: {err}"),
             Self::Response(err) => write!(f, "Couldn't obtain a response: {err}"),
             Self::FromHttpResponse(err) => write!(f, "HTTP response conversion failed: {err}"),
+            Self::InvalidMxcUri(err) => write!(f, "Invalid MXC URI: {err}"),
         }
     }
 }
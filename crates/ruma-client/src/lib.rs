@@ -98,6 +98,9 @@
 //!   * `reqwest-rustls-manual-roots`
 //!   * `reqwest-rustls-webpki-roots`
 //!   * `reqwest-rustls-native-roots`
+//!
+//! The `appservice-api` feature activates [`AppserviceClient`], that can be used by homeservers to
+//! send requests to application services.
 
 #![warn(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
@@ -110,11 +113,15 @@ use ruma_common::{
 };
 use tracing::{info_span, Instrument};
 
+#[cfg(feature = "appservice-api")]
+mod appservice;
 #[cfg(feature = "client-api")]
 mod client;
 mod error;
 pub mod http_client;
 
+#[cfg(feature = "appservice-api")]
+pub use self::appservice::AppserviceClient;
 #[cfg(feature = "client-api")]
 pub use self::client::{Client, ClientBuilder};
 pub use self::{
@@ -0,0 +1,64 @@
+// `cargo bench` works, but if you use `cargo bench -- --save-baseline <name>`
+// or pass any other args to it, it fails with the error
+// `cargo bench unknown option --save-baseline`.
+// To pass args to criterion, use this form
+// `cargo bench --features criterion --bench <name of the bench> -- --save-baseline <name>`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ruma_common::{
+    api::{MatrixVersion, Metadata},
+    event_id, metadata, room_id,
+};
+
+const SEND_TRANSACTION: Metadata = metadata! {
+    method: PUT,
+    rate_limited: false,
+    authentication: ServerSignatures,
+    history: {
+        1.0 => "/_matrix/federation/v1/send/:transaction_id",
+    }
+};
+
+const SEND_JOIN: Metadata = metadata! {
+    method: PUT,
+    rate_limited: false,
+    authentication: ServerSignatures,
+    history: {
+        1.0 => "/_matrix/federation/v2/send_join/:room_id/:event_id",
+    }
+};
+
+fn make_endpoint_url(c: &mut Criterion) {
+    let room_id = room_id!("!n8f893n9:example.com");
+    let event_id = event_id!("$a4ecee13e2accdadf56c1025:example.com");
+
+    c.bench_function("make endpoint URL without escaping", |b| {
+        b.iter(|| {
+            SEND_TRANSACTION
+                .make_endpoint_url(
+                    &[MatrixVersion::V1_4],
+                    "https://example.com",
+                    &[&"1672531200000"],
+                    "",
+                )
+                .unwrap()
+        });
+    });
+
+    c.bench_function("make endpoint URL with escaping", |b| {
+        b.iter(|| {
+            SEND_JOIN
+                .make_endpoint_url(
+                    &[MatrixVersion::V1_4],
+                    "https://example.com",
+                    &[&room_id, &event_id],
+                    "omit_members=true",
+                )
+                .unwrap()
+        });
+    });
+}
+
+criterion_group!(benches, make_endpoint_url);
+
+criterion_main!(benches);
@@ -12,7 +12,7 @@
 //!
 //! [apis]: https://spec.matrix.org/v1.4/#matrix-apis
 
-use std::{convert::TryInto as _, error::Error as StdError};
+use std::{convert::TryInto as _, error::Error as StdError, time::Duration};
 
 use bytes::BufMut;
 use serde::{Deserialize, Serialize};
@@ -40,7 +40,9 @@ use crate::UserId;
 ///   headers on the request. The value must implement `Display`. Generally this is a `String`.
 ///   The attribute value shown above as `HEADER_NAME` must be a `const` expression of the type
 ///   `http::header::HeaderName`, like one of the constants from `http::header`, e.g.
-///   `CONTENT_TYPE`.
+///   `CONTENT_TYPE`, or a lowercase string literal, e.g. `"x-custom-header"`. If the field is
+///   an `Option<String>`, the header is optional. If the field is a `Vec<String>`, the header
+///   can appear several times and is optional.
 /// * `#[ruma_api(path)]`: Fields with this attribute will be inserted into the matching path
 ///   component of the request URL. If there are multiple of these fields, the order in which
 ///   they are declared must match the order in which they occur in the request path.
@@ -59,6 +61,16 @@ use crate::UserId;
 ///   entire request body, but this attribute is for endpoints where the body can be anything,
 ///   not just JSON. The field type must be `Vec<u8>`.
 ///
+/// The macro itself accepts the following arguments:
+///
+/// * `error = ErrorType`: The error type of the endpoint. Defaults to [`MatrixError`].
+/// * `builder`: Generate a `RequestBuilder` type, returned by `Request::builder()`. The
+///   required fields of the request are the arguments of `Request::builder()`, and the builder
+///   has a setter for every optional field, i.e. every field of type `Option<T>` or with a
+///   `#[serde(default)]` attribute.
+///
+/// [`MatrixError`]: error::MatrixError
+///
 /// ## Examples
 ///
 /// ```
@@ -149,7 +161,9 @@ pub use ruma_macros::request;
 /// * `#[ruma_api(header = HEADER_NAME)]`: Fields with this attribute will be treated as HTTP
 ///   headers on the response. The value must implement `Display`. Generally this is a
 ///   `String`. The attribute value shown above as `HEADER_NAME` must be a header name constant
-///   from `http::header`, e.g. `CONTENT_TYPE`.
+///   from `http::header`, e.g. `CONTENT_TYPE`, or a lowercase string literal, e.g.
+///   `"x-custom-header"`. If the field is an `Option<String>`, the header is optional. If the
+///   field is a `Vec<String>`, the header can appear several times and is optional.
 /// * No attribute: Fields without an attribute are part of the body. They can use `#[serde]`
 ///   attributes to customize (de)serialization.
 /// * `#[ruma_api(body)]`: Use this if multiple endpoints should share a response body type, or
@@ -227,8 +241,12 @@ pub use ruma_macros::request;
 /// ```
 pub use ruma_macros::response;
 
+#[cfg(feature = "spec-gen")]
+pub mod descriptor;
 pub mod error;
 mod metadata;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
 pub use metadata::{MatrixVersion, Metadata, VersionHistory, VersioningDecision};
 
@@ -245,6 +263,10 @@ pub enum SendAccessToken<'a> {
     /// Always add the access token.
     Always(&'a str),
 
+    /// Add the given appservice token to the request only if the `METADATA` on the request
+    /// requires it, or allows it for appservices.
+    Appservice(&'a str),
+
     /// Don't add an access token.
     ///
     /// This will lead to an error if the request endpoint requires authentication
@@ -257,7 +279,7 @@ impl<'a> SendAccessToken<'a> {
     /// Returns `Some(_)` if `self` contains an access token.
     pub fn get_required_for_endpoint(self) -> Option<&'a str> {
         match self {
-            Self::IfRequired(tok) | Self::Always(tok) => Some(tok),
+            Self::IfRequired(tok) | Self::Always(tok) | Self::Appservice(tok) => Some(tok),
             Self::None => None,
         }
     }
@@ -268,6 +290,17 @@ impl<'a> SendAccessToken<'a> {
     pub fn get_not_required_for_endpoint(self) -> Option<&'a str> {
         match self {
             Self::Always(tok) => Some(tok),
+            Self::IfRequired(_) | Self::Appservice(_) | Self::None => None,
+        }
+    }
+
+    /// Get the access token for an endpoint that requires an appservice token.
+    ///
+    /// Returns `Some(_)` only if `self` is `SendAccessToken::Appservice(_)` or
+    /// `SendAccessToken::Always(_)`.
+    pub fn get_required_for_appservice(self) -> Option<&'a str> {
+        match self {
+            Self::Appservice(tok) | Self::Always(tok) => Some(tok),
             Self::IfRequired(_) | Self::None => None,
         }
     }
@@ -337,28 +370,96 @@ pub trait OutgoingRequestAppserviceExt: OutgoingRequest {
         let mut http_request =
             self.try_into_http_request(base_url, access_token, considering_versions)?;
         let user_id_query = serde_html_form::to_string([("user_id", user_id)])?;
+        append_query(&mut http_request, &user_id_query)?;
+
+        Ok(http_request)
+    }
+}
+
+impl<T: OutgoingRequest> OutgoingRequestAppserviceExt for T {}
+
+/// Extra options to convert an [`OutgoingRequest`] into an `http::Request`.
+///
+/// Used with [`OutgoingRequestExt::try_into_http_request_with_options`].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct OutgoingRequestOptions<'a> {
+    /// Extra query parameters to append to the query string of the request.
+    ///
+    /// This can be used for parameters that are not part of the request type, like experimental
+    /// toggles of unstable features.
+    pub extra_query: Vec<(&'a str, &'a str)>,
+
+    /// A hint for the maximum duration the HTTP client should wait for the response.
+    ///
+    /// It is added to the extensions of the `http::Request` as a [`TimeoutHint`], for the HTTP
+    /// client to use, e.g. to wait longer for sync requests that are long-polling.
+    pub timeout: Option<Duration>,
+}
 
-        let uri = http_request.uri().to_owned();
-        let mut parts = uri.into_parts();
+impl OutgoingRequestOptions<'_> {
+    /// Creates empty `OutgoingRequestOptions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
-        let path_and_query_with_user_id = match &parts.path_and_query {
-            Some(path_and_query) => match path_and_query.query() {
-                Some(_) => format!("{path_and_query}&{user_id_query}"),
-                None => format!("{path_and_query}?{user_id_query}"),
-            },
-            None => format!("/?{user_id_query}"),
-        };
+/// A hint for the maximum duration an HTTP client should wait for the response to a request.
+///
+/// This is found in the extensions of `http::Request`s created with
+/// [`OutgoingRequestExt::try_into_http_request_with_options`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct TimeoutHint(pub Duration);
 
-        parts.path_and_query =
-            Some(path_and_query_with_user_id.try_into().map_err(http::Error::from)?);
+/// An extension to [`OutgoingRequest`] to customize the `http::Request` without changing the
+/// request type.
+pub trait OutgoingRequestExt: OutgoingRequest {
+    /// Tries to convert this request into an `http::Request` with the given extra options.
+    fn try_into_http_request_with_options<T: Default + BufMut>(
+        self,
+        base_url: &str,
+        access_token: SendAccessToken<'_>,
+        considering_versions: &'_ [MatrixVersion],
+        options: &OutgoingRequestOptions<'_>,
+    ) -> Result<http::Request<T>, IntoHttpError> {
+        let mut http_request =
+            self.try_into_http_request(base_url, access_token, considering_versions)?;
 
-        *http_request.uri_mut() = parts.try_into().map_err(http::Error::from)?;
+        if !options.extra_query.is_empty() {
+            let extra_query = serde_html_form::to_string(&options.extra_query)?;
+            append_query(&mut http_request, &extra_query)?;
+        }
+
+        if let Some(timeout) = options.timeout {
+            http_request.extensions_mut().insert(TimeoutHint(timeout));
+        }
 
         Ok(http_request)
     }
 }
 
-impl<T: OutgoingRequest> OutgoingRequestAppserviceExt for T {}
+impl<T: OutgoingRequest> OutgoingRequestExt for T {}
+
+/// Append the given serialized query parameters to the query string of the given request.
+fn append_query<T>(http_request: &mut http::Request<T>, query: &str) -> Result<(), IntoHttpError> {
+    let uri = http_request.uri().to_owned();
+    let mut parts = uri.into_parts();
+
+    let path_and_query = match &parts.path_and_query {
+        Some(path_and_query) => match path_and_query.query() {
+            Some(_) => format!("{path_and_query}&{query}"),
+            None => format!("{path_and_query}?{query}"),
+        },
+        None => format!("/?{query}"),
+    };
+
+    parts.path_and_query = Some(path_and_query.try_into().map_err(http::Error::from)?);
+
+    *http_request.uri_mut() = parts.try_into().map_err(http::Error::from)?;
+
+    Ok(())
+}
 
 /// A request type for a Matrix API endpoint, used for receiving requests.
 pub trait IncomingRequest: Sized {
@@ -406,6 +507,7 @@ pub trait EndpointError: OutgoingResponse + StdError + Sized + Send + 'static {
 
 /// Authentication scheme used by the endpoint.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "spec-gen", derive(Serialize))]
 #[allow(clippy::exhaustive_enums)]
 pub enum AuthScheme {
     /// No authentication is performed.
@@ -417,11 +519,64 @@ pub enum AuthScheme {
     /// It is recommended to use the header over the query parameter.
     AccessToken,
 
+    /// Authentication is optional, and it is performed by including an access token in the
+    /// `Authentication` http header, or an `access_token` query parameter.
+    ///
+    /// The access token is sent if it is available, but its absence is not an error.
+    AccessTokenOptional,
+
+    /// Authentication is only performed for appservices, by including the appservice's access
+    /// token in the `Authentication` http header, or an `access_token` query parameter.
+    ///
+    /// Other clients don't send an access token to this endpoint.
+    AppserviceToken,
+
     /// Authentication is performed by including X-Matrix signatures in the request headers,
     /// as defined in the federation API.
     ServerSignatures,
 }
 
+/// The category of the rate limits that a server should apply to an endpoint.
+///
+/// This allows servers to configure their rate limiters per family of endpoints, in addition to
+/// the `rate_limited` flag of [`Metadata`] which only says whether the specification recommends to
+/// rate limit the endpoint.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spec-gen", derive(Serialize))]
+#[non_exhaustive]
+pub enum RateLimitCategory {
+    /// The endpoint doesn't belong to a more specific category.
+    #[default]
+    General,
+
+    /// The registration of a new account, or the checks before it.
+    Registration,
+
+    /// Logging in, or refreshing an access token.
+    Login,
+
+    /// Sending an event in a room.
+    RoomSend,
+
+    /// Joining or knocking on a room.
+    RoomJoin,
+
+    /// Inviting a user to a room.
+    Invite,
+
+    /// Uploading media to the content repository.
+    MediaUpload,
+
+    /// Requesting the validation of a third-party identifier, which sends an email or an SMS.
+    ThirdPartyIdValidation,
+
+    /// Sending a transaction of events to another server or to an application service.
+    Transaction,
+
+    /// Querying an application service about a user, a room alias or third-party entities.
+    AppserviceQuery,
+}
+
 /// The direction to return events from.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[allow(clippy::exhaustive_enums)]
@@ -445,6 +600,8 @@ pub enum Direction {
 /// const _: Metadata = metadata! {
 ///     method: GET, // one of the associated constants of http::Method
 ///     rate_limited: true,
+///     // optional, one of the variants of api::RateLimitCategory, defaults to `General`
+///     rate_limit_category: RoomSend,
 ///     authentication: AccessToken, // one of the variants of api::AuthScheme
 ///
 ///     // history of endpoint paths
@@ -462,11 +619,35 @@ pub enum Direction {
 #[macro_export]
 macro_rules! metadata {
     ( $( $field:ident: $rhs:tt ),+ $(,)? ) => {
+        $crate::metadata!(@fields [] [] $( $field: $rhs, )+)
+    };
+
+    // Collect the fields, the rate limit category is handled separately to be able to default it
+    ( @fields [ $($fields:tt)* ] [ $($category:tt)* ]
+        rate_limit_category: $new_category:ident, $($rest:tt)*
+    ) => {
+        $crate::metadata!(@fields [ $($fields)* ] [ $new_category ] $($rest)*)
+    };
+
+    ( @fields [ $($fields:tt)* ] [ $($category:tt)* ] $field:ident: $rhs:tt, $($rest:tt)* ) => {
+        $crate::metadata!(
+            @fields
+            [ $($fields)* $field: $crate::metadata!(@field $field: $rhs), ]
+            [ $($category)* ]
+            $($rest)*
+        )
+    };
+
+    ( @fields [ $($fields:tt)* ] [ $($category:ident)? ] ) => {
         $crate::api::Metadata {
-            $( $field: $crate::metadata!(@field $field: $rhs) ),+
+            $($fields)*
+            rate_limit_category: $crate::metadata!(@rate_limit_category $($category)?),
         }
     };
 
+    ( @rate_limit_category ) => { $crate::api::RateLimitCategory::General };
+    ( @rate_limit_category $category:ident ) => { $crate::api::RateLimitCategory::$category };
+
     ( @field method: $method:ident ) => { $crate::exports::http::Method::$method };
 
     ( @field authentication: $scheme:ident ) => { $crate::api::AuthScheme::$scheme };
@@ -509,3 +690,22 @@ macro_rules! metadata {
     ( @optional_version ) => { None };
     ( @optional_version $version:literal ) => { Some($crate::api::MatrixVersion::from_lit(stringify!($version))) }
 }
+
+/// Expands to the given items if the `spec-gen` feature of this crate is enabled.
+///
+/// This is used by the `request` and `response` macros, so the crates defining endpoints don't
+/// need a `spec-gen` feature of their own.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "spec-gen")]
+macro_rules! __spec_gen {
+    ( $( $item:item )* ) => { $( $item )* };
+}
+
+/// Expands to the given items if the `spec-gen` feature of this crate is enabled.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "spec-gen"))]
+macro_rules! __spec_gen {
+    ( $( $item:item )* ) => {};
+}
@@ -0,0 +1,187 @@
+//! Serializable descriptions of endpoints.
+//!
+//! These are generated by the [`request`](super::request) and [`response`](super::response)
+//! macros and can be used to generate OpenAPI documents or route tables.
+
+use serde::Serialize;
+
+use super::{AuthScheme, Metadata, RateLimitCategory};
+
+/// A serializable description of an endpoint.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct EndpointDescriptor {
+    /// The HTTP method used by this endpoint.
+    pub method: String,
+
+    /// Whether or not this endpoint is rate limited by the server.
+    pub rate_limited: bool,
+
+    /// The category of rate limits that applies to this endpoint.
+    pub rate_limit_category: RateLimitCategory,
+
+    /// What authentication scheme the server uses for this endpoint.
+    pub authentication: AuthScheme,
+
+    /// The unstable paths of this endpoint.
+    pub unstable_paths: Vec<&'static str>,
+
+    /// The stable paths of this endpoint, with the Matrix version they were added in.
+    pub stable_paths: Vec<StablePathDescriptor>,
+
+    /// The Matrix version that deprecated this endpoint, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+
+    /// The Matrix version that removed this endpoint, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<String>,
+
+    /// The description of the request.
+    pub request: MessageDescriptor,
+
+    /// The description of the response.
+    pub response: MessageDescriptor,
+}
+
+impl EndpointDescriptor {
+    /// Creates a new `EndpointDescriptor` from the given metadata and request and response
+    /// descriptions.
+    pub fn new(
+        metadata: &Metadata,
+        request: MessageDescriptor,
+        response: MessageDescriptor,
+    ) -> Self {
+        let history = &metadata.history;
+
+        Self {
+            method: metadata.method.to_string(),
+            rate_limited: metadata.rate_limited,
+            rate_limit_category: metadata.rate_limit_category,
+            authentication: metadata.authentication,
+            unstable_paths: history.unstable_paths().collect(),
+            stable_paths: history
+                .stable_paths()
+                .map(|(version, path)| StablePathDescriptor { version: version.to_string(), path })
+                .collect(),
+            deprecated: history.deprecated_in().map(|v| v.to_string()),
+            removed: history.removed_in().map(|v| v.to_string()),
+            request,
+            response,
+        }
+    }
+}
+
+/// A stable path of an endpoint.
+#[derive(Clone, Debug, Serialize)]
+#[allow(clippy::exhaustive_structs)]
+pub struct StablePathDescriptor {
+    /// The Matrix version this path was added in.
+    pub version: String,
+
+    /// The path.
+    pub path: &'static str,
+}
+
+/// A serializable description of a request or a response.
+#[derive(Clone, Debug, Serialize)]
+#[allow(clippy::exhaustive_structs)]
+pub struct MessageDescriptor {
+    /// The name of the Rust type.
+    pub type_name: &'static str,
+
+    /// The fields in the path of the request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<FieldDescriptor>,
+
+    /// The fields in the query string of the request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<FieldDescriptor>,
+
+    /// The fields in the headers.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<FieldDescriptor>,
+
+    /// The body.
+    pub body: BodyDescriptor,
+}
+
+/// A field of a request or response.
+#[derive(Clone, Debug, Serialize)]
+#[allow(clippy::exhaustive_structs)]
+pub struct FieldDescriptor {
+    /// The name of the field, after applying `#[serde(rename)]` if present.
+    ///
+    /// For header fields, this is the name of the header.
+    pub name: String,
+
+    /// The Rust type of the field, as written in the endpoint definition.
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+}
+
+/// The body of a request or response.
+#[derive(Clone, Debug, Serialize)]
+#[allow(clippy::exhaustive_enums)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BodyDescriptor {
+    /// There is no body, or an empty JSON object.
+    Empty,
+
+    /// A JSON object with the given fields.
+    Fields {
+        /// The fields of the JSON object.
+        fields: Vec<FieldDescriptor>,
+    },
+
+    /// A JSON value of the given type.
+    Newtype {
+        /// The Rust type of the body.
+        #[serde(rename = "type")]
+        ty: &'static str,
+    },
+
+    /// Arbitrary bytes.
+    Raw,
+}
+
+/// A request or response type that can describe itself.
+///
+/// This is implemented by the [`request`](super::request) and [`response`](super::response)
+/// macros when the `spec-gen` feature of this crate is enabled.
+pub trait DescribeMessage {
+    /// Get the description of this type.
+    fn describe_message() -> MessageDescriptor;
+}
+
+/// A request type that can describe itself and knows its endpoint.
+///
+/// This is implemented by the [`request`](super::request) macro when the `spec-gen` feature of
+/// this crate is enabled.
+pub trait DescribeRequest: DescribeMessage {
+    /// The response type of the endpoint.
+    type Response;
+
+    /// The metadata of the endpoint.
+    const METADATA: Metadata;
+}
+
+/// A request type that can describe its endpoint.
+///
+/// This is implemented for all the requests that implement [`DescribeRequest`] and whose response
+/// implements [`DescribeMessage`], so it is available when both the request and the response are
+/// generated by the [`request`](super::request) and [`response`](super::response) macros.
+pub trait DescribeEndpoint: DescribeMessage {
+    /// Get the description of the endpoint of this request.
+    fn describe_endpoint() -> EndpointDescriptor;
+}
+
+impl<T> DescribeEndpoint for T
+where
+    T: DescribeRequest,
+    T::Response: DescribeMessage,
+{
+    fn describe_endpoint() -> EndpointDescriptor {
+        EndpointDescriptor::new(&T::METADATA, T::describe_message(), T::Response::describe_message())
+    }
+}
@@ -2,13 +2,14 @@
 //! converting between http requests / responses and ruma's representation of
 //! matrix API requests / responses.
 
-use std::{error::Error as StdError, fmt, sync::Arc};
+use std::{error::Error as StdError, fmt, sync::Arc, time::Duration};
 
 use bytes::{BufMut, Bytes};
 use serde_json::{from_slice as from_json_slice, Value as JsonValue};
 use thiserror::Error;
 
 use super::{EndpointError, MatrixVersion, OutgoingResponse};
+use crate::{serde::JsonObject, RoomVersionId};
 
 /// A general-purpose Matrix error type consisting of an HTTP status code and a JSON body.
 ///
@@ -91,6 +92,52 @@ impl MatrixErrorBody {
             },
         }
     }
+
+    /// The JSON object of the body, if it is one.
+    ///
+    /// This contains all the fields of the body, including the ones that don't have a dedicated
+    /// accessor.
+    pub fn as_object(&self) -> Option<&JsonObject> {
+        match self {
+            Self::Json(JsonValue::Object(object)) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// The `errcode` field of the body, if it is present and a string.
+    pub fn errcode(&self) -> Option<&str> {
+        self.get("errcode")?.as_str()
+    }
+
+    /// The `error` field of the body, if it is present and a string.
+    pub fn error(&self) -> Option<&str> {
+        self.get("error")?.as_str()
+    }
+
+    /// The `soft_logout` field of the body, if it is present and a boolean.
+    ///
+    /// It is used with the `M_UNKNOWN_TOKEN` error code.
+    pub fn soft_logout(&self) -> Option<bool> {
+        self.get("soft_logout")?.as_bool()
+    }
+
+    /// The `retry_after_ms` field of the body, if it is present and a valid duration.
+    ///
+    /// It is used with the `M_LIMIT_EXCEEDED` error code.
+    pub fn retry_after_ms(&self) -> Option<Duration> {
+        self.get("retry_after_ms")?.as_u64().map(Duration::from_millis)
+    }
+
+    /// The `room_version` field of the body, if it is present and a valid room version.
+    ///
+    /// It is used with the `M_INCOMPATIBLE_ROOM_VERSION` error code.
+    pub fn room_version(&self) -> Option<RoomVersionId> {
+        self.get("room_version")?.as_str()?.try_into().ok()
+    }
+
+    fn get(&self, field: &str) -> Option<&JsonValue> {
+        self.as_object()?.get(field)
+    }
 }
 
 /// An error when converting one of ruma's endpoint-specific request or response
@@ -296,3 +343,63 @@ impl fmt::Display for IncorrectArgumentCount {
 }
 
 impl StdError for IncorrectArgumentCount {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{MatrixError, MatrixErrorBody};
+    use crate::{
+        api::{EndpointError, OutgoingResponse},
+        RoomVersionId,
+    };
+
+    #[test]
+    fn body_accessors() {
+        let body = MatrixErrorBody::Json(json!({
+            "errcode": "M_LIMIT_EXCEEDED",
+            "error": "Too many requests",
+            "retry_after_ms": 2000,
+            "soft_logout": true,
+            "room_version": "9",
+        }));
+
+        assert_eq!(body.errcode(), Some("M_LIMIT_EXCEEDED"));
+        assert_eq!(body.error(), Some("Too many requests"));
+        assert_eq!(body.retry_after_ms(), Some(Duration::from_millis(2000)));
+        assert_eq!(body.soft_logout(), Some(true));
+        assert_eq!(body.room_version(), Some(RoomVersionId::V9));
+
+        let body = MatrixErrorBody::from_bytes(b"<html>");
+        assert_eq!(body.as_object(), None);
+        assert_eq!(body.errcode(), None);
+    }
+
+    #[test]
+    fn round_trip_extra_fields() {
+        let json = json!({
+            "errcode": "M_UNKNOWN",
+            "error": "Something happened",
+            "org.example.extra": { "foo": [1, 2.5] },
+        });
+        let http_response = http::Response::builder()
+            .status(http::StatusCode::BAD_REQUEST)
+            .body(to_json_vec(&json).unwrap())
+            .unwrap();
+
+        let error = MatrixError::from_http_response(http_response);
+        assert_eq!(
+            error.body.as_object().unwrap()["org.example.extra"],
+            json!({ "foo": [1, 2.5] })
+        );
+
+        let http_response = error.try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(http_response.status(), http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(http_response.body()).unwrap(),
+            json
+        );
+    }
+}
@@ -14,7 +14,7 @@ use tracing::warn;
 
 use super::{
     error::{IntoHttpError, UnknownVersionError},
-    AuthScheme, SendAccessToken,
+    AuthScheme, RateLimitCategory, SendAccessToken,
 };
 use crate::{serde::slice_to_buf, RoomVersionId};
 
@@ -28,6 +28,9 @@ pub struct Metadata {
     /// Whether or not this endpoint is rate limited by the server.
     pub rate_limited: bool,
 
+    /// The category of rate limits that applies to this endpoint.
+    pub rate_limit_category: RateLimitCategory,
+
     /// What authentication scheme the server uses for this endpoint.
     pub authentication: AuthScheme,
 
@@ -52,7 +55,7 @@ impl Metadata {
     }
 
     /// Transform the `SendAccessToken` into an access token if the endpoint requires it, or if it
-    /// is `SendAccessToken::Force`.
+    /// is `SendAccessToken::Always`.
     ///
     /// Fails if the endpoint requires an access token but the parameter is `SendAccessToken::None`,
     /// or if the access token can't be converted to a [`HeaderValue`].
@@ -74,6 +77,16 @@ impl Metadata {
                 Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?))
             }
 
+            AuthScheme::AccessTokenOptional => match access_token.get_required_for_endpoint() {
+                Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
+                None => None,
+            },
+
+            AuthScheme::AppserviceToken => match access_token.get_required_for_appservice() {
+                Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
+                None => None,
+            },
+
             AuthScheme::ServerSignatures => None,
         })
     }
@@ -87,8 +100,14 @@ impl Metadata {
         query_string: &str,
     ) -> Result<String, IntoHttpError> {
         let path_with_placeholders = self.history.select_path(versions)?;
+        let base_url = base_url.strip_suffix('/').unwrap_or(base_url);
+
+        // Most path arguments are short IDs, so this is usually enough to avoid reallocations.
+        let mut res = String::with_capacity(
+            base_url.len() + path_with_placeholders.len() + query_string.len() + 64,
+        );
+        res.push_str(base_url);
 
-        let mut res = base_url.strip_suffix('/').unwrap_or(base_url).to_owned();
         let mut segments = path_with_placeholders.split('/');
         let mut path_args = path_args.iter();
 
@@ -99,11 +118,18 @@ impl Metadata {
             if segment.starts_with(':') {
                 let arg = path_args
                     .next()
-                    .expect("number of placeholders must match number of arguments")
-                    .to_string();
-                let arg = utf8_percent_encode(&arg, percent_encoding::NON_ALPHANUMERIC);
+                    .expect("number of placeholders must match number of arguments");
 
-                write!(res, "/{arg}").expect("writing to a String using fmt::Write can't fail");
+                res.push('/');
+                let arg_start = res.len();
+                write!(res, "{arg}").expect("writing to a String using fmt::Write can't fail");
+
+                // Only percent-encode the argument if it contains characters that need escaping,
+                // to avoid an allocation in the common case.
+                if !res.as_bytes()[arg_start..].iter().all(u8::is_ascii_alphanumeric) {
+                    let arg = res.split_off(arg_start);
+                    res.extend(utf8_percent_encode(&arg, percent_encoding::NON_ALPHANUMERIC));
+                }
             } else {
                 res.reserve(segment.len() + 1);
                 res.push('/');
@@ -674,14 +700,15 @@ mod tests {
     use super::{
         AuthScheme,
         MatrixVersion::{self, V1_0, V1_1, V1_2, V1_3},
-        Metadata, VersionHistory,
+        Metadata, RateLimitCategory, VersionHistory,
     };
-    use crate::api::error::IntoHttpError;
+    use crate::api::{error::IntoHttpError, SendAccessToken};
 
     fn stable_only_metadata(stable_paths: &'static [(MatrixVersion, &'static str)]) -> Metadata {
         Metadata {
             method: Method::GET,
             rate_limited: false,
+            rate_limit_category: RateLimitCategory::General,
             authentication: AuthScheme::None,
             history: VersionHistory {
                 unstable_paths: &[],
@@ -715,6 +742,15 @@ mod tests {
         assert_eq!(url, "https://example.org/s/?foo=bar");
     }
 
+    #[test]
+    fn make_endpoint_url_with_escaped_path_args() {
+        let meta = stable_only_metadata(&[(V1_0, "/s/:x/:y")]);
+        let url = meta
+            .make_endpoint_url(&[V1_0], "https://example.org/", &[&"!a:b.c", &"d e"], "q=1")
+            .unwrap();
+        assert_eq!(url, "https://example.org/s/%21a%3Ab%2Ec/d%20e?q=1");
+    }
+
     #[test]
     #[should_panic]
     fn make_endpoint_url_wrong_num_path_args() {
@@ -722,6 +758,48 @@ mod tests {
         _ = meta.make_endpoint_url(&[V1_0], "https://example.org", &[], "");
     }
 
+    #[test]
+    fn authorization_header() {
+        fn header(authentication: AuthScheme, access_token: SendAccessToken<'_>) -> Option<String> {
+            let mut meta = stable_only_metadata(&[(V1_0, "/s")]);
+            meta.authentication = authentication;
+            meta.authorization_header(access_token)
+                .unwrap()
+                .map(|(_, value)| value.to_str().unwrap().to_owned())
+        }
+
+        let bearer = Some("Bearer tok".to_owned());
+
+        assert_eq!(header(AuthScheme::None, SendAccessToken::IfRequired("tok")), None);
+        assert_eq!(header(AuthScheme::None, SendAccessToken::Appservice("tok")), None);
+        assert_eq!(header(AuthScheme::None, SendAccessToken::Always("tok")), bearer);
+
+        assert_eq!(header(AuthScheme::AccessToken, SendAccessToken::IfRequired("tok")), bearer);
+        assert_eq!(header(AuthScheme::AccessToken, SendAccessToken::Appservice("tok")), bearer);
+
+        assert_eq!(
+            header(AuthScheme::AccessTokenOptional, SendAccessToken::IfRequired("tok")),
+            bearer
+        );
+        assert_eq!(header(AuthScheme::AccessTokenOptional, SendAccessToken::None), None);
+
+        assert_eq!(header(AuthScheme::AppserviceToken, SendAccessToken::IfRequired("tok")), None);
+        assert_eq!(header(AuthScheme::AppserviceToken, SendAccessToken::Appservice("tok")), bearer);
+        assert_eq!(header(AuthScheme::AppserviceToken, SendAccessToken::None), None);
+
+        assert_eq!(header(AuthScheme::ServerSignatures, SendAccessToken::Always("tok")), None);
+    }
+
+    #[test]
+    fn authorization_header_required() {
+        let mut meta = stable_only_metadata(&[(V1_0, "/s")]);
+        meta.authentication = AuthScheme::AccessToken;
+        assert_matches!(
+            meta.authorization_header(SendAccessToken::None),
+            Err(IntoHttpError::NeedsAuthentication)
+        );
+    }
+
     const EMPTY: VersionHistory =
         VersionHistory { unstable_paths: &[], stable_paths: &[], deprecated: None, removed: None };
 
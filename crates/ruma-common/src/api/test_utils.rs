@@ -0,0 +1,207 @@
+//! Helpers to test the (de)serialization of endpoints against example JSON.
+//!
+//! The [`assert_round_trip!`](crate::assert_round_trip) macro checks that the example request and
+//! response of an [`EndpointFixture`] survive a round-trip through the Ruma types unchanged.
+
+use std::collections::BTreeSet;
+
+use http::header::CONTENT_TYPE;
+use percent_encoding::percent_decode_str;
+use serde_json::Value as JsonValue;
+
+use super::{
+    IncomingRequest, IncomingResponse, MatrixVersion, OutgoingRequest, OutgoingResponse,
+    SendAccessToken,
+};
+
+/// Example JSON for an endpoint, usually taken from the examples in the Matrix specification.
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct EndpointFixture {
+    /// The path of the request, with the query string if any.
+    ///
+    /// The path must match one of the paths of the endpoint's metadata.
+    pub request_path: &'static str,
+
+    /// The JSON body of the request, if any.
+    pub request_body: Option<&'static str>,
+
+    /// The JSON body of a successful response.
+    pub response_body: &'static str,
+}
+
+/// Assert that the request and response of the given endpoint round-trip the JSON of the given
+/// [`EndpointFixture`] unchanged.
+///
+/// The first argument is the path of the module of the endpoint, containing the `Request` and
+/// `Response` types.
+///
+/// # Example
+///
+/// ```ignore
+/// assert_round_trip!(membership::joined_rooms::v3, fixtures::membership::JOINED_ROOMS);
+/// ```
+#[macro_export]
+macro_rules! assert_round_trip {
+    ( $( $endpoint:ident )::+, $fixture:expr $(,)? ) => {{
+        let fixture: $crate::api::test_utils::EndpointFixture = $fixture;
+        $crate::api::test_utils::assert_request_round_trip::<$( $endpoint )::+::Request>(
+            fixture.request_path,
+            fixture.request_body,
+        );
+        $crate::api::test_utils::assert_response_round_trip::<$( $endpoint )::+::Response>(
+            fixture.response_body,
+        );
+    }};
+}
+
+/// Assert that the request with the given path and JSON body is parsed and serialized back to the
+/// same path and JSON body.
+///
+/// # Panics
+///
+/// Panics if the path doesn't match the endpoint, if the request can't be (de)serialized or if
+/// the result is different.
+pub fn assert_request_round_trip<R>(request_path: &str, request_body: Option<&str>)
+where
+    R: IncomingRequest + OutgoingRequest,
+{
+    let metadata = <R as IncomingRequest>::METADATA;
+    let (path, query) = request_path.split_once('?').unwrap_or((request_path, ""));
+
+    let (version, path_args) = metadata
+        .history
+        .unstable_paths()
+        .map(|template| (None, template))
+        .chain(metadata.history.stable_paths().map(|(version, template)| (Some(version), template)))
+        .find_map(|(version, template)| Some((version, match_path(template, path)?)))
+        .unwrap_or_else(|| panic!("`{path}` doesn't match any path of the endpoint"));
+
+    let body = match request_body {
+        Some(body) => body.as_bytes().to_vec(),
+        None => metadata.empty_request_body(),
+    };
+    let http_request = http::Request::builder()
+        .method(metadata.method.clone())
+        .uri(request_path)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap();
+
+    let request = R::try_from_http_request(http_request, &path_args)
+        .unwrap_or_else(|e| panic!("failed to deserialize the request: {e}"));
+
+    let considering_versions: &[MatrixVersion] = match &version {
+        Some(version) => std::slice::from_ref(version),
+        None => &[],
+    };
+    let http_request = request
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::IfRequired("access_token"),
+            considering_versions,
+        )
+        .unwrap_or_else(|e| panic!("failed to serialize the request: {e}"));
+
+    assert_eq!(
+        percent_decode_str(http_request.uri().path()).decode_utf8_lossy(),
+        percent_decode_str(path).decode_utf8_lossy(),
+        "the request path changed"
+    );
+    assert_eq!(
+        query_pairs(http_request.uri().query().unwrap_or("")),
+        query_pairs(query),
+        "the request query string changed"
+    );
+
+    match request_body {
+        Some(body) => assert_eq!(
+            parse_json(http_request.body()),
+            parse_json(body.as_bytes()),
+            "the request body changed"
+        ),
+        None => assert!(
+            matches!(http_request.body().as_slice(), b"" | b"{}"),
+            "the request has an unexpected body"
+        ),
+    }
+}
+
+/// Assert that the response with the given JSON body is parsed and serialized back to the same
+/// JSON body.
+///
+/// # Panics
+///
+/// Panics if the response can't be (de)serialized or if the result is different.
+pub fn assert_response_round_trip<R>(response_body: &str)
+where
+    R: IncomingResponse + OutgoingResponse,
+{
+    let http_response = http::Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(response_body.as_bytes())
+        .unwrap();
+
+    let response = R::try_from_http_response(http_response)
+        .unwrap_or_else(|e| panic!("failed to deserialize the response: {e}"));
+    let http_response = response
+        .try_into_http_response::<Vec<u8>>()
+        .unwrap_or_else(|e| panic!("failed to serialize the response: {e}"));
+
+    assert_eq!(
+        parse_json(http_response.body()),
+        parse_json(response_body.as_bytes()),
+        "the response body changed"
+    );
+}
+
+/// Match the given path against the given path template, and return the percent-decoded path
+/// arguments if they match.
+fn match_path(template: &str, path: &str) -> Option<Vec<String>> {
+    let template_segments: Vec<_> = template.split('/').collect();
+    let path_segments: Vec<_> = path.split('/').collect();
+
+    if template_segments.len() != path_segments.len() {
+        return None;
+    }
+
+    let mut path_args = Vec::new();
+    for (template_segment, path_segment) in template_segments.into_iter().zip(path_segments) {
+        if template_segment.starts_with(':') {
+            path_args.push(percent_decode_str(path_segment).decode_utf8().ok()?.into_owned());
+        } else if template_segment != path_segment {
+            return None;
+        }
+    }
+
+    Some(path_args)
+}
+
+fn query_pairs(query: &str) -> BTreeSet<(String, String)> {
+    form_urlencoded::parse(query.as_bytes()).into_owned().collect()
+}
+
+fn parse_json(body: &[u8]) -> JsonValue {
+    serde_json::from_slice(body).unwrap_or_else(|e| panic!("body is not valid JSON: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_path;
+
+    #[test]
+    fn match_path_template() {
+        assert_eq!(
+            match_path(
+                "/_matrix/client/v3/rooms/:room_id/join",
+                "/_matrix/client/v3/rooms/%21r:s/join"
+            ),
+            Some(vec!["!r:s".to_owned()])
+        );
+        assert_eq!(
+            match_path("/_matrix/client/v3/rooms/:room_id/join", "/_matrix/client/v3/join"),
+            None
+        );
+        assert_eq!(match_path("/_matrix/client/v3/sync", "/_matrix/client/r0/sync"), None);
+    }
+}
@@ -5,16 +5,20 @@ use std::{fmt, mem};
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 
+mod validate;
 mod value;
 
-use crate::RoomVersionId;
-#[cfg(feature = "events")]
+#[cfg(feature = "events-core")]
 use crate::{
     events::room::redaction::{OriginalRoomRedactionEvent, OriginalSyncRoomRedactionEvent},
     serde::Raw,
 };
+use crate::{room_version_rules::RedactionRules, RoomVersionId};
 
-pub use self::value::{CanonicalJsonObject, CanonicalJsonValue};
+pub use self::{
+    validate::{validate_pdu_json, PduValidationError},
+    value::{CanonicalJsonObject, CanonicalJsonValue},
+};
 
 /// The set of possible errors when serializing to canonical JSON.
 #[cfg(feature = "canonical-json")]
@@ -57,6 +61,12 @@ pub enum RedactionError {
 
     /// The given required field is missing from a JSON object.
     JsonFieldMissingFromObject(String),
+
+    /// The rules of the room version are unknown.
+    ///
+    /// The rules of custom room versions can be registered with
+    /// [`RoomVersionRules::register_custom()`](crate::room_version_rules::RoomVersionRules::register_custom).
+    UnsupportedRoomVersion(RoomVersionId),
 }
 
 impl fmt::Display for RedactionError {
@@ -68,6 +78,9 @@ impl fmt::Display for RedactionError {
             RedactionError::JsonFieldMissingFromObject(field) => {
                 write!(f, "JSON object must contain the field {field:?}")
             }
+            RedactionError::UnsupportedRoomVersion(version) => {
+                write!(f, "unsupported room version {version}")
+            }
         }
     }
 }
@@ -85,7 +98,7 @@ impl RedactionError {
 }
 
 /// A JSON type enum for [`RedactionError`] variants.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[allow(clippy::exhaustive_enums)]
 pub enum JsonType {
     /// A JSON Object.
@@ -133,7 +146,7 @@ impl From<CanonicalJsonObject> for RedactedBecause {
     }
 }
 
-#[cfg(feature = "events")]
+#[cfg(feature = "events-core")]
 impl TryFrom<&Raw<OriginalRoomRedactionEvent>> for RedactedBecause {
     type Error = serde_json::Error;
 
@@ -142,7 +155,7 @@ impl TryFrom<&Raw<OriginalRoomRedactionEvent>> for RedactedBecause {
     }
 }
 
-#[cfg(feature = "events")]
+#[cfg(feature = "events-core")]
 impl TryFrom<&Raw<OriginalSyncRoomRedactionEvent>> for RedactedBecause {
     type Error = serde_json::Error;
 
@@ -175,6 +188,7 @@ impl TryFrom<&Raw<OriginalSyncRoomRedactionEvent>> for RedactedBecause {
 /// * `object` contains a field called `hashes` that is not a JSON object.
 /// * `object` contains a field called `signatures` that is not a JSON object.
 /// * `object` is missing the `type` field or the field is not a JSON string.
+/// * The rules of `version` are unknown.
 pub fn redact(
     mut object: CanonicalJsonObject,
     version: &RoomVersionId,
@@ -198,7 +212,7 @@ pub fn redact_in_place(
     // can't teach rust that this is a disjoint borrow with `get_mut("content")`.
     let allowed_content_keys: &[&str] = match event.get("type") {
         Some(CanonicalJsonValue::String(event_type)) => {
-            allowed_content_keys_for(event_type, version)
+            allowed_content_keys_for(event_type, version)?
         }
         Some(_) => return Err(RedactionError::not_of_type("type", JsonType::String)),
         None => return Err(RedactionError::field_missing_from_object("type")),
@@ -235,12 +249,17 @@ pub fn redact_in_place(
 /// Redacts event content using the rules specified in the Matrix client-server specification.
 ///
 /// Edits the `object` in-place.
+///
+/// # Errors
+///
+/// Returns an error if the rules of `version` are unknown.
 pub fn redact_content_in_place(
     object: &mut CanonicalJsonObject,
     version: &RoomVersionId,
     event_type: impl AsRef<str>,
-) {
-    object_retain_keys(object, allowed_content_keys_for(event_type.as_ref(), version));
+) -> Result<(), RedactionError> {
+    object_retain_keys(object, allowed_content_keys_for(event_type.as_ref(), version)?);
+    Ok(())
 }
 
 fn object_retain_keys(object: &mut CanonicalJsonObject, keys: &[&str]) {
@@ -272,19 +291,26 @@ static ALLOWED_KEYS: &[&str] = &[
     "membership",
 ];
 
-fn allowed_content_keys_for(event_type: &str, version: &RoomVersionId) -> &'static [&'static str] {
-    match event_type {
-        "m.room.member" => match version {
-            RoomVersionId::V9 | RoomVersionId::V10 => {
-                &["membership", "join_authorised_via_users_server"]
-            }
-            _ => &["membership"],
-        },
+fn allowed_content_keys_for(
+    event_type: &str,
+    version: &RoomVersionId,
+) -> Result<&'static [&'static str], RedactionError> {
+    let rules = match RedactionRules::for_room_version(version) {
+        Some(rules) => rules,
+        // The MSC2870 room version is based on room version 10.
+        #[cfg(feature = "unstable-msc2870")]
+        None if version.as_str() == "org.matrix.msc2870" => RedactionRules::V9,
+        None => return Err(RedactionError::UnsupportedRoomVersion(version.clone())),
+    };
+
+    let keys: &[&str] = match event_type {
+        "m.room.member" if rules.keep_room_member_join_authorised_via_users_server => {
+            &["membership", "join_authorised_via_users_server"]
+        }
+        "m.room.member" => &["membership"],
         "m.room.create" => &["creator"],
-        "m.room.join_rules" => match version {
-            RoomVersionId::V8 | RoomVersionId::V9 | RoomVersionId::V10 => &["join_rule", "allow"],
-            _ => &["join_rule"],
-        },
+        "m.room.join_rules" if rules.keep_room_join_rules_allow => &["join_rule", "allow"],
+        "m.room.join_rules" => &["join_rule"],
         "m.room.power_levels" => &[
             "ban",
             "events",
@@ -295,33 +321,31 @@ fn allowed_content_keys_for(event_type: &str, version: &RoomVersionId) -> &'stat
             "users",
             "users_default",
         ],
-        "m.room.aliases" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5 => &["aliases"],
-            // All other room versions, including custom ones, are treated by version 6 rules.
-            // TODO: Should we return an error for unknown versions instead?
-            _ => &[],
-        },
+        "m.room.aliases" if rules.keep_room_aliases_aliases => &["aliases"],
+        "m.room.aliases" => &[],
         #[cfg(feature = "unstable-msc2870")]
         "m.room.server_acl" if version.as_str() == "org.matrix.msc2870" => {
             &["allow", "deny", "allow_ip_literals"]
         }
         "m.room.history_visibility" => &["history_visibility"],
         _ => &[],
-    }
+    };
+
+    Ok(keys)
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
+    use assert_matches::assert_matches;
     use js_int::int;
     use serde_json::{from_str as from_json_str, json, to_string as to_json_string};
 
-    use super::{to_canonical_value, try_from_json_map, value::CanonicalJsonValue};
+    use super::{
+        redact, to_canonical_value, try_from_json_map, value::CanonicalJsonValue, RedactionError,
+    };
+    use crate::RoomVersionId;
 
     #[test]
     fn serialize_canon() {
@@ -411,4 +435,21 @@ mod tests {
 
         assert_eq!(to_canonical_value(t).unwrap(), CanonicalJsonValue::Object(expected));
     }
+
+    #[test]
+    fn redact_unknown_room_version() {
+        let object = try_from_json_map(
+            json!({ "type": "m.room.aliases", "content": { "aliases": ["#room:localhost"] } })
+                .as_object()
+                .unwrap()
+                .clone(),
+        )
+        .unwrap();
+        let version = RoomVersionId::try_from("org.example.unknown").unwrap();
+
+        assert_matches!(
+            redact(object, &version, None),
+            Err(RedactionError::UnsupportedRoomVersion(unknown)) if unknown == version
+        );
+    }
 }
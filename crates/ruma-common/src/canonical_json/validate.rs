@@ -0,0 +1,314 @@
+use std::fmt;
+
+use serde_json::{value::RawValue as RawJsonValue, Map as JsonObject, Value as JsonValue};
+
+use super::JsonType;
+use crate::{room_version_rules::EventFormatVersion, RoomVersionId};
+
+/// The maximum size of a PDU, in bytes.
+const MAX_PDU_BYTES: usize = 65_536;
+
+/// The maximum size of the string fields of a PDU that are checked by [`validate_pdu_json`], in
+/// bytes.
+const MAX_FIELD_BYTES: usize = 255;
+
+/// The largest integer that can be represented in canonical JSON.
+const MAX_SAFE_INT: i64 = 0x001F_FFFF_FFFF_FFFF;
+
+/// The fields that are required in the PDUs of all room versions.
+static REQUIRED_FIELDS: &[(&str, JsonType)] = &[
+    ("auth_events", JsonType::Array),
+    ("content", JsonType::Object),
+    ("depth", JsonType::Integer),
+    ("hashes", JsonType::Object),
+    ("origin_server_ts", JsonType::Integer),
+    ("prev_events", JsonType::Array),
+    ("room_id", JsonType::String),
+    ("sender", JsonType::String),
+    ("signatures", JsonType::Object),
+    ("type", JsonType::String),
+];
+
+/// The string fields whose size is limited.
+static SIZE_LIMITED_FIELDS: &[&str] = &["event_id", "room_id", "sender", "state_key", "type"];
+
+/// Errors that can happen when validating the JSON of a PDU.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum PduValidationError {
+    /// The PDU is not valid JSON.
+    InvalidJson(serde_json::Error),
+
+    /// The PDU is not a JSON object.
+    NotAnObject,
+
+    /// The PDU is larger than 65536 bytes once encoded as canonical JSON.
+    TooLarge(usize),
+
+    /// The given required field is missing from the PDU.
+    MissingField(String),
+
+    /// The field `field` is not of the correct type `of_type` ([`JsonType`]).
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    NotOfType {
+        /// The field name.
+        field: String,
+        /// The expected JSON type.
+        of_type: JsonType,
+    },
+
+    /// The given field is larger than 255 bytes.
+    FieldTooLong(String),
+
+    /// The number at the given path is not an integer.
+    Float(String),
+
+    /// The integer at the given path is outside of the range allowed by canonical JSON.
+    IntegerOutOfRange(String),
+}
+
+impl fmt::Display for PduValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PduValidationError::InvalidJson(err) => write!(f, "invalid JSON: {err}"),
+            PduValidationError::NotAnObject => f.write_str("PDU must be a JSON object"),
+            PduValidationError::TooLarge(size) => {
+                write!(f, "PDU is {size} bytes long, the maximum is {MAX_PDU_BYTES} bytes")
+            }
+            PduValidationError::MissingField(field) => {
+                write!(f, "PDU must contain the field {field:?}")
+            }
+            PduValidationError::NotOfType { field, of_type } => {
+                write!(f, "Value in {field:?} must be a JSON {of_type:?}")
+            }
+            PduValidationError::FieldTooLong(field) => {
+                write!(f, "Value in {field:?} must not be larger than {MAX_FIELD_BYTES} bytes")
+            }
+            PduValidationError::Float(path) => {
+                write!(f, "Number at {path:?} must be an integer")
+            }
+            PduValidationError::IntegerOutOfRange(path) => {
+                write!(f, "Integer at {path:?} is outside of the range allowed by canonical JSON")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PduValidationError {}
+
+/// Check that the given PDU received over federation is valid according to the rules of the given
+/// room version.
+///
+/// This checks that:
+///
+/// * The PDU is a JSON object of at most 65536 bytes once encoded as canonical JSON.
+/// * The fields required in the room version are present, with the correct JSON type.
+/// * The `event_id`, `room_id`, `sender`, `state_key` and `type` fields are at most 255 bytes long.
+/// * Since room version 6, all the numbers in the PDU are integers in the range allowed by
+///   canonical JSON, `[-(2**53)+1, (2**53)-1]`.
+///
+/// This doesn't check the hashes or the signatures of the PDU, nor the content of the event.
+pub fn validate_pdu_json(
+    room_version: &RoomVersionId,
+    pdu: &RawJsonValue,
+) -> Result<(), PduValidationError> {
+    let object = match serde_json::from_str(pdu.get()).map_err(PduValidationError::InvalidJson)? {
+        JsonValue::Object(object) => object,
+        _ => return Err(PduValidationError::NotAnObject),
+    };
+
+    // Custom room versions are assumed to follow the rules of the latest room versions.
+    let rules = room_version.rules();
+    let strict_numbers = rules.as_ref().map_or(true, |rules| rules.strict_canonicaljson);
+
+    if strict_numbers {
+        for (key, value) in &object {
+            check_numbers(value, key)?;
+        }
+    }
+
+    let event_id_field = rules
+        .map_or(false, |rules| rules.event_format == EventFormatVersion::V1)
+        .then_some(("event_id", JsonType::String));
+
+    for (field, of_type) in REQUIRED_FIELDS.iter().chain(&event_id_field) {
+        match object.get(*field) {
+            Some(value) if is_of_type(value, of_type) => {}
+            Some(_) => {
+                return Err(PduValidationError::NotOfType {
+                    field: (*field).to_owned(),
+                    of_type: of_type.clone(),
+                })
+            }
+            None => return Err(PduValidationError::MissingField((*field).to_owned())),
+        }
+    }
+
+    for &field in SIZE_LIMITED_FIELDS {
+        match object.get(field) {
+            Some(JsonValue::String(s)) if s.len() > MAX_FIELD_BYTES => {
+                return Err(PduValidationError::FieldTooLong(field.to_owned()));
+            }
+            Some(JsonValue::String(_)) | None => {}
+            Some(_) => {
+                return Err(PduValidationError::NotOfType {
+                    field: field.to_owned(),
+                    of_type: JsonType::String,
+                })
+            }
+        }
+    }
+
+    let size = canonical_size(&object);
+    if size > MAX_PDU_BYTES {
+        return Err(PduValidationError::TooLarge(size));
+    }
+
+    Ok(())
+}
+
+/// Check recursively that all the numbers in the given value are valid canonical JSON integers.
+fn check_numbers(value: &JsonValue, path: &str) -> Result<(), PduValidationError> {
+    match value {
+        JsonValue::Number(n) => {
+            if n.is_f64() {
+                return Err(PduValidationError::Float(path.to_owned()));
+            }
+
+            match n.as_i64() {
+                Some(n) if (-MAX_SAFE_INT..=MAX_SAFE_INT).contains(&n) => Ok(()),
+                _ => Err(PduValidationError::IntegerOutOfRange(path.to_owned())),
+            }
+        }
+        JsonValue::Array(array) => array
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, value)| check_numbers(value, &format!("{path}[{i}]"))),
+        JsonValue::Object(object) => object
+            .iter()
+            .try_for_each(|(key, value)| check_numbers(value, &format!("{path}.{key}"))),
+        _ => Ok(()),
+    }
+}
+
+fn is_of_type(value: &JsonValue, of_type: &JsonType) -> bool {
+    match of_type {
+        JsonType::Object => value.is_object(),
+        JsonType::String => value.is_string(),
+        JsonType::Integer => value.is_i64() || value.is_u64(),
+        JsonType::Array => value.is_array(),
+        JsonType::Boolean => value.is_boolean(),
+        JsonType::Null => value.is_null(),
+    }
+}
+
+/// The size of the given object encoded as canonical JSON.
+///
+/// Canonical JSON only differs from compact JSON by the order of the keys of objects, which doesn't
+/// change the size. This also works for room versions before 6, which allow numbers that can't be
+/// represented in canonical JSON.
+fn canonical_size(object: &JsonObject<String, JsonValue>) -> usize {
+    serde_json::to_string(object).map_or(usize::MAX, |s| s.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::{json, value::to_raw_value as to_raw_json_value, Value as JsonValue};
+
+    use super::{validate_pdu_json, PduValidationError};
+    use crate::{canonical_json::JsonType, RoomVersionId};
+
+    fn pdu() -> JsonValue {
+        json!({
+            "auth_events": [],
+            "content": { "body": "Hello", "msgtype": "m.text" },
+            "depth": 12,
+            "hashes": { "sha256": "ThisHashCoversAllFieldsInCaseThisIsRedacted" },
+            "origin": "example.org",
+            "origin_server_ts": 1_234_567_890,
+            "prev_events": [],
+            "room_id": "!room:example.org",
+            "sender": "@alice:example.org",
+            "signatures": {},
+            "type": "m.room.message",
+        })
+    }
+
+    fn validate(room_version: &RoomVersionId, pdu: &JsonValue) -> Result<(), PduValidationError> {
+        validate_pdu_json(room_version, &to_raw_json_value(pdu).unwrap())
+    }
+
+    #[test]
+    fn valid_pdu() {
+        validate(&RoomVersionId::V10, &pdu()).unwrap();
+    }
+
+    #[test]
+    fn missing_or_invalid_fields() {
+        let mut missing_depth = pdu();
+        missing_depth.as_object_mut().unwrap().remove("depth");
+        let field = assert_matches!(
+            validate(&RoomVersionId::V10, &missing_depth),
+            Err(PduValidationError::MissingField(field)) => field
+        );
+        assert_eq!(field, "depth");
+
+        let mut string_content = pdu();
+        string_content["content"] = json!("Hello");
+        let field = assert_matches!(
+            validate(&RoomVersionId::V10, &string_content),
+            Err(PduValidationError::NotOfType { field, of_type: JsonType::Object }) => field
+        );
+        assert_eq!(field, "content");
+
+        // The event ID is only required in room versions 1 and 2.
+        let field = assert_matches!(
+            validate(&RoomVersionId::V1, &pdu()),
+            Err(PduValidationError::MissingField(field)) => field
+        );
+        assert_eq!(field, "event_id");
+        let mut with_event_id = pdu();
+        with_event_id["event_id"] = json!("$event:example.org");
+        validate(&RoomVersionId::V1, &with_event_id).unwrap();
+    }
+
+    #[test]
+    fn size_limits() {
+        let mut long_sender = pdu();
+        long_sender["sender"] = format!("@{}:example.org", "a".repeat(255)).into();
+        let field = assert_matches!(
+            validate(&RoomVersionId::V10, &long_sender),
+            Err(PduValidationError::FieldTooLong(field)) => field
+        );
+        assert_eq!(field, "sender");
+
+        let mut large_pdu = pdu();
+        large_pdu["content"]["body"] = "a".repeat(65_536).into();
+        assert_matches!(
+            validate(&RoomVersionId::V10, &large_pdu),
+            Err(PduValidationError::TooLarge(_))
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        let mut float = pdu();
+        float["content"]["info"] = json!({ "sizes": [1, 2.5] });
+        let path = assert_matches!(
+            validate(&RoomVersionId::V6, &float),
+            Err(PduValidationError::Float(path)) => path
+        );
+        assert_eq!(path, "content.info.sizes[1]");
+        validate(&RoomVersionId::V5, &float).unwrap();
+
+        let mut large_int = pdu();
+        large_int["content"]["count"] = json!(9_007_199_254_740_992_u64);
+        let path = assert_matches!(
+            validate(&RoomVersionId::V10, &large_int),
+            Err(PduValidationError::IntegerOutOfRange(path)) => path
+        );
+        assert_eq!(path, "content.count");
+        validate(&RoomVersionId::V4, &large_int).unwrap();
+    }
+}
@@ -8,7 +8,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     serde::{Base64, StringEnum},
-    EventEncryptionAlgorithm, OwnedDeviceId, OwnedDeviceKeyId, OwnedUserId, PrivOwnedStr,
+    DeviceKeyAlgorithm, EventEncryptionAlgorithm, OwnedDeviceId, OwnedDeviceKeyId, OwnedRoomId,
+    OwnedUserId, PrivOwnedStr,
 };
 
 /// Identity keys for a device.
@@ -172,3 +173,93 @@ pub enum KeyUsage {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+/// A room key in the [key export format].
+///
+/// To create an instance of this type, first create an `ExportedRoomKeyInit` and convert it via
+/// `ExportedRoomKey::from` / `.into()`.
+///
+/// [key export format]: https://spec.matrix.org/v1.4/client-server-api/#key-export-format
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ExportedRoomKey {
+    /// The encryption algorithm the key is to be used with.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// Chain of Curve25519 keys through which this session was forwarded, via
+    /// `m.forwarded_room_key` events.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// The room where the key is used.
+    pub room_id: OwnedRoomId,
+
+    /// The keys of the device which initiated the session originally, by algorithm.
+    pub sender_claimed_keys: BTreeMap<DeviceKeyAlgorithm, String>,
+
+    /// The Curve25519 key of the device which initiated the session originally.
+    pub sender_key: String,
+
+    /// The ID of the session that the key is for.
+    pub session_id: String,
+
+    /// The key for the session.
+    pub session_key: String,
+
+    /// Whether this key can be shared with users invited to the room after it was used, as
+    /// defined in [MSC3061].
+    ///
+    /// [MSC3061]: https://github.com/matrix-org/matrix-spec-proposals/pull/3061
+    #[cfg(feature = "unstable-msc3061")]
+    #[serde(
+        default,
+        rename = "org.matrix.msc3061.shared_history",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub shared_history: bool,
+}
+
+/// Initial set of fields of `ExportedRoomKey`.
+///
+/// This struct will not be updated even if additional fields are added to `ExportedRoomKey` in a
+/// new (non-breaking) release of the Matrix specification.
+#[derive(Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct ExportedRoomKeyInit {
+    /// The encryption algorithm the key is to be used with.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// Chain of Curve25519 keys through which this session was forwarded, via
+    /// `m.forwarded_room_key` events.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// The room where the key is used.
+    pub room_id: OwnedRoomId,
+
+    /// The keys of the device which initiated the session originally, by algorithm.
+    pub sender_claimed_keys: BTreeMap<DeviceKeyAlgorithm, String>,
+
+    /// The Curve25519 key of the device which initiated the session originally.
+    pub sender_key: String,
+
+    /// The ID of the session that the key is for.
+    pub session_id: String,
+
+    /// The key for the session.
+    pub session_key: String,
+}
+
+impl From<ExportedRoomKeyInit> for ExportedRoomKey {
+    fn from(init: ExportedRoomKeyInit) -> Self {
+        Self {
+            algorithm: init.algorithm,
+            forwarding_curve25519_key_chain: init.forwarding_curve25519_key_chain,
+            room_id: init.room_id,
+            sender_claimed_keys: init.sender_claimed_keys,
+            sender_key: init.sender_key,
+            session_id: init.session_id,
+            session_key: init.session_key,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history: false,
+        }
+    }
+}
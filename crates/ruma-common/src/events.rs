@@ -122,13 +122,20 @@ pub mod macros {
 
 #[cfg(feature = "unstable-msc3246")]
 pub mod audio;
+#[cfg(feature = "unstable-msc3672")]
+pub mod beacon;
+#[cfg(feature = "unstable-msc3672")]
+pub mod beacon_info;
+#[cfg(feature = "events-voip")]
 pub mod call;
 pub mod direct;
+#[cfg(feature = "events-e2ee")]
 pub mod dummy;
 #[cfg(feature = "unstable-msc1767")]
 pub mod emote;
 #[cfg(feature = "unstable-msc3551")]
 pub mod file;
+#[cfg(feature = "events-e2ee")]
 pub mod forwarded_room_key;
 pub mod fully_read;
 pub mod identity_server;
@@ -138,26 +145,34 @@ pub mod image;
 pub mod key;
 #[cfg(feature = "unstable-msc3488")]
 pub mod location;
+#[cfg(feature = "unstable-msc2867")]
+pub mod marked_unread;
 #[cfg(feature = "unstable-msc1767")]
 pub mod message;
 #[cfg(feature = "unstable-msc1767")]
 pub mod notice;
 #[cfg(feature = "unstable-pdu")]
 pub mod pdu;
+#[cfg(feature = "events-policy")]
 pub mod policy;
 #[cfg(feature = "unstable-msc3381")]
 pub mod poll;
 pub mod presence;
 pub mod push_rules;
-#[cfg(feature = "unstable-msc2677")]
 pub mod reaction;
 pub mod receipt;
 pub mod relation;
 pub mod room;
+#[cfg(feature = "events-e2ee")]
 pub mod room_key;
+#[cfg(feature = "events-e2ee")]
 pub mod room_key_request;
+#[cfg(feature = "events-e2ee")]
 pub mod secret;
+#[cfg(feature = "events-e2ee")]
 pub mod secret_storage;
+pub mod size;
+#[cfg(feature = "events-space")]
 pub mod space;
 pub mod sticker;
 pub mod tag;
@@ -174,8 +189,8 @@ pub use self::{
     relation::BundledRelations,
     state_key::EmptyStateKey,
     unsigned::{
-        MessageLikeUnsigned, RedactedUnsigned, StateUnsigned, StateUnsignedFromParts,
-        UnsignedRoomRedactionEvent,
+        compute_age, MessageLikeUnsigned, RedactedUnsigned, StateUnsigned, StateUnsignedFromParts,
+        UnsignedData, UnsignedRoomRedactionEvent,
     },
 };
 
@@ -1,15 +1,20 @@
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::value::RawValue as RawJsonValue;
 
 use super::{
-    EphemeralRoomEventContent, EphemeralRoomEventType, EventContent, GlobalAccountDataEventContent,
-    GlobalAccountDataEventType, MessageLikeEventContent, MessageLikeEventType,
-    OriginalStateEventContent, RedactContent, RedactedEventContent,
-    RedactedMessageLikeEventContent, RedactedStateEventContent, RoomAccountDataEventContent,
-    RoomAccountDataEventType, StateEventContent, StateEventType, StateUnsigned,
+    AnyEphemeralRoomEvent, AnyGlobalAccountDataEvent, AnyMessageLikeEvent, AnyRoomAccountDataEvent,
+    AnyStateEvent, AnyStrippedStateEvent, AnySyncEphemeralRoomEvent, AnySyncMessageLikeEvent,
+    AnySyncStateEvent, AnyToDeviceEvent, EphemeralRoomEvent, EphemeralRoomEventContent,
+    EphemeralRoomEventType, EventContent, EventTypeDeHelper, GlobalAccountDataEvent,
+    GlobalAccountDataEventContent, GlobalAccountDataEventType, MessageLikeEvent,
+    MessageLikeEventContent, MessageLikeEventType, OriginalStateEventContent, RedactContent,
+    RedactedEventContent, RedactedMessageLikeEventContent, RedactedStateEventContent,
+    RoomAccountDataEvent, RoomAccountDataEventContent, RoomAccountDataEventType, StateEvent,
+    StateEventContent, StateEventType, StateUnsigned, StaticEventContent, StrippedStateEvent,
+    SyncEphemeralRoomEvent, SyncMessageLikeEvent, SyncStateEvent, ToDeviceEvent,
     ToDeviceEventContent, ToDeviceEventType,
 };
-use crate::RoomVersionId;
+use crate::{serde::Raw, RoomVersionId};
 
 macro_rules! custom_event_content {
     ($i:ident, $evt:ident) => {
@@ -78,3 +83,78 @@ impl RedactedStateEventContent for CustomStateEventContent {}
 
 custom_event_content!(CustomToDeviceEventContent, ToDeviceEventType);
 impl ToDeviceEventContent for CustomToDeviceEventContent {}
+
+macro_rules! deserialize_static {
+    ($enum:ident => $event:ident where $($bounds:tt)*) => {
+        impl Raw<$enum> {
+            #[doc = concat!("Deserialize this event as a [`", stringify!($event), "`] with the")]
+            /// content type `C`, if its type is `C::TYPE`.
+            ///
+            /// Returns `Ok(None)` if the event has another type.
+            ///
+            /// This allows to get the typed content of custom events, that is not kept by the
+            /// `_Custom` variant of the enum, with event content types declared with the
+            /// [`EventContent`](super::macros::EventContent) derive macro.
+            pub fn deserialize_static<C>(&self) -> serde_json::Result<Option<$event<C>>>
+            where
+                $($bounds)*,
+                $event<C>: DeserializeOwned,
+            {
+                if self.deserialize_as::<EventTypeDeHelper<'_>>()?.ev_type != C::TYPE {
+                    return Ok(None);
+                }
+
+                self.deserialize_as().map(Some)
+            }
+        }
+    };
+}
+
+deserialize_static!(
+    AnyGlobalAccountDataEvent => GlobalAccountDataEvent
+    where C: StaticEventContent + GlobalAccountDataEventContent
+);
+deserialize_static!(
+    AnyRoomAccountDataEvent => RoomAccountDataEvent
+    where C: StaticEventContent + RoomAccountDataEventContent
+);
+deserialize_static!(
+    AnyEphemeralRoomEvent => EphemeralRoomEvent
+    where C: StaticEventContent + EphemeralRoomEventContent
+);
+deserialize_static!(
+    AnySyncEphemeralRoomEvent => SyncEphemeralRoomEvent
+    where C: StaticEventContent + EphemeralRoomEventContent
+);
+deserialize_static!(
+    AnyMessageLikeEvent => MessageLikeEvent
+    where
+        C: StaticEventContent + MessageLikeEventContent + RedactContent,
+        C::Redacted: RedactedMessageLikeEventContent
+);
+deserialize_static!(
+    AnySyncMessageLikeEvent => SyncMessageLikeEvent
+    where
+        C: StaticEventContent + MessageLikeEventContent + RedactContent,
+        C::Redacted: RedactedMessageLikeEventContent
+);
+deserialize_static!(
+    AnyStateEvent => StateEvent
+    where
+        C: StaticEventContent + OriginalStateEventContent,
+        C::Redacted: RedactedStateEventContent
+);
+deserialize_static!(
+    AnySyncStateEvent => SyncStateEvent
+    where
+        C: StaticEventContent + OriginalStateEventContent,
+        C::Redacted: RedactedStateEventContent
+);
+deserialize_static!(
+    AnyStrippedStateEvent => StrippedStateEvent
+    where C: StaticEventContent + StateEventContent
+);
+deserialize_static!(
+    AnyToDeviceEvent => ToDeviceEvent
+    where C: StaticEventContent + ToDeviceEventContent
+);
@@ -0,0 +1,149 @@
+//! Types for the `org.matrix.msc3672.beacon_info` state event, the unstable version of
+//! `m.beacon_info` ([MSC3672]).
+//!
+//! [MSC3672]: https://github.com/matrix-org/matrix-spec-proposals/pull/3672
+
+use std::time::{Duration, SystemTime};
+
+use ruma_macros::EventContent;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue as RawJsonValue;
+
+use crate::{
+    events::{location::AssetContent, EventContent, StateEventContent, StateEventType},
+    MilliSecondsSinceUnixEpoch, OwnedUserId,
+};
+
+/// The content of a beacon_info state.
+///
+/// A beacon info describes a live location sharing session of the user in the state key. The
+/// locations themselves are sent with [`BeaconEventContent`](super::beacon::BeaconEventContent)s
+/// referencing this event.
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[ruma_event(
+    type = "org.matrix.msc3672.beacon_info",
+    alias = "m.beacon_info",
+    kind = State,
+    state_key_type = OwnedUserId,
+    custom_possibly_redacted
+)]
+pub struct BeaconInfoEventContent {
+    /// The description of the location.
+    ///
+    /// It should be used to label the location on a map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether the user starts sharing their location.
+    pub live: bool,
+
+    /// The time when location sharing started.
+    #[serde(rename = "org.matrix.msc3488.ts")]
+    pub ts: MilliSecondsSinceUnixEpoch,
+
+    /// The duration that the location sharing will be live.
+    ///
+    /// Meaning that the location will stop being shared at `ts + timeout`.
+    #[serde(with = "crate::serde::duration::ms")]
+    pub timeout: Duration,
+
+    /// The asset that this message refers to.
+    #[serde(default, rename = "org.matrix.msc3488.asset")]
+    pub asset: AssetContent,
+}
+
+impl BeaconInfoEventContent {
+    /// Creates a new `BeaconInfoEventContent` with the given description, live, timeout and
+    /// start time.
+    ///
+    /// If `ts` is `None`, the current time is used.
+    pub fn new(
+        description: Option<String>,
+        timeout: Duration,
+        live: bool,
+        ts: Option<MilliSecondsSinceUnixEpoch>,
+    ) -> Self {
+        Self {
+            description,
+            live,
+            ts: ts.unwrap_or_else(MilliSecondsSinceUnixEpoch::now),
+            timeout,
+            asset: Default::default(),
+        }
+    }
+
+    /// Starts the beacon being live, from the current time.
+    pub fn start(&mut self) {
+        self.ts = MilliSecondsSinceUnixEpoch::now();
+        self.live = true;
+    }
+
+    /// Stops the beacon from being live.
+    pub fn stop(&mut self) {
+        self.live = false;
+    }
+
+    /// Whether the beacon is live and has not timed out yet.
+    pub fn is_live(&self) -> bool {
+        self.live
+            && self
+                .ts
+                .to_system_time()
+                .and_then(|t| t.checked_add(self.timeout))
+                .map_or(false, |expiry| expiry > SystemTime::now())
+    }
+}
+
+/// The possibly redacted form of [`BeaconInfoEventContent`].
+///
+/// This type is used when it's not obvious whether the content is redacted or not.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct PossiblyRedactedBeaconInfoEventContent {
+    /// The description of the location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether the user starts sharing their location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live: Option<bool>,
+
+    /// The time when location sharing started.
+    #[serde(rename = "org.matrix.msc3488.ts", skip_serializing_if = "Option::is_none")]
+    pub ts: Option<MilliSecondsSinceUnixEpoch>,
+
+    /// The duration that the location sharing will be live.
+    #[serde(
+        default,
+        with = "crate::serde::duration::opt_ms",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout: Option<Duration>,
+
+    /// The asset that this message refers to.
+    #[serde(rename = "org.matrix.msc3488.asset", skip_serializing_if = "Option::is_none")]
+    pub asset: Option<AssetContent>,
+}
+
+impl EventContent for PossiblyRedactedBeaconInfoEventContent {
+    type EventType = StateEventType;
+
+    fn event_type(&self) -> Self::EventType {
+        StateEventType::BeaconInfo
+    }
+
+    fn from_parts(event_type: &str, content: &RawJsonValue) -> serde_json::Result<Self> {
+        if event_type != "org.matrix.msc3672.beacon_info" && event_type != "m.beacon_info" {
+            return Err(::serde::de::Error::custom(format!(
+                "expected event type `org.matrix.msc3672.beacon_info`, found `{event_type}`",
+            )));
+        }
+
+        serde_json::from_str(content.get())
+    }
+}
+
+impl StateEventContent for PossiblyRedactedBeaconInfoEventContent {
+    type StateKey = OwnedUserId;
+}
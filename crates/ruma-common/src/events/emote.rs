@@ -26,7 +26,7 @@ pub struct EmoteEventContent {
     #[serde(
         flatten,
         skip_serializing_if = "Option::is_none",
-        deserialize_with = "crate::events::room::message::relation_serde::deserialize_relation"
+        deserialize_with = "crate::events::relation::deserialize_relation"
     )]
     pub relates_to: Option<Relation<EmoteEventContentWithoutRelation>>,
 }
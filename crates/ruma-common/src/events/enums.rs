@@ -15,13 +15,18 @@ event_enum! {
         "m.identity_server" => super::identity_server,
         "m.ignored_user_list" => super::ignored_user_list,
         "m.push_rules" => super::push_rules,
+        #[cfg(feature = "events-e2ee")]
         "m.secret_storage.default_key" => super::secret_storage::default_key,
+        #[cfg(feature = "events-e2ee")]
         "m.secret_storage.key.*" => super::secret_storage::key,
     }
 
     /// Any room account data event.
     enum RoomAccountData {
         "m.fully_read" => super::fully_read,
+        #[cfg(feature = "unstable-msc2867")]
+        #[ruma_enum(alias = "m.marked_unread")]
+        "com.famedly.marked_unread" => super::marked_unread,
         "m.tag" => super::tag,
     }
 
@@ -35,15 +40,22 @@ event_enum! {
     enum MessageLike {
         #[cfg(feature = "unstable-msc3246")]
         "m.audio" => super::audio,
+        #[cfg(feature = "unstable-msc3672")]
+        #[ruma_enum(alias = "m.beacon")]
+        "org.matrix.msc3672.beacon" => super::beacon,
+        #[cfg(feature = "events-voip")]
         "m.call.answer" => super::call::answer,
+        #[cfg(feature = "events-voip")]
         "m.call.invite" => super::call::invite,
+        #[cfg(feature = "events-voip")]
         "m.call.hangup" => super::call::hangup,
+        #[cfg(feature = "events-voip")]
         "m.call.candidates" => super::call::candidates,
-        #[cfg(feature = "unstable-msc2746")]
+        #[cfg(all(feature = "events-voip", feature = "unstable-msc2746"))]
         "m.call.negotiate" => super::call::negotiate,
-        #[cfg(feature = "unstable-msc2746")]
+        #[cfg(all(feature = "events-voip", feature = "unstable-msc2746"))]
         "m.call.reject" => super::call::reject,
-        #[cfg(feature = "unstable-msc2746")]
+        #[cfg(all(feature = "events-voip", feature = "unstable-msc2746"))]
         "m.call.select_answer" => super::call::select_answer,
         #[cfg(feature = "unstable-msc1767")]
         "m.emote" => super::emote,
@@ -73,7 +85,6 @@ event_enum! {
         #[cfg(feature = "unstable-msc3381")]
         #[ruma_enum(alias = "m.poll.end")]
         "org.matrix.msc3381.poll.end" => super::poll::end,
-        #[cfg(feature = "unstable-msc2677")]
         "m.reaction" => super::reaction,
         "m.room.encrypted" => super::room::encrypted,
         "m.room.message" => super::room::message,
@@ -87,8 +98,14 @@ event_enum! {
 
     /// Any state event.
     enum State {
+        #[cfg(feature = "unstable-msc3672")]
+        #[ruma_enum(alias = "m.beacon_info")]
+        "org.matrix.msc3672.beacon_info" => super::beacon_info,
+        #[cfg(feature = "events-policy")]
         "m.policy.rule.room" => super::policy::rule::room,
+        #[cfg(feature = "events-policy")]
         "m.policy.rule.server" => super::policy::rule::server,
+        #[cfg(feature = "events-policy")]
         "m.policy.rule.user" => super::policy::rule::user,
         "m.room.aliases" => super::room::aliases,
         "m.room.avatar" => super::room::avatar,
@@ -106,15 +123,23 @@ event_enum! {
         "m.room.third_party_invite" => super::room::third_party_invite,
         "m.room.tombstone" => super::room::tombstone,
         "m.room.topic" => super::room::topic,
+        #[cfg(feature = "events-space")]
         "m.space.child" => super::space::child,
+        #[cfg(feature = "events-space")]
         "m.space.parent" => super::space::parent,
     }
 
     /// Any to-device event.
     enum ToDevice {
+        #[cfg(feature = "events-e2ee")]
         "m.dummy" => super::dummy,
+        #[cfg(feature = "events-e2ee")]
         "m.room_key" => super::room_key,
+        #[cfg(feature = "events-e2ee")]
+        "m.room_key.withheld" => super::room_key::withheld,
+        #[cfg(feature = "events-e2ee")]
         "m.room_key_request" => super::room_key_request,
+        #[cfg(feature = "events-e2ee")]
         "m.forwarded_room_key" => super::forwarded_room_key,
         "m.key.verification.request" => super::key::verification::request,
         "m.key.verification.ready" => super::key::verification::ready,
@@ -125,7 +150,9 @@ event_enum! {
         "m.key.verification.mac" => super::key::verification::mac,
         "m.key.verification.done" => super::key::verification::done,
         "m.room.encrypted" => super::room::encrypted,
+        #[cfg(feature = "events-e2ee")]
         "m.secret.request"=> super::secret::request,
+        #[cfg(feature = "events-e2ee")]
         "m.secret.send" => super::secret::send,
     }
 }
@@ -311,7 +338,6 @@ impl AnyMessageLikeEventContent {
             | Self::KeyVerificationDone(KeyVerificationDoneEventContent { relates_to, .. }) => {
                 Some(encrypted::Relation::Reference(relates_to.clone()))
             },
-            #[cfg(feature = "unstable-msc2677")]
             Self::Reaction(ev) => Some(encrypted::Relation::Annotation(ev.relates_to.clone())),
             Self::RoomEncrypted(ev) => ev.relates_to.clone(),
             Self::RoomMessage(ev) => ev.relates_to.clone().map(Into::into),
@@ -339,16 +365,18 @@ impl AnyMessageLikeEventContent {
                 Some(encrypted::Relation::Reference(relates_to.clone()))
             }
             #[cfg(feature = "unstable-msc3381")]
-            Self::PollStart(_) => None,
-            #[cfg(feature = "unstable-msc2746")]
+            Self::PollStart(ev) => ev.relates_to.clone().map(Into::into),
+            #[cfg(feature = "unstable-msc3672")]
+            Self::Beacon(ev) => Some(encrypted::Relation::Reference(ev.relates_to.clone())),
+            #[cfg(all(feature = "events-voip", feature = "unstable-msc2746"))]
             Self::CallNegotiate(_) | Self::CallReject(_) | Self::CallSelectAnswer(_) => None,
+            #[cfg(feature = "events-voip")]
             Self::CallAnswer(_)
             | Self::CallInvite(_)
             | Self::CallHangup(_)
-            | Self::CallCandidates(_)
-            | Self::RoomRedaction(_)
-            | Self::Sticker(_)
-            | Self::_Custom { .. } => None,
+            | Self::CallCandidates(_) => None,
+            Self::Sticker(ev) => ev.relates_to.clone().map(Into::into),
+            Self::RoomRedaction(_) | Self::_Custom { .. } => None,
         }
     }
 }
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     message::MessageContent,
-    room::{message::Relation, EncryptedFile, JsonWebKey},
+    room::{message::Relation, EncryptedFile, JsonWebKey, MediaSource},
 };
 use crate::{serde::Base64, OwnedMxcUri};
 
@@ -37,7 +37,7 @@ pub struct FileEventContent {
     #[serde(
         flatten,
         skip_serializing_if = "Option::is_none",
-        deserialize_with = "crate::events::room::message::relation_serde::deserialize_relation"
+        deserialize_with = "crate::events::relation::deserialize_relation"
     )]
     pub relates_to: Option<Relation<FileEventContentWithoutRelation>>,
 }
@@ -131,6 +131,13 @@ impl FileContent {
     pub fn is_encrypted(&self) -> bool {
         self.encryption_info.is_some()
     }
+
+    /// Replace the URL and the encryption info with the ones of the given media source.
+    pub(crate) fn set_source(&mut self, source: MediaSource) {
+        let (url, encryption_info) = source.into_extensible_content();
+        self.url = url;
+        self.encryption_info = encryption_info.map(Box::new);
+    }
 }
 
 /// Information about a file content.
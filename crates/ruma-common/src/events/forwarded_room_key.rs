@@ -5,7 +5,10 @@
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{EventEncryptionAlgorithm, OwnedRoomId};
+use super::room_key_request::RequestedKeyInfo;
+use crate::{
+    encryption::ExportedRoomKey, DeviceKeyAlgorithm, EventEncryptionAlgorithm, OwnedRoomId,
+};
 
 /// The content of an `m.forwarded_room_key` event.
 ///
@@ -44,6 +47,86 @@ pub struct ToDeviceForwardedRoomKeyEventContent {
     /// key is forwarded from A to B to C, this field is empty between A and B, and contains
     /// A's Curve25519 key between B and C.
     pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// Whether this key can be shared with users invited to the room after it was used, as
+    /// defined in [MSC3061].
+    ///
+    /// [MSC3061]: https://github.com/matrix-org/matrix-spec-proposals/pull/3061
+    #[cfg(feature = "unstable-msc3061")]
+    #[serde(
+        default,
+        rename = "org.matrix.msc3061.shared_history",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub shared_history: bool,
+}
+
+impl ToDeviceForwardedRoomKeyEventContent {
+    /// Whether this key matches the key described by the given [`RequestedKeyInfo`].
+    ///
+    /// This compares the algorithm, room ID and session ID.
+    pub fn matches_request(&self, info: &RequestedKeyInfo) -> bool {
+        self.algorithm == info.algorithm
+            && self.room_id == info.room_id
+            && self.session_id == info.session_id
+    }
+
+    /// Add the Curve25519 key of the device this key was received from to the end of the
+    /// `forwarding_curve25519_key_chain`, before forwarding it to another device.
+    pub fn push_forwarding_key(&mut self, curve25519_key: String) {
+        self.forwarding_curve25519_key_chain.push(curve25519_key);
+    }
+
+    /// Creates a new `ToDeviceForwardedRoomKeyEventContent` from the given room key in the key
+    /// export format.
+    ///
+    /// Returns `None` if the exported key doesn't have a claimed Ed25519 key for the device which
+    /// initiated the session.
+    pub fn from_exported(key: ExportedRoomKey) -> Option<Self> {
+        let ExportedRoomKey {
+            algorithm,
+            forwarding_curve25519_key_chain,
+            room_id,
+            mut sender_claimed_keys,
+            sender_key,
+            session_id,
+            session_key,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history,
+        } = key;
+
+        Some(Self {
+            algorithm,
+            room_id,
+            sender_key,
+            session_id,
+            session_key,
+            sender_claimed_ed25519_key: sender_claimed_keys.remove(&DeviceKeyAlgorithm::Ed25519)?,
+            forwarding_curve25519_key_chain,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history,
+        })
+    }
+}
+
+impl From<ToDeviceForwardedRoomKeyEventContent> for ExportedRoomKey {
+    fn from(content: ToDeviceForwardedRoomKeyEventContent) -> Self {
+        ExportedRoomKey {
+            algorithm: content.algorithm,
+            forwarding_curve25519_key_chain: content.forwarding_curve25519_key_chain,
+            room_id: content.room_id,
+            sender_claimed_keys: [(
+                DeviceKeyAlgorithm::Ed25519,
+                content.sender_claimed_ed25519_key,
+            )]
+            .into(),
+            sender_key: content.sender_key,
+            session_id: content.session_id,
+            session_key: content.session_key,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history: content.shared_history,
+        }
+    }
 }
 
 /// Initial set of fields of `ToDeviceForwardedRoomKeyEventContent`.
@@ -94,6 +177,122 @@ impl From<ToDeviceForwardedRoomKeyEventContentInit> for ToDeviceForwardedRoomKey
             session_key: init.session_key,
             sender_claimed_ed25519_key: init.sender_claimed_ed25519_key,
             forwarding_curve25519_key_chain: init.forwarding_curve25519_key_chain,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::ToDeviceForwardedRoomKeyEventContent;
+    use crate::{
+        encryption::ExportedRoomKey, events::room_key_request::RequestedKeyInfo, room_id,
+        EventEncryptionAlgorithm,
+    };
+
+    #[test]
+    fn matches_request_and_forward() {
+        let mut content: ToDeviceForwardedRoomKeyEventContent = from_json_value(json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!room:example.org",
+            "sender_key": "alice_curve25519",
+            "session_id": "session_id",
+            "session_key": "session_key",
+            "sender_claimed_ed25519_key": "alice_ed25519",
+            "forwarding_curve25519_key_chain": [],
+        }))
+        .unwrap();
+
+        let mut info = RequestedKeyInfo::new(
+            EventEncryptionAlgorithm::MegolmV1AesSha2,
+            room_id!("!room:example.org").to_owned(),
+            "alice_curve25519".into(),
+            "session_id".into(),
+        );
+        assert!(content.matches_request(&info));
+
+        info.session_id = "other_session_id".into();
+        assert!(!content.matches_request(&info));
+
+        content.push_forwarding_key("alice_curve25519".into());
+        content.push_forwarding_key("bob_curve25519".into());
+        assert_eq!(
+            content.forwarding_curve25519_key_chain,
+            ["alice_curve25519".to_owned(), "bob_curve25519".to_owned()]
+        );
+    }
+
+    #[test]
+    fn exported_room_key_roundtrip() {
+        let json = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!room:example.org",
+            "sender_key": "alice_curve25519",
+            "session_id": "session_id",
+            "session_key": "session_key",
+            "sender_claimed_ed25519_key": "alice_ed25519",
+            "forwarding_curve25519_key_chain": ["bob_curve25519"],
+        });
+        let content: ToDeviceForwardedRoomKeyEventContent = from_json_value(json.clone()).unwrap();
+
+        let exported = ExportedRoomKey::from(content);
+        assert_eq!(
+            to_json_value(&exported).unwrap(),
+            json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "forwarding_curve25519_key_chain": ["bob_curve25519"],
+                "room_id": "!room:example.org",
+                "sender_claimed_keys": { "ed25519": "alice_ed25519" },
+                "sender_key": "alice_curve25519",
+                "session_id": "session_id",
+                "session_key": "session_key",
+            })
+        );
+
+        let content = ToDeviceForwardedRoomKeyEventContent::from_exported(exported).unwrap();
+        assert_eq!(to_json_value(content).unwrap(), json);
+    }
+
+    #[test]
+    fn exported_room_key_without_ed25519() {
+        let exported: ExportedRoomKey = from_json_value(json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "forwarding_curve25519_key_chain": [],
+            "room_id": "!room:example.org",
+            "sender_claimed_keys": {},
+            "sender_key": "alice_curve25519",
+            "session_id": "session_id",
+            "session_key": "session_key",
+        }))
+        .unwrap();
+
+        assert!(ToDeviceForwardedRoomKeyEventContent::from_exported(exported).is_none());
+    }
+
+    #[cfg(feature = "unstable-msc3061")]
+    #[test]
+    fn shared_history_is_exported() {
+        let content: ToDeviceForwardedRoomKeyEventContent = from_json_value(json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!room:example.org",
+            "sender_key": "alice_curve25519",
+            "session_id": "session_id",
+            "session_key": "session_key",
+            "sender_claimed_ed25519_key": "alice_ed25519",
+            "forwarding_curve25519_key_chain": [],
+            "org.matrix.msc3061.shared_history": true,
+        }))
+        .unwrap();
+        assert!(content.shared_history);
+
+        let exported = ExportedRoomKey::from(content);
+        assert!(exported.shared_history);
+        assert_eq!(to_json_value(&exported).unwrap()["org.matrix.msc3061.shared_history"], true);
+
+        let content = ToDeviceForwardedRoomKeyEventContent::from_exported(exported).unwrap();
+        assert!(content.shared_history);
+    }
+}
@@ -14,6 +14,7 @@ pub mod cancel;
 pub mod done;
 pub mod key;
 pub mod mac;
+pub mod qr_code;
 pub mod ready;
 pub mod request;
 pub mod start;
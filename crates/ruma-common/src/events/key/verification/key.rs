@@ -52,3 +52,45 @@ impl KeyVerificationKeyEventContent {
         Self { key, relates_to }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::KeyVerificationKeyEventContent;
+    use crate::{event_id, events::relation::Reference, serde::Base64};
+
+    #[test]
+    fn serialization() {
+        let event_id = event_id!("$1598361704261elfgc:localhost").to_owned();
+        let content = KeyVerificationKeyEventContent::new(
+            Base64::new(b"ephemeral".to_vec()),
+            Reference::new(event_id.clone()),
+        );
+
+        let json_data = json!({
+            "key": "ZXBoZW1lcmFs",
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": event_id,
+            }
+        });
+
+        assert_eq!(to_json_value(&content).unwrap(), json_data);
+    }
+
+    #[test]
+    fn deserialization() {
+        let json_data = json!({
+            "key": "ZXBoZW1lcmFs",
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": "$1598361704261elfgc:localhost",
+            }
+        });
+
+        let content = from_json_value::<KeyVerificationKeyEventContent>(json_data).unwrap();
+        assert_eq!(content.key.as_bytes(), b"ephemeral");
+        assert_eq!(content.relates_to.event_id, "$1598361704261elfgc:localhost");
+    }
+}
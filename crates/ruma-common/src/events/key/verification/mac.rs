@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{events::relation::Reference, serde::Base64, OwnedTransactionId};
 
-/// The content of a to-device `m.key.verification.` event.
+/// The content of a to-device `m.key.verification.mac` event.
 ///
 /// Sends the MAC of a device's key to the partner device.
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
@@ -43,7 +43,7 @@ impl ToDeviceKeyVerificationMacEventContent {
     }
 }
 
-/// The content of an in-room `m.key.verification.` event.
+/// The content of an in-room `m.key.verification.mac` event.
 ///
 /// Sends the MAC of a device's key to the partner device.
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
@@ -71,3 +71,56 @@ impl KeyVerificationMacEventContent {
         Self { mac, keys, relates_to }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::KeyVerificationMacEventContent;
+    use crate::{event_id, events::relation::Reference, serde::Base64};
+
+    #[test]
+    fn serialization() {
+        let event_id = event_id!("$1598361704261elfgc:localhost").to_owned();
+        let content = KeyVerificationMacEventContent::new(
+            BTreeMap::from([("ed25519:ABCDEF".to_owned(), Base64::new(b"mac".to_vec()))]),
+            Base64::new(b"keys".to_vec()),
+            Reference::new(event_id.clone()),
+        );
+
+        let json_data = json!({
+            "mac": {
+                "ed25519:ABCDEF": "bWFj",
+            },
+            "keys": "a2V5cw",
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": event_id,
+            }
+        });
+
+        assert_eq!(to_json_value(&content).unwrap(), json_data);
+    }
+
+    #[test]
+    fn deserialization() {
+        let json_data = json!({
+            "mac": {
+                "ed25519:ABCDEF": "bWFj",
+            },
+            "keys": "a2V5cw",
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": "$1598361704261elfgc:localhost",
+            }
+        });
+
+        let content = from_json_value::<KeyVerificationMacEventContent>(json_data).unwrap();
+        assert_eq!(content.mac.len(), 1);
+        assert_eq!(content.mac["ed25519:ABCDEF"].as_bytes(), b"mac");
+        assert_eq!(content.keys.as_bytes(), b"keys");
+        assert_eq!(content.relates_to.event_id, "$1598361704261elfgc:localhost");
+    }
+}
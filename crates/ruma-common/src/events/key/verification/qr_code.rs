@@ -0,0 +1,332 @@
+//! Types for the QR code verification method.
+//!
+//! The QR code encodes the keys that the device displaying it expects, and a shared secret that
+//! the scanning device sends back in an [`m.key.verification.start`] event using the
+//! `m.reciprocate.v1` method.
+//!
+//! See the [QR code format] in the specification.
+//!
+//! [`m.key.verification.start`]: super::start
+//! [QR code format]: https://spec.matrix.org/v1.4/client-server-api/#qr-code-format
+
+use std::str;
+
+use crate::serde::{base64::Standard, Base64};
+
+use super::start::ReciprocateV1Content;
+
+/// The header of every QR code, the ASCII string `MATRIX`.
+const HEADER: &[u8] = b"MATRIX";
+
+/// The version of the QR code format.
+const VERSION: u8 = 0x02;
+
+/// The minimum length of the shared secret, in bytes.
+const MIN_SECRET_LEN: usize = 8;
+
+/// The length of a key, in bytes.
+const KEY_LEN: usize = 32;
+
+/// The data encoded in a QR code used for key verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct QrCodeData {
+    /// The mode of the QR code, which determines the meaning of the keys.
+    pub mode: QrCodeMode,
+
+    /// The ID of the verification flow.
+    ///
+    /// This is the transaction ID of to-device verification requests, or the event ID of the
+    /// `m.key.verification.request` event for in-room verification.
+    pub flow_id: String,
+
+    /// The first key, whose meaning depends on the [`mode`](Self::mode).
+    pub first_key: Base64<Standard, [u8; KEY_LEN]>,
+
+    /// The second key, whose meaning depends on the [`mode`](Self::mode).
+    pub second_key: Base64<Standard, [u8; KEY_LEN]>,
+
+    /// The random shared secret.
+    ///
+    /// It must be at least 8 bytes long.
+    pub shared_secret: Base64,
+}
+
+impl QrCodeData {
+    /// Creates a new `QrCodeData` with the given mode, flow ID, keys and shared secret.
+    pub fn new(
+        mode: QrCodeMode,
+        flow_id: String,
+        first_key: [u8; KEY_LEN],
+        second_key: [u8; KEY_LEN],
+        shared_secret: Base64,
+    ) -> Self {
+        Self {
+            mode,
+            flow_id,
+            first_key: Base64::new(first_key),
+            second_key: Base64::new(second_key),
+            shared_secret,
+        }
+    }
+
+    /// Decode the binary data of a QR code.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QrCodeDecodeError> {
+        let bytes = bytes.strip_prefix(HEADER).ok_or(QrCodeDecodeError::InvalidHeader)?;
+
+        let (&version, bytes) = bytes.split_first().ok_or(QrCodeDecodeError::TooShort)?;
+        if version != VERSION {
+            return Err(QrCodeDecodeError::UnsupportedVersion(version));
+        }
+
+        let (&mode, bytes) = bytes.split_first().ok_or(QrCodeDecodeError::TooShort)?;
+        let mode = QrCodeMode::from_byte(mode).ok_or(QrCodeDecodeError::UnknownMode(mode))?;
+
+        let (flow_id_len, bytes) = split_at_checked(bytes, 2)?;
+        let flow_id_len = u16::from_be_bytes([flow_id_len[0], flow_id_len[1]]).into();
+        let (flow_id, bytes) = split_at_checked(bytes, flow_id_len)?;
+        let flow_id = str::from_utf8(flow_id).map_err(|_| QrCodeDecodeError::InvalidFlowId)?;
+
+        let (first_key, bytes) = split_at_checked(bytes, KEY_LEN)?;
+        let (second_key, shared_secret) = split_at_checked(bytes, KEY_LEN)?;
+
+        if shared_secret.len() < MIN_SECRET_LEN {
+            return Err(QrCodeDecodeError::SharedSecretTooShort);
+        }
+
+        Ok(Self {
+            mode,
+            flow_id: flow_id.to_owned(),
+            first_key: Base64::new(first_key.try_into().expect("slice has the length of a key")),
+            second_key: Base64::new(second_key.try_into().expect("slice has the length of a key")),
+            shared_secret: Base64::new(shared_secret.to_vec()),
+        })
+    }
+
+    /// Encode this data as the binary data of a QR code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flow ID is longer than 65535 bytes, or if the shared secret is
+    /// shorter than 8 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, QrCodeEncodeError> {
+        let flow_id_len =
+            u16::try_from(self.flow_id.len()).map_err(|_| QrCodeEncodeError::FlowIdTooLong)?;
+        let shared_secret = self.shared_secret.as_bytes();
+
+        if shared_secret.len() < MIN_SECRET_LEN {
+            return Err(QrCodeEncodeError::SharedSecretTooShort);
+        }
+
+        let mut bytes = Vec::with_capacity(
+            HEADER.len() + 4 + self.flow_id.len() + 2 * KEY_LEN + shared_secret.len(),
+        );
+        bytes.extend_from_slice(HEADER);
+        bytes.push(VERSION);
+        bytes.push(self.mode.as_byte());
+        bytes.extend_from_slice(&flow_id_len.to_be_bytes());
+        bytes.extend_from_slice(self.flow_id.as_bytes());
+        bytes.extend_from_slice(self.first_key.as_bytes());
+        bytes.extend_from_slice(self.second_key.as_bytes());
+        bytes.extend_from_slice(shared_secret);
+
+        Ok(bytes)
+    }
+
+    /// Get the content to send in an `m.key.verification.start` event after scanning this QR code.
+    pub fn reciprocate_content(&self) -> ReciprocateV1Content {
+        ReciprocateV1Content::new(self.shared_secret.clone())
+    }
+}
+
+fn split_at_checked(bytes: &[u8], mid: usize) -> Result<(&[u8], &[u8]), QrCodeDecodeError> {
+    if bytes.len() < mid {
+        return Err(QrCodeDecodeError::TooShort);
+    }
+
+    Ok(bytes.split_at(mid))
+}
+
+/// The mode of a QR code, which determines the meaning of the keys it contains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum QrCodeMode {
+    /// The user is verifying another user, and both users have a cross-signing master key.
+    ///
+    /// The first key is the master key of the user displaying the QR code, and the second key is
+    /// what the device displaying the QR code thinks the master key of the other user is.
+    VerifyingAnotherUser,
+
+    /// The user is verifying one of their own devices, and the device displaying the QR code
+    /// trusts the master key.
+    ///
+    /// The first key is the master key of the user, and the second key is what the device
+    /// displaying the QR code thinks the key of the other device is.
+    SelfVerifying,
+
+    /// The user is verifying one of their own devices, and the device displaying the QR code
+    /// doesn't trust the master key yet.
+    ///
+    /// The first key is the key of the device displaying the QR code, and the second key is what
+    /// the device displaying the QR code thinks the master key of the user is.
+    SelfVerifyingMasterKeyUntrusted,
+}
+
+impl QrCodeMode {
+    /// The byte encoding this mode in a QR code.
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::VerifyingAnotherUser => 0x00,
+            Self::SelfVerifying => 0x01,
+            Self::SelfVerifyingMasterKeyUntrusted => 0x02,
+        }
+    }
+
+    /// The mode encoded by the given byte, if it is known.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Self::VerifyingAnotherUser),
+            0x01 => Some(Self::SelfVerifying),
+            0x02 => Some(Self::SelfVerifyingMasterKeyUntrusted),
+            _ => None,
+        }
+    }
+}
+
+/// An error encountered when decoding the data of a QR code.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum QrCodeDecodeError {
+    /// The data doesn't start with `MATRIX`.
+    #[error("QR code data doesn't start with `MATRIX`")]
+    InvalidHeader,
+
+    /// The version of the QR code format is not supported.
+    #[error("unsupported QR code version: {0:#04x}")]
+    UnsupportedVersion(u8),
+
+    /// The mode of the QR code is unknown.
+    #[error("unknown QR code mode: {0:#04x}")]
+    UnknownMode(u8),
+
+    /// The data is too short to contain all the fields.
+    #[error("QR code data is too short")]
+    TooShort,
+
+    /// The flow ID is not valid UTF-8.
+    #[error("QR code flow ID is not valid UTF-8")]
+    InvalidFlowId,
+
+    /// The shared secret is shorter than 8 bytes.
+    #[error("QR code shared secret is shorter than 8 bytes")]
+    SharedSecretTooShort,
+}
+
+/// An error encountered when encoding the data of a QR code.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum QrCodeEncodeError {
+    /// The flow ID is longer than 65535 bytes.
+    #[error("QR code flow ID is longer than 65535 bytes")]
+    FlowIdTooLong,
+
+    /// The shared secret is shorter than 8 bytes.
+    #[error("QR code shared secret is shorter than 8 bytes")]
+    SharedSecretTooShort,
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::{QrCodeData, QrCodeDecodeError, QrCodeMode};
+    use crate::serde::Base64;
+
+    /// The example from the specification, with a flow ID of 45 bytes.
+    fn spec_example() -> (Vec<u8>, String) {
+        let flow_id = "$ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqr".to_owned();
+        assert_eq!(flow_id.len(), 0x2D);
+
+        let mut bytes = vec![0x4D, 0x41, 0x54, 0x52, 0x49, 0x58, 0x02, 0x00, 0x00, 0x2D];
+        bytes.extend_from_slice(flow_id.as_bytes());
+        bytes.extend(0x00..0x20);
+        bytes.extend(0x10..0x30);
+        bytes.extend(0x20..0x28);
+
+        (bytes, flow_id)
+    }
+
+    #[test]
+    fn decode_spec_example() {
+        let (bytes, flow_id) = spec_example();
+        let data = QrCodeData::from_bytes(&bytes).unwrap();
+
+        assert_eq!(data.mode, QrCodeMode::VerifyingAnotherUser);
+        assert_eq!(data.flow_id, flow_id);
+        assert_eq!(data.first_key.as_bytes(), (0x00..0x20).collect::<Vec<u8>>());
+        assert_eq!(data.second_key.as_bytes(), (0x10..0x30).collect::<Vec<u8>>());
+        assert_eq!(data.shared_secret.as_bytes(), (0x20..0x28).collect::<Vec<u8>>());
+        assert_eq!(data.reciprocate_content().secret.encode(), "ICEiIyQlJic");
+    }
+
+    #[test]
+    fn encode_spec_example() {
+        let (bytes, flow_id) = spec_example();
+        let mut first_key = [0; 32];
+        first_key.iter_mut().zip(0x00..).for_each(|(b, v)| *b = v);
+        let mut second_key = [0; 32];
+        second_key.iter_mut().zip(0x10..).for_each(|(b, v)| *b = v);
+
+        let data = QrCodeData::new(
+            QrCodeMode::VerifyingAnotherUser,
+            flow_id,
+            first_key,
+            second_key,
+            Base64::new((0x20..0x28).collect()),
+        );
+
+        assert_eq!(data.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn roundtrip_modes() {
+        for mode in [
+            QrCodeMode::VerifyingAnotherUser,
+            QrCodeMode::SelfVerifying,
+            QrCodeMode::SelfVerifyingMasterKeyUntrusted,
+        ] {
+            let data =
+                QrCodeData::new(mode, "txn".to_owned(), [1; 32], [2; 32], Base64::new(vec![3; 16]));
+            let bytes = data.to_bytes().unwrap();
+            assert_eq!(bytes[7], mode.as_byte());
+            assert_eq!(QrCodeData::from_bytes(&bytes).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let (bytes, _) = spec_example();
+
+        assert_matches!(QrCodeData::from_bytes(&bytes[1..]), Err(QrCodeDecodeError::InvalidHeader));
+
+        let mut invalid = bytes.clone();
+        invalid[6] = 0x01;
+        assert_matches!(
+            QrCodeData::from_bytes(&invalid),
+            Err(QrCodeDecodeError::UnsupportedVersion(0x01))
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[7] = 0x03;
+        assert_matches!(
+            QrCodeData::from_bytes(&invalid),
+            Err(QrCodeDecodeError::UnknownMode(0x03))
+        );
+
+        assert_matches!(
+            QrCodeData::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(QrCodeDecodeError::SharedSecretTooShort)
+        );
+        assert_matches!(QrCodeData::from_bytes(&bytes[..50]), Err(QrCodeDecodeError::TooShort));
+    }
+}
@@ -0,0 +1,89 @@
+//! Types for the [`m.marked_unread`] event.
+//!
+//! [`m.marked_unread`]: https://github.com/matrix-org/matrix-spec-proposals/pull/2867
+
+use ruma_macros::EventContent;
+use serde::{Deserialize, Serialize};
+
+/// The content of an `m.marked_unread` event.
+///
+/// Whether the user marked the room as unread. This is independent of the user's read marker,
+/// stored in the [`m.fully_read`](super::fully_read) event, and of the read receipts.
+///
+/// This event appears in the user's room account data for the room the marker is applicable for.
+///
+/// Clients should set `unread` to `false` when the user opens the room or when the read marker
+/// moves, to clear the manual marker.
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[ruma_event(type = "com.famedly.marked_unread", alias = "m.marked_unread", kind = RoomAccountData)]
+pub struct MarkedUnreadEventContent {
+    /// Whether the room has been explicitly marked as unread.
+    pub unread: bool,
+}
+
+impl MarkedUnreadEventContent {
+    /// Creates a new `MarkedUnreadEventContent` with the given value.
+    pub fn new(unread: bool) -> Self {
+        Self { unread }
+    }
+
+    /// Creates a new `MarkedUnreadEventContent` that marks the room as unread.
+    pub fn unread() -> Self {
+        Self::new(true)
+    }
+
+    /// Creates a new `MarkedUnreadEventContent` that clears the manual unread marker of the room.
+    pub fn read() -> Self {
+        Self::new(false)
+    }
+
+    /// Creates a new `MarkedUnreadEventContent` with the opposite value of this one.
+    pub fn toggled(&self) -> Self {
+        Self::new(!self.unread)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::MarkedUnreadEventContent;
+    use crate::events::{AnyRoomAccountDataEvent, RoomAccountDataEvent};
+
+    #[test]
+    fn serialization() {
+        let content = MarkedUnreadEventContent::unread();
+        assert_eq!(to_json_value(&content).unwrap(), json!({ "unread": true }));
+        assert!(!content.toggled().unread);
+    }
+
+    #[test]
+    fn deserialization() {
+        let json = json!({
+            "content": { "unread": true },
+            "type": "m.marked_unread",
+        });
+
+        let event = assert_matches!(
+            from_json_value::<AnyRoomAccountDataEvent>(json),
+            Ok(AnyRoomAccountDataEvent::MarkedUnread(event)) => event
+        );
+        assert_matches!(
+            event,
+            RoomAccountDataEvent { content: MarkedUnreadEventContent { unread: true } }
+        );
+
+        let json = json!({
+            "content": { "unread": false },
+            "type": "com.famedly.marked_unread",
+        });
+
+        let event = assert_matches!(
+            from_json_value::<AnyRoomAccountDataEvent>(json),
+            Ok(AnyRoomAccountDataEvent::MarkedUnread(event)) => event
+        );
+        assert!(!event.content.unread);
+    }
+}
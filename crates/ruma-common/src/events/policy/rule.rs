@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{serde::StringEnum, PrivOwnedStr};
+use crate::{glob, serde::StringEnum, PrivOwnedStr};
 
 pub mod room;
 pub mod server;
@@ -30,6 +30,11 @@ impl PolicyRuleEventContent {
     pub fn new(entity: String, recommendation: Recommendation, reason: String) -> Self {
         Self { entity, recommendation, reason }
     }
+
+    /// Whether the given entity, like a user ID or a server name, is affected by this rule.
+    pub fn matches_entity(&self, entity: &str) -> bool {
+        glob::matches(&self.entity, entity)
+    }
 }
 
 /// The possibly redacted form of [`PolicyRuleEventContent`].
@@ -8,12 +8,21 @@ mod poll_answers_serde;
 
 use poll_answers_serde::PollAnswersDeHelper;
 
-use crate::{events::message::MessageContent, serde::StringEnum, PrivOwnedStr};
+use crate::{
+    events::{message::MessageContent, relation::Relation},
+    serde::StringEnum,
+    PrivOwnedStr,
+};
 
 /// The payload for a poll start event.
 #[derive(Clone, Debug, Serialize, Deserialize, EventContent)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[ruma_event(type = "org.matrix.msc3381.poll.start", alias = "m.poll.start", kind = MessageLike)]
+#[ruma_event(
+    type = "org.matrix.msc3381.poll.start",
+    alias = "m.poll.start",
+    kind = MessageLike,
+    without_relation
+)]
 pub struct PollStartEventContent {
     /// The poll start content of the message.
     #[serde(rename = "org.matrix.msc3381.poll.start", alias = "m.poll.start")]
@@ -22,12 +31,20 @@ pub struct PollStartEventContent {
     /// Optional fallback text representation of the message, for clients that don't support polls.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub message: Option<MessageContent>,
+
+    /// Information about related messages.
+    #[serde(
+        flatten,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::events::relation::deserialize_relation"
+    )]
+    pub relates_to: Option<Relation<PollStartEventContentWithoutRelation>>,
 }
 
 impl PollStartEventContent {
     /// Creates a new `PollStartEventContent` with the given poll start content.
     pub fn new(poll_start: PollStartContent) -> Self {
-        Self { poll_start, message: None }
+        Self { poll_start, message: None, relates_to: None }
     }
 }
 
@@ -2,12 +2,18 @@
 //!
 //! The only content valid for this event is `PresenceEventContent`.
 
+use std::collections::BTreeMap;
+
 use js_int::UInt;
 use ruma_macros::{Event, EventContent};
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use serde_json::{from_value as from_json_value, Value as JsonValue};
 
 use super::{EventContent, EventKind, StaticEventContent};
-use crate::{presence::PresenceState, OwnedMxcUri, OwnedUserId};
+use crate::{
+    presence::{PresenceState, PresenceUpdate},
+    MilliSecondsSinceUnixEpoch, OwnedMxcUri, OwnedUserId,
+};
 
 /// Presence event.
 #[derive(Clone, Debug, Event)]
@@ -42,6 +48,9 @@ impl Serialize for PresenceEvent {
 pub struct PresenceEventContent {
     /// The current avatar URL for this user.
     ///
+    /// Like `displayname`, it is propagated from the profile of the user and is not affected by
+    /// changes of the presence of the user.
+    ///
     /// If you activate the `compat` feature, this field being an empty string in JSON will result
     /// in `None` here during deserialization.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,8 +76,16 @@ pub struct PresenceEventContent {
     pub presence: PresenceState,
 
     /// An optional description to accompany the presence.
+    ///
+    /// Its length can be checked with [`PresenceEventContent::validate()`].
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status_msg: Option<String>,
+
+    /// Custom fields sent by some servers outside of the specification.
+    ///
+    /// They are kept so the content can be forwarded without losing data.
+    #[serde(flatten)]
+    custom: BTreeMap<String, JsonValue>,
 }
 
 impl PresenceEventContent {
@@ -81,8 +98,116 @@ impl PresenceEventContent {
             last_active_ago: None,
             presence,
             status_msg: None,
+            custom: BTreeMap::new(),
+        }
+    }
+
+    /// The maximum length of `status_msg` accepted by [`validate()`](Self::validate), in
+    /// characters.
+    ///
+    /// The Matrix specification doesn't limit the length of `status_msg`, but this allows to reject
+    /// presence updates with unreasonably large status messages.
+    pub const MAX_STATUS_MSG_LENGTH: usize = 255;
+
+    /// Check that this content is valid.
+    ///
+    /// Returns an error if `status_msg` is longer than [`Self::MAX_STATUS_MSG_LENGTH`].
+    pub fn validate(&self) -> Result<(), PresenceValidationError> {
+        if let Some(length) = self
+            .status_msg
+            .as_deref()
+            .map(|status_msg| status_msg.chars().count())
+            .filter(|length| *length > Self::MAX_STATUS_MSG_LENGTH)
+        {
+            return Err(PresenceValidationError::StatusMsgTooLong { length });
+        }
+
+        Ok(())
+    }
+
+    /// Truncate `status_msg` to [`Self::MAX_STATUS_MSG_LENGTH`] characters, so this content passes
+    /// [`validate()`](Self::validate).
+    pub fn truncate_status_msg(&mut self) {
+        if let Some(status_msg) = &mut self.status_msg {
+            if let Some((index, _)) = status_msg.char_indices().nth(Self::MAX_STATUS_MSG_LENGTH) {
+                status_msg.truncate(index);
+            }
+        }
+    }
+
+    /// Update the presence of the user with the given [`PresenceUpdate`], sent at `now`.
+    ///
+    /// The fields propagated from the profile of the user, `avatar_url` and `displayname`, and the
+    /// custom fields are kept.
+    pub fn update_presence(&mut self, update: &PresenceUpdate, now: MilliSecondsSinceUnixEpoch) {
+        self.presence = update.presence.clone();
+        self.status_msg = update.status_msg.clone();
+        self.currently_active = update.currently_active;
+        self.last_active_ago = update.last_active_ago(now);
+    }
+
+    /// Get the value of the custom field with the given name, if it is set.
+    pub fn custom_field(&self, name: &str) -> Option<&JsonValue> {
+        self.custom.get(name)
+    }
+
+    /// Set the value of the custom field with the given name.
+    ///
+    /// If `name` is the name of a field of the specification, the value is deserialized into that
+    /// field instead, and an error is returned if it fails.
+    pub fn set_custom_field(&mut self, name: &str, value: JsonValue) -> serde_json::Result<()> {
+        match name {
+            "avatar_url" => self.avatar_url = from_json_value(value)?,
+            "currently_active" => self.currently_active = from_json_value(value)?,
+            "displayname" => self.displayname = from_json_value(value)?,
+            "last_active_ago" => self.last_active_ago = from_json_value(value)?,
+            "presence" => self.presence = from_json_value(value)?,
+            "status_msg" => self.status_msg = from_json_value(value)?,
+            _ => {
+                self.custom.insert(name.to_owned(), value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convert this content, received at `received_at`, to a [`PresenceUpdate`].
+    pub fn to_presence_update(&self, received_at: MilliSecondsSinceUnixEpoch) -> PresenceUpdate {
+        PresenceUpdate {
+            status_msg: self.status_msg.clone(),
+            currently_active: self.currently_active,
+            ..PresenceUpdate::with_last_active_ago(
+                self.presence.clone(),
+                self.last_active_ago,
+                received_at,
+            )
         }
     }
+
+    /// Creates a new `PresenceEventContent` from the given [`PresenceUpdate`], to be sent at `now`.
+    pub fn from_presence_update(update: &PresenceUpdate, now: MilliSecondsSinceUnixEpoch) -> Self {
+        Self {
+            currently_active: update.currently_active,
+            last_active_ago: update.last_active_ago(now),
+            status_msg: update.status_msg.clone(),
+            ..Self::new(update.presence.clone())
+        }
+    }
+}
+
+/// An error encountered when validating a [`PresenceEventContent`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PresenceValidationError {
+    /// The `status_msg` is longer than [`PresenceEventContent::MAX_STATUS_MSG_LENGTH`].
+    #[error(
+        "status_msg is {length} characters long, the maximum is {}",
+        PresenceEventContent::MAX_STATUS_MSG_LENGTH
+    )]
+    StatusMsgTooLong {
+        /// The length of the `status_msg`, in characters.
+        length: usize,
+    },
 }
 
 impl StaticEventContent for PresenceEventContent {
@@ -92,11 +217,18 @@ impl StaticEventContent for PresenceEventContent {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
+    use assert_matches::assert_matches;
     use js_int::uint;
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{PresenceEvent, PresenceEventContent};
-    use crate::{mxc_uri, presence::PresenceState};
+    use super::{PresenceEvent, PresenceEventContent, PresenceValidationError};
+    use crate::{
+        mxc_uri,
+        presence::{PresenceState, PresenceUpdate},
+        MilliSecondsSinceUnixEpoch,
+    };
 
     #[test]
     fn serialization() {
@@ -107,6 +239,7 @@ mod tests {
             last_active_ago: Some(uint!(2_478_593)),
             presence: PresenceState::Online,
             status_msg: Some("Making cupcakes".into()),
+            custom: BTreeMap::new(),
         };
 
         let json = json!({
@@ -170,4 +303,102 @@ mod tests {
             assert_eq!(ev.sender, "@example:localhost");
         }
     }
+
+    #[test]
+    fn presence_update() {
+        let mut content = PresenceEventContent::new(PresenceState::Unavailable);
+        content.last_active_ago = Some(uint!(2_000));
+        content.currently_active = Some(false);
+        content.displayname = Some("Alice".to_owned());
+
+        let update = content.to_presence_update(MilliSecondsSinceUnixEpoch(uint!(10_000)));
+        assert_eq!(update.presence, PresenceState::Unavailable);
+        assert_eq!(update.currently_active, Some(false));
+        assert_eq!(update.last_active, Some(MilliSecondsSinceUnixEpoch(uint!(8_000))));
+
+        let content = PresenceEventContent::from_presence_update(
+            &update,
+            MilliSecondsSinceUnixEpoch(uint!(12_500)),
+        );
+        assert_eq!(content.presence, PresenceState::Unavailable);
+        assert_eq!(content.last_active_ago, Some(uint!(4_500)));
+        assert_eq!(content.displayname, None);
+
+        let content = PresenceEventContent::from_presence_update(
+            &PresenceUpdate::new(PresenceState::Offline),
+            MilliSecondsSinceUnixEpoch(uint!(12_500)),
+        );
+        assert_eq!(content.last_active_ago, None);
+    }
+
+    #[test]
+    fn validate_status_msg() {
+        let mut content = PresenceEventContent::new(PresenceState::Online);
+        content.validate().unwrap();
+
+        content.status_msg = Some("é".repeat(PresenceEventContent::MAX_STATUS_MSG_LENGTH));
+        content.validate().unwrap();
+
+        content.status_msg = Some("é".repeat(PresenceEventContent::MAX_STATUS_MSG_LENGTH + 2));
+        let length = assert_matches!(
+            content.validate(),
+            Err(PresenceValidationError::StatusMsgTooLong { length }) => length
+        );
+        assert_eq!(length, PresenceEventContent::MAX_STATUS_MSG_LENGTH + 2);
+
+        content.truncate_status_msg();
+        assert_eq!(
+            content.status_msg.as_deref().unwrap().chars().count(),
+            PresenceEventContent::MAX_STATUS_MSG_LENGTH
+        );
+        content.validate().unwrap();
+    }
+
+    #[test]
+    fn update_presence_keeps_profile() {
+        let mut content = PresenceEventContent::new(PresenceState::Online);
+        content.displayname = Some("Alice".to_owned());
+        content.avatar_url = Some(mxc_uri!("mxc://localhost/wefuiwegh8742w").to_owned());
+        content.status_msg = Some("Making cupcakes".to_owned());
+
+        let mut update = PresenceUpdate::new(PresenceState::Unavailable);
+        update.last_active = Some(MilliSecondsSinceUnixEpoch(uint!(8_000)));
+        content.update_presence(&update, MilliSecondsSinceUnixEpoch(uint!(10_000)));
+
+        assert_eq!(content.presence, PresenceState::Unavailable);
+        assert_eq!(content.status_msg, None);
+        assert_eq!(content.last_active_ago, Some(uint!(2_000)));
+        assert_eq!(content.displayname.as_deref(), Some("Alice"));
+        assert_eq!(content.avatar_url.as_deref(), Some(mxc_uri!("mxc://localhost/wefuiwegh8742w")));
+    }
+
+    #[test]
+    fn custom_fields_roundtrip() {
+        let json = json!({
+            "presence": "online",
+            "org.example.mood": "happy",
+            "org.example.device": { "kind": "mobile" },
+        });
+
+        let mut content = from_json_value::<PresenceEventContent>(json.clone()).unwrap();
+        assert_eq!(content.presence, PresenceState::Online);
+        assert_eq!(content.custom_field("org.example.mood"), Some(&json!("happy")));
+        assert_eq!(to_json_value(&content).unwrap(), json);
+
+        content.set_custom_field("status_msg", json!("Making cupcakes")).unwrap();
+        assert_eq!(content.status_msg.as_deref(), Some("Making cupcakes"));
+        assert_eq!(content.custom_field("status_msg"), None);
+        content.set_custom_field("presence", json!(true)).unwrap_err();
+
+        content.set_custom_field("org.example.mood", json!("sleepy")).unwrap();
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "presence": "online",
+                "status_msg": "Making cupcakes",
+                "org.example.mood": "sleepy",
+                "org.example.device": { "kind": "mobile" },
+            })
+        );
+    }
 }
@@ -13,6 +13,62 @@ use crate::{
     MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedUserId, PrivOwnedStr,
 };
 
+mod relation_serde;
+
+pub use relation_serde::deserialize_relation;
+pub(crate) use relation_serde::{
+    deserialize_relation_without_new_content, serialize_relation_without_new_content,
+};
+
+/// The relation of an event to another event, in the `m.relates_to` field of its content.
+///
+/// This is used by all the message-like event contents that can have a relation.
+///
+/// `C` is the type of the new content of a [`Replacement`].
+///
+/// To (de)serialize the relation of an event content, flatten it and use
+/// [`deserialize_relation`] to deserialize it.
+#[derive(Clone, Debug)]
+#[allow(clippy::manual_non_exhaustive)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum Relation<C> {
+    /// An `m.in_reply_to` relation indicating that the event is a reply to another event.
+    Reply {
+        /// Information about another message being replied to.
+        in_reply_to: InReplyTo,
+    },
+
+    /// An event that replaces another event.
+    Replacement(Replacement<C>),
+
+    /// A reference to another event.
+    Reference(Reference),
+
+    /// An annotation to an event.
+    Annotation(Annotation),
+
+    /// An event that belongs to a thread.
+    Thread(Thread),
+
+    #[doc(hidden)]
+    _Custom,
+}
+
+impl<C> Relation<C> {
+    /// The type of this relation.
+    ///
+    /// Returns `None` for a reply, which is not a relation type, or for a custom relation.
+    pub fn rel_type(&self) -> Option<RelationType> {
+        match self {
+            Self::Replacement(_) => Some(RelationType::Replacement),
+            Self::Reference(_) => Some(RelationType::Reference),
+            Self::Annotation(_) => Some(RelationType::Annotation),
+            Self::Thread(_) => Some(RelationType::Thread),
+            Self::Reply { .. } | Self::_Custom => None,
+        }
+    }
+}
+
 /// Information about the event a [rich reply] is replying to.
 ///
 /// [rich reply]: https://spec.matrix.org/v1.5/client-server-api/#rich-replies
@@ -34,7 +90,6 @@ impl InReplyTo {
 ///
 /// [annotation]: https://github.com/matrix-org/matrix-spec-proposals/pull/2677
 #[derive(Clone, Debug, Deserialize, Serialize)]
-#[cfg(feature = "unstable-msc2677")]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 #[serde(tag = "rel_type", rename = "m.annotation")]
 pub struct Annotation {
@@ -50,7 +105,6 @@ pub struct Annotation {
     pub key: String,
 }
 
-#[cfg(feature = "unstable-msc2677")]
 impl Annotation {
     /// Creates a new `Annotation` with the given event ID and key.
     pub fn new(event_id: OwnedEventId, key: String) -> Self {
@@ -298,7 +352,14 @@ pub struct BundledRelations {
     pub replace: Option<Box<BundledReplacement>>,
 
     /// Thread relation.
-    #[serde(rename = "m.thread", skip_serializing_if = "Option::is_none")]
+    ///
+    /// This also accepts the `io.element.thread` key used by servers implementing the unstable
+    /// version of threads.
+    #[serde(
+        rename = "m.thread",
+        alias = "io.element.thread",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub thread: Option<Box<BundledThread>>,
 
     /// Reference relations.
@@ -318,6 +379,21 @@ impl BundledRelations {
         }
     }
 
+    /// Get the bundled relations in the `unsigned` field of the given event.
+    ///
+    /// Returns empty `BundledRelations` if the event doesn't have any.
+    ///
+    /// Returns an error if the `unsigned` field or the bundled relations can't be deserialized.
+    pub fn from_raw_event<T>(event: &Raw<T>) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        struct Unsigned {
+            #[serde(rename = "m.relations", default)]
+            relations: BundledRelations,
+        }
+
+        Ok(event.get_field::<Unsigned>("unsigned")?.map(|u| u.relations).unwrap_or_default())
+    }
+
     /// Returns `true` if all fields are empty.
     pub fn is_empty(&self) -> bool {
         #[cfg(not(feature = "unstable-msc2677"))]
@@ -338,7 +414,6 @@ impl BundledRelations {
 #[non_exhaustive]
 pub enum RelationType {
     /// `m.annotation`, an annotation, principally used by reactions.
-    #[cfg(feature = "unstable-msc2677")]
     Annotation,
 
     /// `m.replace`, a replacement.
@@ -0,0 +1,263 @@
+use serde::{
+    de::{self, IgnoredAny},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{Annotation, InReplyTo, Reference, Relation, Replacement, Thread};
+use crate::OwnedEventId;
+
+/// Deserialize an event's `relates_to` field.
+///
+/// Use it like this:
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use ruma_common::events::{
+///     relation::{deserialize_relation, Relation},
+///     room::message::MessageType,
+/// };
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct MyEventContent {
+///     #[serde(
+///         flatten,
+///         skip_serializing_if = "Option::is_none",
+///         deserialize_with = "deserialize_relation"
+///     )]
+///     relates_to: Option<Relation<MessageType>>,
+/// }
+/// ```
+pub fn deserialize_relation<'de, D, C>(deserializer: D) -> Result<Option<Relation<C>>, D::Error>
+where
+    D: Deserializer<'de>,
+    C: Deserialize<'de>,
+{
+    let ev = EventWithRelatesToJsonRepr::deserialize(deserializer)?;
+    let new_content = ev.new_content;
+
+    ev.relates_to
+        .into_relation(|| new_content.ok_or_else(|| de::Error::missing_field("m.new_content")))
+}
+
+/// Deserialize an event's `relates_to` field, without the new content of a replacement.
+///
+/// This is used when the new content is not next to the relation, like in encrypted events.
+pub(crate) fn deserialize_relation_without_new_content<'de, D>(
+    deserializer: D,
+) -> Result<Option<Relation<()>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ev = EventWithRelatesToJsonRepr::<IgnoredAny>::deserialize(deserializer)?;
+    ev.relates_to.into_relation(|| Ok(()))
+}
+
+/// Serialize an event's `relates_to` field, without the new content of a replacement.
+///
+/// This is used when the new content is not next to the relation, like in encrypted events.
+pub(crate) fn serialize_relation_without_new_content<C, S>(
+    relation: &Relation<C>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    EventWithRelatesToJsonRepr::<()>::new(RelatesToJsonRepr::from_relation(relation))
+        .serialize(serializer)
+}
+
+impl<C> Serialize for Relation<C>
+where
+    C: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let new_content = match self {
+            Relation::Replacement(Replacement { new_content, .. }) => Some(new_content),
+            _ => None,
+        };
+
+        EventWithRelatesToJsonRepr {
+            relates_to: RelatesToJsonRepr::from_relation(self),
+            new_content,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(deserialize = "C: Deserialize<'de>"))]
+struct EventWithRelatesToJsonRepr<C> {
+    #[serde(rename = "m.relates_to", default, skip_serializing_if = "RelatesToJsonRepr::is_empty")]
+    relates_to: RelatesToJsonRepr,
+
+    #[serde(
+        rename = "m.new_content",
+        default = "Option::default",
+        skip_serializing_if = "Option::is_none"
+    )]
+    new_content: Option<C>,
+}
+
+impl<C> EventWithRelatesToJsonRepr<C> {
+    fn new(relates_to: RelatesToJsonRepr) -> Self {
+        Self { relates_to, new_content: None }
+    }
+}
+
+/// Struct modeling the different ways relationships can be expressed in a `m.relates_to` field of
+/// an event.
+#[derive(Default, Deserialize, Serialize)]
+struct RelatesToJsonRepr {
+    #[serde(rename = "m.in_reply_to", skip_serializing_if = "Option::is_none")]
+    in_reply_to: Option<InReplyTo>,
+
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    relation: Option<RelationJsonRepr>,
+}
+
+impl RelatesToJsonRepr {
+    fn from_relation<C>(relation: &Relation<C>) -> Self {
+        match relation {
+            Relation::Reply { in_reply_to } => {
+                Self { in_reply_to: Some(in_reply_to.clone()), ..Default::default() }
+            }
+            Relation::Replacement(Replacement { event_id, .. }) => Self {
+                relation: Some(RelationJsonRepr::Replacement(ReplacementJsonRepr {
+                    event_id: event_id.clone(),
+                })),
+                ..Default::default()
+            },
+            Relation::Reference(r) => Self {
+                relation: Some(RelationJsonRepr::Reference(r.clone())),
+                ..Default::default()
+            },
+            Relation::Annotation(a) => Self {
+                relation: Some(RelationJsonRepr::Annotation(a.clone())),
+                ..Default::default()
+            },
+            Relation::Thread(Thread { event_id, in_reply_to, is_falling_back }) => Self {
+                in_reply_to: Some(in_reply_to.clone()),
+                relation: Some(RelationJsonRepr::ThreadStable(ThreadStableJsonRepr {
+                    event_id: event_id.clone(),
+                    is_falling_back: *is_falling_back,
+                })),
+            },
+            Relation::_Custom => Self::default(),
+        }
+    }
+
+    /// Convert this to a relation, using `new_content` to get the new content of a replacement.
+    fn into_relation<C, E>(
+        self,
+        new_content: impl FnOnce() -> Result<C, E>,
+    ) -> Result<Option<Relation<C>>, E>
+    where
+        E: de::Error,
+    {
+        if let Some(
+            RelationJsonRepr::ThreadStable(ThreadStableJsonRepr { event_id, is_falling_back })
+            | RelationJsonRepr::ThreadUnstable(ThreadUnstableJsonRepr { event_id, is_falling_back }),
+        ) = self.relation
+        {
+            let in_reply_to =
+                self.in_reply_to.ok_or_else(|| de::Error::missing_field("m.in_reply_to"))?;
+            return Ok(Some(Relation::Thread(Thread { event_id, in_reply_to, is_falling_back })));
+        }
+
+        let rel = if let Some(in_reply_to) = self.in_reply_to {
+            Some(Relation::Reply { in_reply_to })
+        } else if let Some(relation) = self.relation {
+            match relation {
+                RelationJsonRepr::Annotation(a) => Some(Relation::Annotation(a)),
+                RelationJsonRepr::Reference(r) => Some(Relation::Reference(r)),
+                RelationJsonRepr::Replacement(ReplacementJsonRepr { event_id }) => {
+                    Some(Relation::Replacement(Replacement {
+                        event_id,
+                        new_content: new_content()?,
+                    }))
+                }
+                // FIXME: Maybe we should log this, though at this point we don't even have
+                // access to the rel_type of the unknown relation.
+                RelationJsonRepr::Unknown => Some(Relation::_Custom),
+                RelationJsonRepr::ThreadStable(_) | RelationJsonRepr::ThreadUnstable(_) => {
+                    unreachable!()
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(rel)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.in_reply_to.is_none() && self.relation.is_none()
+    }
+}
+
+/// A relation, which associates new information to an existing event.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "rel_type")]
+enum RelationJsonRepr {
+    /// An annotation to an event.
+    #[serde(rename = "m.annotation")]
+    Annotation(Annotation),
+
+    /// A reference to another event.
+    #[serde(rename = "m.reference")]
+    Reference(Reference),
+
+    /// An event that replaces another event.
+    #[serde(rename = "m.replace")]
+    Replacement(ReplacementJsonRepr),
+
+    /// An event that belongs to a thread, with stable names.
+    #[serde(rename = "m.thread")]
+    ThreadStable(ThreadStableJsonRepr),
+
+    /// An event that belongs to a thread, with unstable names.
+    #[serde(rename = "io.element.thread")]
+    ThreadUnstable(ThreadUnstableJsonRepr),
+
+    /// An unknown relation type.
+    ///
+    /// Not available in the public API, but exists here so deserialization
+    /// doesn't fail with new / custom `rel_type`s.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct ReplacementJsonRepr {
+    event_id: OwnedEventId,
+}
+
+/// A thread relation without the reply fallback, with stable names.
+#[derive(Clone, Deserialize, Serialize)]
+struct ThreadStableJsonRepr {
+    /// The ID of the root message in the thread.
+    event_id: OwnedEventId,
+
+    /// Whether the `m.in_reply_to` field is a fallback for older clients or a real reply in a
+    /// thread.
+    #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
+    is_falling_back: bool,
+}
+
+/// A thread relation without the reply fallback, with unstable names.
+#[derive(Clone, Deserialize, Serialize)]
+struct ThreadUnstableJsonRepr {
+    /// The ID of the root message in the thread.
+    event_id: OwnedEventId,
+
+    /// Whether the `m.in_reply_to` field is a fallback for older clients or a real reply in a
+    /// thread.
+    #[serde(
+        rename = "io.element.show_reply",
+        default,
+        skip_serializing_if = "ruma_common::serde::is_default"
+    )]
+    is_falling_back: bool,
+}
@@ -18,7 +18,7 @@ use super::image::ThumbnailFileContent;
 use crate::MxcUri;
 use crate::{
     serde::{base64::UrlSafe, Base64},
-    OwnedMxcUri,
+    OwnedMxcUri, RoomVersionId,
 };
 
 pub mod aliases;
@@ -162,6 +162,26 @@ impl ImageInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Set the width and height of the image, in pixels.
+    pub fn with_dimensions(self, width: UInt, height: UInt) -> Self {
+        Self { width: Some(width), height: Some(height), ..self }
+    }
+
+    /// Set the MIME type of the image.
+    pub fn with_mimetype(self, mimetype: String) -> Self {
+        Self { mimetype: Some(mimetype), ..self }
+    }
+
+    /// Set the file size of the image, in bytes.
+    pub fn with_size(self, size: UInt) -> Self {
+        Self { size: Some(size), ..self }
+    }
+
+    /// Set the source of the thumbnail of the image, and its metadata.
+    pub fn with_thumbnail(self, source: MediaSource, info: Option<ThumbnailInfo>) -> Self {
+        Self { thumbnail_source: Some(source), thumbnail_info: info.map(Box::new), ..self }
+    }
 }
 
 /// Metadata about a thumbnail.
@@ -219,6 +239,43 @@ pub struct EncryptedFile {
     pub v: String,
 }
 
+impl EncryptedFile {
+    /// Creates a new `EncryptedFile` for version `v2` of the encrypted attachments protocol, with
+    /// the given URL, key, counter block and SHA-256 hash of the ciphertext.
+    pub fn new(url: OwnedMxcUri, key: JsonWebKey, iv: Base64, sha256: Base64) -> Self {
+        Self { url, key, iv, hashes: [("sha256".to_owned(), sha256)].into(), v: "v2".to_owned() }
+    }
+
+    /// Checks that this file follows version `v2` of the encrypted attachments protocol.
+    ///
+    /// The key must be valid according to [`JsonWebKey::validate()`], the counter block must be
+    /// 128 bits long and the hashes must contain a 256-bit `sha256` hash.
+    ///
+    /// The base64 encoding of the fields is checked during deserialization.
+    pub fn validate(&self) -> Result<(), EncryptedFileValidationError> {
+        if self.v != "v2" {
+            return Err(EncryptedFileValidationError::UnsupportedVersion(self.v.clone()));
+        }
+
+        self.key.validate()?;
+
+        if self.iv.as_bytes().len() != 16 {
+            return Err(EncryptedFileValidationError::InvalidLength { field: "iv", expected: 16 });
+        }
+
+        match self.hashes.get("sha256") {
+            None => Err(EncryptedFileValidationError::MissingSha256Hash),
+            Some(hash) if hash.as_bytes().len() != 32 => {
+                Err(EncryptedFileValidationError::InvalidLength {
+                    field: "hashes.sha256",
+                    expected: 32,
+                })
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
 #[cfg(feature = "unstable-msc3551")]
 impl EncryptedFile {
     /// Create an `EncryptedFile` from the given url and encryption info.
@@ -326,6 +383,60 @@ pub struct JsonWebKeyInit {
     pub ext: bool,
 }
 
+impl JsonWebKey {
+    /// Creates a new `JsonWebKey` for the `A256CTR` algorithm with the given 256-bit key.
+    ///
+    /// The other fields are set to the values required by the encrypted attachments protocol.
+    pub fn new(k: Base64<UrlSafe>) -> Self {
+        Self {
+            kty: "oct".to_owned(),
+            key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+            alg: "A256CTR".to_owned(),
+            k,
+            ext: true,
+        }
+    }
+
+    /// Checks that this key has the parameters required by the encrypted attachments protocol.
+    ///
+    /// The key type must be `oct`, the key operations must contain `encrypt` and `decrypt`, the
+    /// algorithm must be `A256CTR`, the key must be extractable and 256 bits long.
+    pub fn validate(&self) -> Result<(), EncryptedFileValidationError> {
+        if self.kty != "oct" {
+            return Err(EncryptedFileValidationError::InvalidKeyParameter {
+                field: "kty",
+                value: self.kty.clone(),
+            });
+        }
+
+        if self.alg != "A256CTR" {
+            return Err(EncryptedFileValidationError::InvalidKeyParameter {
+                field: "alg",
+                value: self.alg.clone(),
+            });
+        }
+
+        for op in ["encrypt", "decrypt"] {
+            if !self.key_ops.iter().any(|key_op| key_op == op) {
+                return Err(EncryptedFileValidationError::MissingKeyOperation(op));
+            }
+        }
+
+        if !self.ext {
+            return Err(EncryptedFileValidationError::InvalidKeyParameter {
+                field: "ext",
+                value: self.ext.to_string(),
+            });
+        }
+
+        if self.k.as_bytes().len() != 32 {
+            return Err(EncryptedFileValidationError::InvalidLength { field: "k", expected: 32 });
+        }
+
+        Ok(())
+    }
+}
+
 impl From<JsonWebKeyInit> for JsonWebKey {
     fn from(init: JsonWebKeyInit) -> Self {
         let JsonWebKeyInit { kty, key_ops, alg, k, ext } = init;
@@ -333,6 +444,81 @@ impl From<JsonWebKeyInit> for JsonWebKey {
     }
 }
 
+/// An error encountered when validating an [`EncryptedFile`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum EncryptedFileValidationError {
+    /// The version of the encrypted attachments protocol is not `v2`.
+    #[error("unsupported encrypted attachments version {0}, expected v2")]
+    UnsupportedVersion(String),
+
+    /// A parameter of the key doesn't have the required value.
+    #[error("invalid value {value} for key parameter {field}")]
+    InvalidKeyParameter {
+        /// The name of the parameter.
+        field: &'static str,
+
+        /// The value of the parameter.
+        value: String,
+    },
+
+    /// The key operations don't contain a required operation.
+    #[error("key operations don't contain {0}")]
+    MissingKeyOperation(&'static str),
+
+    /// A decoded base64 field doesn't have the required length.
+    #[error("{field} should be {expected} bytes long")]
+    InvalidLength {
+        /// The name of the field.
+        field: &'static str,
+
+        /// The expected length, in bytes.
+        expected: usize,
+    },
+
+    /// The hashes don't contain a SHA-256 hash.
+    #[error("no sha256 hash of the ciphertext")]
+    MissingSha256Hash,
+}
+
+/// An error encountered when validating the content of a state event for a room version.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RoomVersionValidationError {
+    /// The content uses a field that is not supported in the room version.
+    #[error("field {field} is not supported in room version {room_version}")]
+    UnsupportedField {
+        /// The name of the field.
+        field: &'static str,
+
+        /// The room version.
+        room_version: RoomVersionId,
+    },
+
+    /// The content uses a value that is not supported in the room version.
+    #[error("value {value} of field {field} is not supported in room version {room_version}")]
+    UnsupportedValue {
+        /// The name of the field.
+        field: &'static str,
+
+        /// The value of the field.
+        value: String,
+
+        /// The room version.
+        room_version: RoomVersionId,
+    },
+
+    /// The `room_version` of an `m.room.create` event doesn't match the room version.
+    #[error("room version {found} doesn't match room version {expected}")]
+    RoomVersionMismatch {
+        /// The room version the content was validated for.
+        expected: RoomVersionId,
+
+        /// The room version in the content.
+        found: RoomVersionId,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -343,7 +529,7 @@ mod tests {
 
     use crate::{mxc_uri, serde::Base64};
 
-    use super::{EncryptedFile, JsonWebKey, MediaSource};
+    use super::{EncryptedFile, EncryptedFileValidationError, JsonWebKey, MediaSource};
 
     #[derive(Deserialize)]
     struct MsgWithAttachment {
@@ -394,4 +580,56 @@ mod tests {
 
         assert_matches!(msg.source, MediaSource::Encrypted(_));
     }
+
+    #[test]
+    fn validate_encrypted_file() {
+        let file = EncryptedFile::new(
+            mxc_uri!("mxc://localhost/encryptedfile").to_owned(),
+            JsonWebKey::new(Base64::new(vec![0; 32])),
+            Base64::new(vec![0; 16]),
+            Base64::new(vec![0; 32]),
+        );
+        file.validate().unwrap();
+
+        let mut invalid = file.clone();
+        invalid.v = "v1".to_owned();
+        assert_eq!(
+            invalid.validate(),
+            Err(EncryptedFileValidationError::UnsupportedVersion("v1".to_owned()))
+        );
+
+        let mut invalid = file.clone();
+        invalid.key.alg = "A128CTR".to_owned();
+        assert_eq!(
+            invalid.validate(),
+            Err(EncryptedFileValidationError::InvalidKeyParameter {
+                field: "alg",
+                value: "A128CTR".to_owned()
+            })
+        );
+
+        let mut invalid = file.clone();
+        invalid.key.key_ops.retain(|op| op != "decrypt");
+        assert_eq!(
+            invalid.validate(),
+            Err(EncryptedFileValidationError::MissingKeyOperation("decrypt"))
+        );
+
+        let mut invalid = file.clone();
+        invalid.iv = Base64::new(vec![0; 8]);
+        assert_eq!(
+            invalid.validate(),
+            Err(EncryptedFileValidationError::InvalidLength { field: "iv", expected: 16 })
+        );
+
+        let mut invalid = file;
+        invalid.hashes.clear();
+        assert_eq!(invalid.validate(), Err(EncryptedFileValidationError::MissingSha256Hash));
+
+        // The dummy key is too long.
+        assert_eq!(
+            encrypted_file().validate(),
+            Err(EncryptedFileValidationError::InvalidLength { field: "k", expected: 32 })
+        );
+    }
 }
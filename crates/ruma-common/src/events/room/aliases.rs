@@ -9,6 +9,7 @@ use crate::{
         EventContent, RedactContent, RedactedEventContent, RedactedStateEventContent,
         StateEventContent, StateEventType,
     },
+    room_version_rules::RedactionRules,
     OwnedRoomAliasId, OwnedServerName, RoomVersionId,
 };
 
@@ -34,16 +35,11 @@ impl RedactContent for RoomAliasesEventContent {
     type Redacted = RedactedRoomAliasesEventContent;
 
     fn redact(self, version: &RoomVersionId) -> RedactedRoomAliasesEventContent {
-        // We compare the long way to avoid pre version 6 behavior if/when
-        // a new room version is introduced.
-        let aliases = match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5 => Some(self.aliases),
-            _ => None,
-        };
+        // Redacting contents can't fail, room versions with unknown rules use the version 6 rules.
+        let aliases = RedactionRules::for_room_version(version)
+            .unwrap_or(RedactionRules::V6)
+            .keep_room_aliases_aliases
+            .then_some(self.aliases);
 
         RedactedRoomAliasesEventContent { aliases }
     }
@@ -2,10 +2,12 @@
 //!
 //! [`m.room.canonical_alias`]: https://spec.matrix.org/v1.4/client-server-api/#mroomcanonical_alias
 
+use std::collections::BTreeSet;
+
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{events::EmptyStateKey, OwnedRoomAliasId};
+use crate::{events::EmptyStateKey, OwnedRoomAliasId, OwnedRoomId, RoomAliasId, RoomId};
 
 /// The content of an `m.room.canonical_alias` event.
 ///
@@ -35,14 +37,80 @@ impl RoomCanonicalAliasEventContent {
     pub fn new() -> Self {
         Self { alias: None, alt_aliases: Vec::new() }
     }
+
+    /// Returns an iterator over the canonical alias, if any, followed by the alternative aliases.
+    pub fn aliases(&self) -> impl Iterator<Item = &RoomAliasId> {
+        self.alias.iter().chain(&self.alt_aliases).map(|alias| &**alias)
+    }
+
+    /// Removes duplicate entries from `alt_aliases`, keeping the first occurrence of each alias.
+    pub fn dedup_alt_aliases(&mut self) {
+        let mut seen = BTreeSet::new();
+        self.alt_aliases.retain(|alias| seen.insert(alias.clone()));
+    }
+
+    /// Checks that the aliases in this content are valid for the room with the given ID.
+    ///
+    /// `resolve` is called for every alias and should return the ID of the room the alias
+    /// currently points to, or `None` if the alias doesn't exist.
+    ///
+    /// According to the spec, servers should reject an `m.room.canonical_alias` event with
+    /// `M_BAD_ALIAS` if this fails.
+    pub fn validate<F>(&self, room_id: &RoomId, mut resolve: F) -> Result<(), CanonicalAliasError>
+    where
+        F: FnMut(&RoomAliasId) -> Option<OwnedRoomId>,
+    {
+        let mut seen = BTreeSet::new();
+        if let Some(alias) = self.alt_aliases.iter().find(|alias| !seen.insert(*alias)) {
+            return Err(CanonicalAliasError::DuplicateAltAlias(alias.clone()));
+        }
+
+        for alias in self.aliases() {
+            match resolve(alias) {
+                Some(target) if target == room_id => {}
+                Some(target) => {
+                    return Err(CanonicalAliasError::WrongRoom {
+                        alias: alias.to_owned(),
+                        room_id: target,
+                    })
+                }
+                None => return Err(CanonicalAliasError::UnknownAlias(alias.to_owned())),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error encountered when validating a `RoomCanonicalAliasEventContent`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CanonicalAliasError {
+    /// An alias appears several times in `alt_aliases`.
+    #[error("alias {0} appears several times in alt_aliases")]
+    DuplicateAltAlias(OwnedRoomAliasId),
+
+    /// An alias doesn't exist.
+    #[error("alias {0} doesn't exist")]
+    UnknownAlias(OwnedRoomAliasId),
+
+    /// An alias points to another room.
+    #[error("alias {alias} points to room {room_id}")]
+    WrongRoom {
+        /// The alias.
+        alias: OwnedRoomAliasId,
+
+        /// The ID of the room the alias points to.
+        room_id: OwnedRoomId,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::RoomCanonicalAliasEventContent;
-    use crate::{events::OriginalStateEvent, room_alias_id};
+    use super::{CanonicalAliasError, RoomCanonicalAliasEventContent};
+    use crate::{events::OriginalStateEvent, room_alias_id, room_id};
 
     #[test]
     fn serialization_with_optional_fields_as_none() {
@@ -146,4 +214,57 @@ mod tests {
             alias
         );
     }
+
+    #[test]
+    fn dedup_alt_aliases() {
+        let mut content = RoomCanonicalAliasEventContent {
+            alias: Some(room_alias_id!("#somewhere:localhost").to_owned()),
+            alt_aliases: vec![
+                room_alias_id!("#elsewhere:localhost").to_owned(),
+                room_alias_id!("#anywhere:localhost").to_owned(),
+                room_alias_id!("#elsewhere:localhost").to_owned(),
+            ],
+        };
+        content.dedup_alt_aliases();
+
+        assert_eq!(
+            content.alt_aliases,
+            [room_alias_id!("#elsewhere:localhost"), room_alias_id!("#anywhere:localhost")]
+        );
+        assert_eq!(content.aliases().count(), 3);
+    }
+
+    #[test]
+    fn validate() {
+        let room_id = room_id!("!room:localhost");
+        let other_room_id = room_id!("!other:localhost");
+        let mut content = RoomCanonicalAliasEventContent {
+            alias: Some(room_alias_id!("#somewhere:localhost").to_owned()),
+            alt_aliases: vec![room_alias_id!("#elsewhere:localhost").to_owned()],
+        };
+
+        assert_eq!(content.validate(room_id, |_| Some(room_id.to_owned())), Ok(()));
+        assert_eq!(
+            content.validate(room_id, |alias| (alias.alias() == "somewhere")
+                .then(|| room_id.to_owned())),
+            Err(CanonicalAliasError::UnknownAlias(
+                room_alias_id!("#elsewhere:localhost").to_owned()
+            ))
+        );
+        assert_eq!(
+            content.validate(room_id, |_| Some(other_room_id.to_owned())),
+            Err(CanonicalAliasError::WrongRoom {
+                alias: room_alias_id!("#somewhere:localhost").to_owned(),
+                room_id: other_room_id.to_owned(),
+            })
+        );
+
+        content.alt_aliases.push(room_alias_id!("#elsewhere:localhost").to_owned());
+        assert_eq!(
+            content.validate(room_id, |_| Some(room_id.to_owned())),
+            Err(CanonicalAliasError::DuplicateAltAlias(
+                room_alias_id!("#elsewhere:localhost").to_owned()
+            ))
+        );
+    }
 }
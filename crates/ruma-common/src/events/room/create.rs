@@ -5,6 +5,7 @@
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
+use super::RoomVersionValidationError;
 use crate::{
     events::EmptyStateKey, room::RoomType, OwnedEventId, OwnedRoomId, OwnedUserId, RoomVersionId,
 };
@@ -60,6 +61,23 @@ impl RoomCreateEventContent {
             room_type: None,
         }
     }
+
+    /// Checks that this content is valid for a room with the given version.
+    ///
+    /// The `room_version` of the content must match the version of the room.
+    pub fn validate_for_version(
+        &self,
+        room_version: &RoomVersionId,
+    ) -> Result<(), RoomVersionValidationError> {
+        if self.room_version != *room_version {
+            return Err(RoomVersionValidationError::RoomVersionMismatch {
+                expected: room_version.clone(),
+                found: self.room_version.clone(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 /// A reference to an old room replaced during a room version upgrade.
@@ -91,7 +109,7 @@ mod tests {
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{RoomCreateEventContent, RoomType};
-    use crate::{user_id, RoomVersionId};
+    use crate::{events::room::RoomVersionValidationError, user_id, RoomVersionId};
 
     #[test]
     fn serialization() {
@@ -164,4 +182,19 @@ mod tests {
         assert_matches!(content.predecessor, None);
         assert_eq!(content.room_type, Some(RoomType::Space));
     }
+
+    #[test]
+    fn validate_for_version() {
+        let mut content = RoomCreateEventContent::new(user_id!("@carl:example.com").to_owned());
+        content.room_version = RoomVersionId::V9;
+
+        content.validate_for_version(&RoomVersionId::V9).unwrap();
+        assert_eq!(
+            content.validate_for_version(&RoomVersionId::V10),
+            Err(RoomVersionValidationError::RoomVersionMismatch {
+                expected: RoomVersionId::V10,
+                found: RoomVersionId::V9,
+            })
+        );
+    }
 }
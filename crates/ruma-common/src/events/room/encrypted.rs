@@ -4,20 +4,18 @@
 
 use std::collections::BTreeMap;
 
-use js_int::UInt;
+use js_int::{uint, UInt};
 use ruma_macros::EventContent;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use super::message;
-#[cfg(feature = "unstable-msc2677")]
-use crate::events::relation::Annotation;
 use crate::{
-    events::relation::{InReplyTo, Reference, Thread},
-    OwnedDeviceId, OwnedEventId,
+    events::{
+        relation::{self, Annotation, InReplyTo, Reference, Thread},
+        ToDeviceEventType,
+    },
+    OwnedDeviceId, OwnedEventId, OwnedUserId, UserId,
 };
 
-mod relation_serde;
-
 /// The content of an `m.room.encrypted` event.
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -31,7 +29,7 @@ pub struct RoomEncryptedEventContent {
     #[serde(
         flatten,
         skip_serializing_if = "Option::is_none",
-        deserialize_with = "relation_serde::deserialize_relation"
+        deserialize_with = "deserialize_relation"
     )]
     pub relates_to: Option<Relation>,
 }
@@ -106,7 +104,6 @@ pub enum Relation {
     Reference(Reference),
 
     /// An annotation to an event.
-    #[cfg(feature = "unstable-msc2677")]
     Annotation(Annotation),
 
     /// An event that belongs to a thread.
@@ -116,23 +113,50 @@ pub enum Relation {
     _Custom,
 }
 
-impl<C> From<message::Relation<C>> for Relation {
-    fn from(rel: message::Relation<C>) -> Self {
+impl<C> From<relation::Relation<C>> for Relation {
+    fn from(rel: relation::Relation<C>) -> Self {
         match rel {
-            message::Relation::Reply { in_reply_to } => Self::Reply { in_reply_to },
-            message::Relation::Replacement(re) => {
+            relation::Relation::Reply { in_reply_to } => Self::Reply { in_reply_to },
+            relation::Relation::Replacement(re) => {
                 Self::Replacement(Replacement { event_id: re.event_id })
             }
-            message::Relation::Thread(t) => Self::Thread(Thread {
-                event_id: t.event_id,
-                in_reply_to: t.in_reply_to,
-                is_falling_back: t.is_falling_back,
-            }),
-            message::Relation::_Custom => Self::_Custom,
+            relation::Relation::Reference(r) => Self::Reference(r),
+            relation::Relation::Annotation(a) => Self::Annotation(a),
+            relation::Relation::Thread(t) => Self::Thread(t),
+            relation::Relation::_Custom => Self::_Custom,
         }
     }
 }
 
+impl Serialize for Relation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let relation: relation::Relation<()> = match self {
+            Relation::Reply { in_reply_to } => {
+                relation::Relation::Reply { in_reply_to: in_reply_to.clone() }
+            }
+            Relation::Replacement(re) => {
+                relation::Relation::Replacement(relation::Replacement::new(re.event_id.clone(), ()))
+            }
+            Relation::Reference(r) => relation::Relation::Reference(r.clone()),
+            Relation::Annotation(a) => relation::Relation::Annotation(a.clone()),
+            Relation::Thread(t) => relation::Relation::Thread(t.clone()),
+            Relation::_Custom => relation::Relation::_Custom,
+        };
+
+        relation::serialize_relation_without_new_content(&relation, serializer)
+    }
+}
+
+fn deserialize_relation<'de, D>(deserializer: D) -> Result<Option<Relation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(relation::deserialize_relation_without_new_content(deserializer)?.map(Into::into))
+}
+
 /// The event this relation belongs to [replaces another event].
 ///
 /// In contrast to [`relation::Replacement`](crate::events::relation::Replacement), this struct
@@ -170,6 +194,11 @@ impl OlmV1Curve25519AesSha2Content {
     pub fn new(ciphertext: BTreeMap<String, CiphertextInfo>, sender_key: String) -> Self {
         Self { ciphertext, sender_key }
     }
+
+    /// Get the ciphertext for the device with the given Curve25519 identity key, if any.
+    pub fn ciphertext_for(&self, recipient_key: &str) -> Option<&CiphertextInfo> {
+        self.ciphertext.get(recipient_key)
+    }
 }
 
 /// Ciphertext information holding the ciphertext and message type.
@@ -191,6 +220,165 @@ impl CiphertextInfo {
     pub fn new(body: String, message_type: UInt) -> Self {
         Self { body, message_type }
     }
+
+    /// Creates a new `CiphertextInfo` with the given body and Olm message type.
+    pub fn with_olm_message_type(body: String, message_type: OlmMessageType) -> Self {
+        Self { body, message_type: message_type.into() }
+    }
+
+    /// The Olm message type, if it is known.
+    pub fn olm_message_type(&self) -> Option<OlmMessageType> {
+        OlmMessageType::from_uint(self.message_type)
+    }
+}
+
+/// The type of an Olm message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum OlmMessageType {
+    /// A pre-key message, used to establish a new Olm session.
+    ///
+    /// Encoded as `0`.
+    PreKey,
+
+    /// A normal message, sent in an established Olm session.
+    ///
+    /// Encoded as `1`.
+    Normal,
+}
+
+impl OlmMessageType {
+    /// The Olm message type encoded by the given integer, if it is known.
+    pub fn from_uint(message_type: UInt) -> Option<Self> {
+        if message_type == uint!(0) {
+            Some(Self::PreKey)
+        } else if message_type == uint!(1) {
+            Some(Self::Normal)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<OlmMessageType> for UInt {
+    fn from(message_type: OlmMessageType) -> Self {
+        match message_type {
+            OlmMessageType::PreKey => uint!(0),
+            OlmMessageType::Normal => uint!(1),
+        }
+    }
+}
+
+/// The decrypted payload of a ciphertext encrypted with the `m.olm.v1.curve25519-aes-sha2`
+/// algorithm.
+///
+/// The content is usually deserialized as [`Raw`](crate::serde::Raw), and then deserialized
+/// according to the [`event_type`](Self::event_type), after calling [`validate`](Self::validate).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DecryptedOlmV1Event<C> {
+    /// The type of the encrypted event.
+    #[serde(rename = "type")]
+    pub event_type: ToDeviceEventType,
+
+    /// The content of the encrypted event.
+    pub content: C,
+
+    /// The user ID of the sender.
+    pub sender: OwnedUserId,
+
+    /// The user ID of the recipient.
+    pub recipient: OwnedUserId,
+
+    /// The keys of the recipient device.
+    pub recipient_keys: OlmV1Keys,
+
+    /// The keys of the sender device.
+    pub keys: OlmV1Keys,
+}
+
+impl<C> DecryptedOlmV1Event<C> {
+    /// Creates a new `DecryptedOlmV1Event` with the given event type, content, sender, recipient
+    /// and keys.
+    pub fn new(
+        event_type: ToDeviceEventType,
+        content: C,
+        sender: OwnedUserId,
+        recipient: OwnedUserId,
+        recipient_keys: OlmV1Keys,
+        keys: OlmV1Keys,
+    ) -> Self {
+        Self { event_type, content, sender, recipient, recipient_keys, keys }
+    }
+
+    /// Check that this payload was meant for the given recipient and comes from the given sender.
+    ///
+    /// * `sender` - The sender of the `m.room.encrypted` to-device event.
+    /// * `recipient` - The user ID of the current user.
+    /// * `recipient_ed25519_key` - The Ed25519 key of the current device.
+    /// * `sender_ed25519_key` - The Ed25519 key of the device of the sender that is linked to the
+    ///   Curve25519 key used to encrypt this payload, if it is known.
+    pub fn validate(
+        &self,
+        sender: &UserId,
+        recipient: &UserId,
+        recipient_ed25519_key: &str,
+        sender_ed25519_key: Option<&str>,
+    ) -> Result<(), OlmV1ValidationError> {
+        if self.sender != sender {
+            return Err(OlmV1ValidationError::Sender);
+        }
+
+        if self.recipient != recipient {
+            return Err(OlmV1ValidationError::Recipient);
+        }
+
+        if self.recipient_keys.ed25519 != recipient_ed25519_key {
+            return Err(OlmV1ValidationError::RecipientKeys);
+        }
+
+        if sender_ed25519_key.map_or(false, |key| self.keys.ed25519 != key) {
+            return Err(OlmV1ValidationError::Keys);
+        }
+
+        Ok(())
+    }
+}
+
+/// The keys of a device in a [`DecryptedOlmV1Event`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct OlmV1Keys {
+    /// The unpadded base64-encoded Ed25519 key of the device.
+    pub ed25519: String,
+}
+
+impl OlmV1Keys {
+    /// Creates a new `OlmV1Keys` with the given Ed25519 key.
+    pub fn new(ed25519: String) -> Self {
+        Self { ed25519 }
+    }
+}
+
+/// An error encountered when validating a [`DecryptedOlmV1Event`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum OlmV1ValidationError {
+    /// The `sender` doesn't match the sender of the to-device event.
+    #[error("the sender of the decrypted payload doesn't match the sender of the event")]
+    Sender,
+
+    /// The `recipient` doesn't match the current user.
+    #[error("the recipient of the decrypted payload doesn't match the current user")]
+    Recipient,
+
+    /// The `recipient_keys` don't match the keys of the current device.
+    #[error("the recipient keys of the decrypted payload don't match the current device")]
+    RecipientKeys,
+
+    /// The `keys` don't match the keys of the device of the sender.
+    #[error("the keys of the decrypted payload don't match the device of the sender")]
+    Keys,
 }
 
 /// The content of an `m.room.encrypted` event using the `m.megolm.v1.aes-sha2` algorithm.
@@ -251,10 +439,10 @@ mod tests {
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
-        EncryptedEventScheme, InReplyTo, MegolmV1AesSha2ContentInit, Relation,
+        CiphertextInfo, EncryptedEventScheme, MegolmV1AesSha2ContentInit, OlmMessageType, Relation,
         RoomEncryptedEventContent,
     };
-    use crate::{event_id, serde::Raw};
+    use crate::{event_id, events::relation::InReplyTo, serde::Raw};
 
     #[test]
     fn serialization() {
@@ -358,4 +546,68 @@ mod tests {
         .deserialize()
         .unwrap_err();
     }
+
+    #[test]
+    fn olm_message_type() {
+        let info = CiphertextInfo::with_olm_message_type("body".into(), OlmMessageType::PreKey);
+        assert_eq!(info.message_type, uint!(0));
+        assert_eq!(info.olm_message_type(), Some(OlmMessageType::PreKey));
+
+        let info = CiphertextInfo::new("body".into(), uint!(1));
+        assert_eq!(info.olm_message_type(), Some(OlmMessageType::Normal));
+
+        let info = CiphertextInfo::new("body".into(), uint!(2));
+        assert_eq!(info.olm_message_type(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "events-e2ee")]
+    fn decrypted_olm_v1_event() {
+        use super::{DecryptedOlmV1Event, OlmV1ValidationError};
+        use crate::{
+            events::{room_key::ToDeviceRoomKeyEventContent, ToDeviceEventType},
+            user_id,
+        };
+
+        let json_data = json!({
+            "type": "m.room_key",
+            "content": {
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "room_id": "!room:example.org",
+                "session_id": "session_id",
+                "session_key": "session_key",
+            },
+            "sender": "@alice:example.org",
+            "recipient": "@bob:example.org",
+            "recipient_keys": { "ed25519": "bob_ed25519" },
+            "keys": { "ed25519": "alice_ed25519" },
+        });
+
+        let event: DecryptedOlmV1Event<Raw<ToDeviceRoomKeyEventContent>> =
+            from_json_value(json_data).unwrap();
+        assert_eq!(event.event_type, ToDeviceEventType::RoomKey);
+        assert_eq!(event.content.deserialize().unwrap().session_id, "session_id");
+
+        let alice = user_id!("@alice:example.org");
+        let bob = user_id!("@bob:example.org");
+
+        assert_eq!(event.validate(alice, bob, "bob_ed25519", Some("alice_ed25519")), Ok(()));
+        assert_eq!(event.validate(alice, bob, "bob_ed25519", None), Ok(()));
+        assert_eq!(
+            event.validate(bob, bob, "bob_ed25519", None),
+            Err(OlmV1ValidationError::Sender)
+        );
+        assert_eq!(
+            event.validate(alice, alice, "bob_ed25519", None),
+            Err(OlmV1ValidationError::Recipient)
+        );
+        assert_eq!(
+            event.validate(alice, bob, "other_ed25519", None),
+            Err(OlmV1ValidationError::RecipientKeys)
+        );
+        assert_eq!(
+            event.validate(alice, bob, "bob_ed25519", Some("other_ed25519")),
+            Err(OlmV1ValidationError::Keys)
+        );
+    }
 }
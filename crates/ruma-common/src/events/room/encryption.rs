@@ -2,11 +2,13 @@
 //!
 //! [`m.room.encryption`]: https://spec.matrix.org/v1.4/client-server-api/#mroomencryption
 
+use std::collections::BTreeSet;
+
 use js_int::UInt;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::events::{EmptyStateKey, EventEncryptionAlgorithm};
+use crate::events::{EmptyStateKey, EventEncryptionAlgorithm, MessageLikeEventType};
 
 /// The content of an `m.room.encryption` event.
 ///
@@ -39,3 +41,71 @@ impl RoomEncryptionEventContent {
         Self { algorithm, rotation_period_ms: None, rotation_period_msgs: None }
     }
 }
+
+/// The message-like events that must be encrypted in a room where encryption is enabled.
+///
+/// Once an `m.room.encryption` event is sent in a room, clients are expected to encrypt all the
+/// message-like events they send to it. Servers can use this to refuse the plaintext events that
+/// should have been encrypted.
+///
+/// State events are never encrypted, so they are not affected by this policy.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct EncryptionEnforcement {
+    /// The message-like event types that are allowed in plaintext.
+    pub allowed_plaintext: BTreeSet<MessageLikeEventType>,
+}
+
+impl EncryptionEnforcement {
+    /// Creates a new `EncryptionEnforcement` with the recommended allowed plaintext event types.
+    ///
+    /// Only `m.room.encrypted` and `m.room.redaction` events are allowed in plaintext, since the
+    /// server needs to read the ID of the redacted event.
+    pub fn new() -> Self {
+        Self {
+            allowed_plaintext: [
+                MessageLikeEventType::RoomEncrypted,
+                MessageLikeEventType::RoomRedaction,
+            ]
+            .into(),
+        }
+    }
+
+    /// Allow events of the given type in plaintext.
+    pub fn allow_plaintext(mut self, event_type: MessageLikeEventType) -> Self {
+        self.allowed_plaintext.insert(event_type);
+        self
+    }
+
+    /// Whether plaintext events of the given type must be refused in a room where encryption is
+    /// enabled.
+    pub fn requires_encryption(&self, event_type: &MessageLikeEventType) -> bool {
+        !self.allowed_plaintext.contains(event_type)
+    }
+}
+
+impl Default for EncryptionEnforcement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncryptionEnforcement;
+    use crate::events::MessageLikeEventType;
+
+    #[test]
+    fn encryption_enforcement() {
+        let enforcement = EncryptionEnforcement::new();
+        assert!(enforcement.requires_encryption(&MessageLikeEventType::RoomMessage));
+        assert!(enforcement.requires_encryption(&MessageLikeEventType::Reaction));
+        assert!(enforcement.requires_encryption(&"org.example.custom".into()));
+        assert!(!enforcement.requires_encryption(&MessageLikeEventType::RoomEncrypted));
+        assert!(!enforcement.requires_encryption(&MessageLikeEventType::RoomRedaction));
+
+        let enforcement = enforcement.allow_plaintext(MessageLikeEventType::Reaction);
+        assert!(!enforcement.requires_encryption(&MessageLikeEventType::Reaction));
+        assert!(enforcement.requires_encryption(&MessageLikeEventType::RoomMessage));
+    }
+}
@@ -5,7 +5,7 @@
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{events::EmptyStateKey, serde::StringEnum, PrivOwnedStr};
+use crate::{events::EmptyStateKey, serde::StringEnum, MilliSecondsSinceUnixEpoch, PrivOwnedStr};
 
 /// The content of an `m.room.history_visibility` event.
 ///
@@ -79,3 +79,137 @@ pub enum HistoryVisibility {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+impl HistoryVisibility {
+    /// Whether a member should receive the room keys of an event sent at the given time while
+    /// this was the history visibility of the room.
+    ///
+    /// This encodes the same semantics as the visibility of events, so that end-to-end encryption
+    /// implementations agree on which keys are shared with which members:
+    ///
+    /// * With [`HistoryVisibility::Shared`] and [`HistoryVisibility::WorldReadable`], the keys of
+    ///   all the events sent before the member left the room are shared, as long as they were
+    ///   invited or joined at some point.
+    /// * With [`HistoryVisibility::Invited`], the keys of the events sent between the member's
+    ///   invite, or join if they were not invited, and the time they left are shared.
+    /// * With [`HistoryVisibility::Joined`], the keys of the events sent between the member's join
+    ///   and the time they left are shared.
+    ///
+    /// Unknown history visibilities are handled like [`HistoryVisibility::Joined`], the most
+    /// restrictive one.
+    pub fn should_share_keys(
+        &self,
+        membership: &MembershipTimeline,
+        event_ts: MilliSecondsSinceUnixEpoch,
+    ) -> bool {
+        let start = match self {
+            Self::Shared | Self::WorldReadable => {
+                if membership.invited_at.is_none() && membership.joined_at.is_none() {
+                    return false;
+                }
+
+                None
+            }
+            Self::Invited => match membership.invited_at.or(membership.joined_at) {
+                Some(ts) => Some(ts),
+                None => return false,
+            },
+            _ => match membership.joined_at {
+                Some(ts) => Some(ts),
+                None => return false,
+            },
+        };
+
+        start.map_or(true, |start| event_ts >= start)
+            && membership.left_at.map_or(true, |left_at| event_ts < left_at)
+    }
+
+    /// Whether the room keys of events sent while this is the history visibility of the room
+    /// should be flagged as shared history, according to [MSC3061].
+    ///
+    /// Keys flagged as shared history can be shared with users invited to the room after the
+    /// events were sent. This is the case for [`HistoryVisibility::Shared`] and
+    /// [`HistoryVisibility::WorldReadable`].
+    ///
+    /// [MSC3061]: https://github.com/matrix-org/matrix-spec-proposals/pull/3061
+    #[cfg(feature = "unstable-msc3061")]
+    pub fn is_shared_history(&self) -> bool {
+        matches!(self, Self::Shared | Self::WorldReadable)
+    }
+}
+
+/// The times at which the membership of a user in a room changed.
+///
+/// Used by [`HistoryVisibility::should_share_keys()`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct MembershipTimeline {
+    /// The time at which the user was invited to the room, if they were.
+    pub invited_at: Option<MilliSecondsSinceUnixEpoch>,
+
+    /// The time at which the user joined the room, if they did.
+    pub joined_at: Option<MilliSecondsSinceUnixEpoch>,
+
+    /// The time at which the user left the room, if they did.
+    ///
+    /// This includes the user being kicked or banned.
+    pub left_at: Option<MilliSecondsSinceUnixEpoch>,
+}
+
+impl MembershipTimeline {
+    /// Creates an empty `MembershipTimeline`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistoryVisibility, MembershipTimeline};
+    use crate::MilliSecondsSinceUnixEpoch;
+
+    fn ts(ts: u32) -> MilliSecondsSinceUnixEpoch {
+        MilliSecondsSinceUnixEpoch(ts.into())
+    }
+
+    #[test]
+    fn should_share_keys() {
+        let mut membership = MembershipTimeline::new();
+        membership.invited_at = Some(ts(10));
+        membership.joined_at = Some(ts(20));
+        membership.left_at = Some(ts(30));
+
+        let check = |visibility: HistoryVisibility| {
+            [5, 10, 15, 20, 25, 30]
+                .into_iter()
+                .map(|event_ts| visibility.should_share_keys(&membership, ts(event_ts)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(check(HistoryVisibility::WorldReadable), [true, true, true, true, true, false]);
+        assert_eq!(check(HistoryVisibility::Shared), [true, true, true, true, true, false]);
+        assert_eq!(check(HistoryVisibility::Invited), [false, true, true, true, true, false]);
+        assert_eq!(check(HistoryVisibility::Joined), [false, false, false, true, true, false]);
+        assert_eq!(check("custom".into()), [false, false, false, true, true, false]);
+
+        // A user that was never in the room doesn't get any keys.
+        let never_joined = MembershipTimeline::new();
+        assert!(!HistoryVisibility::Shared.should_share_keys(&never_joined, ts(5)));
+        assert!(!HistoryVisibility::Invited.should_share_keys(&never_joined, ts(5)));
+
+        // Without an invite, the join is the start of the history for `invited`.
+        let mut joined = MembershipTimeline::new();
+        joined.joined_at = Some(ts(20));
+        assert!(!HistoryVisibility::Invited.should_share_keys(&joined, ts(15)));
+        assert!(HistoryVisibility::Invited.should_share_keys(&joined, ts(40)));
+    }
+
+    #[cfg(feature = "unstable-msc3061")]
+    #[test]
+    fn is_shared_history() {
+        assert!(HistoryVisibility::WorldReadable.is_shared_history());
+        assert!(HistoryVisibility::Shared.is_shared_history());
+        assert!(!HistoryVisibility::Invited.is_shared_history());
+        assert!(!HistoryVisibility::Joined.is_shared_history());
+    }
+}
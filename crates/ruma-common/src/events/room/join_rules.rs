@@ -11,7 +11,11 @@ use serde::{
 };
 use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
 
-use crate::{events::EmptyStateKey, serde::from_raw_json_value, OwnedRoomId, PrivOwnedStr};
+use super::RoomVersionValidationError;
+use crate::{
+    events::EmptyStateKey, serde::from_raw_json_value, OwnedRoomId, PrivOwnedStr, RoomId,
+    RoomVersionId,
+};
 
 /// The content of an `m.room.join_rules` event.
 ///
@@ -43,6 +47,39 @@ impl RoomJoinRulesEventContent {
     pub fn knock_restricted(allow: Vec<AllowRule>) -> Self {
         Self { join_rule: JoinRule::KnockRestricted(Restricted::new(allow)) }
     }
+
+    /// Checks that the join rule is supported in the given room version.
+    ///
+    /// The `knock` join rule is only supported since room version 7, `restricted` since room
+    /// version 8 and `knock_restricted` since room version 10.
+    pub fn validate_for_version(
+        &self,
+        room_version: &RoomVersionId,
+    ) -> Result<(), RoomVersionValidationError> {
+        // Custom room versions are assumed to support all join rules, since their rules are
+        // unknown.
+        let rules = match room_version.rules() {
+            Some(rules) => rules,
+            None => return Ok(()),
+        };
+
+        let supported = match &self.join_rule {
+            JoinRule::Knock => rules.allow_knocking,
+            JoinRule::Restricted(_) => rules.restricted_join_rules,
+            JoinRule::KnockRestricted(_) => rules.knock_restricted_join_rule,
+            _ => true,
+        };
+
+        if !supported {
+            return Err(RoomVersionValidationError::UnsupportedValue {
+                field: "join_rule",
+                value: self.join_rule.as_str().to_owned(),
+                room_version: room_version.clone(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 impl<'de> Deserialize<'de> for RoomJoinRulesEventContent {
@@ -124,6 +161,35 @@ impl JoinRule {
             JoinRule::_Custom(rule) => &rule.0,
         }
     }
+
+    /// Returns the configuration of the `Restricted` or `KnockRestricted` join rule, if this is
+    /// one of them.
+    pub fn restricted(&self) -> Option<&Restricted> {
+        match self {
+            JoinRule::Restricted(restricted) | JoinRule::KnockRestricted(restricted) => {
+                Some(restricted)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a user can join the room without an invite, given the rooms they are joined to.
+    ///
+    /// `is_joined` is called with the IDs of the rooms referenced by the allow rules of a
+    /// restricted join rule, and should return `true` if the user is joined to that room.
+    ///
+    /// This doesn't take into account the current membership of the user in the room, so a
+    /// banned user might not be able to join even if this returns `true`, and an invited user
+    /// can join even if this returns `false`.
+    pub fn allows_join(&self, is_joined: impl FnMut(&RoomId) -> bool) -> bool {
+        match self {
+            JoinRule::Public => true,
+            JoinRule::Restricted(restricted) | JoinRule::KnockRestricted(restricted) => {
+                restricted.allows_join(is_joined)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for JoinRule {
@@ -169,6 +235,23 @@ impl Restricted {
     pub fn new(allow: Vec<AllowRule>) -> Self {
         Self { allow }
     }
+
+    /// The IDs of the rooms whose members are allowed to join.
+    pub fn room_ids(&self) -> impl Iterator<Item = &RoomId> {
+        self.allow.iter().filter_map(|rule| match rule {
+            AllowRule::RoomMembership(membership) => Some(&*membership.room_id),
+            AllowRule::_Custom(_) => None,
+        })
+    }
+
+    /// Whether one of the allow rules allows a user to join, given the rooms they are joined to.
+    ///
+    /// `is_joined` is called with the IDs of the rooms referenced by the allow rules, and should
+    /// return `true` if the user is joined to that room. Unknown allow rules never allow a user
+    /// to join.
+    pub fn allows_join(&self, is_joined: impl FnMut(&RoomId) -> bool) -> bool {
+        self.room_ids().any(is_joined)
+    }
 }
 
 /// An allow rule which defines a condition that allows joining a room.
@@ -247,8 +330,10 @@ impl<'de> Deserialize<'de> for AllowRule {
 mod tests {
     use assert_matches::assert_matches;
 
+    use serde_json::json;
+
     use super::{AllowRule, JoinRule, OriginalSyncRoomJoinRulesEvent, RoomJoinRulesEventContent};
-    use crate::room_id;
+    use crate::{events::room::RoomVersionValidationError, room_id, RoomVersionId};
 
     #[test]
     fn deserialize() {
@@ -314,4 +399,59 @@ mod tests {
         assert_matches!(&allow_rule, AllowRule::_Custom(_));
         assert_eq!(serde_json::to_string(&allow_rule).unwrap(), json);
     }
+
+    #[test]
+    fn allows_join() {
+        let mods = room_id!("!mods:example.org");
+        let users = room_id!("!users:example.org");
+        let other = room_id!("!other:example.org");
+
+        let json = json!({
+            "join_rule": "knock_restricted",
+            "allow": [
+                { "type": "m.room_membership", "room_id": mods },
+                { "type": "org.msc9000.something", "room_id": other },
+                { "type": "m.room_membership", "room_id": users },
+            ],
+        });
+        let join_rule =
+            serde_json::from_value::<RoomJoinRulesEventContent>(json).unwrap().join_rule;
+
+        let restricted = join_rule.restricted().unwrap();
+        assert_eq!(restricted.room_ids().collect::<Vec<_>>(), [mods, users]);
+
+        assert!(join_rule.allows_join(|room_id| room_id == users));
+        assert!(!join_rule.allows_join(|room_id| room_id == other));
+        assert!(!join_rule.allows_join(|_| false));
+
+        assert!(JoinRule::Public.allows_join(|_| false));
+        assert!(!JoinRule::Invite.allows_join(|_| true));
+        assert_eq!(JoinRule::Invite.restricted(), None);
+    }
+
+    #[test]
+    fn validate_for_version() {
+        let knock = RoomJoinRulesEventContent::new(JoinRule::Knock);
+        knock.validate_for_version(&RoomVersionId::V7).unwrap();
+        assert_eq!(
+            knock.validate_for_version(&RoomVersionId::V6),
+            Err(RoomVersionValidationError::UnsupportedValue {
+                field: "join_rule",
+                value: "knock".to_owned(),
+                room_version: RoomVersionId::V6,
+            })
+        );
+
+        let restricted = RoomJoinRulesEventContent::restricted(vec![]);
+        restricted.validate_for_version(&RoomVersionId::V8).unwrap();
+        restricted.validate_for_version(&RoomVersionId::V7).unwrap_err();
+
+        let knock_restricted = RoomJoinRulesEventContent::knock_restricted(vec![]);
+        knock_restricted.validate_for_version(&RoomVersionId::V10).unwrap();
+        knock_restricted.validate_for_version(&RoomVersionId::V9).unwrap_err();
+
+        RoomJoinRulesEventContent::new(JoinRule::Public)
+            .validate_for_version(&RoomVersionId::V1)
+            .unwrap();
+    }
 }
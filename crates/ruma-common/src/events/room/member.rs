@@ -7,14 +7,16 @@ use std::collections::BTreeMap;
 use js_int::Int;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue, Value as JsonValue};
 
+use super::RoomVersionValidationError;
 use crate::{
     events::{
         AnyStrippedStateEvent, BundledRelations, EventContent, RedactContent, RedactedEventContent,
         RedactedStateEventContent, StateEventContent, StateEventType, StateUnsignedFromParts,
         StaticEventContent,
     },
+    room_version_rules::RedactionRules,
     serde::{CanBeEmpty, Raw, StringEnum},
     OwnedMxcUri, OwnedServerName, OwnedServerSigningKeyId, OwnedTransactionId, OwnedUserId,
     PrivOwnedStr, RoomVersionId, UserId,
@@ -133,6 +135,33 @@ impl RoomMemberEventContent {
         }
     }
 
+    /// The `signed` block of the third-party invite of this event, if any.
+    ///
+    /// It can be verified with the keys of the `m.room.third_party_invite` event whose state key is
+    /// its `token`.
+    pub fn third_party_invite_signed(&self) -> Option<&SignedContent> {
+        self.third_party_invite.as_ref().map(|invite| &invite.signed)
+    }
+
+    /// Checks that the fields of this content are supported in the given room version.
+    ///
+    /// The `join_authorised_via_users_server` field is only supported since room version 8.
+    pub fn validate_for_version(
+        &self,
+        room_version: &RoomVersionId,
+    ) -> Result<(), RoomVersionValidationError> {
+        if self.join_authorized_via_users_server.is_some()
+            && room_version.rules().map_or(false, |rules| !rules.restricted_join_rules)
+        {
+            return Err(RoomVersionValidationError::UnsupportedField {
+                field: "join_authorised_via_users_server",
+                room_version: room_version.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Obtain the details about this event that are required to calculate a membership change.
     ///
     /// This is required when you want to calculate the change a redacted `m.room.member` event
@@ -169,13 +198,17 @@ impl RoomMemberEventContent {
 impl RedactContent for RoomMemberEventContent {
     type Redacted = RedactedRoomMemberEventContent;
 
-    fn redact(self, _version: &RoomVersionId) -> RedactedRoomMemberEventContent {
+    fn redact(self, version: &RoomVersionId) -> RedactedRoomMemberEventContent {
+        // Redacting contents can't fail, room versions with unknown rules use the version 6 rules.
+        let keep_join_authorized_via_users_server = RedactionRules::for_room_version(version)
+            .unwrap_or(RedactionRules::V6)
+            .keep_room_member_join_authorised_via_users_server;
+
         RedactedRoomMemberEventContent {
             membership: self.membership,
-            join_authorized_via_users_server: match _version {
-                RoomVersionId::V9 | RoomVersionId::V10 => self.join_authorized_via_users_server,
-                _ => None,
-            },
+            join_authorized_via_users_server: self
+                .join_authorized_via_users_server
+                .filter(|_| keep_join_authorized_via_users_server),
         }
     }
 }
@@ -348,6 +381,12 @@ pub struct SignedContent {
 
     /// The token property of the containing `third_party_invite` object.
     pub token: String,
+
+    /// Other fields signed by the verifying server.
+    ///
+    /// They must be kept to be able to verify the signatures.
+    #[serde(flatten)]
+    other: BTreeMap<String, JsonValue>,
 }
 
 impl SignedContent {
@@ -357,7 +396,7 @@ impl SignedContent {
         mxid: OwnedUserId,
         token: String,
     ) -> Self {
-        Self { mxid, signatures, token }
+        Self { mxid, signatures, token, other: BTreeMap::new() }
     }
 }
 
@@ -569,12 +608,14 @@ mod tests {
     use assert_matches::assert_matches;
     use js_int::uint;
     use maplit::btreemap;
-    use serde_json::{from_value as from_json_value, json};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{MembershipState, RoomMemberEventContent};
     use crate::{
-        events::OriginalStateEvent, mxc_uri, serde::CanBeEmpty, server_name, server_signing_key_id,
-        user_id, MilliSecondsSinceUnixEpoch,
+        events::{room::RoomVersionValidationError, OriginalStateEvent},
+        mxc_uri,
+        serde::CanBeEmpty,
+        server_name, server_signing_key_id, user_id, MilliSecondsSinceUnixEpoch, RoomVersionId,
     };
 
     #[test]
@@ -705,6 +746,37 @@ mod tests {
         assert_eq!(third_party_invite.signed.token, "abc123");
     }
 
+    #[test]
+    fn third_party_invite_signed_keeps_other_fields() {
+        let signed = json!({
+            "mxid": "@alice:example.org",
+            "sender": "@bob:example.org",
+            "signatures": {
+                "magic.forest": {
+                    "ed25519:3": "foobar"
+                }
+            },
+            "token": "abc123"
+        });
+        let content = from_json_value::<RoomMemberEventContent>(json!({
+            "membership": "invite",
+            "third_party_invite": {
+                "display_name": "alice",
+                "signed": signed,
+            },
+        }))
+        .unwrap();
+
+        let content_signed = content.third_party_invite_signed().unwrap();
+        assert_eq!(content_signed.token, "abc123");
+        assert_eq!(to_json_value(content_signed).unwrap(), signed);
+
+        assert_matches!(
+            RoomMemberEventContent::new(MembershipState::Join).third_party_invite_signed(),
+            None
+        );
+    }
+
     #[test]
     fn serde_with_prev_content_full() {
         let json = json!({
@@ -808,4 +880,20 @@ mod tests {
             Some(user_id!("@notcarl:example.com"))
         );
     }
+
+    #[test]
+    fn validate_for_version() {
+        let mut content = RoomMemberEventContent::new(MembershipState::Join);
+        content.validate_for_version(&RoomVersionId::V1).unwrap();
+
+        content.join_authorized_via_users_server = Some(user_id!("@carl:example.com").to_owned());
+        content.validate_for_version(&RoomVersionId::V8).unwrap();
+        assert_matches!(
+            content.validate_for_version(&RoomVersionId::V7),
+            Err(RoomVersionValidationError::UnsupportedField {
+                field: "join_authorised_via_users_server",
+                ..
+            })
+        );
+    }
 }
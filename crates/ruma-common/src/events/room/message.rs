@@ -8,10 +8,22 @@ use ruma_macros::EventContent;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
+#[cfg(feature = "canonical-json")]
+use crate::canonical_json::{try_from_json_map, CanonicalJsonError, CanonicalJsonObject};
+#[cfg(feature = "unstable-msc1767")]
+use crate::events::message::MessageContent;
+#[cfg(feature = "unstable-msc3245")]
 use crate::{
-    events::relation::{InReplyTo, Replacement, Thread},
+    events::{audio::Waveform, room::EncryptedFile},
+    OwnedMxcUri,
+};
+use crate::{
+    events::{
+        relation::{InReplyTo, Replacement, Thread},
+        size::{estimate_pdu_size, truncate_at_char_boundary, SizeWarning, MAX_PDU_SIZE},
+    },
     serde::{JsonObject, StringEnum},
-    OwnedEventId, PrivOwnedStr,
+    OwnedEventId, PrivOwnedStr, RoomId, UserId,
 };
 
 mod audio;
@@ -22,13 +34,13 @@ mod image;
 mod key_verification_request;
 mod location;
 mod notice;
-pub(crate) mod relation_serde;
 mod reply;
 pub mod sanitize;
 mod server_notice;
 mod text;
 mod video;
 
+pub use crate::events::relation::{deserialize_relation, Relation};
 pub use audio::{AudioInfo, AudioMessageEventContent};
 pub use emote::EmoteMessageEventContent;
 pub use file::{FileInfo, FileMessageEventContent};
@@ -36,7 +48,6 @@ pub use image::ImageMessageEventContent;
 pub use key_verification_request::KeyVerificationRequestEventContent;
 pub use location::{LocationInfo, LocationMessageEventContent};
 pub use notice::NoticeMessageEventContent;
-pub use relation_serde::deserialize_relation;
 #[cfg(feature = "unstable-sanitize")]
 use sanitize::{
     remove_plain_reply_fallback, sanitize_html, HtmlSanitizerMode, RemoveReplyFallback,
@@ -105,6 +116,33 @@ impl RoomMessageEventContent {
         Self::new(MessageType::notice_markdown(body))
     }
 
+    /// A constructor to create a non-encrypted voice message with the given body, url, optional
+    /// extra info and optional waveform.
+    #[cfg(feature = "unstable-msc3245")]
+    pub fn voice_plain(
+        body: impl Into<String>,
+        url: OwnedMxcUri,
+        info: Option<Box<AudioInfo>>,
+        waveform: Option<Waveform>,
+    ) -> Self {
+        Self::new(MessageType::Audio(
+            AudioMessageEventContent::plain(body.into(), url, info).into_voice(waveform),
+        ))
+    }
+
+    /// A constructor to create an encrypted voice message with the given body, encrypted file
+    /// and optional waveform.
+    #[cfg(feature = "unstable-msc3245")]
+    pub fn voice_encrypted(
+        body: impl Into<String>,
+        file: EncryptedFile,
+        waveform: Option<Waveform>,
+    ) -> Self {
+        Self::new(MessageType::Audio(
+            AudioMessageEventContent::encrypted(body.into(), file).into_voice(waveform),
+        ))
+    }
+
     /// Turns `self` into a reply to the given message.
     ///
     /// Takes the `body` / `formatted_body` (if any) in `self` for the main text and prepends a
@@ -295,6 +333,140 @@ impl RoomMessageEventContent {
         self
     }
 
+    /// Applies the given [replacement] (or edit) to `self`.
+    ///
+    /// This replaces the `msgtype` of `self` with the `m.new_content` of the replacement. The
+    /// `relates_to` of `self` is kept, since a replacement can't change the relation of the
+    /// original event.
+    ///
+    /// This doesn't check that the replacement is valid, use
+    /// [`OriginalRoomMessageEvent::apply_replacement()`] for that.
+    ///
+    /// [replacement]: https://spec.matrix.org/v1.4/client-server-api/#event-replacements
+    pub fn apply_replacement(&mut self, replacement: Replacement<MessageType>) {
+        self.msgtype = replacement.new_content;
+    }
+
+    /// Trim this message so that it fits in [`MAX_PDU_SIZE`] once it is formatted for federation.
+    ///
+    /// The size of the event is estimated with [`estimate_pdu_size`]. If it is too large, the
+    /// formatted body of text, emote and notice messages is removed first, then their body is
+    /// truncated. With the `unstable-msc1767` feature, their extensible-event representation is
+    /// replaced by the remaining body.
+    ///
+    /// Returns the changes that were made, with a [`SizeWarning::StillTooLarge`] if the message
+    /// couldn't be trimmed enough.
+    pub fn trim_to_pdu_size(
+        &mut self,
+        room_id: &RoomId,
+        sender: &UserId,
+    ) -> serde_json::Result<Vec<SizeWarning>> {
+        let mut warnings = Vec::new();
+        let mut size = estimate_pdu_size(self, room_id, sender, None)?;
+
+        if size > MAX_PDU_SIZE {
+            let formatted = match &mut self.msgtype {
+                MessageType::Emote(m) => m.formatted.take(),
+                MessageType::Notice(m) => m.formatted.take(),
+                MessageType::Text(m) => m.formatted.take(),
+                _ => None,
+            };
+            let mirrored = self.msgtype.mirror_body_as_extensible_text();
+
+            if formatted.is_some() {
+                warnings.push(SizeWarning::FormattedBodyRemoved);
+            }
+            if formatted.is_some() || mirrored {
+                size = estimate_pdu_size(self, room_id, sender, None)?;
+            }
+        }
+
+        let original_len = self.msgtype.body().len();
+        while size > MAX_PDU_SIZE {
+            #[cfg(feature = "unstable-msc1767")]
+            let mirrored = match &self.msgtype {
+                MessageType::Emote(m) => m.message.is_some(),
+                MessageType::Notice(m) => m.message.is_some(),
+                MessageType::Text(m) => m.message.is_some(),
+                _ => false,
+            };
+            #[cfg(not(feature = "unstable-msc1767"))]
+            let mirrored = false;
+
+            let body = match &mut self.msgtype {
+                MessageType::Emote(m) => &mut m.body,
+                MessageType::Notice(m) => &mut m.body,
+                MessageType::Text(m) => &mut m.body,
+                _ => break,
+            };
+
+            // The body is serialized twice if it is mirrored in the extensible-event
+            // representation.
+            let excess = size - MAX_PDU_SIZE;
+            let excess = if mirrored { (excess + 1) / 2 } else { excess };
+
+            let len = body.len();
+            truncate_at_char_boundary(body, len.saturating_sub(excess));
+            if body.len() == len {
+                break;
+            }
+
+            self.msgtype.mirror_body_as_extensible_text();
+            size = estimate_pdu_size(self, room_id, sender, None)?;
+        }
+
+        let len = self.msgtype.body().len();
+        if len < original_len {
+            warnings.push(SizeWarning::BodyTruncated { original_len, len });
+        }
+
+        if size > MAX_PDU_SIZE {
+            warnings.push(SizeWarning::StillTooLarge { estimated_size: size });
+        }
+
+        Ok(warnings)
+    }
+
+    /// Limit the number of user mentions in the formatted body of this message to
+    /// `max_mentions`.
+    ///
+    /// User mentions are links to the `matrix.to` URI of a user in the formatted body of text,
+    /// emote and notice messages. The links after the first `max_mentions` ones are replaced by
+    /// their text. With the `unstable-msc1767` feature, the extensible-event representation is
+    /// updated too.
+    ///
+    /// Returns a [`SizeWarning::MentionsRemoved`] if some mentions were removed.
+    pub fn limit_mentions(&mut self, max_mentions: usize) -> Option<SizeWarning> {
+        let formatted = match &mut self.msgtype {
+            MessageType::Emote(m) => m.formatted.as_mut(),
+            MessageType::Notice(m) => m.formatted.as_mut(),
+            MessageType::Text(m) => m.formatted.as_mut(),
+            _ => None,
+        }
+        .filter(|formatted| formatted.format == MessageFormat::Html)?;
+
+        let (html, count) = remove_mention_links(&formatted.body, max_mentions);
+        if count == 0 {
+            return None;
+        }
+
+        formatted.body = html;
+
+        #[cfg(feature = "unstable-msc1767")]
+        if let Some(message) = match &mut self.msgtype {
+            MessageType::Emote(m) => m.message.as_mut(),
+            MessageType::Notice(m) => m.message.as_mut(),
+            MessageType::Text(m) => m.message.as_mut(),
+            _ => None,
+        } {
+            for text in message.0.iter_mut().filter(|text| text.mimetype == "text/html") {
+                text.body = remove_mention_links(&text.body, max_mentions).0;
+            }
+        }
+
+        Some(SizeWarning::MentionsRemoved { count })
+    }
+
     /// Returns a reference to the `msgtype` string.
     ///
     /// If you want to access the message type-specific data rather than the message type itself,
@@ -420,6 +592,29 @@ pub enum MessageType {
 }
 
 impl MessageType {
+    /// Replace the extensible-event representation of text, emote and notice messages by their
+    /// plain `body`, if they have one.
+    ///
+    /// Returns whether the representation was replaced.
+    fn mirror_body_as_extensible_text(&mut self) -> bool {
+        #[cfg(feature = "unstable-msc1767")]
+        {
+            let (body, message) = match self {
+                Self::Emote(m) => (&m.body, &mut m.message),
+                Self::Notice(m) => (&m.body, &mut m.message),
+                Self::Text(m) => (&m.body, &mut m.message),
+                _ => return false,
+            };
+
+            if message.is_some() {
+                *message = Some(MessageContent::plain(body.clone()));
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Creates a new `MessageType`.
     ///
     /// The `msgtype` and `body` are required fields as defined by [the `m.room.message` spec](https://spec.matrix.org/v1.4/client-server-api/#mroommessage).
@@ -558,6 +753,137 @@ impl MessageType {
             Self::_Custom(c) => Cow::Borrowed(&c.data),
         }
     }
+
+    /// Returns the associated data as a canonical JSON object.
+    ///
+    /// Like [`.data()`](Self::data), the returned object won't contain the `msgtype` and `body`
+    /// fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data contains values that can't be represented as canonical JSON,
+    /// like floats.
+    #[cfg(feature = "canonical-json")]
+    pub fn canonical_data(&self) -> Result<CanonicalJsonObject, CanonicalJsonError> {
+        try_from_json_map(self.data().into_owned())
+    }
+
+    /// Whether this is a message type that doesn't have a dedicated variant.
+    ///
+    /// Such message types can only be inspected through [`.msgtype()`](Self::msgtype),
+    /// [`.body()`](Self::body) and [`.data()`](Self::data).
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::_Custom(_))
+    }
+
+    /// Returns a mutable reference to the associated data of a custom message type.
+    ///
+    /// This allows to add arbitrary fields to a message type created with
+    /// [`MessageType::new()`]. Returns `None` if this message type has a dedicated variant.
+    pub fn custom_data_mut(&mut self) -> Option<&mut JsonObject> {
+        match self {
+            Self::_Custom(c) => Some(&mut c.data),
+            _ => None,
+        }
+    }
+}
+
+impl OriginalRoomMessageEvent {
+    /// Applies the content of the given [replacement] (or edit) event to `self`.
+    ///
+    /// The replacement is only applied if it is valid for this event: both events must be in the
+    /// same room and have the same sender, the replacement must have a [`Relation::Replacement`]
+    /// to this event and this event must not be a replacement itself.
+    ///
+    /// See [`RoomMessageEventContent::apply_replacement()`] for how the replacement is applied.
+    ///
+    /// [replacement]: https://spec.matrix.org/v1.4/client-server-api/#event-replacements
+    pub fn apply_replacement(
+        &mut self,
+        replacement: &OriginalRoomMessageEvent,
+    ) -> Result<(), ReplacementValidationError> {
+        if replacement.room_id != self.room_id {
+            return Err(ReplacementValidationError::DifferentRoom);
+        }
+
+        if replacement.sender != self.sender {
+            return Err(ReplacementValidationError::DifferentSender);
+        }
+
+        if let Some(Relation::Replacement(_)) = &self.content.relates_to {
+            return Err(ReplacementValidationError::OriginalIsReplacement);
+        }
+
+        match &replacement.content.relates_to {
+            Some(Relation::Replacement(r)) if r.event_id == self.event_id => {
+                self.content.apply_replacement(r.clone());
+                Ok(())
+            }
+            _ => Err(ReplacementValidationError::NotAReplacement),
+        }
+    }
+}
+
+/// An error encountered when validating a replacement of an [`OriginalRoomMessageEvent`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ReplacementValidationError {
+    /// The replacement is not in the same room as the original event.
+    #[error("the replacement is not in the same room as the original event")]
+    DifferentRoom,
+
+    /// The replacement doesn't have the same sender as the original event.
+    #[error("the replacement doesn't have the same sender as the original event")]
+    DifferentSender,
+
+    /// The replacement doesn't have a replacement relation to the original event.
+    #[error("the event is not a replacement of the original event")]
+    NotAReplacement,
+
+    /// The original event is a replacement itself.
+    #[error("the original event is a replacement itself")]
+    OriginalIsReplacement,
+}
+
+/// Replace the links to the `matrix.to` URIs of users in the given HTML after the first
+/// `max_mentions` ones by their text.
+///
+/// Returns the new HTML and the number of links that were replaced.
+fn remove_mention_links(html: &str, max_mentions: usize) -> (String, usize) {
+    const MENTION_START: &str = "<a href=\"https://matrix.to/#/@";
+    const LINK_END: &str = "</a>";
+
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut mentions = 0;
+    let mut removed = 0;
+
+    while let Some(start) = rest.find(MENTION_START) {
+        let (before, link) = rest.split_at(start);
+        result.push_str(before);
+
+        let (text_start, text_end) = match (link.find('>'), link.find(LINK_END)) {
+            (Some(tag_end), Some(link_end)) if tag_end < link_end => (tag_end + 1, link_end),
+            _ => {
+                // Malformed link, keep the rest as is.
+                rest = link;
+                break;
+            }
+        };
+
+        mentions += 1;
+        if mentions > max_mentions {
+            result.push_str(&link[text_start..text_end]);
+            removed += 1;
+        } else {
+            result.push_str(&link[..text_end + LINK_END.len()]);
+        }
+
+        rest = &link[text_end + LINK_END.len()..];
+    }
+
+    result.push_str(rest);
+    (result, removed)
 }
 
 impl From<MessageType> for RoomMessageEventContent {
@@ -572,27 +898,6 @@ impl From<RoomMessageEventContent> for MessageType {
     }
 }
 
-/// Message event relationship.
-#[derive(Clone, Debug)]
-#[allow(clippy::manual_non_exhaustive)]
-#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-pub enum Relation<C> {
-    /// An `m.in_reply_to` relation indicating that the event is a reply to another event.
-    Reply {
-        /// Information about another message being replied to.
-        in_reply_to: InReplyTo,
-    },
-
-    /// An event that replaces another event.
-    Replacement(Replacement<C>),
-
-    /// An event that belongs to a thread.
-    Thread(Thread),
-
-    #[doc(hidden)]
-    _Custom,
-}
-
 /// The format for the formatted representation of a message body.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, Debug, PartialEq, Eq, StringEnum)]
@@ -3,14 +3,14 @@ use std::time::Duration;
 use js_int::UInt;
 use serde::{Deserialize, Serialize};
 
-#[cfg(feature = "unstable-msc3245")]
-use crate::events::voice::VoiceContent;
 #[cfg(feature = "unstable-msc3246")]
 use crate::events::{
     audio::AudioContent,
     file::{EncryptedContent, FileContent, FileContentInfo},
     message::MessageContent,
 };
+#[cfg(feature = "unstable-msc3245")]
+use crate::events::{audio::Waveform, voice::VoiceContent};
 use crate::{
     events::room::{EncryptedFile, MediaSource},
     OwnedMxcUri,
@@ -130,6 +130,31 @@ impl AudioMessageEventContent {
         }
     }
 
+    /// Replaces the source of this audio file with the given encrypted file.
+    ///
+    /// This can be used to send the audio file in an encrypted room after encrypting its data. The
+    /// source of the thumbnail in the info is not changed.
+    pub fn into_encrypted(self, file: EncryptedFile) -> Self {
+        self.with_source(MediaSource::Encrypted(Box::new(file)))
+    }
+
+    /// Replaces the source of this audio file with the given unencrypted URL.
+    ///
+    /// The source of the thumbnail in the info is not changed.
+    pub fn into_plain(self, url: OwnedMxcUri) -> Self {
+        self.with_source(MediaSource::Plain(url))
+    }
+
+    fn with_source(mut self, source: MediaSource) -> Self {
+        #[cfg(feature = "unstable-msc3246")]
+        if let Some(file) = &mut self.file {
+            file.set_source(source.clone());
+        }
+
+        self.source = source;
+        self
+    }
+
     /// Create a new `AudioMessageEventContent` with the given message, file info and audio info.
     #[cfg(feature = "unstable-msc3246")]
     pub(crate) fn from_extensible_content(
@@ -170,6 +195,19 @@ impl AudioMessageEventContent {
         content.voice = Some(voice);
         content
     }
+
+    /// Turns this audio message into a voice message, with the given waveform.
+    ///
+    /// This sets the voice flag of the message, and the waveform in its extensible audio info if
+    /// it is provided.
+    #[cfg(feature = "unstable-msc3245")]
+    pub fn into_voice(mut self, waveform: Option<Waveform>) -> Self {
+        if waveform.is_some() {
+            self.audio.get_or_insert_with(Default::default).waveform = waveform;
+        }
+        self.voice = Some(VoiceContent::new());
+        self
+    }
 }
 
 /// Metadata about an audio clip.
@@ -5,13 +5,13 @@ use serde_json::value::RawValue as RawJsonValue;
 
 #[cfg(feature = "unstable-msc3552")]
 use super::ImageMessageEventContent;
-use super::{relation_serde::deserialize_relation, MessageType, RoomMessageEventContent};
 #[cfg(feature = "unstable-msc3246")]
 use super::{AudioInfo, AudioMessageEventContent};
 #[cfg(feature = "unstable-msc3551")]
 use super::{FileInfo, FileMessageEventContent};
 #[cfg(feature = "unstable-msc3488")]
 use super::{LocationInfo, LocationMessageEventContent};
+use super::{MessageType, RoomMessageEventContent};
 #[cfg(feature = "unstable-msc3553")]
 use super::{VideoInfo, VideoMessageEventContent};
 #[cfg(feature = "unstable-msc3246")]
@@ -36,9 +36,9 @@ use crate::events::room::MediaSource;
 use crate::events::video::VideoContent;
 #[cfg(feature = "unstable-msc3245")]
 use crate::events::voice::VoiceContent;
-use crate::serde::from_raw_json_value;
 #[cfg(feature = "unstable-msc3488")]
 use crate::MilliSecondsSinceUnixEpoch;
+use crate::{events::relation::deserialize_relation, serde::from_raw_json_value};
 
 impl<'de> Deserialize<'de> for RoomMessageEventContent {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -49,7 +49,15 @@ impl<'de> Deserialize<'de> for RoomMessageEventContent {
         let mut deserializer = serde_json::Deserializer::from_str(json.get());
         let relates_to = deserialize_relation(&mut deserializer).map_err(de::Error::custom)?;
 
-        Ok(Self { msgtype: from_raw_json_value(&json)?, relates_to })
+        let mut msgtype = from_raw_json_value(&json)?;
+
+        // The relation is not part of the data of custom message types.
+        if let MessageType::_Custom(c) = &mut msgtype {
+            c.data.remove("m.relates_to");
+            c.data.remove("m.new_content");
+        }
+
+        Ok(Self { msgtype, relates_to })
     }
 }
 
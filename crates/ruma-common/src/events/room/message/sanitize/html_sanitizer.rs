@@ -1,11 +1,11 @@
 use html5ever::{tendril::StrTendril, Attribute};
 use phf::{phf_map, phf_set, Map, Set};
-use wildmatch::WildMatch;
 
 use super::{
     html_fragment::{ElementData, Fragment, NodeData},
     HtmlSanitizerMode, RemoveReplyFallback,
 };
+use crate::glob;
 
 /// A sanitizer to filter [HTML tags and attributes] according to the Matrix specification.
 ///
@@ -160,7 +160,7 @@ impl HtmlSanitizer {
                         let mut changed = false;
                         let attr_classes = value.split_whitespace().filter(|attr_class| {
                             for class in classes.iter() {
-                                if WildMatch::new(class).matches(attr_class) {
+                                if glob::matches(class, attr_class) {
                                     return true;
                                 }
                             }
@@ -149,6 +149,31 @@ impl VideoMessageEventContent {
             info: None,
         }
     }
+
+    /// Replaces the source of this video with the given encrypted file.
+    ///
+    /// This can be used to send the video in an encrypted room after encrypting its data. The
+    /// source of the thumbnail in the info is not changed.
+    pub fn into_encrypted(self, file: EncryptedFile) -> Self {
+        self.with_source(MediaSource::Encrypted(Box::new(file)))
+    }
+
+    /// Replaces the source of this video with the given unencrypted URL.
+    ///
+    /// The source of the thumbnail in the info is not changed.
+    pub fn into_plain(self, url: OwnedMxcUri) -> Self {
+        self.with_source(MediaSource::Plain(url))
+    }
+
+    fn with_source(mut self, source: MediaSource) -> Self {
+        #[cfg(feature = "unstable-msc3553")]
+        if let Some(file) = &mut self.file {
+            file.set_source(source.clone());
+        }
+
+        self.source = source;
+        self
+    }
 }
 
 /// Metadata about a video.
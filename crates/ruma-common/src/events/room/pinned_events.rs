@@ -25,13 +25,23 @@ impl RoomPinnedEventsEventContent {
     }
 }
 
-#[cfg(all(test, feature = "rand"))]
+#[cfg(test)]
 mod tests {
-    use super::RoomPinnedEventsEventContent;
-    use crate::{server_name, EventId};
+    use assert_matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
+    use super::{RedactedRoomPinnedEventsEventContent, RoomPinnedEventsEventContent};
+    use crate::{
+        event_id,
+        events::{AnySyncStateEvent, RedactContent, SyncStateEvent},
+        RoomVersionId,
+    };
+
+    #[cfg(feature = "rand")]
     #[test]
     fn serialization_deserialization() {
+        use crate::{server_name, EventId};
+
         let mut content: RoomPinnedEventsEventContent =
             RoomPinnedEventsEventContent { pinned: Vec::new() };
         let server_name = server_name!("example.com");
@@ -45,4 +55,52 @@ mod tests {
 
         assert_eq!(parsed_content.pinned, content.pinned);
     }
+
+    #[test]
+    fn deserialize_state_event() {
+        let json = json!({
+            "content": {
+                "pinned": ["$one:example.org", "$two:example.org"],
+            },
+            "event_id": "$143273582443PhrSn:example.org",
+            "origin_server_ts": 1_432_735_824_653_u64,
+            "sender": "@example:example.org",
+            "state_key": "",
+            "type": "m.room.pinned_events",
+        });
+
+        let event = assert_matches!(
+            from_json_value::<AnySyncStateEvent>(json),
+            Ok(AnySyncStateEvent::RoomPinnedEvents(SyncStateEvent::Original(event))) => event
+        );
+        assert_eq!(
+            event.content.pinned,
+            [event_id!("$one:example.org"), event_id!("$two:example.org")]
+        );
+    }
+
+    #[test]
+    fn redaction_removes_pinned() {
+        let content =
+            RoomPinnedEventsEventContent::new(vec![event_id!("$one:example.org").to_owned()]);
+
+        let redacted = content.redact(&RoomVersionId::V10);
+        assert_matches!(redacted, RedactedRoomPinnedEventsEventContent { .. });
+        assert_eq!(to_json_value(redacted).unwrap(), json!({}));
+    }
+
+    #[cfg(feature = "canonical-json")]
+    #[test]
+    fn redaction_removes_pinned_in_all_room_versions() {
+        use crate::{canonical_json::redact_content_in_place, CanonicalJsonObject};
+
+        let json = json!({ "pinned": ["$one:example.org"] });
+
+        for version in [RoomVersionId::V1, RoomVersionId::V6, RoomVersionId::V9, RoomVersionId::V10]
+        {
+            let mut object = from_json_value::<CanonicalJsonObject>(json.clone()).unwrap();
+            redact_content_in_place(&mut object, &version, "m.room.pinned_events").unwrap();
+            assert!(object.is_empty());
+        }
+    }
 }
@@ -7,11 +7,12 @@ use std::{cmp::max, collections::BTreeMap};
 use js_int::{int, Int};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue as RawJsonValue;
 
 use crate::{
     events::{EmptyStateKey, MessageLikeEventType, StateEventType, TimelineEventType},
-    power_levels::{default_power_level, NotificationPowerLevels},
-    OwnedUserId, UserId,
+    power_levels::{default_power_level, NotificationPowerLevels, PowerLevelParsing},
+    OwnedUserId, RoomVersionId, UserId,
 };
 
 /// The content of an `m.room.power_levels` event.
@@ -130,6 +131,31 @@ impl RoomPowerLevelsEventContent {
             notifications: NotificationPowerLevels::default(),
         }
     }
+
+    /// Deserialize the given JSON content with the given parsing rules for power levels.
+    ///
+    /// The `Deserialize` implementation of this type always uses [`PowerLevelParsing::Lenient`].
+    pub fn deserialize_with_parsing(
+        content: &RawJsonValue,
+        parsing: PowerLevelParsing,
+    ) -> serde_json::Result<Self> {
+        match parsing {
+            PowerLevelParsing::Lenient => serde_json::from_str(content.get()),
+            PowerLevelParsing::Strict => {
+                serde_json::from_str::<IntRoomPowerLevelsEventContent>(content.get())
+                    .map(Into::into)
+            }
+        }
+    }
+
+    /// Deserialize the given JSON content with the parsing rules for power levels of the given
+    /// room version.
+    pub fn deserialize_for_room_version(
+        content: &RawJsonValue,
+        room_version: &RoomVersionId,
+    ) -> serde_json::Result<Self> {
+        Self::deserialize_with_parsing(content, PowerLevelParsing::for_room_version(room_version))
+    }
 }
 
 impl Default for RoomPowerLevelsEventContent {
@@ -346,6 +372,89 @@ pub enum NotificationPowerLevelType {
     Room,
 }
 
+/// `RoomPowerLevelsEventContent` that only accepts integers.
+#[derive(Deserialize)]
+struct IntRoomPowerLevelsEventContent {
+    #[serde(default = "default_power_level")]
+    ban: Int,
+
+    #[serde(default)]
+    events: BTreeMap<TimelineEventType, Int>,
+
+    #[serde(default)]
+    events_default: Int,
+
+    #[serde(default)]
+    invite: Int,
+
+    #[serde(default = "default_power_level")]
+    kick: Int,
+
+    #[serde(default = "default_power_level")]
+    redact: Int,
+
+    #[serde(default = "default_power_level")]
+    state_default: Int,
+
+    #[serde(default)]
+    users: BTreeMap<OwnedUserId, Int>,
+
+    #[serde(default)]
+    users_default: Int,
+
+    #[serde(default)]
+    notifications: IntNotificationPowerLevels,
+}
+
+impl From<IntRoomPowerLevelsEventContent> for RoomPowerLevelsEventContent {
+    fn from(int_pl: IntRoomPowerLevelsEventContent) -> Self {
+        let IntRoomPowerLevelsEventContent {
+            ban,
+            events,
+            events_default,
+            invite,
+            kick,
+            redact,
+            state_default,
+            users,
+            users_default,
+            notifications,
+        } = int_pl;
+
+        Self {
+            ban,
+            events,
+            events_default,
+            invite,
+            kick,
+            redact,
+            state_default,
+            users,
+            users_default,
+            notifications: NotificationPowerLevels {
+                room: notifications.room,
+                custom: notifications.custom,
+            },
+        }
+    }
+}
+
+/// `NotificationPowerLevels` that only accepts integers.
+#[derive(Deserialize)]
+struct IntNotificationPowerLevels {
+    #[serde(default = "default_power_level")]
+    room: Int,
+
+    #[serde(flatten)]
+    custom: BTreeMap<String, Int>,
+}
+
+impl Default for IntNotificationPowerLevels {
+    fn default() -> Self {
+        Self { room: default_power_level(), custom: BTreeMap::new() }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -353,10 +462,13 @@ mod tests {
     use assign::assign;
     use js_int::int;
     use maplit::btreemap;
-    use serde_json::{json, to_value as to_json_value};
+    use serde_json::{json, to_value as to_json_value, value::to_raw_value as to_raw_json_value};
 
-    use super::{default_power_level, NotificationPowerLevels, RoomPowerLevelsEventContent};
-    use crate::user_id;
+    use super::{
+        default_power_level, NotificationPowerLevels, PowerLevelParsing,
+        RoomPowerLevelsEventContent,
+    };
+    use crate::{user_id, RoomVersionId};
 
     #[test]
     fn serialization_with_optional_fields_as_none() {
@@ -423,4 +535,68 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn custom_notification_levels() {
+        let json = json!({
+            "notifications": {
+                "room": 20,
+                "org.example.ping": "80",
+            },
+        });
+
+        let content: RoomPowerLevelsEventContent = serde_json::from_value(json).unwrap();
+        let notifications = &content.notifications;
+        assert_eq!(notifications.room, int!(20));
+        assert_eq!(notifications.get("org.example.ping"), Some(&int!(80)));
+        assert_eq!(notifications.get("org.example.unknown"), None);
+        assert_eq!(
+            notifications.iter().collect::<Vec<_>>(),
+            [("room", &int!(20)), ("org.example.ping", &int!(80))]
+        );
+
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "notifications": {
+                    "room": 20,
+                    "org.example.ping": 80,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_for_room_version() {
+        let json = to_raw_json_value(&json!({
+            "ban": "60",
+            "users": { "@carl:example.com": "100" },
+        }))
+        .unwrap();
+
+        let content =
+            RoomPowerLevelsEventContent::deserialize_for_room_version(&json, &RoomVersionId::V9)
+                .unwrap();
+        assert_eq!(content.ban, int!(60));
+        assert_eq!(content.users.get(user_id!("@carl:example.com")), Some(&int!(100)));
+
+        RoomPowerLevelsEventContent::deserialize_for_room_version(&json, &RoomVersionId::V10)
+            .unwrap_err();
+
+        let json = to_raw_json_value(&json!({ "notifications": { "room": "20" } })).unwrap();
+        RoomPowerLevelsEventContent::deserialize_with_parsing(&json, PowerLevelParsing::Strict)
+            .unwrap_err();
+
+        let json = to_raw_json_value(&json!({
+            "ban": 60,
+            "notifications": { "room": 20 },
+        }))
+        .unwrap();
+        let content =
+            RoomPowerLevelsEventContent::deserialize_for_room_version(&json, &RoomVersionId::V10)
+                .unwrap();
+        assert_eq!(content.ban, int!(60));
+        assert_eq!(content.kick, int!(50));
+        assert_eq!(content.notifications.room, int!(20));
+    }
 }
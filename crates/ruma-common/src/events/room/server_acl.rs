@@ -4,9 +4,8 @@
 
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
-use wildmatch::WildMatch;
 
-use crate::{events::EmptyStateKey, ServerName};
+use crate::{events::EmptyStateKey, glob, ServerName};
 
 /// The content of an `m.room.server_acl` event.
 ///
@@ -50,15 +49,18 @@ impl RoomServerAclEventContent {
     }
 
     /// Returns true if and only if the server is allowed by the ACL rules.
+    ///
+    /// The port of the server name is ignored, and the host is matched against the patterns
+    /// without taking case into account, like domain names.
     pub fn is_allowed(&self, server_name: &ServerName) -> bool {
         if !self.allow_ip_literals && server_name.is_ip_literal() {
             return false;
         }
 
-        let host = server_name.host();
+        let host = server_name.host().to_lowercase();
+        let matches = |pattern: &String| glob::matches(&pattern.to_lowercase(), &host);
 
-        self.deny.iter().all(|d| !WildMatch::new(d).matches(host))
-            && self.allow.iter().any(|a| WildMatch::new(a).matches(host))
+        !self.deny.iter().any(matches) && self.allow.iter().any(matches)
     }
 }
 
@@ -173,4 +175,17 @@ mod tests {
         assert!(!acl_event.is_allowed(server_name!("[2001:db8:1234::2]")));
         assert!(acl_event.is_allowed(server_name!("[2001:db8:1234::1]")));
     }
+
+    #[test]
+    fn acl_case_insensitive() {
+        let acl_event = RoomServerAclEventContent {
+            allow_ip_literals: false,
+            allow: vec!["*.Example.org".to_owned()],
+            deny: vec!["EVIL.example.org".to_owned()],
+        };
+        assert!(acl_event.is_allowed(server_name!("matrix.example.org")));
+        assert!(acl_event.is_allowed(server_name!("MATRIX.EXAMPLE.ORG")));
+        assert!(!acl_event.is_allowed(server_name!("evil.example.org")));
+        assert!(!acl_event.is_allowed(server_name!("Evil.Example.Org:8448")));
+    }
 }
@@ -50,6 +50,17 @@ impl RoomThirdPartyInviteEventContent {
     pub fn new(display_name: String, key_validity_url: String, public_key: Base64) -> Self {
         Self { display_name, key_validity_url, public_key, public_keys: None }
     }
+
+    /// All the public keys with which the token may be signed.
+    ///
+    /// This returns the keys in `public_keys` and the key in `public_key`.
+    pub fn all_public_keys(&self) -> impl Iterator<Item = &Base64> {
+        self.public_keys
+            .iter()
+            .flatten()
+            .map(|key| &key.public_key)
+            .chain(std::iter::once(&self.public_key))
+    }
 }
 
 /// A public key for signing a third party invite token.
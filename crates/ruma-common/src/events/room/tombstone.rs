@@ -32,3 +32,56 @@ impl RoomTombstoneEventContent {
         Self { body, replacement_room }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::RoomTombstoneEventContent;
+    use crate::{
+        events::{AnySyncStateEvent, StateEventType, SyncStateEvent},
+        room_id,
+    };
+
+    #[test]
+    fn serialization() {
+        let content = RoomTombstoneEventContent::new(
+            "This room has been replaced".to_owned(),
+            room_id!("!newroom:example.org").to_owned(),
+        );
+
+        assert_eq!(
+            to_json_value(content).unwrap(),
+            json!({
+                "body": "This room has been replaced",
+                "replacement_room": "!newroom:example.org",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialization() {
+        let json = json!({
+            "content": {
+                "body": "This room has been replaced",
+                "replacement_room": "!newroom:example.org",
+            },
+            "event_id": "$143273582443PhrSn:example.org",
+            "origin_server_ts": 1_432_735_824_653_u64,
+            "sender": "@example:example.org",
+            "state_key": "",
+            "type": "m.room.tombstone",
+        });
+
+        let event = from_json_value::<AnySyncStateEvent>(json).unwrap();
+        assert_eq!(event.event_type(), StateEventType::RoomTombstone);
+
+        let event = assert_matches!(
+            event,
+            AnySyncStateEvent::RoomTombstone(SyncStateEvent::Original(event)) => event
+        );
+        assert_eq!(event.content.body, "This room has been replaced");
+        assert_eq!(event.content.replacement_room, "!newroom:example.org");
+    }
+}
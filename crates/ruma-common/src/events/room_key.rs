@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{EventEncryptionAlgorithm, OwnedRoomId};
 
+pub mod withheld;
+
 /// The content of an `m.room_key` event.
 ///
 /// Typically encrypted as an `m.room.encrypted` event, then sent as a to-device event.
@@ -27,6 +29,18 @@ pub struct ToDeviceRoomKeyEventContent {
 
     /// The key to be exchanged.
     pub session_key: String,
+
+    /// Whether this key can be shared with users invited to the room after it was used, as
+    /// defined in [MSC3061].
+    ///
+    /// [MSC3061]: https://github.com/matrix-org/matrix-spec-proposals/pull/3061
+    #[cfg(feature = "unstable-msc3061")]
+    #[serde(
+        default,
+        rename = "org.matrix.msc3061.shared_history",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub shared_history: bool,
 }
 
 impl ToDeviceRoomKeyEventContent {
@@ -38,7 +52,14 @@ impl ToDeviceRoomKeyEventContent {
         session_id: String,
         session_key: String,
     ) -> Self {
-        Self { algorithm, room_id, session_id, session_key }
+        Self {
+            algorithm,
+            room_id,
+            session_id,
+            session_key,
+            #[cfg(feature = "unstable-msc3061")]
+            shared_history: false,
+        }
     }
 }
 
@@ -51,12 +72,34 @@ mod tests {
 
     #[test]
     fn serialization() {
-        let content = ToDeviceRoomKeyEventContent {
-            algorithm: EventEncryptionAlgorithm::MegolmV1AesSha2,
-            room_id: room_id!("!testroomid:example.org").to_owned(),
-            session_id: "SessId".into(),
-            session_key: "SessKey".into(),
-        };
+        let content = ToDeviceRoomKeyEventContent::new(
+            EventEncryptionAlgorithm::MegolmV1AesSha2,
+            room_id!("!testroomid:example.org").to_owned(),
+            "SessId".into(),
+            "SessKey".into(),
+        );
+
+        assert_eq!(
+            to_json_value(content).unwrap(),
+            json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "room_id": "!testroomid:example.org",
+                "session_id": "SessId",
+                "session_key": "SessKey",
+            })
+        );
+    }
+
+    #[cfg(feature = "unstable-msc3061")]
+    #[test]
+    fn shared_history_serialization() {
+        let mut content = ToDeviceRoomKeyEventContent::new(
+            EventEncryptionAlgorithm::MegolmV1AesSha2,
+            room_id!("!testroomid:example.org").to_owned(),
+            "SessId".into(),
+            "SessKey".into(),
+        );
+        content.shared_history = true;
 
         assert_eq!(
             to_json_value(content).unwrap(),
@@ -65,6 +108,7 @@ mod tests {
                 "room_id": "!testroomid:example.org",
                 "session_id": "SessId",
                 "session_key": "SessKey",
+                "org.matrix.msc3061.shared_history": true,
             })
         );
     }
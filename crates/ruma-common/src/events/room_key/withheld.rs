@@ -0,0 +1,175 @@
+//! Types for the [`m.room_key.withheld`] event.
+//!
+//! [`m.room_key.withheld`]: https://spec.matrix.org/v1.4/client-server-api/#mroom_keywithheld
+
+use ruma_macros::EventContent;
+use serde::{Deserialize, Serialize};
+
+use crate::{serde::StringEnum, EventEncryptionAlgorithm, OwnedRoomId, PrivOwnedStr};
+
+/// The content of an `m.room_key.withheld` event.
+///
+/// Typically sent unencrypted as a to-device event, to indicate that the sender will not share a
+/// room key with the recipient.
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[ruma_event(type = "m.room_key.withheld", kind = ToDevice)]
+pub struct ToDeviceRoomKeyWithheldEventContent {
+    /// The encryption algorithm the key that is withheld is to be used with.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// The reason why the key is withheld.
+    pub code: WithheldCode,
+
+    /// A human-readable explanation of why the key is withheld.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// The room for the key that is withheld.
+    ///
+    /// Required if `code` is not [`WithheldCode::NoOlm`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_id: Option<OwnedRoomId>,
+
+    /// The unpadded base64-encoded Curve25519 key of the sender of the event.
+    pub sender_key: String,
+
+    /// The ID of the session that the key is for.
+    ///
+    /// Required if `code` is not [`WithheldCode::NoOlm`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+impl ToDeviceRoomKeyWithheldEventContent {
+    /// Creates a new `ToDeviceRoomKeyWithheldEventContent` with the given algorithm, code and
+    /// sender key.
+    ///
+    /// The `room_id` and `session_id` must be set, unless the code is [`WithheldCode::NoOlm`].
+    pub fn new(
+        algorithm: EventEncryptionAlgorithm,
+        code: WithheldCode,
+        sender_key: String,
+    ) -> Self {
+        Self { algorithm, code, reason: None, room_id: None, sender_key, session_id: None }
+    }
+
+    /// Creates a new `ToDeviceRoomKeyWithheldEventContent` for the session with the given
+    /// algorithm, code, sender key, room ID and session ID.
+    pub fn for_session(
+        algorithm: EventEncryptionAlgorithm,
+        code: WithheldCode,
+        sender_key: String,
+        room_id: OwnedRoomId,
+        session_id: String,
+    ) -> Self {
+        Self {
+            algorithm,
+            code,
+            reason: None,
+            room_id: Some(room_id),
+            sender_key,
+            session_id: Some(session_id),
+        }
+    }
+}
+
+/// The reason why a room key is withheld.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, Debug, PartialEq, Eq, StringEnum)]
+#[non_exhaustive]
+pub enum WithheldCode {
+    /// The user or device was blacklisted.
+    #[ruma_enum(rename = "m.blacklisted")]
+    Blacklisted,
+
+    /// The user or device was not verified, and the sender is only sharing keys with verified
+    /// users or devices.
+    #[ruma_enum(rename = "m.unverified")]
+    Unverified,
+
+    /// The user or device is not allowed to have the key.
+    ///
+    /// For example, this could be sent in response to a key request if the user or device was
+    /// not in the room when the original message was sent.
+    #[ruma_enum(rename = "m.unauthorised")]
+    Unauthorised,
+
+    /// The sender was unable to retrieve the key, in response to a key request.
+    #[ruma_enum(rename = "m.unavailable")]
+    Unavailable,
+
+    /// An Olm session could not be established.
+    ///
+    /// This may happen, for example, if the sender was unable to obtain a one-time key from the
+    /// recipient.
+    #[ruma_enum(rename = "m.no_olm")]
+    NoOlm,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{ToDeviceRoomKeyWithheldEventContent, WithheldCode};
+    use crate::{room_id, EventEncryptionAlgorithm};
+
+    #[test]
+    fn serialization() {
+        let mut content = ToDeviceRoomKeyWithheldEventContent::for_session(
+            EventEncryptionAlgorithm::MegolmV1AesSha2,
+            WithheldCode::Unverified,
+            "SenderKey".into(),
+            room_id!("!testroomid:example.org").to_owned(),
+            "SessId".into(),
+        );
+        content.reason = Some("Device not verified".into());
+
+        assert_eq!(
+            to_json_value(content).unwrap(),
+            json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "code": "m.unverified",
+                "reason": "Device not verified",
+                "room_id": "!testroomid:example.org",
+                "sender_key": "SenderKey",
+                "session_id": "SessId",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialization() {
+        let json = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "code": "m.no_olm",
+            "sender_key": "SenderKey",
+        });
+
+        assert_matches!(
+            from_json_value::<ToDeviceRoomKeyWithheldEventContent>(json),
+            Ok(ToDeviceRoomKeyWithheldEventContent {
+                algorithm: EventEncryptionAlgorithm::MegolmV1AesSha2,
+                code: WithheldCode::NoOlm,
+                reason: None,
+                room_id: None,
+                sender_key,
+                session_id: None,
+            }) => {
+                assert_eq!(sender_key, "SenderKey");
+            }
+        );
+
+        let json = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "code": "org.example.custom",
+            "sender_key": "SenderKey",
+        });
+        let content = from_json_value::<ToDeviceRoomKeyWithheldEventContent>(json).unwrap();
+        assert_eq!(content.code.as_str(), "org.example.custom");
+    }
+}
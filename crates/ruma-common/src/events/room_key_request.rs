@@ -44,6 +44,45 @@ impl ToDeviceRoomKeyRequestEventContent {
     ) -> Self {
         Self { action, body, requesting_device_id, request_id }
     }
+
+    /// Creates a new `ToDeviceRoomKeyRequestEventContent` to request the key described by the
+    /// given body.
+    pub fn request(
+        body: RequestedKeyInfo,
+        requesting_device_id: OwnedDeviceId,
+        request_id: OwnedTransactionId,
+    ) -> Self {
+        Self::new(Action::Request, Some(body), requesting_device_id, request_id)
+    }
+
+    /// Creates a new `ToDeviceRoomKeyRequestEventContent` to cancel the request with the given
+    /// device ID and request ID.
+    pub fn cancellation(
+        requesting_device_id: OwnedDeviceId,
+        request_id: OwnedTransactionId,
+    ) -> Self {
+        Self::new(Action::CancelRequest, None, requesting_device_id, request_id)
+    }
+
+    /// Creates the cancellation of this request.
+    pub fn to_cancellation(&self) -> Self {
+        Self::cancellation(self.requesting_device_id.clone(), self.request_id.clone())
+    }
+
+    /// Whether this event is about the same request as the given event.
+    ///
+    /// Requests are correlated by their `requesting_device_id` and `request_id`.
+    pub fn is_same_request(&self, other: &Self) -> bool {
+        self.requesting_device_id == other.requesting_device_id
+            && self.request_id == other.request_id
+    }
+
+    /// Whether this event cancels the given request.
+    pub fn cancels(&self, request: &Self) -> bool {
+        self.action == Action::CancelRequest
+            && request.action == Action::Request
+            && self.is_same_request(request)
+    }
 }
 
 /// A new key request or a cancellation of a previous request.
@@ -94,3 +133,60 @@ impl RequestedKeyInfo {
         Self { algorithm, room_id, sender_key, session_id }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{Action, RequestedKeyInfo, ToDeviceRoomKeyRequestEventContent};
+    use crate::{device_id, room_id, EventEncryptionAlgorithm};
+
+    #[test]
+    fn request_and_cancellation() {
+        let request = ToDeviceRoomKeyRequestEventContent::request(
+            RequestedKeyInfo::new(
+                EventEncryptionAlgorithm::MegolmV1AesSha2,
+                room_id!("!room:example.org").to_owned(),
+                "sender_key".into(),
+                "session_id".into(),
+            ),
+            device_id!("DEVICE").to_owned(),
+            "req".into(),
+        );
+
+        assert_eq!(
+            to_json_value(&request).unwrap(),
+            json!({
+                "action": "request",
+                "body": {
+                    "algorithm": "m.megolm.v1.aes-sha2",
+                    "room_id": "!room:example.org",
+                    "sender_key": "sender_key",
+                    "session_id": "session_id",
+                },
+                "requesting_device_id": "DEVICE",
+                "request_id": "req",
+            })
+        );
+
+        let cancellation: ToDeviceRoomKeyRequestEventContent = from_json_value(json!({
+            "action": "request_cancellation",
+            "requesting_device_id": "DEVICE",
+            "request_id": "req",
+        }))
+        .unwrap();
+
+        assert_eq!(cancellation.action, Action::CancelRequest);
+        assert!(cancellation.body.is_none());
+        assert!(cancellation.is_same_request(&request));
+        assert!(cancellation.cancels(&request));
+        assert!(!request.cancels(&cancellation));
+        assert!(request.to_cancellation().cancels(&request));
+
+        let other = ToDeviceRoomKeyRequestEventContent::cancellation(
+            device_id!("DEVICE").to_owned(),
+            "other".into(),
+        );
+        assert!(!other.cancels(&request));
+    }
+}
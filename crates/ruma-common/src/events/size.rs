@@ -0,0 +1,144 @@
+//! Helpers to estimate the size of events before sending them.
+//!
+//! Homeservers reject events that are larger than [`MAX_PDU_SIZE`] once they are formatted for
+//! federation, with an `M_TOO_LARGE` error.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::EventContent;
+use crate::{RoomId, UserId};
+
+/// The maximum size of an event formatted for federation, in bytes.
+pub const MAX_PDU_SIZE: usize = 65_536;
+
+/// The estimated size of the fields that are added by the homeserver when an event is formatted
+/// for federation, in bytes.
+///
+/// This covers the event ID, `auth_events`, `prev_events`, `depth`, `hashes`, `signatures`,
+/// `origin` and `origin_server_ts`, with some margin.
+pub const ESTIMATED_PDU_OVERHEAD: usize = 2_048;
+
+/// A change made to the content of an event to reduce its size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum SizeWarning {
+    /// The formatted body was removed.
+    FormattedBodyRemoved,
+
+    /// The body was truncated.
+    BodyTruncated {
+        /// The original size of the body, in bytes.
+        original_len: usize,
+
+        /// The size of the truncated body, in bytes.
+        len: usize,
+    },
+
+    /// Mentions of users were removed.
+    MentionsRemoved {
+        /// The number of mentions that were removed.
+        count: usize,
+    },
+
+    /// The event is still too large after trimming its content.
+    StillTooLarge {
+        /// The estimated size of the event, in bytes.
+        estimated_size: usize,
+    },
+}
+
+/// Estimate the size of an event with the given content, once it is formatted for federation.
+///
+/// The size is computed from the serialized content, room ID, sender, event type and state key,
+/// plus [`ESTIMATED_PDU_OVERHEAD`].
+pub fn estimate_pdu_size<C>(
+    content: &C,
+    room_id: &RoomId,
+    sender: &UserId,
+    state_key: Option<&str>,
+) -> serde_json::Result<usize>
+where
+    C: EventContent,
+    C::EventType: fmt::Display,
+{
+    #[derive(Serialize)]
+    struct PartialPdu<'a, C> {
+        content: &'a C,
+        room_id: &'a RoomId,
+        sender: &'a UserId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        state_key: Option<&'a str>,
+        #[serde(rename = "type")]
+        event_type: String,
+    }
+
+    let pdu = PartialPdu {
+        content,
+        room_id,
+        sender,
+        state_key,
+        event_type: content.event_type().to_string(),
+    };
+
+    Ok(serde_json::to_string(&pdu)?.len() + ESTIMATED_PDU_OVERHEAD)
+}
+
+/// Truncate the given string to at most `max_len` bytes, at a character boundary.
+pub(crate) fn truncate_at_char_boundary(s: &mut String, max_len: usize) {
+    if s.len() <= max_len {
+        return;
+    }
+
+    let mut len = max_len;
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    s.truncate(len);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{estimate_pdu_size, truncate_at_char_boundary, ESTIMATED_PDU_OVERHEAD};
+    use crate::{
+        events::room::{message::RoomMessageEventContent, topic::RoomTopicEventContent},
+        room_id, user_id,
+    };
+
+    #[test]
+    fn estimate_size() {
+        let room_id = room_id!("!room:example.org");
+        let sender = user_id!("@alice:example.org");
+
+        let size =
+            estimate_pdu_size(&RoomMessageEventContent::text_plain("Hello"), room_id, sender, None)
+                .unwrap();
+        #[cfg(not(feature = "unstable-msc1767"))]
+        let expected = r#"{"content":{"msgtype":"m.text","body":"Hello"},"room_id":"!room:example.org","sender":"@alice:example.org","type":"m.room.message"}"#;
+        #[cfg(feature = "unstable-msc1767")]
+        let expected = r#"{"content":{"msgtype":"m.text","body":"Hello","org.matrix.msc1767.text":"Hello"},"room_id":"!room:example.org","sender":"@alice:example.org","type":"m.room.message"}"#;
+        assert_eq!(size, expected.len() + ESTIMATED_PDU_OVERHEAD);
+
+        let size = estimate_pdu_size(
+            &RoomTopicEventContent::new("Topic".into()),
+            room_id,
+            sender,
+            Some(""),
+        )
+        .unwrap();
+        let expected = r#"{"content":{"topic":"Topic"},"room_id":"!room:example.org","sender":"@alice:example.org","state_key":"","type":"m.room.topic"}"#;
+        assert_eq!(size, expected.len() + ESTIMATED_PDU_OVERHEAD);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut s = "aé".to_owned();
+        truncate_at_char_boundary(&mut s, 2);
+        assert_eq!(s, "a");
+
+        let mut s = "abc".to_owned();
+        truncate_at_char_boundary(&mut s, 5);
+        assert_eq!(s, "abc");
+    }
+}
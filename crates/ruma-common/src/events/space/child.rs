@@ -2,10 +2,12 @@
 //!
 //! [`m.space.child`]: https://spec.matrix.org/v1.4/client-server-api/#mspacechild
 
+use std::cmp::Ordering;
+
 use ruma_macros::{Event, EventContent};
 use serde::{Deserialize, Serialize};
 
-use crate::{MilliSecondsSinceUnixEpoch, OwnedRoomId, OwnedServerName, OwnedUserId};
+use crate::{MilliSecondsSinceUnixEpoch, OwnedRoomId, OwnedServerName, OwnedUserId, RoomId};
 
 /// The content of an `m.space.child` event.
 ///
@@ -49,6 +51,98 @@ impl SpaceChildEventContent {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The `order` of this child, if it is valid.
+    ///
+    /// An `order` is only valid if it consists of at most 50 ascii characters in the range `\x20`
+    /// (space) to `\x7E` (`~`). Invalid `order`s must be ignored.
+    pub fn valid_order(&self) -> Option<&str> {
+        self.order
+            .as_deref()
+            .filter(|order| order.len() <= 50 && order.bytes().all(|b| (0x20..=0x7E).contains(&b)))
+    }
+}
+
+/// The sort key of a child of a space.
+///
+/// Its implementation of [`Ord`] follows the [ordering rules] of the children of a space, so a
+/// list of children can be sorted with it:
+///
+/// ```
+/// use ruma_common::{
+///     events::space::child::{SpaceChildEventContent, SpaceChildOrd},
+///     room_id, MilliSecondsSinceUnixEpoch,
+/// };
+///
+/// let mut ordered = SpaceChildEventContent::new();
+/// ordered.order = Some("a".to_owned());
+/// let unordered = SpaceChildEventContent::new();
+///
+/// let mut children = vec![
+///     (room_id!("!b:example.org"), unordered, MilliSecondsSinceUnixEpoch::now()),
+///     (room_id!("!a:example.org"), ordered, MilliSecondsSinceUnixEpoch::now()),
+/// ];
+/// children.sort_by(|(a_id, a, a_ts), (b_id, b, b_ts)| {
+///     SpaceChildOrd::new(a, a_id, *a_ts).cmp(&SpaceChildOrd::new(b, b_id, *b_ts))
+/// });
+///
+/// assert_eq!(children[0].0, "!a:example.org");
+/// ```
+///
+/// [ordering rules]: https://spec.matrix.org/v1.4/client-server-api/#ordering-of-children-within-a-space
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct SpaceChildOrd<'a> {
+    /// The valid `order` of the child, if any.
+    pub order: Option<&'a str>,
+
+    /// The `origin_server_ts` of the `m.room.create` event of the child.
+    pub create_origin_server_ts: MilliSecondsSinceUnixEpoch,
+
+    /// The ID of the child room.
+    pub room_id: &'a RoomId,
+}
+
+impl<'a> SpaceChildOrd<'a> {
+    /// Creates a new `SpaceChildOrd` from the given `m.space.child` content, the ID of the child
+    /// room and the `origin_server_ts` of its `m.room.create` event.
+    pub fn new(
+        content: &'a SpaceChildEventContent,
+        room_id: &'a RoomId,
+        create_origin_server_ts: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        Self { order: content.valid_order(), create_origin_server_ts, room_id }
+    }
+}
+
+impl PartialEq for SpaceChildOrd<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SpaceChildOrd<'_> {}
+
+impl PartialOrd for SpaceChildOrd<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SpaceChildOrd<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Children with an `order` come first, sorted by the Unicode codepoints of their `order`.
+        let order = match (self.order, other.order) {
+            (Some(order), Some(other_order)) => order.cmp(other_order),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+
+        order
+            .then_with(|| self.create_origin_server_ts.cmp(&other.create_origin_server_ts))
+            .then_with(|| self.room_id.cmp(other.room_id))
+    }
 }
 
 /// An `m.space.child` event represented as a Stripped State Event with an added `origin_server_ts`
@@ -74,8 +168,8 @@ mod tests {
     use js_int::uint;
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{HierarchySpaceChildEvent, SpaceChildEventContent};
-    use crate::{server_name, MilliSecondsSinceUnixEpoch};
+    use super::{HierarchySpaceChildEvent, SpaceChildEventContent, SpaceChildOrd};
+    use crate::{room_id, server_name, MilliSecondsSinceUnixEpoch};
 
     #[test]
     fn space_child_serialization() {
@@ -126,4 +220,60 @@ mod tests {
         assert_eq!(ev.content.order, None);
         assert!(!ev.content.suggested);
     }
+
+    #[test]
+    fn valid_order() {
+        let mut content = SpaceChildEventContent::new();
+        assert_eq!(content.valid_order(), None);
+
+        content.order = Some("a ~".to_owned());
+        assert_eq!(content.valid_order(), Some("a ~"));
+
+        content.order = Some("é".to_owned());
+        assert_eq!(content.valid_order(), None);
+
+        content.order = Some("\n".to_owned());
+        assert_eq!(content.valid_order(), None);
+
+        content.order = Some("a".repeat(51));
+        assert_eq!(content.valid_order(), None);
+    }
+
+    #[test]
+    fn space_children_ordering() {
+        let with_order = |order: &str| SpaceChildEventContent {
+            order: Some(order.to_owned()),
+            ..SpaceChildEventContent::new()
+        };
+        let old = MilliSecondsSinceUnixEpoch(uint!(1));
+        let new = MilliSecondsSinceUnixEpoch(uint!(2));
+
+        let a = with_order("a");
+        let b = with_order("b");
+        let invalid = with_order("\u{7F}");
+        let none = SpaceChildEventContent::new();
+
+        let mut children = [
+            SpaceChildOrd::new(&none, room_id!("!a:example.org"), new),
+            SpaceChildOrd::new(&invalid, room_id!("!c:example.org"), old),
+            SpaceChildOrd::new(&none, room_id!("!b:example.org"), old),
+            SpaceChildOrd::new(&b, room_id!("!d:example.org"), old),
+            SpaceChildOrd::new(&a, room_id!("!f:example.org"), new),
+            SpaceChildOrd::new(&a, room_id!("!e:example.org"), new),
+        ];
+        children.sort();
+
+        let room_ids: Vec<_> = children.iter().map(|child| child.room_id.as_str()).collect();
+        assert_eq!(
+            room_ids,
+            [
+                "!e:example.org",
+                "!f:example.org",
+                "!d:example.org",
+                "!b:example.org",
+                "!c:example.org",
+                "!a:example.org",
+            ]
+        );
+    }
 }
@@ -11,7 +11,13 @@ use super::{
     image::{ImageContent, ThumbnailContent},
     message::MessageContent,
 };
-use crate::{events::room::ImageInfo, OwnedMxcUri};
+use crate::{
+    events::{
+        relation::Relation,
+        room::{EncryptedFile, ImageInfo, MediaSource},
+    },
+    OwnedMxcUri,
+};
 
 /// The content of an `m.sticker` event.
 ///
@@ -23,7 +29,7 @@ use crate::{events::room::ImageInfo, OwnedMxcUri};
 /// [`message`]: super::message
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[ruma_event(type = "m.sticker", kind = MessageLike)]
+#[ruma_event(type = "m.sticker", kind = MessageLike, without_relation)]
 pub struct StickerEventContent {
     /// A textual representation or associated description of the sticker image.
     ///
@@ -31,11 +37,15 @@ pub struct StickerEventContent {
     /// describe the sticker.
     pub body: String,
 
-    /// Metadata about the image referred to in `url` including a thumbnail representation.
+    /// Metadata about the image referred to in `source` including a thumbnail representation.
     pub info: ImageInfo,
 
-    /// The URL to the sticker image.
-    pub url: OwnedMxcUri,
+    /// The source of the sticker image.
+    ///
+    /// This is serialized as the `url` field for unencrypted stickers, and as the `file` field for
+    /// encrypted stickers.
+    #[serde(flatten)]
+    pub source: MediaSource,
 
     /// Extensible-event text representation of the message.
     ///
@@ -46,7 +56,7 @@ pub struct StickerEventContent {
 
     /// Extensible-event file content of the message.
     ///
-    /// If present, this should be preferred over the `url`, `file` and `info` fields.
+    /// If present, this should be preferred over the `source` and `info` fields.
     #[cfg(feature = "unstable-msc3552")]
     #[serde(
         rename = "org.matrix.msc1767.file",
@@ -87,20 +97,51 @@ pub struct StickerEventContent {
         skip_serializing_if = "Option::is_none"
     )]
     pub caption: Option<MessageContent>,
+
+    /// Information about related messages.
+    #[serde(
+        flatten,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::events::relation::deserialize_relation"
+    )]
+    pub relates_to: Option<Relation<StickerEventContentWithoutRelation>>,
 }
 
 impl StickerEventContent {
-    /// Creates a new `StickerEventContent` with the given body, image info and URL.
+    /// Creates a new unencrypted `StickerEventContent` with the given body, image info and URL.
     pub fn new(body: String, info: ImageInfo, url: OwnedMxcUri) -> Self {
+        Self::with_source(body, info, MediaSource::Plain(url))
+    }
+
+    /// Creates a new encrypted `StickerEventContent` with the given body, image info and encrypted
+    /// file.
+    pub fn encrypted(body: String, info: ImageInfo, file: EncryptedFile) -> Self {
+        Self::with_source(body, info, MediaSource::Encrypted(Box::new(file)))
+    }
+
+    /// Creates a new `StickerEventContent` with the given body, image info and source.
+    ///
+    /// With the `unstable-msc3552` feature, the extensible-event representation of the sticker is
+    /// built from the body, the image info and the source.
+    pub fn with_source(body: String, info: ImageInfo, source: MediaSource) -> Self {
         Self {
             #[cfg(feature = "unstable-msc3552")]
             message: Some(MessageContent::plain(body.clone())),
             #[cfg(feature = "unstable-msc3552")]
-            file: Some(FileContent::plain(
-                url.clone(),
-                FileContentInfo::from_room_message_content(None, info.mimetype.clone(), info.size)
-                    .map(Box::new),
-            )),
+            file: Some({
+                let file_info = FileContentInfo::from_room_message_content(
+                    None,
+                    info.mimetype.clone(),
+                    info.size,
+                )
+                .map(Box::new);
+                match source.clone().into_extensible_content() {
+                    (url, None) => FileContent::plain(url, file_info),
+                    (url, Some(encryption_info)) => {
+                        FileContent::encrypted(url, encryption_info, file_info)
+                    }
+                }
+            }),
             #[cfg(feature = "unstable-msc3552")]
             image: Some(Box::new(
                 ImageContent::from_room_message_content(info.width, info.height)
@@ -116,7 +157,25 @@ impl StickerEventContent {
             caption: None,
             body,
             info,
-            url,
+            source,
+            relates_to: None,
         }
     }
+
+    /// Creates a new `StickerEventContent` with the given image info and source, and a fallback
+    /// body.
+    ///
+    /// The body is required by clients that don't support stickers, so this generates one that
+    /// mentions the sticker and its dimensions, if they are known.
+    pub fn with_fallback_body(info: ImageInfo, source: MediaSource) -> Self {
+        Self::with_source(body_fallback(&info), info, source)
+    }
+}
+
+/// Generate a textual representation of a sticker from its image info.
+fn body_fallback(info: &ImageInfo) -> String {
+    match (info.width, info.height) {
+        (Some(width), Some(height)) => format!("Sticker ({width}x{height})"),
+        _ => "Sticker".to_owned(),
+    }
 }
@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
 use js_int::Int;
-use serde::Deserialize;
-use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{
+    from_str as from_json_str,
+    value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+};
 
 use super::{
     relation::BundledRelations, room::redaction::RoomRedactionEventContent, StateEventContent,
@@ -183,3 +188,254 @@ pub struct UnsignedRoomRedactionEvent {
     #[serde(default)]
     pub unsigned: MessageLikeUnsigned,
 }
+
+/// Compute the `age` of an event, i.e. the time in milliseconds that elapsed between its
+/// `origin_server_ts` and `now`.
+///
+/// Since `origin_server_ts` is set by the clock of the originating homeserver, the result can be
+/// negative if that clock is ahead of the local one.
+pub fn compute_age(
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    now: MilliSecondsSinceUnixEpoch,
+) -> Int {
+    Int::from(now.get()) - Int::from(origin_server_ts.get())
+}
+
+/// Extra information about an event that is not incorporated into the event's hash, in a form that
+/// is shared between the client-server (including sync) and federation representations of events.
+///
+/// Unlike [`MessageLikeUnsigned`] and [`StateUnsigned`], this type doesn't attempt to deserialize
+/// the nested events and contents, and keeps any unknown keys in [`custom`](Self::custom), so that
+/// it round-trips losslessly.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct UnsignedData {
+    /// The time in milliseconds that has elapsed since the event was sent.
+    ///
+    /// This field is generated by the local homeserver, and may be incorrect if the local time on
+    /// at least one of the two servers is out of sync, which can cause the age to either be
+    /// negative or greater than it actually is.
+    pub age: Option<Int>,
+
+    /// The client-supplied transaction ID, if the client being given the event is the same one
+    /// which sent it.
+    pub transaction_id: Option<OwnedTransactionId>,
+
+    /// Optional previous content of the event, if it is a state event.
+    pub prev_content: Option<Box<RawJsonValue>>,
+
+    /// The event that redacted this event, if any.
+    pub redacted_because: Option<Box<RawJsonValue>>,
+
+    /// [Bundled aggregations] of related child events.
+    ///
+    /// [Bundled aggregations]: https://spec.matrix.org/v1.4/client-server-api/#aggregations
+    pub relations: Option<Raw<BundledRelations>>,
+
+    /// Any other keys of the unsigned data.
+    pub custom: BTreeMap<String, Box<RawJsonValue>>,
+}
+
+impl UnsignedData {
+    const AGE: &'static str = "age";
+    const TRANSACTION_ID: &'static str = "transaction_id";
+    const PREV_CONTENT: &'static str = "prev_content";
+    const REDACTED_BECAUSE: &'static str = "redacted_because";
+    const RELATIONS: &'static str = "m.relations";
+
+    /// Create a new empty `UnsignedData`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `age` field to the time elapsed between `origin_server_ts` and the current time of
+    /// the local clock.
+    pub fn set_age(&mut self, origin_server_ts: MilliSecondsSinceUnixEpoch) {
+        self.age = Some(compute_age(origin_server_ts, MilliSecondsSinceUnixEpoch::now()));
+    }
+
+    /// The time at which the event was sent according to the local clock, given the time at which
+    /// it was received.
+    ///
+    /// This is an alternative to the `origin_server_ts` of the event that doesn't depend on the
+    /// clock of the originating homeserver. Returns `None` if the `age` field is not set or the
+    /// result can't be represented.
+    pub fn local_origin_server_ts(
+        &self,
+        received_at: MilliSecondsSinceUnixEpoch,
+    ) -> Option<MilliSecondsSinceUnixEpoch> {
+        let ts = i64::from(received_at.get()).checked_sub(self.age?.into())?;
+        ts.try_into().ok().map(MilliSecondsSinceUnixEpoch)
+    }
+
+    /// Convert the unsigned data of a PDU to `UnsignedData`.
+    ///
+    /// Returns an error if one of the known fields has an invalid type.
+    pub fn from_pdu_unsigned(
+        mut unsigned: BTreeMap<String, Box<RawJsonValue>>,
+    ) -> serde_json::Result<Self> {
+        fn take<T: de::DeserializeOwned>(
+            map: &mut BTreeMap<String, Box<RawJsonValue>>,
+            key: &str,
+        ) -> serde_json::Result<Option<T>> {
+            map.remove(key).map(|json| from_json_str(json.get())).transpose()
+        }
+
+        Ok(Self {
+            age: take(&mut unsigned, Self::AGE)?,
+            transaction_id: take(&mut unsigned, Self::TRANSACTION_ID)?,
+            prev_content: unsigned.remove(Self::PREV_CONTENT),
+            redacted_because: unsigned.remove(Self::REDACTED_BECAUSE),
+            relations: unsigned.remove(Self::RELATIONS).map(Raw::from_json),
+            custom: unsigned,
+        })
+    }
+
+    /// Convert this `UnsignedData` to the unsigned data of a PDU.
+    pub fn into_pdu_unsigned(self) -> BTreeMap<String, Box<RawJsonValue>> {
+        let mut unsigned = self.custom;
+
+        if let Some(age) = self.age {
+            unsigned.insert(Self::AGE.to_owned(), to_raw_json_value(&age).unwrap());
+        }
+        if let Some(transaction_id) = self.transaction_id {
+            unsigned.insert(
+                Self::TRANSACTION_ID.to_owned(),
+                to_raw_json_value(&transaction_id).unwrap(),
+            );
+        }
+        if let Some(prev_content) = self.prev_content {
+            unsigned.insert(Self::PREV_CONTENT.to_owned(), prev_content);
+        }
+        if let Some(redacted_because) = self.redacted_because {
+            unsigned.insert(Self::REDACTED_BECAUSE.to_owned(), redacted_because);
+        }
+        if let Some(relations) = self.relations {
+            unsigned.insert(Self::RELATIONS.to_owned(), relations.into_json());
+        }
+
+        unsigned
+    }
+}
+
+impl CanBeEmpty for UnsignedData {
+    /// Whether this unsigned data is empty (all fields are `None` and there are no custom keys).
+    fn is_empty(&self) -> bool {
+        self.age.is_none()
+            && self.transaction_id.is_none()
+            && self.prev_content.is_none()
+            && self.redacted_because.is_none()
+            && self.relations.is_none()
+            && self.custom.is_empty()
+    }
+}
+
+impl<'de> Deserialize<'de> for UnsignedData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let unsigned = BTreeMap::<String, Box<RawJsonValue>>::deserialize(deserializer)?;
+        Self::from_pdu_unsigned(unsigned).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for UnsignedData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+
+        if let Some(age) = &self.age {
+            map.serialize_entry(Self::AGE, age)?;
+        }
+        if let Some(transaction_id) = &self.transaction_id {
+            map.serialize_entry(Self::TRANSACTION_ID, transaction_id)?;
+        }
+        if let Some(prev_content) = &self.prev_content {
+            map.serialize_entry(Self::PREV_CONTENT, prev_content)?;
+        }
+        if let Some(redacted_because) = &self.redacted_because {
+            map.serialize_entry(Self::REDACTED_BECAUSE, redacted_because)?;
+        }
+        if let Some(relations) = &self.relations {
+            map.serialize_entry(Self::RELATIONS, relations)?;
+        }
+        for (key, value) in &self.custom {
+            map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::{int, uint};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{compute_age, UnsignedData};
+    use crate::{serde::CanBeEmpty, MilliSecondsSinceUnixEpoch};
+
+    #[test]
+    fn age() {
+        let origin_server_ts = MilliSecondsSinceUnixEpoch(uint!(1_000));
+
+        assert_eq!(
+            compute_age(origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(1_500))),
+            int!(500)
+        );
+        assert_eq!(
+            compute_age(origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(800))),
+            int!(-200)
+        );
+
+        let mut unsigned = UnsignedData::new();
+        assert_eq!(unsigned.local_origin_server_ts(MilliSecondsSinceUnixEpoch(uint!(1_500))), None);
+
+        unsigned.age = Some(int!(500));
+        assert_eq!(
+            unsigned.local_origin_server_ts(MilliSecondsSinceUnixEpoch(uint!(1_500))),
+            Some(origin_server_ts)
+        );
+        assert_eq!(unsigned.local_origin_server_ts(MilliSecondsSinceUnixEpoch(uint!(200))), None);
+    }
+
+    #[test]
+    fn unsigned_data_roundtrip() {
+        let json = json!({
+            "age": 1234,
+            "transaction_id": "txn",
+            "prev_content": { "membership": "invite" },
+            "m.relations": { "m.thread": { "count": 1 } },
+            "io.ruma.custom": { "foo": "bar" },
+            "replaces_state": "$replaced",
+        });
+
+        let unsigned = from_json_value::<UnsignedData>(json.clone()).unwrap();
+        assert_eq!(unsigned.age, Some(int!(1234)));
+        assert_eq!(unsigned.transaction_id.as_deref().map(|t| t.as_str()), Some("txn"));
+        assert!(unsigned.prev_content.is_some());
+        assert!(unsigned.redacted_because.is_none());
+        assert!(unsigned.relations.is_some());
+        assert_eq!(unsigned.custom.len(), 2);
+        assert_eq!(unsigned.custom["replaces_state"].get(), r#""$replaced""#);
+
+        assert_eq!(to_json_value(&unsigned).unwrap(), json);
+
+        let pdu_unsigned = unsigned.into_pdu_unsigned();
+        assert_eq!(pdu_unsigned.len(), 6);
+        let unsigned = UnsignedData::from_pdu_unsigned(pdu_unsigned).unwrap();
+        assert_eq!(to_json_value(&unsigned).unwrap(), json);
+    }
+
+    #[test]
+    fn unsigned_data_empty() {
+        let unsigned = from_json_value::<UnsignedData>(json!({})).unwrap();
+        assert!(unsigned.is_empty());
+        assert_eq!(to_json_value(&unsigned).unwrap(), json!({}));
+
+        from_json_value::<UnsignedData>(json!({ "age": "1234" })).unwrap_err();
+    }
+}
@@ -0,0 +1,219 @@
+//! Matching of glob patterns, as used in the Matrix specification.
+//!
+//! Globs are used in several places of the specification, like the `pattern` of push rule
+//! conditions, the `allow` and `deny` lists of server ACLs or the `entity` of policy rules. They
+//! support two wildcards:
+//!
+//! * `*` matches zero or more characters,
+//! * `?` matches exactly one character.
+//!
+//! All other characters match themselves. There is no way to escape the wildcards.
+
+use std::fmt;
+
+/// A glob pattern.
+///
+/// Matching is case-sensitive and always applies to the whole value. Use
+/// [`Glob::is_match_case_insensitive`] for case-insensitive matching.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Glob {
+    pattern: Box<str>,
+}
+
+impl Glob {
+    /// Creates a new `Glob` from the given pattern.
+    pub fn new(pattern: impl Into<Box<str>>) -> Self {
+        Self { pattern: pattern.into() }
+    }
+
+    /// The pattern of this glob.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the pattern of this glob contains wildcards.
+    pub fn has_wildcards(&self) -> bool {
+        has_wildcards(&self.pattern)
+    }
+
+    /// Whether the given value matches this glob.
+    pub fn is_match(&self, value: &str) -> bool {
+        matches(&self.pattern, value)
+    }
+
+    /// Whether the given value matches this glob, ignoring case.
+    pub fn is_match_case_insensitive(&self, value: &str) -> bool {
+        matches(&self.pattern.to_lowercase(), &value.to_lowercase())
+    }
+
+    /// Convert this glob to an equivalent regular expression, without anchors.
+    ///
+    /// All the characters that are not wildcards are escaped, and sequences of wildcards are
+    /// simplified to avoid performance issues.
+    pub fn to_regex(&self) -> String {
+        to_regex(&self.pattern)
+    }
+}
+
+impl fmt::Debug for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Glob").field(&self.pattern).finish()
+    }
+}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl From<&str> for Glob {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<String> for Glob {
+    fn from(pattern: String) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// Whether the given value matches the given glob pattern.
+///
+/// The match is case-sensitive and applies to the whole value.
+pub fn matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    let mut p = 0;
+    let mut v = 0;
+    // The position after the last `*` in the pattern, and the position in the value it was
+    // matched at, to backtrack to if the rest of the pattern doesn't match.
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, v));
+            }
+            Some(&c) if c == '?' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` match one more character.
+                Some((star_p, star_v)) => {
+                    p = star_p;
+                    v = star_v + 1;
+                    backtrack = Some((star_p, star_v + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Whether the given glob pattern contains wildcards.
+pub fn has_wildcards(pattern: &str) -> bool {
+    pattern.contains(is_wildcard)
+}
+
+/// Convert the given glob pattern to an equivalent regular expression, without anchors.
+///
+/// All the characters that are not wildcards are escaped, and sequences of wildcards are
+/// simplified to avoid performance issues.
+pub fn to_regex(pattern: &str) -> String {
+    let mut regex = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while !rest.is_empty() {
+        let literal_end = rest.find(is_wildcard).unwrap_or(rest.len());
+        regex.push_str(&regex::escape(&rest[..literal_end]));
+        rest = &rest[literal_end..];
+
+        let wildcards_end = rest.find(|c| !is_wildcard(c)).unwrap_or(rest.len());
+        if wildcards_end > 0 {
+            regex.push_str(&wildcards_to_regex(&rest[..wildcards_end]));
+        }
+        rest = &rest[wildcards_end..];
+    }
+
+    regex
+}
+
+fn is_wildcard(c: char) -> bool {
+    matches!(c, '*' | '?')
+}
+
+/// Translate a sequence of wildcards to a regex syntax.
+fn wildcards_to_regex(wildcards: &str) -> String {
+    // Simplify pattern to avoid performance issues:
+    // - The glob `?**?**?` is equivalent to the glob `???*`
+    // - The glob `???*` is equivalent to the regex `.{3,}`
+    let question_marks = wildcards.matches('?').count();
+
+    if wildcards.contains('*') {
+        format!(".{{{question_marks},}}")
+    } else {
+        format!(".{{{question_marks}}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches, to_regex, Glob};
+
+    #[test]
+    fn literal() {
+        assert!(matches("", ""));
+        assert!(matches("matrix.org", "matrix.org"));
+        assert!(!matches("matrix.org", "matrix.org.evil.com"));
+        assert!(!matches("matrix.org", "Matrix.org"));
+        assert!(!matches("", "a"));
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*", ""));
+        assert!(matches("*", "anything"));
+        assert!(matches("*.matrix.org", "server.matrix.org"));
+        assert!(!matches("*.matrix.org", "matrix.org"));
+        assert!(matches("?", "a"));
+        assert!(!matches("?", ""));
+        assert!(!matches("?", "ab"));
+        assert!(matches("ma?rix", "matrix"));
+        assert!(matches("m*x", "matrix"));
+        assert!(matches("m*r*x", "matrix"));
+        assert!(!matches("m*r*y", "matrix"));
+        assert!(matches("*a*a*a*", "banana and"));
+        assert!(matches("??*", "ab"));
+        assert!(!matches("??*", "a"));
+        assert!(matches("[1234:5678::abcd]:*", "[1234:5678::abcd]:8448"));
+    }
+
+    #[test]
+    fn unicode() {
+        assert!(matches("?", "é"));
+        assert!(matches("caf?", "café"));
+        assert!(matches("*é", "café"));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let glob = Glob::new("*.Matrix.org");
+        assert!(!glob.is_match("server.matrix.org"));
+        assert!(glob.is_match_case_insensitive("server.matrix.ORG"));
+    }
+
+    #[test]
+    fn regex() {
+        assert_eq!(to_regex("matrix.org"), r"matrix\.org");
+        assert_eq!(to_regex("*.org"), r".{0,}\.org");
+        assert_eq!(to_regex("a?**?b"), "a.{2,}b");
+        assert_eq!(to_regex("??"), ".{2}");
+    }
+}
@@ -4,12 +4,12 @@ use ruma_macros::IdZst;
 
 /// A VoIP identifier.
 ///
-/// VoIP IDs in Matrix are opaque strings. This type is provided simply for its semantic
-/// value.
+/// VoIP IDs in Matrix are opaque strings, used as the `call_id` and `party_id` of `m.call.*`
+/// events. This type is provided simply for its semantic value.
 ///
-/// You can create one from a string (using `VoipId::parse()`) but the recommended way is to
-/// use `VoipId::new()` to generate a random one. If that function is not available for you,
-/// you need to activate this crate's `rand` Cargo feature.
+/// You can create one from a string (using `.into()`) but the recommended way is to use
+/// `VoipId::new()` to generate a random one. If that function is not available for you, you need
+/// to activate this crate's `rand` Cargo feature.
 #[repr(transparent)]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, IdZst)]
 pub struct VoipId(str);
@@ -18,7 +18,7 @@ impl VoipId {
     /// Creates a random VoIP identifier.
     ///
     /// This will currently be a UUID without hyphens, but no guarantees are made about the
-    /// structure of client secrets generated from this function.
+    /// structure of VoIP identifiers generated from this function.
     #[cfg(feature = "rand")]
     #[allow(clippy::new_ret_no_self)]
     pub fn new() -> OwnedVoipId {
@@ -35,4 +35,13 @@ mod tests {
     fn try_from() {
         <&VoipId>::try_from("this_-_a_valid_secret_1337").unwrap();
     }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn new() {
+        let id = VoipId::new();
+        assert_eq!(id.as_str().len(), 32);
+        assert!(id.as_str().bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_ne!(id, VoipId::new());
+    }
 }
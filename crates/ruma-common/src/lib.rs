@@ -24,13 +24,15 @@ pub mod authentication;
 pub mod canonical_json;
 pub mod directory;
 pub mod encryption;
-#[cfg(feature = "events")]
+#[cfg(feature = "events-core")]
 pub mod events;
+pub mod glob;
 mod identifiers;
 pub mod power_levels;
 pub mod presence;
 pub mod push;
 pub mod room;
+pub mod room_version_rules;
 pub mod serde;
 pub mod thirdparty;
 mod time;
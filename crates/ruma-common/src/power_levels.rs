@@ -2,9 +2,13 @@
 //!
 //! [power_levels]: https://spec.matrix.org/v1.4/client-server-api/#mroompower_levels
 
+use std::{collections::BTreeMap, iter};
+
 use js_int::{int, Int};
 use serde::{Deserialize, Serialize};
 
+use crate::RoomVersionId;
+
 /// The power level requirements for specific notification types.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -15,24 +19,38 @@ pub struct NotificationPowerLevels {
         deserialize_with = "crate::serde::deserialize_v1_powerlevel"
     )]
     pub room: Int,
+
+    /// The level required to trigger other notification types.
+    ///
+    /// The keys of this map are the notification types, like the `key` of the
+    /// [`SenderNotificationPermission`] push condition.
+    ///
+    /// [`SenderNotificationPermission`]: crate::push::PushCondition::SenderNotificationPermission
+    #[serde(flatten, deserialize_with = "crate::serde::btreemap_deserialize_v1_powerlevel_values")]
+    pub custom: BTreeMap<String, Int>,
 }
 
 impl NotificationPowerLevels {
     /// Create a new `NotificationPowerLevels` with all-default values.
     pub fn new() -> Self {
-        Self { room: default_power_level() }
+        Self { room: default_power_level(), custom: BTreeMap::new() }
     }
 
     /// Value associated with the given `key`.
     pub fn get(&self, key: &str) -> Option<&Int> {
         match key {
             "room" => Some(&self.room),
-            _ => None,
+            _ => self.custom.get(key),
         }
     }
 
+    /// Iterate over all the notification types and their levels, starting with `room`.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Int)> {
+        iter::once(("room", &self.room)).chain(self.custom.iter().map(|(k, v)| (k.as_str(), v)))
+    }
+
     pub(crate) fn is_default(&self) -> bool {
-        self.room == default_power_level()
+        self.room == default_power_level() && self.custom.is_empty()
     }
 }
 
@@ -42,6 +60,32 @@ impl Default for NotificationPowerLevels {
     }
 }
 
+/// How power levels are parsed during deserialization.
+///
+/// Before room version 10, power levels could be stringified integers like `"50"`. Since room
+/// version 10, they must be JSON integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum PowerLevelParsing {
+    /// Accept integers and stringified integers.
+    Lenient,
+
+    /// Only accept integers.
+    Strict,
+}
+
+impl PowerLevelParsing {
+    /// Get the parsing rules of the given room version.
+    ///
+    /// Unknown room versions are assumed to only accept integers.
+    pub fn for_room_version(room_version: &RoomVersionId) -> Self {
+        match room_version.rules() {
+            Some(rules) if !rules.integer_power_levels => Self::Lenient,
+            _ => Self::Strict,
+        }
+    }
+}
+
 /// Used to default power levels to 50 during deserialization.
 pub fn default_power_level() -> Int {
     int!(50)
@@ -2,7 +2,11 @@
 //!
 //! [presence]: https://spec.matrix.org/v1.4/client-server-api/#presence
 
-use crate::{serde::StringEnum, PrivOwnedStr};
+use std::time::Duration;
+
+use js_int::UInt;
+
+use crate::{serde::StringEnum, MilliSecondsSinceUnixEpoch, PrivOwnedStr};
 
 /// A description of a user's connectivity and availability for chat.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
@@ -29,3 +33,103 @@ impl Default for &'_ PresenceState {
         &PresenceState::Online
     }
 }
+
+/// The presence of a user, with the time of the last activity of the user as an absolute
+/// timestamp.
+///
+/// The presence types sent over the network use `last_active_ago`, a number of milliseconds
+/// relative to the time the data is sent, which gets outdated as soon as it is received. This type
+/// can be kept as is until the presence is sent again, with [`PresenceUpdate::last_active_ago`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct PresenceUpdate {
+    /// The presence state of the user.
+    pub presence: PresenceState,
+
+    /// An optional description to accompany the presence.
+    pub status_msg: Option<String>,
+
+    /// Whether or not the user is currently active.
+    pub currently_active: Option<bool>,
+
+    /// The time of the last activity of the user, if it is known.
+    pub last_active: Option<MilliSecondsSinceUnixEpoch>,
+}
+
+impl PresenceUpdate {
+    /// Creates a new `PresenceUpdate` with the given presence state.
+    pub fn new(presence: PresenceState) -> Self {
+        Self { presence, status_msg: None, currently_active: None, last_active: None }
+    }
+
+    /// Creates a new `PresenceUpdate` with the given presence state and the `last_active_ago` of a
+    /// presence that was received at `received_at`.
+    pub fn with_last_active_ago(
+        presence: PresenceState,
+        last_active_ago: Option<UInt>,
+        received_at: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        Self {
+            last_active: last_active_ago
+                .map(|ago| MilliSecondsSinceUnixEpoch(received_at.0.saturating_sub(ago))),
+            ..Self::new(presence)
+        }
+    }
+
+    /// The number of milliseconds that have elapsed between the last activity of the user and
+    /// `now`, if it is known.
+    ///
+    /// This is the value of the `last_active_ago` field when the presence is sent at `now`.
+    pub fn last_active_ago(&self, now: MilliSecondsSinceUnixEpoch) -> Option<UInt> {
+        self.last_active.map(|last_active| now.0.saturating_sub(last_active.0))
+    }
+
+    /// Whether the last activity of the user is older than `max_age` at `now`.
+    ///
+    /// Returns `false` if the time of the last activity of the user is unknown.
+    pub fn is_stale(&self, now: MilliSecondsSinceUnixEpoch, max_age: Duration) -> bool {
+        self.last_active_ago(now).map_or(false, |ago| Duration::from_millis(ago.into()) > max_age)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use js_int::uint;
+
+    use super::{PresenceState, PresenceUpdate};
+    use crate::MilliSecondsSinceUnixEpoch;
+
+    #[test]
+    fn last_active_ago() {
+        let received_at = MilliSecondsSinceUnixEpoch(uint!(10_000));
+        let update = PresenceUpdate::with_last_active_ago(
+            PresenceState::Online,
+            Some(uint!(3_000)),
+            received_at,
+        );
+        assert_eq!(update.last_active, Some(MilliSecondsSinceUnixEpoch(uint!(7_000))));
+
+        let now = MilliSecondsSinceUnixEpoch(uint!(15_000));
+        assert_eq!(update.last_active_ago(now), Some(uint!(8_000)));
+        assert!(!update.is_stale(now, Duration::from_secs(10)));
+        assert!(update.is_stale(now, Duration::from_secs(5)));
+
+        // The last activity can't be in the future, nor before the unix epoch.
+        assert_eq!(
+            update.last_active_ago(MilliSecondsSinceUnixEpoch(uint!(5_000))),
+            Some(uint!(0))
+        );
+        let update = PresenceUpdate::with_last_active_ago(
+            PresenceState::Online,
+            Some(uint!(30_000)),
+            received_at,
+        );
+        assert_eq!(update.last_active, Some(MilliSecondsSinceUnixEpoch(uint!(0))));
+
+        let update = PresenceUpdate::new(PresenceState::Offline);
+        assert_eq!(update.last_active_ago(now), None);
+        assert!(!update.is_stale(now, Duration::ZERO));
+    }
+}
@@ -31,6 +31,7 @@ use crate::{
 mod action;
 mod condition;
 mod iter;
+mod notification_settings;
 mod predefined;
 
 pub use self::{
@@ -39,6 +40,7 @@ pub use self::{
         ComparisonOperator, FlattenedJson, PushCondition, PushConditionRoomCtx, RoomMemberCountIs,
     },
     iter::{AnyPushRule, AnyPushRuleRef, RulesetIntoIter, RulesetIter},
+    notification_settings::{NotificationSettings, RoomNotificationMode},
     predefined::{
         PredefinedContentRuleId, PredefinedOverrideRuleId, PredefinedRuleId,
         PredefinedUnderrideRuleId,
@@ -627,6 +629,41 @@ impl HttpPusherData {
             default_payload: JsonValue::default(),
         }
     }
+
+    /// Checks that the URL of this pusher is valid.
+    ///
+    /// According to the spec, it must be an HTTP(S) URL with a path of `/_matrix/push/v1/notify`.
+    /// Homeservers reject pushers with an invalid URL.
+    pub fn validate_url(&self) -> Result<(), PusherUrlError> {
+        let url = url::Url::parse(&self.url)?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(PusherUrlError::InvalidScheme);
+        }
+
+        if url.path() != "/_matrix/push/v1/notify" {
+            return Err(PusherUrlError::InvalidPath);
+        }
+
+        Ok(())
+    }
+}
+
+/// The error type returned when the URL of an HTTP pusher is invalid.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PusherUrlError {
+    /// The URL could not be parsed.
+    #[error("invalid URL: {0}")]
+    Parse(#[from] url::ParseError),
+
+    /// The scheme of the URL is not `http` or `https`.
+    #[error("the URL scheme must be http or https")]
+    InvalidScheme,
+
+    /// The path of the URL is not `/_matrix/push/v1/notify`.
+    #[error("the URL path must be /_matrix/push/v1/notify")]
+    InvalidPath,
 }
 
 /// A special format that the homeserver should use when sending notifications to a Push Gateway.
@@ -886,7 +923,8 @@ mod tests {
     use super::{
         action::{Action, Tweak},
         condition::{PushCondition, PushConditionRoomCtx, RoomMemberCountIs},
-        AnyPushRule, ConditionalPushRule, PatternedPushRule, Ruleset, SimplePushRule,
+        AnyPushRule, ConditionalPushRule, HttpPusherData, PatternedPushRule, PusherUrlError,
+        Ruleset, SimplePushRule,
     };
     use crate::{power_levels::NotificationPowerLevels, room_id, serde::Raw, user_id};
 
@@ -1336,7 +1374,7 @@ mod tests {
             user_display_name: "Jolly Jumper".into(),
             users_power_levels: BTreeMap::new(),
             default_power_level: int!(50),
-            notification_power_levels: NotificationPowerLevels { room: int!(50) },
+            notification_power_levels: NotificationPowerLevels::new(),
         };
 
         let context_public_room = &PushConditionRoomCtx {
@@ -1346,7 +1384,7 @@ mod tests {
             user_display_name: "Jolly Jumper".into(),
             users_power_levels: BTreeMap::new(),
             default_power_level: int!(50),
-            notification_power_levels: NotificationPowerLevels { room: int!(50) },
+            notification_power_levels: NotificationPowerLevels::new(),
         };
 
         let message = serde_json::from_str::<Raw<JsonValue>>(
@@ -1437,7 +1475,7 @@ mod tests {
             user_display_name: "Jolly Jumper".into(),
             users_power_levels: BTreeMap::new(),
             default_power_level: int!(50),
-            notification_power_levels: NotificationPowerLevels { room: int!(50) },
+            notification_power_levels: NotificationPowerLevels::new(),
         };
 
         let message = serde_json::from_str::<Raw<JsonValue>>(
@@ -1560,4 +1598,29 @@ mod tests {
         );
         assert_eq!(sound, "three");
     }
+
+    #[test]
+    fn http_pusher_url_validation() {
+        HttpPusherData::new("https://push.example.org/_matrix/push/v1/notify".to_owned())
+            .validate_url()
+            .unwrap();
+        HttpPusherData::new("http://localhost:5000/_matrix/push/v1/notify".to_owned())
+            .validate_url()
+            .unwrap();
+
+        assert_matches!(
+            HttpPusherData::new("push.example.org/_matrix/push/v1/notify".to_owned())
+                .validate_url(),
+            Err(PusherUrlError::Parse(_))
+        );
+        assert_matches!(
+            HttpPusherData::new("ftp://push.example.org/_matrix/push/v1/notify".to_owned())
+                .validate_url(),
+            Err(PusherUrlError::InvalidScheme)
+        );
+        assert_matches!(
+            HttpPusherData::new("https://push.example.org/".to_owned()).validate_url(),
+            Err(PusherUrlError::InvalidPath)
+        );
+    }
 }
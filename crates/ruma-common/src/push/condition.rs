@@ -5,9 +5,10 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{to_value as to_json_value, value::Value as JsonValue};
 use tracing::{instrument, warn};
-use wildmatch::WildMatch;
 
-use crate::{power_levels::NotificationPowerLevels, serde::Raw, OwnedRoomId, OwnedUserId, UserId};
+use crate::{
+    glob, power_levels::NotificationPowerLevels, serde::Raw, OwnedRoomId, OwnedUserId, UserId,
+};
 
 mod room_member_count_is;
 
@@ -186,11 +187,6 @@ trait StrExt {
     ///
     /// The match is case sensitive.
     fn matches_word(&self, pattern: &str) -> bool;
-
-    /// Translate the wildcards in `self` to a regex syntax.
-    ///
-    /// `self` must only contain wildcards.
-    fn wildcards_to_regex(&self) -> String;
 }
 
 impl StrExt for str {
@@ -228,7 +224,7 @@ impl StrExt for str {
         if match_words {
             value.matches_word(pattern)
         } else {
-            WildMatch::new(pattern).matches(value)
+            glob::matches(pattern, value)
         }
     }
 
@@ -240,41 +236,10 @@ impl StrExt for str {
             return false;
         }
 
-        let has_wildcards = pattern.contains(|c| matches!(c, '?' | '*'));
-
-        if has_wildcards {
-            let mut chunks: Vec<String> = vec![];
-            let mut prev_wildcard = false;
-            let mut chunk_start = 0;
-
-            for (i, c) in pattern.char_indices() {
-                if matches!(c, '?' | '*') && !prev_wildcard {
-                    if i != 0 {
-                        chunks.push(regex::escape(&pattern[chunk_start..i]));
-                        chunk_start = i;
-                    }
-
-                    prev_wildcard = true;
-                } else if prev_wildcard {
-                    let chunk = &pattern[chunk_start..i];
-                    chunks.push(chunk.wildcards_to_regex());
-
-                    chunk_start = i;
-                    prev_wildcard = false;
-                }
-            }
-
-            let len = pattern.len();
-            if !prev_wildcard {
-                chunks.push(regex::escape(&pattern[chunk_start..len]));
-            } else if prev_wildcard {
-                let chunk = &pattern[chunk_start..len];
-                chunks.push(chunk.wildcards_to_regex());
-            }
-
+        if glob::has_wildcards(pattern) {
             // The word characters in ASCII compatible mode (with the `-u` flag) match the
             // definition in the spec: any character not in the set `[A-Za-z0-9_]`.
-            let regex = format!(r"(?-u:^|\W|\b){}(?-u:\b|\W|$)", chunks.concat());
+            let regex = format!(r"(?-u:^|\W|\b){}(?-u:\b|\W|$)", glob::to_regex(pattern));
             Regex::new(&regex).ok().filter(|re| re.is_match(self)).is_some()
         } else {
             match self.find(pattern) {
@@ -314,19 +279,6 @@ impl StrExt for str {
             }
         }
     }
-
-    fn wildcards_to_regex(&self) -> String {
-        // Simplify pattern to avoid performance issues:
-        // - The glob `?**?**?` is equivalent to the glob `???*`
-        // - The glob `???*` is equivalent to the regex `.{3,}`
-        let question_marks = self.matches('?').count();
-
-        if self.contains('*') {
-            format!(".{{{question_marks},}}")
-        } else {
-            format!(".{{{question_marks}}}")
-        }
-    }
 }
 
 /// The flattened representation of a JSON object.
@@ -584,7 +536,7 @@ mod tests {
             user_display_name: "Groovy Gorilla".into(),
             users_power_levels,
             default_power_level: int!(50),
-            notification_power_levels: NotificationPowerLevels { room: int!(50) },
+            notification_power_levels: NotificationPowerLevels::new(),
         };
 
         let first_event_raw = serde_json::from_str::<Raw<JsonValue>>(
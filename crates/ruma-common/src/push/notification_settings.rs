@@ -0,0 +1,218 @@
+//! High-level operations on push rules.
+
+use super::{
+    Action, InsertPushRuleError, NewConditionalPushRule, NewPatternedPushRule, NewPushRule,
+    NewSimplePushRule, PushCondition, Ruleset, Tweak,
+};
+use crate::{RoomId, UserId};
+
+/// The notification mode of a room.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RoomNotificationMode {
+    /// Notify for all the messages in the room.
+    AllMessages,
+
+    /// Only notify for the messages that mention the user or contain one of their keywords.
+    MentionsAndKeywordsOnly,
+
+    /// Never notify for the messages in the room.
+    Mute,
+}
+
+/// High-level operations on the notification settings of a user.
+///
+/// This creates and removes the push rules of a [`Ruleset`] like most clients do:
+///
+/// * A room is muted with an override rule whose ID is the room ID, that matches all the events of
+///   the room and doesn't notify.
+/// * The notifications of a room are limited to mentions and keywords with a room rule whose ID is
+///   the room ID and doesn't notify.
+/// * Notifications for all the messages of a room are forced with a room rule whose ID is the room
+///   ID and notifies.
+/// * A keyword is a content rule whose ID and pattern are the keyword, and that notifies.
+///
+/// The rules are only updated locally, the changes still need to be sent to the homeserver.
+#[derive(Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct NotificationSettings<'a> {
+    /// The push rules of the user.
+    pub ruleset: &'a mut Ruleset,
+}
+
+impl<'a> NotificationSettings<'a> {
+    /// Creates a new `NotificationSettings` for the given push rules.
+    pub fn new(ruleset: &'a mut Ruleset) -> Self {
+        Self { ruleset }
+    }
+
+    /// The notification mode that was set for the given room.
+    ///
+    /// Returns `None` if the room uses the default notification mode.
+    pub fn room_notification_mode(&self, room_id: &RoomId) -> Option<RoomNotificationMode> {
+        if let Some(rule) = self.ruleset.override_.get(room_id.as_str()) {
+            if rule.enabled && !is_notify(&rule.actions) {
+                return Some(RoomNotificationMode::Mute);
+            }
+        }
+
+        let rule = self.ruleset.room.get(room_id.as_str()).filter(|rule| rule.enabled)?;
+        if is_notify(&rule.actions) {
+            Some(RoomNotificationMode::AllMessages)
+        } else {
+            Some(RoomNotificationMode::MentionsAndKeywordsOnly)
+        }
+    }
+
+    /// Set the notification mode of the given room.
+    ///
+    /// This replaces the rules that were set previously for the room.
+    pub fn set_room_notification_mode(
+        &mut self,
+        room_id: &RoomId,
+        mode: RoomNotificationMode,
+    ) -> Result<(), InsertPushRuleError> {
+        self.restore_room_default(room_id);
+
+        let rule = match mode {
+            RoomNotificationMode::AllMessages => NewPushRule::Room(NewSimplePushRule::new(
+                room_id.to_owned(),
+                vec![Action::Notify, Action::SetTweak(Tweak::Sound("default".into()))],
+            )),
+            RoomNotificationMode::MentionsAndKeywordsOnly => NewPushRule::Room(
+                NewSimplePushRule::new(room_id.to_owned(), vec![Action::DontNotify]),
+            ),
+            RoomNotificationMode::Mute => NewPushRule::Override(NewConditionalPushRule::new(
+                room_id.to_string(),
+                vec![PushCondition::EventMatch {
+                    key: "room_id".into(),
+                    pattern: room_id.to_string(),
+                }],
+                vec![Action::DontNotify],
+            )),
+        };
+
+        self.ruleset.insert(rule, None, None)
+    }
+
+    /// Mute the given room.
+    pub fn mute_room(&mut self, room_id: &RoomId) -> Result<(), InsertPushRuleError> {
+        self.set_room_notification_mode(room_id, RoomNotificationMode::Mute)
+    }
+
+    /// Only notify for the messages of the given room that mention the user or contain one of
+    /// their keywords.
+    pub fn set_room_mentions_and_keywords_only(
+        &mut self,
+        room_id: &RoomId,
+    ) -> Result<(), InsertPushRuleError> {
+        self.set_room_notification_mode(room_id, RoomNotificationMode::MentionsAndKeywordsOnly)
+    }
+
+    /// Remove the rules that were set for the given room, to use the default notification mode.
+    pub fn restore_room_default(&mut self, room_id: &RoomId) {
+        self.ruleset.override_.shift_remove(room_id.as_str());
+        self.ruleset.room.shift_remove(room_id.as_str());
+    }
+
+    /// The keywords of the user.
+    pub fn keywords(&self) -> impl Iterator<Item = &str> {
+        self.ruleset.content.iter().filter(|rule| !rule.default).map(|rule| rule.pattern.as_str())
+    }
+
+    /// Add a keyword that triggers a notification.
+    ///
+    /// Returns an error if the keyword can't be used as a rule ID.
+    pub fn add_keyword(&mut self, keyword: &str) -> Result<(), InsertPushRuleError> {
+        self.ruleset.insert(
+            NewPushRule::Content(NewPatternedPushRule::new(
+                keyword.to_owned(),
+                keyword.to_owned(),
+                vec![Action::Notify, Action::SetTweak(Tweak::Sound("default".into()))],
+            )),
+            None,
+            None,
+        )
+    }
+
+    /// Remove the given keyword.
+    ///
+    /// Returns `false` if the keyword was not found.
+    pub fn remove_keyword(&mut self, keyword: &str) -> bool {
+        match self.ruleset.content.get(keyword) {
+            Some(rule) if !rule.default => self.ruleset.content.shift_remove(keyword),
+            _ => false,
+        }
+    }
+
+    /// Restore the server-default push rules for the given user.
+    ///
+    /// This removes all the rules that were created by the user.
+    pub fn restore_defaults(&mut self, user_id: &UserId) {
+        *self.ruleset = Ruleset::server_default(user_id);
+    }
+}
+
+/// Whether the given actions generate a notification.
+fn is_notify(actions: &[Action]) -> bool {
+    actions.iter().any(|action| matches!(action, Action::Notify | Action::Coalesce))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotificationSettings, RoomNotificationMode};
+    use crate::{push::Ruleset, room_id, user_id};
+
+    #[test]
+    fn room_notification_mode() {
+        let mut ruleset = Ruleset::server_default(user_id!("@alice:example.org"));
+        let mut settings = NotificationSettings::new(&mut ruleset);
+        let room_id = room_id!("!room:example.org");
+
+        assert_eq!(settings.room_notification_mode(room_id), None);
+
+        settings.mute_room(room_id).unwrap();
+        assert_eq!(settings.room_notification_mode(room_id), Some(RoomNotificationMode::Mute));
+        assert_eq!(settings.ruleset.override_.get_index(1).unwrap().rule_id, room_id.as_str());
+
+        settings.set_room_mentions_and_keywords_only(room_id).unwrap();
+        assert_eq!(
+            settings.room_notification_mode(room_id),
+            Some(RoomNotificationMode::MentionsAndKeywordsOnly)
+        );
+        assert!(settings.ruleset.override_.get(room_id.as_str()).is_none());
+
+        settings.set_room_notification_mode(room_id, RoomNotificationMode::AllMessages).unwrap();
+        assert_eq!(
+            settings.room_notification_mode(room_id),
+            Some(RoomNotificationMode::AllMessages)
+        );
+
+        settings.restore_room_default(room_id);
+        assert_eq!(settings.room_notification_mode(room_id), None);
+        assert!(settings.ruleset.room.is_empty());
+    }
+
+    #[test]
+    fn keywords() {
+        let user_id = user_id!("@alice:example.org");
+        let mut ruleset = Ruleset::server_default(user_id);
+        let mut settings = NotificationSettings::new(&mut ruleset);
+
+        settings.add_keyword("ruma").unwrap();
+        settings.add_keyword("matrix").unwrap();
+        settings.add_keyword("invalid/keyword").unwrap_err();
+        assert_eq!(settings.keywords().collect::<Vec<_>>(), ["matrix", "ruma"]);
+
+        assert!(settings.remove_keyword("ruma"));
+        assert!(!settings.remove_keyword("ruma"));
+        // Server-default rules are not keywords.
+        assert!(!settings.remove_keyword(".m.rule.contains_user_name"));
+        assert_eq!(settings.keywords().collect::<Vec<_>>(), ["matrix"]);
+
+        settings.mute_room(room_id!("!room:example.org")).unwrap();
+        settings.restore_defaults(user_id);
+        assert_eq!(settings.keywords().count(), 0);
+        assert!(settings.ruleset.override_.get("!room:example.org").is_none());
+    }
+}
@@ -0,0 +1,401 @@
+//! Types for the rules applied to the different [room versions].
+//!
+//! The rules of the room versions of the Matrix specification are defined in a single place, as
+//! associated constants of [`RoomVersionRules`], and can be looked up with
+//! [`RoomVersionId::rules()`] or listed with [`RoomVersionRules::known_versions()`]. They are used
+//! for event formats, redaction, signatures and authorization.
+//!
+//! The rules of custom room versions, like the unstable room versions of MSCs, are unknown by
+//! default. They can be registered with [`RoomVersionRules::register_custom()`] to experiment with
+//! them.
+//!
+//! [room versions]: https://spec.matrix.org/v1.4/rooms/
+
+use std::sync::{PoisonError, RwLock};
+
+use thiserror::Error;
+
+use crate::RoomVersionId;
+
+/// The stability of a room version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum RoomDisposition {
+    /// A room version that has a stable specification.
+    Stable,
+
+    /// A room version that is not yet fully specified.
+    Unstable,
+}
+
+/// The format of the event IDs of a room version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum EventFormatVersion {
+    /// `$id:server` event ID format, chosen by the origin server and included in the event.
+    V1,
+
+    /// MSC1659-style `$hash` event ID format: introduced for room v3.
+    V2,
+
+    /// MSC1884-style URL-safe `$hash` event ID format: introduced for room v4.
+    V3,
+}
+
+/// The state resolution algorithm of a room version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum StateResolutionVersion {
+    /// State resolution for rooms at version 1.
+    V1,
+
+    /// State resolution for room at version 2 or later.
+    V2,
+}
+
+/// The rules of a room version.
+///
+/// The rules of a custom room version can be defined by modifying the rules of an existing room
+/// version:
+///
+/// ```
+/// use ruma_common::room_version_rules::{RoomDisposition, RoomVersionRules};
+///
+/// let mut rules = RoomVersionRules::V10;
+/// rules.disposition = RoomDisposition::Unstable;
+/// rules.allow_knocking = false;
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct RoomVersionRules {
+    /// The stability of this room version.
+    pub disposition: RoomDisposition,
+
+    /// The format of the event IDs.
+    pub event_format: EventFormatVersion,
+
+    /// Which state resolution algorithm is used.
+    pub state_res: StateResolutionVersion,
+
+    /// Whether the validity period of the signing keys of servers is enforced.
+    ///
+    /// See: [MSC2076](https://github.com/matrix-org/matrix-spec-proposals/pull/2076) for more information.
+    pub enforce_key_validity: bool,
+
+    /// `m.room.aliases` had special auth rules and redaction rules before room version 6.
+    ///
+    /// They were removed by [MSC2261](https://github.com/matrix-org/matrix-spec-proposals/pull/2261)
+    /// and [MSC2432](https://github.com/matrix-org/matrix-spec-proposals/pull/2432).
+    pub special_case_aliases_auth: bool,
+
+    /// Strictly enforce canonical json, do not allow:
+    /// * Integers outside the range of [-2 ^ 53 + 1, 2 ^ 53 - 1]
+    /// * Floats
+    /// * NaN, Infinity, -Infinity
+    pub strict_canonicaljson: bool,
+
+    /// Verify notifications key while checking m.room.power_levels.
+    ///
+    /// See: [MSC2209](https://github.com/matrix-org/matrix-spec-proposals/pull/2209) for more information.
+    pub limit_notifications_power_levels: bool,
+
+    /// Extra rules when verifying redaction events.
+    pub extra_redaction_checks: bool,
+
+    /// Allow knocking in event authentication.
+    ///
+    /// See [room v7 specification](https://spec.matrix.org/v1.4/rooms/v7/) for more information.
+    pub allow_knocking: bool,
+
+    /// Adds support for the restricted join rule.
+    ///
+    /// See: [MSC3289](https://github.com/matrix-org/matrix-spec-proposals/pull/3289) for more information.
+    pub restricted_join_rules: bool,
+
+    /// Adds support for the knock_restricted join rule.
+    ///
+    /// See: [MSC3787](https://github.com/matrix-org/matrix-spec-proposals/pull/3787) for more information.
+    pub knock_restricted_join_rule: bool,
+
+    /// Enforces integer power levels.
+    ///
+    /// See: [MSC3667](https://github.com/matrix-org/matrix-spec-proposals/pull/3667) for more information.
+    pub integer_power_levels: bool,
+
+    /// The fields that are preserved during redaction.
+    pub redaction: RedactionRules,
+}
+
+impl RoomVersionRules {
+    /// Rules for [room version 1](https://spec.matrix.org/v1.4/rooms/v1/).
+    pub const V1: Self = Self {
+        disposition: RoomDisposition::Stable,
+        event_format: EventFormatVersion::V1,
+        state_res: StateResolutionVersion::V1,
+        enforce_key_validity: false,
+        special_case_aliases_auth: true,
+        strict_canonicaljson: false,
+        limit_notifications_power_levels: false,
+        extra_redaction_checks: false,
+        allow_knocking: false,
+        restricted_join_rules: false,
+        knock_restricted_join_rule: false,
+        integer_power_levels: false,
+        redaction: RedactionRules::V1,
+    };
+
+    /// Rules for [room version 2](https://spec.matrix.org/v1.4/rooms/v2/).
+    pub const V2: Self = Self { state_res: StateResolutionVersion::V2, ..Self::V1 };
+
+    /// Rules for [room version 3](https://spec.matrix.org/v1.4/rooms/v3/).
+    pub const V3: Self =
+        Self { event_format: EventFormatVersion::V2, extra_redaction_checks: true, ..Self::V2 };
+
+    /// Rules for [room version 4](https://spec.matrix.org/v1.4/rooms/v4/).
+    pub const V4: Self = Self { event_format: EventFormatVersion::V3, ..Self::V3 };
+
+    /// Rules for [room version 5](https://spec.matrix.org/v1.4/rooms/v5/).
+    pub const V5: Self = Self { enforce_key_validity: true, ..Self::V4 };
+
+    /// Rules for [room version 6](https://spec.matrix.org/v1.4/rooms/v6/).
+    pub const V6: Self = Self {
+        special_case_aliases_auth: false,
+        strict_canonicaljson: true,
+        limit_notifications_power_levels: true,
+        redaction: RedactionRules::V6,
+        ..Self::V5
+    };
+
+    /// Rules for [room version 7](https://spec.matrix.org/v1.4/rooms/v7/).
+    pub const V7: Self = Self { allow_knocking: true, ..Self::V6 };
+
+    /// Rules for [room version 8](https://spec.matrix.org/v1.4/rooms/v8/).
+    pub const V8: Self =
+        Self { restricted_join_rules: true, redaction: RedactionRules::V8, ..Self::V7 };
+
+    /// Rules for [room version 9](https://spec.matrix.org/v1.4/rooms/v9/).
+    pub const V9: Self = Self { redaction: RedactionRules::V9, ..Self::V8 };
+
+    /// Rules for [room version 10](https://spec.matrix.org/v1.4/rooms/v10/).
+    pub const V10: Self =
+        Self { knock_restricted_join_rule: true, integer_power_levels: true, ..Self::V9 };
+}
+
+/// The fields of events that are preserved during the redaction algorithm of a room version.
+///
+/// Only the fields whose preservation changed between room versions are listed here.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct RedactionRules {
+    /// Whether the `aliases` field of `m.room.aliases` events is preserved.
+    ///
+    /// It was removed in room version 6 by
+    /// [MSC2432](https://github.com/matrix-org/matrix-spec-proposals/pull/2432).
+    pub keep_room_aliases_aliases: bool,
+
+    /// Whether the `allow` field of `m.room.join_rules` events is preserved.
+    ///
+    /// It was added in room version 8 by
+    /// [MSC3289](https://github.com/matrix-org/matrix-spec-proposals/pull/3289).
+    pub keep_room_join_rules_allow: bool,
+
+    /// Whether the `join_authorised_via_users_server` field of `m.room.member` events is
+    /// preserved.
+    ///
+    /// It was added in room version 9 by
+    /// [MSC3375](https://github.com/matrix-org/matrix-spec-proposals/pull/3375).
+    pub keep_room_member_join_authorised_via_users_server: bool,
+}
+
+impl RedactionRules {
+    /// Redaction rules of room versions 1 through 5.
+    pub const V1: Self = Self {
+        keep_room_aliases_aliases: true,
+        keep_room_join_rules_allow: false,
+        keep_room_member_join_authorised_via_users_server: false,
+    };
+
+    /// Redaction rules of room versions 6 and 7.
+    pub const V6: Self = Self { keep_room_aliases_aliases: false, ..Self::V1 };
+
+    /// Redaction rules of room version 8.
+    pub const V8: Self = Self { keep_room_join_rules_allow: true, ..Self::V6 };
+
+    /// Redaction rules of room versions 9 and 10.
+    pub const V9: Self =
+        Self { keep_room_member_join_authorised_via_users_server: true, ..Self::V8 };
+
+    /// Get the redaction rules of the given room version.
+    ///
+    /// Returns `None` if the rules of the room version are unknown.
+    pub fn for_room_version(room_version: &RoomVersionId) -> Option<Self> {
+        room_version.rules().map(|rules| rules.redaction)
+    }
+}
+
+/// The room versions of the Matrix specification, with their rules.
+///
+/// A new room version only needs to be added here to be supported everywhere.
+const ROOM_VERSIONS: &[(RoomVersionId, RoomVersionRules)] = &[
+    (RoomVersionId::V1, RoomVersionRules::V1),
+    (RoomVersionId::V2, RoomVersionRules::V2),
+    (RoomVersionId::V3, RoomVersionRules::V3),
+    (RoomVersionId::V4, RoomVersionRules::V4),
+    (RoomVersionId::V5, RoomVersionRules::V5),
+    (RoomVersionId::V6, RoomVersionRules::V6),
+    (RoomVersionId::V7, RoomVersionRules::V7),
+    (RoomVersionId::V8, RoomVersionRules::V8),
+    (RoomVersionId::V9, RoomVersionRules::V9),
+    (RoomVersionId::V10, RoomVersionRules::V10),
+];
+
+/// The custom room versions that were registered with [`RoomVersionRules::register_custom()`].
+static CUSTOM_ROOM_VERSIONS: RwLock<Vec<(RoomVersionId, RoomVersionRules)>> =
+    RwLock::new(Vec::new());
+
+impl RoomVersionRules {
+    /// The room versions of the Matrix specification, with their rules.
+    pub fn known_versions() -> impl Iterator<Item = (RoomVersionId, Self)> {
+        ROOM_VERSIONS.iter().cloned()
+    }
+
+    /// Register the rules of a custom room version.
+    ///
+    /// The rules are registered for the whole process, so [`RoomVersionId::rules()`] returns them
+    /// for this room version and the room version is supported for redaction, signatures and
+    /// authorization. This allows to experiment with unstable room versions, like
+    /// `org.matrix.msc1234`:
+    ///
+    /// ```
+    /// use ruma_common::{
+    ///     room_version_rules::{RoomDisposition, RoomVersionRules},
+    ///     RoomVersionId,
+    /// };
+    ///
+    /// let room_version = RoomVersionId::try_from("org.matrix.msc1234").unwrap();
+    ///
+    /// let mut rules = RoomVersionRules::V10;
+    /// rules.disposition = RoomDisposition::Unstable;
+    /// rules.allow_knocking = false;
+    /// RoomVersionRules::register_custom(room_version.clone(), rules).unwrap();
+    ///
+    /// assert!(!room_version.rules().unwrap().allow_knocking);
+    /// ```
+    ///
+    /// Returns the previous rules of the room version, if it was already registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the room version is a room version of the Matrix specification, since
+    /// their rules can't be changed.
+    pub fn register_custom(
+        room_version: RoomVersionId,
+        rules: Self,
+    ) -> Result<Option<Self>, SpecRoomVersionError> {
+        if ROOM_VERSIONS.iter().any(|(id, _)| *id == room_version) {
+            return Err(SpecRoomVersionError { room_version });
+        }
+
+        // The registry is never left in an inconsistent state, so it can be used after a panic.
+        let mut custom_room_versions =
+            CUSTOM_ROOM_VERSIONS.write().unwrap_or_else(PoisonError::into_inner);
+
+        match custom_room_versions.iter_mut().find(|(id, _)| *id == room_version) {
+            Some((_, previous_rules)) => Ok(Some(std::mem::replace(previous_rules, rules))),
+            None => {
+                custom_room_versions.push((room_version, rules));
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl RoomVersionId {
+    /// The rules of this room version.
+    ///
+    /// Returns `None` for custom room versions, unless their rules were registered with
+    /// [`RoomVersionRules::register_custom()`].
+    pub fn rules(&self) -> Option<RoomVersionRules> {
+        if let Some((_, rules)) = ROOM_VERSIONS.iter().find(|(id, _)| id == self) {
+            return Some(rules.clone());
+        }
+
+        let custom_room_versions =
+            CUSTOM_ROOM_VERSIONS.read().unwrap_or_else(PoisonError::into_inner);
+        custom_room_versions.iter().find(|(id, _)| id == self).map(|(_, rules)| rules.clone())
+    }
+}
+
+/// The error type returned when trying to register the rules of a room version of the Matrix
+/// specification with [`RoomVersionRules::register_custom()`].
+#[derive(Debug, Error)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[error("the rules of room version {room_version} of the Matrix specification can't be changed")]
+pub struct SpecRoomVersionError {
+    /// The room version that was registered.
+    pub room_version: RoomVersionId,
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::{EventFormatVersion, RoomDisposition, RoomVersionRules, SpecRoomVersionError};
+    use crate::RoomVersionId;
+
+    #[test]
+    fn rules_of_room_versions() {
+        let v2 = RoomVersionId::V2.rules().unwrap();
+        assert_eq!(v2.event_format, EventFormatVersion::V1);
+        assert!(v2.redaction.keep_room_aliases_aliases);
+
+        let v8 = RoomVersionId::V8.rules().unwrap();
+        assert!(v8.redaction.keep_room_join_rules_allow);
+        assert!(!v8.redaction.keep_room_member_join_authorised_via_users_server);
+
+        let v9 = RoomVersionId::V9.rules().unwrap();
+        assert!(v9.restricted_join_rules);
+        assert!(!v9.knock_restricted_join_rule);
+        assert!(v9.redaction.keep_room_member_join_authorised_via_users_server);
+
+        let v10 = RoomVersionId::V10.rules().unwrap();
+        assert_eq!(v10.event_format, EventFormatVersion::V3);
+        assert!(v10.integer_power_levels);
+        assert!(!v10.redaction.keep_room_aliases_aliases);
+
+        let custom = RoomVersionId::try_from("org.example.custom").unwrap();
+        assert!(custom.rules().is_none());
+
+        assert_eq!(RoomVersionRules::known_versions().count(), 10);
+    }
+
+    #[test]
+    fn register_custom_room_version() {
+        let room_version = RoomVersionId::try_from("org.example.msc0000").unwrap();
+        assert!(room_version.rules().is_none());
+
+        let mut rules = RoomVersionRules::V9;
+        rules.disposition = RoomDisposition::Unstable;
+        assert!(RoomVersionRules::register_custom(room_version.clone(), rules).unwrap().is_none());
+
+        let rules = room_version.rules().unwrap();
+        assert_eq!(rules.disposition, RoomDisposition::Unstable);
+        assert!(!rules.integer_power_levels);
+
+        let previous =
+            RoomVersionRules::register_custom(room_version.clone(), RoomVersionRules::V10)
+                .unwrap()
+                .unwrap();
+        assert_eq!(previous.disposition, RoomDisposition::Unstable);
+        assert!(room_version.rules().unwrap().integer_power_levels);
+    }
+
+    #[test]
+    fn register_spec_room_version() {
+        assert_matches!(
+            RoomVersionRules::register_custom(RoomVersionId::V9, RoomVersionRules::V10),
+            Err(SpecRoomVersionError { room_version }) if room_version == RoomVersionId::V9
+        );
+        assert!(!RoomVersionId::V9.rules().unwrap().integer_power_levels);
+    }
+}
@@ -1,4 +1,5 @@
 //! De-/serialization functions for `std::time::Duration` objects
 
+pub mod ms;
 pub mod opt_ms;
 pub mod secs;
@@ -0,0 +1,67 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata, user_id, OwnedUserId,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/foo/:user",
+    }
+};
+
+/// Request type for the `builder` endpoint.
+#[request(builder)]
+pub struct Request {
+    #[ruma_api(path)]
+    pub user: OwnedUserId,
+
+    #[ruma_api(query)]
+    pub limit: Option<u32>,
+
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    #[serde(default = "default_order", skip_serializing_if = "is_default_order")]
+    pub order: String,
+}
+
+fn default_order() -> String {
+    "asc".to_owned()
+}
+
+fn is_default_order(order: &str) -> bool {
+    order == "asc"
+}
+
+/// Response type for the `builder` endpoint.
+#[response]
+pub struct Response {}
+
+#[test]
+fn builder_defaults() {
+    let req = Request::builder(user_id!("@alice:example.org").to_owned(), "foo".to_owned()).build();
+
+    assert_eq!(req.user, "@alice:example.org");
+    assert_eq!(req.limit, None);
+    assert_eq!(req.name, "foo");
+    assert!(req.tags.is_empty());
+    assert_eq!(req.order, "asc");
+}
+
+#[test]
+fn builder_setters() {
+    let req = Request::builder(user_id!("@alice:example.org").to_owned(), "foo".to_owned())
+        .limit(10)
+        .tags(vec!["bar".to_owned()])
+        .order("desc".to_owned())
+        .build();
+
+    assert_eq!(req.limit, Some(10));
+    assert_eq!(req.tags, ["bar"]);
+    assert_eq!(req.order, "desc");
+}
@@ -1,10 +1,13 @@
 #![allow(clippy::exhaustive_structs)]
 
+use std::time::Duration;
+
 use http::header::CONTENT_TYPE;
 use ruma_common::{
     api::{
         request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
-        OutgoingRequestAppserviceExt, SendAccessToken,
+        OutgoingRequestAppserviceExt, OutgoingRequestExt, OutgoingRequestOptions, SendAccessToken,
+        TimeoutHint,
     },
     metadata, user_id, OwnedUserId,
 };
@@ -128,6 +131,37 @@ fn request_with_user_id_serde() {
     );
 }
 
+#[test]
+fn request_with_options_serde() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "q1".to_owned(),
+        q2: 55,
+        bar: "barVal".to_owned(),
+        user: user_id!("@bazme:ruma.io").to_owned(),
+    };
+
+    let mut options = OutgoingRequestOptions::new();
+    options.extra_query = vec![("org.example.toggle", "true"), ("foo", "a b")];
+    options.timeout = Some(Duration::from_secs(30));
+
+    let http_req = req
+        .try_into_http_request_with_options::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+            &options,
+        )
+        .unwrap();
+
+    assert_eq!(http_req.uri().query().unwrap(), "q1=q1&q2=55&org.example.toggle=true&foo=a+b");
+    assert_eq!(
+        http_req.extensions().get::<TimeoutHint>(),
+        Some(&TimeoutHint(Duration::from_secs(30)))
+    );
+}
+
 mod without_query {
     use http::header::CONTENT_TYPE;
     use ruma_common::{
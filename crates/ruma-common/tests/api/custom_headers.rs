@@ -0,0 +1,104 @@
+#![allow(clippy::exhaustive_structs)]
+
+use http::header::RETRY_AFTER;
+use ruma_common::{
+    api::{
+        request, response, IncomingRequest as _, IncomingResponse as _, MatrixVersion, Metadata,
+        OutgoingRequest as _, OutgoingResponse as _, SendAccessToken,
+    },
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/my/endpoint",
+    }
+};
+
+/// Request type for the `custom_headers` endpoint.
+#[request]
+pub struct Request {
+    #[ruma_api(header = "x-custom-header")]
+    pub custom: String,
+
+    #[ruma_api(header = "x-optional-header")]
+    pub optional: Option<String>,
+
+    #[ruma_api(header = "x-forwarded-for")]
+    pub forwarded_for: Vec<String>,
+}
+
+/// Response type for the `custom_headers` endpoint.
+#[response]
+pub struct Response {
+    #[ruma_api(header = RETRY_AFTER)]
+    pub retry_after: Option<String>,
+
+    #[ruma_api(header = "x-custom-header")]
+    pub custom: Vec<String>,
+}
+
+#[test]
+fn request_custom_headers() {
+    let req = Request {
+        custom: "custom".to_owned(),
+        optional: None,
+        forwarded_for: vec!["10.0.0.1".to_owned(), "10.0.0.2".to_owned()],
+    };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.headers().get("x-custom-header").unwrap(), "custom");
+    assert!(!http_req.headers().contains_key("x-optional-header"));
+    assert_eq!(
+        http_req.headers().get_all("x-forwarded-for").iter().collect::<Vec<_>>(),
+        ["10.0.0.1", "10.0.0.2"]
+    );
+
+    let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.custom, "custom");
+    assert_eq!(req.optional, None);
+    assert_eq!(req.forwarded_for, ["10.0.0.1", "10.0.0.2"]);
+}
+
+#[test]
+fn request_missing_custom_header() {
+    let http_req = http::Request::builder()
+        .uri("https://homeserver.tld/_matrix/my/endpoint")
+        .body(Vec::<u8>::new())
+        .unwrap();
+
+    Request::try_from_http_request(http_req, &[] as &[String]).unwrap_err();
+}
+
+#[test]
+fn response_custom_headers() {
+    let res = Response {
+        retry_after: Some("120".to_owned()),
+        custom: vec!["first".to_owned(), "second".to_owned()],
+    };
+
+    let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+    assert_eq!(http_res.headers().get(RETRY_AFTER).unwrap(), "120");
+    assert_eq!(
+        http_res.headers().get_all("x-custom-header").iter().collect::<Vec<_>>(),
+        ["first", "second"]
+    );
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.retry_after.as_deref(), Some("120"));
+    assert_eq!(res.custom, ["first", "second"]);
+
+    let http_res = http::Response::new(Vec::<u8>::new());
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.retry_after, None);
+    assert!(res.custom.is_empty());
+}
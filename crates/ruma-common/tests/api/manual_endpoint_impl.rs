@@ -8,7 +8,7 @@ use ruma_common::{
     api::{
         error::{FromHttpRequestError, FromHttpResponseError, IntoHttpError, MatrixError},
         AuthScheme, EndpointError, IncomingRequest, IncomingResponse, MatrixVersion, Metadata,
-        OutgoingRequest, OutgoingResponse, SendAccessToken, VersionHistory,
+        OutgoingRequest, OutgoingResponse, RateLimitCategory, SendAccessToken, VersionHistory,
     },
     OwnedRoomAliasId, OwnedRoomId,
 };
@@ -24,6 +24,7 @@ pub struct Request {
 const METADATA: Metadata = Metadata {
     method: Method::PUT,
     rate_limited: false,
+    rate_limit_category: RateLimitCategory::General,
     authentication: AuthScheme::None,
     history: VersionHistory::new(
         &["/_matrix/client/unstable/directory/room/:room_alias"],
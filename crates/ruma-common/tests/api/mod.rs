@@ -1,9 +1,12 @@
 #![cfg(feature = "api")]
 
+mod builder;
 mod conversions;
+mod custom_headers;
 mod header_override;
 mod manual_endpoint_impl;
 mod no_fields;
 mod optional_headers;
 mod ruma_api;
 mod ruma_api_macros;
+mod spec_gen;
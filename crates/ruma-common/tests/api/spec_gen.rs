@@ -0,0 +1,83 @@
+#![cfg(feature = "spec-gen")]
+#![allow(clippy::exhaustive_structs)]
+
+use std::collections::BTreeMap;
+
+use http::header::CONTENT_TYPE;
+use ruma_common::{
+    api::{descriptor::DescribeEndpoint, request, response, Metadata},
+    metadata, OwnedUserId,
+};
+use serde_json::{json, to_value as to_json_value};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: true,
+    rate_limit_category: RoomSend,
+    authentication: AccessToken,
+    history: {
+        unstable => "/_matrix/foo/unstable/:user",
+        1.1 => "/_matrix/foo/v1/:user",
+        1.2 => deprecated,
+    }
+};
+
+/// Request type for the `spec_gen` endpoint.
+#[request]
+pub struct Request {
+    #[ruma_api(path)]
+    pub user: OwnedUserId,
+
+    #[ruma_api(query)]
+    pub limit: Option<u32>,
+
+    #[ruma_api(header = CONTENT_TYPE)]
+    pub content_type: String,
+
+    #[serde(rename = "m.data")]
+    pub data: Vec<String>,
+
+    pub ranges: BTreeMap<String, Vec<(u32, u32)>>,
+}
+
+/// Response type for the `spec_gen` endpoint.
+#[response]
+pub struct Response {
+    #[ruma_api(body)]
+    pub value: Option<String>,
+}
+
+#[test]
+fn describe_endpoint() {
+    let descriptor = Request::describe_endpoint();
+
+    assert_eq!(
+        to_json_value(descriptor).unwrap(),
+        json!({
+            "method": "POST",
+            "rate_limited": true,
+            "rate_limit_category": "RoomSend",
+            "authentication": "AccessToken",
+            "unstable_paths": ["/_matrix/foo/unstable/:user"],
+            "stable_paths": [{ "version": "v1.1", "path": "/_matrix/foo/v1/:user" }],
+            "deprecated": "v1.2",
+            "request": {
+                "type_name": "tests::api::spec_gen::Request",
+                "path": [{ "name": "user", "type": "OwnedUserId" }],
+                "query": [{ "name": "limit", "type": "Option<u32>" }],
+                "headers": [{ "name": "content-type", "type": "String" }],
+                "body": {
+                    "kind": "fields",
+                    "fields": [
+                        { "name": "m.data", "type": "Vec<String>" },
+                        { "name": "ranges", "type": "BTreeMap<String, Vec<(u32, u32)>>" },
+                    ],
+                },
+            },
+            "response": {
+                "type_name": "tests::api::spec_gen::Response",
+                "body": { "kind": "newtype", "type": "Option<String>" },
+            },
+        })
+    );
+}
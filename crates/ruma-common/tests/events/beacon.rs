@@ -0,0 +1,101 @@
+#![cfg(feature = "unstable-msc3672")]
+
+use assert_matches::assert_matches;
+use js_int::uint;
+use ruma_common::{
+    event_id,
+    events::{
+        beacon::BeaconEventContent, room::encrypted::Relation, AnyMessageLikeEvent,
+        AnyMessageLikeEventContent, MessageLikeEvent,
+    },
+    room_id,
+    serde::CanBeEmpty,
+    user_id, MilliSecondsSinceUnixEpoch,
+};
+use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+fn get_beacon_event_content() -> BeaconEventContent {
+    BeaconEventContent::new(
+        event_id!("$beacon_info_event_id:example.com").to_owned(),
+        "geo:51.5008,0.1247;u=35".to_owned(),
+        Some(MilliSecondsSinceUnixEpoch(uint!(1_636_829_458))),
+    )
+}
+
+fn get_beacon_event_json() -> serde_json::Value {
+    json!({
+        "content": {
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": "$beacon_info_event_id:example.com",
+            },
+            "org.matrix.msc3488.location": {
+                "uri": "geo:51.5008,0.1247;u=35",
+            },
+            "org.matrix.msc3488.ts": 1_636_829_458,
+        },
+        "event_id": "$beacon_event_id:example.com",
+        "origin_server_ts": 1_636_829_458,
+        "room_id": "!roomid:example.com",
+        "sender": "@example:example.com",
+        "type": "org.matrix.msc3672.beacon",
+    })
+}
+
+#[test]
+fn beacon_event_content_serialization() {
+    let event_content = get_beacon_event_content();
+
+    assert_eq!(
+        to_json_value(&event_content).unwrap(),
+        json!({
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": "$beacon_info_event_id:example.com",
+            },
+            "org.matrix.msc3488.location": {
+                "uri": "geo:51.5008,0.1247;u=35",
+            },
+            "org.matrix.msc3488.ts": 1_636_829_458,
+        })
+    );
+}
+
+#[test]
+fn beacon_event_deserialization() {
+    let event = from_json_value::<AnyMessageLikeEvent>(get_beacon_event_json()).unwrap();
+    let message_event = assert_matches!(
+        event,
+        AnyMessageLikeEvent::Beacon(MessageLikeEvent::Original(message_event)) => message_event
+    );
+
+    assert_eq!(message_event.event_id, "$beacon_event_id:example.com");
+    assert_eq!(message_event.origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(1_636_829_458)));
+    assert_eq!(message_event.room_id, room_id!("!roomid:example.com"));
+    assert_eq!(message_event.sender, user_id!("@example:example.com"));
+    assert!(message_event.unsigned.is_empty());
+
+    let content = message_event.content;
+    assert_eq!(content.relates_to.event_id, event_id!("$beacon_info_event_id:example.com"));
+    assert_eq!(content.location.uri, "geo:51.5008,0.1247;u=35");
+    assert_eq!(content.location.description, None);
+    assert_eq!(content.ts, MilliSecondsSinceUnixEpoch(uint!(1_636_829_458)));
+}
+
+#[test]
+fn beacon_event_stable_deserialization() {
+    let mut json_data = get_beacon_event_json();
+    json_data["type"] = "m.beacon".into();
+
+    let event = from_json_value::<AnyMessageLikeEvent>(json_data).unwrap();
+    assert_matches!(event, AnyMessageLikeEvent::Beacon(MessageLikeEvent::Original(_)));
+}
+
+#[test]
+fn beacon_event_relation() {
+    let content = AnyMessageLikeEventContent::Beacon(get_beacon_event_content());
+
+    let reference =
+        assert_matches!(content.relation(), Some(Relation::Reference(reference)) => reference);
+    assert_eq!(reference.event_id, "$beacon_info_event_id:example.com");
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "unstable-msc3672")]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use assert_matches::assert_matches;
+use js_int::uint;
+use ruma_common::{
+    events::{beacon_info::BeaconInfoEventContent, location::AssetType, AnyStateEvent, StateEvent},
+    serde::Raw,
+    MilliSecondsSinceUnixEpoch,
+};
+use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+fn get_beacon_info_event_content(
+    duration: Option<Duration>,
+    ts: Option<MilliSecondsSinceUnixEpoch>,
+) -> BeaconInfoEventContent {
+    let description = Some("Kylie's live location".to_owned());
+    let duration_or = duration.unwrap_or(Duration::from_secs(60));
+    let ts_or = Some(ts.unwrap_or(MilliSecondsSinceUnixEpoch::now()));
+
+    BeaconInfoEventContent::new(description, duration_or, true, ts_or)
+}
+
+fn get_beacon_info_json() -> serde_json::Value {
+    json!({
+        "type": "org.matrix.msc3672.beacon_info",
+        "sender": "@example:localhost",
+        "state_key": "@example:localhost",
+        "event_id": "$beacon_info_event_id:example.com",
+        "origin_server_ts": 1_636_829_458,
+        "room_id": "!roomid:example.com",
+        "content": {
+            "description": "Kylie's live location",
+            "live": true,
+            "org.matrix.msc3488.ts": 1_636_829_458,
+            "timeout": 60_000,
+            "org.matrix.msc3488.asset": { "type": "m.self" },
+        },
+    })
+}
+
+#[test]
+fn beacon_info_is_live() {
+    let event_content = get_beacon_info_event_content(None, None);
+
+    assert!(event_content.is_live());
+}
+
+#[test]
+fn beacon_info_is_not_live() {
+    let started_at = MilliSecondsSinceUnixEpoch::from_system_time(
+        UNIX_EPOCH + Duration::from_secs(1_636_829_458),
+    );
+    let event_content = get_beacon_info_event_content(None, started_at);
+
+    assert!(!event_content.is_live());
+}
+
+#[test]
+fn beacon_info_stop_event() {
+    let ts = Some(MilliSecondsSinceUnixEpoch(uint!(1_636_829_458)));
+
+    let mut event_content = get_beacon_info_event_content(None, ts);
+    event_content.stop();
+
+    assert_eq!(
+        to_json_value(&event_content).unwrap(),
+        json!({
+            "description": "Kylie's live location",
+            "live": false,
+            "org.matrix.msc3488.ts": 1_636_829_458,
+            "timeout": 60_000,
+            "org.matrix.msc3488.asset": { "type": "m.self" },
+        })
+    );
+}
+
+#[test]
+fn beacon_info_start_event() {
+    let ts = MilliSecondsSinceUnixEpoch(uint!(1_636_829_458));
+
+    let mut event_content = BeaconInfoEventContent::new(
+        Some("Kylie's live location".to_owned()),
+        Duration::from_secs(60),
+        false,
+        Some(ts),
+    );
+    assert!(!event_content.is_live());
+
+    event_content.start();
+
+    assert!(event_content.is_live());
+    assert_ne!(event_content.ts, ts);
+}
+
+#[test]
+fn beacon_info_start_event_content_serialization() {
+    let ts = Some(MilliSecondsSinceUnixEpoch(uint!(1_636_829_458)));
+
+    let event_content = get_beacon_info_event_content(None, ts);
+
+    assert_eq!(
+        to_json_value(&event_content).unwrap(),
+        json!({
+            "description": "Kylie's live location",
+            "live": true,
+            "org.matrix.msc3488.ts": 1_636_829_458,
+            "timeout": 60_000,
+            "org.matrix.msc3488.asset": { "type": "m.self" },
+        })
+    );
+}
+
+#[test]
+fn beacon_info_start_event_deserialization() {
+    let json_data = get_beacon_info_json();
+
+    let event = from_json_value::<AnyStateEvent>(json_data).unwrap();
+    let beacon_info_event = assert_matches!(
+        event,
+        AnyStateEvent::BeaconInfo(StateEvent::Original(beacon_info_event)) => beacon_info_event
+    );
+
+    assert_eq!(beacon_info_event.event_id, "$beacon_info_event_id:example.com");
+    assert_eq!(beacon_info_event.state_key, "@example:localhost");
+    assert_eq!(beacon_info_event.sender, "@example:localhost");
+
+    let content = beacon_info_event.content;
+    assert_eq!(content.description.as_deref(), Some("Kylie's live location"));
+    assert!(content.live);
+    assert_eq!(content.ts, MilliSecondsSinceUnixEpoch(uint!(1_636_829_458)));
+    assert_eq!(content.timeout, Duration::from_secs(60));
+    assert_eq!(content.asset.type_, AssetType::Self_);
+    assert!(!content.is_live());
+}
+
+#[test]
+fn beacon_info_stable_deserialization() {
+    let mut json_data = get_beacon_info_json();
+    json_data["type"] = "m.beacon_info".into();
+
+    let event = from_json_value::<AnyStateEvent>(json_data).unwrap();
+    assert_matches!(event, AnyStateEvent::BeaconInfo(StateEvent::Original(_)));
+}
+
+#[test]
+fn beacon_info_redacted_deserialization() {
+    let json_data = json!({
+        "type": "org.matrix.msc3672.beacon_info",
+        "sender": "@example:localhost",
+        "state_key": "@example:localhost",
+        "event_id": "$beacon_info_event_id:example.com",
+        "origin_server_ts": 1_636_829_458,
+        "room_id": "!roomid:example.com",
+        "content": {},
+        "unsigned": {
+            "redacted_because": {
+                "type": "m.room.redaction",
+                "content": {},
+                "redacts": "$beacon_info_event_id:example.com",
+                "event_id": "$redaction:example.com",
+                "origin_server_ts": 1_636_829_500,
+                "sender": "@example:localhost",
+            },
+        },
+    });
+
+    let event = from_json_value::<Raw<AnyStateEvent>>(json_data).unwrap().deserialize().unwrap();
+    assert_matches!(event, AnyStateEvent::BeaconInfo(StateEvent::Redacted(_)));
+}
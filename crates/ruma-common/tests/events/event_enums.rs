@@ -1,10 +1,14 @@
 use assert_matches::assert_matches;
 use js_int::uint;
 use ruma_common::{
-    events::{AnyMessageLikeEvent, MessageLikeEvent},
-    serde::CanBeEmpty,
+    events::{
+        macros::EventContent, AnyGlobalAccountDataEvent, AnyMessageLikeEvent, AnySyncStateEvent,
+        MessageLikeEvent,
+    },
+    serde::{CanBeEmpty, Raw},
     MilliSecondsSinceUnixEpoch, VoipVersionId,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::{from_value as from_json_value, json};
 
 #[test]
@@ -49,3 +53,53 @@ fn deserialize_message_event() {
     assert_eq!(content.call_id, "foofoo");
     assert_eq!(content.version, VoipVersionId::V0);
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[ruma_event(type = "org.example.settings", kind = GlobalAccountData)]
+struct SettingsEventContent {
+    theme: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[ruma_event(type = "org.example.topic", kind = State, state_key_type = String)]
+struct ExampleTopicEventContent {
+    topic: String,
+}
+
+#[test]
+fn deserialize_static_custom_events() {
+    let account_data = from_json_value::<Raw<AnyGlobalAccountDataEvent>>(json!({
+        "content": { "theme": "dark" },
+        "type": "org.example.settings",
+    }))
+    .unwrap();
+
+    assert_eq!(account_data.deserialize().unwrap().event_type(), "org.example.settings".into());
+    let event = account_data.deserialize_static::<SettingsEventContent>().unwrap().unwrap();
+    assert_eq!(event.content.theme, "dark");
+
+    let state = from_json_value::<Raw<AnySyncStateEvent>>(json!({
+        "content": { "topic": "Custom topic" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "org.example.topic",
+    }))
+    .unwrap();
+
+    let event = state.deserialize_static::<ExampleTopicEventContent>().unwrap().unwrap();
+    assert_eq!(event.as_original().unwrap().content.topic, "Custom topic");
+
+    // The event type doesn't match.
+    let state = from_json_value::<Raw<AnySyncStateEvent>>(json!({
+        "content": { "topic": "Room topic" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.topic",
+    }))
+    .unwrap();
+    assert!(state.deserialize_static::<ExampleTopicEventContent>().unwrap().is_none());
+}
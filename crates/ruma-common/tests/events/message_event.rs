@@ -5,8 +5,8 @@ use ruma_common::{
     events::{
         room::{ImageInfo, MediaSource, ThumbnailInfo},
         sticker::StickerEventContent,
-        AnyMessageLikeEvent, AnyMessageLikeEventContent, AnySyncMessageLikeEvent, MessageLikeEvent,
-        MessageLikeEventType,
+        AnyMessageLikeEvent, AnyMessageLikeEventContent, AnySyncMessageLikeEvent, AnyTimelineEvent,
+        MessageLikeEvent, MessageLikeEventType,
     },
     mxc_uri, room_id,
     serde::{CanBeEmpty, Raw},
@@ -198,7 +198,8 @@ fn deserialize_message_sticker() {
     assert_eq!(content.info.width, Some(uint!(1011)));
     assert_eq!(content.info.mimetype.as_deref(), Some("image/png"));
     assert_eq!(content.info.size, Some(uint!(84242)));
-    assert_eq!(content.url, "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD");
+    let url = assert_matches!(content.source, MediaSource::Plain(url) => url);
+    assert_eq!(url, "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD");
 
     let thumbnail_url = assert_matches!(
         content.info.thumbnail_source,
@@ -212,6 +213,40 @@ fn deserialize_message_sticker() {
     assert_eq!(thumbnail_info.size, Some(uint!(82595)));
 }
 
+#[test]
+fn deserialize_timeline_sticker() {
+    let json_data = json!({
+        "content": {
+            "body": "Hello",
+            "info": {
+                "h": 423,
+                "mimetype": "image/png",
+                "w": 1011
+            },
+            "url": "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD"
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "m.sticker"
+    });
+
+    let event = from_json_value::<AnyTimelineEvent>(json_data).unwrap();
+    assert_eq!(event.event_type().to_string(), "m.sticker");
+    let message_event = assert_matches!(
+        event,
+        AnyTimelineEvent::MessageLike(AnyMessageLikeEvent::Sticker(
+            MessageLikeEvent::Original(message_event)
+        )) => message_event
+    );
+
+    assert_eq!(message_event.event_id, "$h29iv0s8:example.com");
+    assert_eq!(message_event.content.body, "Hello");
+    let url = assert_matches!(message_event.content.source, MediaSource::Plain(url) => url);
+    assert_eq!(url, "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD");
+}
+
 #[test]
 fn deserialize_message_then_convert_to_full() {
     let rid = room_id!("!roomid:room.com");
@@ -1,6 +1,8 @@
 #![cfg(feature = "events")]
 
 mod audio;
+mod beacon;
+mod beacon_info;
 mod call;
 mod encrypted;
 mod enums;
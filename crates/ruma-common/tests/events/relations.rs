@@ -1,11 +1,23 @@
 use assert_matches::assert_matches;
 use assign::assign;
+use js_int::uint;
 use ruma_common::{
     event_id,
     events::{
-        relation::{InReplyTo, Replacement, Thread},
-        room::message::{MessageType, Relation, RoomMessageEventContent},
+        relation::{
+            deserialize_relation, BundledRelations, InReplyTo, Reference, Replacement, Thread,
+        },
+        room::{
+            encrypted,
+            message::{
+                MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
+            },
+            topic::OriginalSyncRoomTopicEvent,
+        },
+        sticker::StickerEventContent,
+        AnyMessageLikeEvent,
     },
+    serde::Raw,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -298,3 +310,238 @@ fn thread_unstable_deserialize() {
     assert_eq!(thread.in_reply_to.event_id, "$latesteventid");
     assert!(!thread.is_falling_back);
 }
+
+#[test]
+fn bundled_thread_deserialize() {
+    let latest_event = json!({
+        "content": {
+            "body": "Hello from the thread",
+            "msgtype": "m.text",
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": "$root",
+                "is_falling_back": true,
+                "m.in_reply_to": { "event_id": "$previous" },
+            },
+        },
+        "event_id": "$latest",
+        "origin_server_ts": 134_829_848,
+        "room_id": "!roomid:notareal.hs",
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+    });
+    let json = json!({
+        "content": {
+            "body": "Thread root",
+            "msgtype": "m.text",
+        },
+        "event_id": "$root",
+        "origin_server_ts": 134_829_848,
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+        "unsigned": {
+            "m.relations": {
+                "m.thread": {
+                    "latest_event": latest_event,
+                    "count": 7,
+                    "current_user_participated": true,
+                },
+            },
+        },
+    });
+
+    let event = from_json_value::<OriginalSyncRoomMessageEvent>(json).unwrap();
+    let thread = event.unsigned.relations.thread.unwrap();
+    assert_eq!(thread.count, uint!(7));
+    assert!(thread.current_user_participated);
+
+    let latest_event = assert_matches!(
+        thread.latest_event.deserialize(),
+        Ok(AnyMessageLikeEvent::RoomMessage(ev)) => ev
+    );
+    let relation = latest_event.as_original().unwrap().content.relates_to.as_ref().unwrap();
+    let thread_relation = assert_matches!(relation, Relation::Thread(thread) => thread);
+    assert_eq!(thread_relation.event_id, "$root");
+    assert!(thread_relation.is_falling_back);
+}
+
+#[test]
+fn bundled_thread_unstable_deserialize() {
+    let event = from_json_value::<Raw<AnyMessageLikeEvent>>(json!({
+        "content": {
+            "body": "Thread root",
+            "msgtype": "m.text",
+        },
+        "event_id": "$root",
+        "origin_server_ts": 134_829_848,
+        "room_id": "!roomid:notareal.hs",
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+        "unsigned": {
+            "m.relations": {
+                "io.element.thread": {
+                    "latest_event": {
+                        "content": {
+                            "body": "Hello from the thread",
+                            "msgtype": "m.text",
+                        },
+                        "event_id": "$latest",
+                        "origin_server_ts": 134_829_848,
+                        "room_id": "!roomid:notareal.hs",
+                        "sender": "@carl:example.com",
+                        "type": "m.room.message",
+                    },
+                    "count": 1,
+                    "current_user_participated": false,
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    let relations = BundledRelations::from_raw_event(&event).unwrap();
+    let thread = relations.thread.as_ref().unwrap();
+    assert_eq!(thread.count, uint!(1));
+    assert!(!thread.current_user_participated);
+
+    // The stable name is used for serialization.
+    let relations = to_json_value(relations).unwrap();
+    assert!(relations.get("m.thread").is_some());
+    assert!(relations.get("io.element.thread").is_none());
+}
+
+#[test]
+fn bundled_relations_deserialize() {
+    let relations = json!({
+        "m.annotation": {
+            "chunk": [{ "type": "m.reaction", "key": "👍", "count": 3 }],
+        },
+        "m.replace": {
+            "event_id": "$replacement",
+            "origin_server_ts": 134_829_848,
+            "sender": "@carl:example.com",
+        },
+        "m.reference": {
+            "chunk": [{ "event_id": "$reference" }],
+        },
+    });
+    let message = json!({
+        "content": {
+            "body": "Hello",
+            "msgtype": "m.text",
+        },
+        "event_id": "$message",
+        "origin_server_ts": 134_829_848,
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+        "unsigned": { "m.relations": relations },
+    });
+    let topic = json!({
+        "content": { "topic": "Hello" },
+        "event_id": "$topic",
+        "origin_server_ts": 134_829_848,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.topic",
+        "unsigned": { "m.relations": relations },
+    });
+
+    let message = from_json_value::<OriginalSyncRoomMessageEvent>(message).unwrap();
+    let topic = from_json_value::<OriginalSyncRoomTopicEvent>(topic).unwrap();
+
+    for relations in [message.unsigned.relations, topic.unsigned.relations] {
+        let replace = relations.replace.unwrap();
+        assert_eq!(replace.event_id, "$replacement");
+        assert_eq!(replace.sender, "@carl:example.com");
+
+        let reference = relations.reference.unwrap();
+        assert_eq!(reference.chunk.len(), 1);
+        assert_eq!(reference.chunk[0].event_id, "$reference");
+
+        assert!(relations.thread.is_none());
+
+        #[cfg(feature = "unstable-msc2677")]
+        {
+            let annotation = relations.annotation.unwrap();
+            assert_eq!(annotation.chunk.len(), 1);
+            assert_eq!(annotation.chunk[0].key, "👍");
+            assert_eq!(annotation.chunk[0].count, uint!(3));
+        }
+    }
+}
+
+#[test]
+fn reference_deserialize() {
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "rel_type": "m.reference",
+            "event_id": "$referenced",
+        },
+    });
+
+    let event_id = assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json),
+        Ok(RoomMessageEventContent {
+            msgtype: MessageType::Text(_),
+            relates_to: Some(Relation::Reference(Reference { event_id, .. })),
+            ..
+        }) => event_id
+    );
+    assert_eq!(event_id, "$referenced");
+}
+
+#[test]
+fn sticker_thread_deserialize() {
+    let json = json!({
+        "body": "Hello",
+        "info": {},
+        "url": "mxc://notareal.hs/abcdef",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$thread_root",
+            "m.in_reply_to": {
+                "event_id": "$latest_event",
+            },
+            "is_falling_back": true,
+        },
+    });
+
+    let thread = assert_matches!(
+        from_json_value::<StickerEventContent>(json),
+        Ok(StickerEventContent { relates_to: Some(Relation::Thread(thread)), .. }) => thread
+    );
+    assert_eq!(thread.event_id, "$thread_root");
+    assert_eq!(thread.in_reply_to.event_id, "$latest_event");
+    assert!(thread.is_falling_back);
+}
+
+#[test]
+fn replacement_to_encrypted_relation() {
+    let content = RoomMessageEventContent::text_plain("edited")
+        .make_replacement(event_id!("$replaced").to_owned(), None);
+    let relation = encrypted::Relation::from(content.relates_to.unwrap());
+
+    assert_eq!(
+        to_json_value(&relation).unwrap(),
+        json!({
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": "$replaced",
+            },
+        })
+    );
+}
+
+#[test]
+fn replacement_missing_new_content() {
+    let json = json!({
+        "m.relates_to": {
+            "rel_type": "m.replace",
+            "event_id": "$replaced",
+        },
+    });
+
+    deserialize_relation::<_, MessageType>(json).unwrap_err();
+}
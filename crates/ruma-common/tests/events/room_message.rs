@@ -6,17 +6,22 @@ use ruma_common::{
     event_id,
     events::{
         key::verification::VerificationMethod,
+        relation::InReplyTo,
         room::{
             message::{
-                AudioMessageEventContent, ForwardThread, KeyVerificationRequestEventContent,
-                MessageType, OriginalRoomMessageEvent, RoomMessageEventContent,
+                AudioMessageEventContent, FileMessageEventContent, ForwardThread,
+                KeyVerificationRequestEventContent, MessageType, OriginalRoomMessageEvent,
+                Relation, ReplacementValidationError, RoomMessageEventContent,
                 TextMessageEventContent,
             },
-            MediaSource,
+            EncryptedFile, JsonWebKey, MediaSource,
         },
+        size::{estimate_pdu_size, SizeWarning, MAX_PDU_SIZE},
         MessageLikeUnsigned,
     },
-    mxc_uri, room_id, user_id, MilliSecondsSinceUnixEpoch, OwnedDeviceId,
+    mxc_uri, room_id,
+    serde::{Base64, JsonObject},
+    user_id, MilliSecondsSinceUnixEpoch, OwnedDeviceId,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -143,6 +148,148 @@ fn custom_content_deserialization() {
     assert_eq!(custom_event.data(), Cow::Owned(expected_json_data));
 }
 
+#[test]
+fn custom_msgtype_extra_fields() {
+    let mut custom_msgtype =
+        MessageType::new("my_custom_msgtype", "my message body".into(), json_object! {}).unwrap();
+    assert!(custom_msgtype.is_custom());
+    custom_msgtype.custom_data_mut().unwrap().insert("custom_field".into(), json!({ "a": 1 }));
+
+    assert_eq!(
+        to_json_value(&custom_msgtype).unwrap(),
+        json!({
+            "msgtype": "my_custom_msgtype",
+            "body": "my message body",
+            "custom_field": { "a": 1 },
+        })
+    );
+
+    let mut text = MessageType::text_plain("Hello");
+    assert!(!text.is_custom());
+    assert_matches!(text.custom_data_mut(), None);
+}
+
+#[test]
+fn custom_content_with_relation_roundtrip() {
+    let json_data = json!({
+        "msgtype": "my_custom_msgtype",
+        "body": "my custom message",
+        "custom_field": "baba",
+        "m.relates_to": {
+            "m.in_reply_to": {
+                "event_id": "$replied_to:example.org",
+            },
+        },
+    });
+
+    let content: RoomMessageEventContent = from_json_value(json_data.clone()).unwrap();
+    assert_eq!(
+        content.msgtype.data(),
+        Cow::<'_, JsonObject>::Owned(json_object! { "custom_field".into() => json!("baba") })
+    );
+    assert_eq!(to_json_value(&content).unwrap(), json_data);
+}
+
+#[test]
+#[cfg(feature = "canonical-json")]
+fn custom_content_canonical_data() {
+    use ruma_common::CanonicalJsonValue;
+
+    let custom_msgtype: MessageType = from_json_value(json!({
+        "msgtype": "my_custom_msgtype",
+        "body": "my custom message",
+        "custom_field": { "nested": [1, 2] },
+    }))
+    .unwrap();
+
+    let data = custom_msgtype.canonical_data().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(
+        data["custom_field"],
+        CanonicalJsonValue::try_from(json!({ "nested": [1, 2] })).unwrap()
+    );
+}
+
+#[test]
+fn trim_to_pdu_size() {
+    let room_id = room_id!("!room:example.org");
+    let sender = user_id!("@alice:example.org");
+
+    let mut content = RoomMessageEventContent::text_html("Hello", "<b>Hello</b>");
+    assert_eq!(content.trim_to_pdu_size(room_id, sender).unwrap(), []);
+    assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { formatted: Some(_), .. })
+    );
+
+    // Removing the formatted body is enough.
+    let mut content = RoomMessageEventContent::text_html("Hello", "a".repeat(MAX_PDU_SIZE));
+    assert_eq!(
+        content.trim_to_pdu_size(room_id, sender).unwrap(),
+        [SizeWarning::FormattedBodyRemoved]
+    );
+    assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { formatted: None, .. })
+    );
+    assert_eq!(content.msgtype.body(), "Hello");
+    #[cfg(feature = "unstable-msc1767")]
+    assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { message: Some(message), .. })
+            if message.find_html().is_none() && message.find_plain() == Some("Hello")
+    );
+
+    // The body needs to be truncated.
+    let mut content = RoomMessageEventContent::text_html("é".repeat(MAX_PDU_SIZE), "<b>Hello</b>");
+    let warnings = content.trim_to_pdu_size(room_id, sender).unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0], SizeWarning::FormattedBodyRemoved);
+    let len = assert_matches!(
+        warnings[1],
+        SizeWarning::BodyTruncated { original_len, len } if original_len == 2 * MAX_PDU_SIZE => len
+    );
+    assert_eq!(content.msgtype.body().len(), len);
+    assert!(estimate_pdu_size(&content, room_id, sender, None).unwrap() <= MAX_PDU_SIZE);
+    #[cfg(feature = "unstable-msc1767")]
+    assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { message: Some(message), .. })
+            if message.find_plain() == Some(content.msgtype.body())
+    );
+}
+
+#[test]
+fn limit_mentions() {
+    let mut content = RoomMessageEventContent::text_html(
+        "alice bob carol",
+        "<a href=\"https://matrix.to/#/@alice:example.org\">alice</a> \
+         <a href=\"https://matrix.to/#/!room:example.org\">room</a> \
+         <a href=\"https://matrix.to/#/@bob:example.org\">bob</a> \
+         <a href=\"https://matrix.to/#/@carol:example.org\">carol</a>",
+    );
+
+    assert_eq!(content.limit_mentions(3), None);
+    assert_eq!(content.limit_mentions(1), Some(SizeWarning::MentionsRemoved { count: 2 }));
+
+    let expected = "<a href=\"https://matrix.to/#/@alice:example.org\">alice</a> \
+                    <a href=\"https://matrix.to/#/!room:example.org\">room</a> bob carol";
+    let formatted = assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { formatted: Some(formatted), .. }) => formatted
+    );
+    assert_eq!(formatted.body, expected);
+    #[cfg(feature = "unstable-msc1767")]
+    assert_matches!(
+        &content.msgtype,
+        MessageType::Text(TextMessageEventContent { message: Some(message), .. })
+            if message.find_html() == Some(expected)
+    );
+
+    let mut content = RoomMessageEventContent::text_plain("@alice:example.org");
+    assert_eq!(content.limit_mentions(0), None);
+}
+
 #[test]
 fn formatted_body_serialization() {
     let message_event_content =
@@ -579,6 +726,65 @@ fn make_replacement_no_reply() {
     assert_eq!(formatted.body, "* This is <em>an edited</em> message.");
 }
 
+#[test]
+fn apply_replacement() {
+    let original_message = |relates_to| {
+        let mut content = RoomMessageEventContent::text_plain("This is a message.");
+        content.relates_to = relates_to;
+
+        OriginalRoomMessageEvent {
+            content,
+            event_id: event_id!("$original:example.org").to_owned(),
+            origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+            room_id: room_id!("!testroomid:example.org").to_owned(),
+            sender: user_id!("@user:example.org").to_owned(),
+            unsigned: MessageLikeUnsigned::default(),
+        }
+    };
+    let replacement = OriginalRoomMessageEvent {
+        content: RoomMessageEventContent::text_plain("This is an edited message.")
+            .make_replacement(event_id!("$original:example.org").to_owned(), None),
+        event_id: event_id!("$replacement:example.org").to_owned(),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(20_000)),
+        room_id: room_id!("!testroomid:example.org").to_owned(),
+        sender: user_id!("@user:example.org").to_owned(),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+
+    // The relation of the original message is kept.
+    let in_reply_to = Relation::Reply {
+        in_reply_to: InReplyTo::new(event_id!("$replied_to:example.org").to_owned()),
+    };
+    let mut message = original_message(Some(in_reply_to));
+    message.apply_replacement(&replacement).unwrap();
+    assert_eq!(message.content.body(), "This is an edited message.");
+    assert_matches!(message.content.relates_to, Some(Relation::Reply { .. }));
+
+    // The replacement must be from the same sender.
+    let mut message = original_message(None);
+    message.sender = user_id!("@other:example.org").to_owned();
+    assert_eq!(
+        message.apply_replacement(&replacement),
+        Err(ReplacementValidationError::DifferentSender)
+    );
+    assert_eq!(message.content.body(), "This is a message.");
+
+    // The replacement must relate to the original message.
+    let mut message = original_message(None);
+    message.event_id = event_id!("$other:example.org").to_owned();
+    assert_eq!(
+        message.apply_replacement(&replacement),
+        Err(ReplacementValidationError::NotAReplacement)
+    );
+
+    // A replacement can't be replaced.
+    let mut message = original_message(replacement.content.relates_to.clone());
+    assert_eq!(
+        message.apply_replacement(&replacement),
+        Err(ReplacementValidationError::OriginalIsReplacement)
+    );
+}
+
 #[test]
 fn make_replacement_with_reply() {
     let replied_to_message = OriginalRoomMessageEvent {
@@ -628,3 +834,41 @@ fn make_replacement_with_reply() {
         "
     );
 }
+
+#[test]
+fn file_into_encrypted_and_plain() {
+    let content = FileMessageEventContent::plain(
+        "file.txt".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        None,
+    );
+
+    let file = EncryptedFile::new(
+        mxc_uri!("mxc://notareal.hs/encrypted").to_owned(),
+        JsonWebKey::new(Base64::new(vec![0; 32])),
+        Base64::new(vec![0; 16]),
+        Base64::new(vec![0; 32]),
+    );
+    let content = content.into_encrypted(file);
+
+    let encrypted = assert_matches!(&content.source, MediaSource::Encrypted(file) => file);
+    assert_eq!(encrypted.url, "mxc://notareal.hs/encrypted");
+    encrypted.validate().unwrap();
+    #[cfg(feature = "unstable-msc3551")]
+    {
+        let file = content.file.as_ref().unwrap();
+        assert_eq!(file.url, "mxc://notareal.hs/encrypted");
+        assert!(file.is_encrypted());
+    }
+
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(json.get("url"), None);
+    assert_eq!(json["file"]["url"], "mxc://notareal.hs/encrypted");
+
+    let content = content.into_plain(mxc_uri!("mxc://notareal.hs/file").to_owned());
+
+    let url = assert_matches!(&content.source, MediaSource::Plain(url) => url);
+    assert_eq!(url, "mxc://notareal.hs/file");
+    #[cfg(feature = "unstable-msc3551")]
+    assert!(!content.file.unwrap().is_encrypted());
+}
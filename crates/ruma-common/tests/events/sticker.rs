@@ -1,8 +1,14 @@
 #![cfg(feature = "unstable-msc3552")]
 
+use assert_matches::assert_matches;
+use js_int::uint;
 use ruma_common::{
-    events::{room::ImageInfo, sticker::StickerEventContent},
+    events::{
+        room::{EncryptedFileInit, ImageInfo, JsonWebKeyInit, MediaSource},
+        sticker::StickerEventContent,
+    },
     mxc_uri,
+    serde::Base64,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -44,7 +50,8 @@ fn content_stable_deserialization() {
 
     let content = from_json_value::<StickerEventContent>(json_data).unwrap();
     assert_eq!(content.body, "Upload: my_image.jpg");
-    assert_eq!(content.url, "mxc://notareal.hs/file");
+    let url = assert_matches!(content.source, MediaSource::Plain(url) => url);
+    assert_eq!(url, "mxc://notareal.hs/file");
     let message = content.message.unwrap();
     assert_eq!(message.len(), 1);
     assert_eq!(message[0].body, "Upload: my_image.jpg");
@@ -68,7 +75,8 @@ fn content_unstable_deserialization() {
 
     let content = from_json_value::<StickerEventContent>(json_data).unwrap();
     assert_eq!(content.body, "Upload: my_image.jpg");
-    assert_eq!(content.url, "mxc://notareal.hs/file");
+    let url = assert_matches!(content.source, MediaSource::Plain(url) => url);
+    assert_eq!(url, "mxc://notareal.hs/file");
     let message = content.message.unwrap();
     assert_eq!(message.len(), 1);
     assert_eq!(message[0].body, "Upload: my_image.jpg");
@@ -76,3 +84,59 @@ fn content_unstable_deserialization() {
     assert_eq!(file.url, "mxc://notareal.hs/file");
     assert!(!file.is_encrypted());
 }
+
+#[test]
+fn encrypted_content_serialization() {
+    let info = ImageInfo::new()
+        .with_dimensions(uint!(64), uint!(32))
+        .with_mimetype("image/png".to_owned())
+        .with_size(uint!(1024));
+
+    let content = StickerEventContent::with_fallback_body(
+        info,
+        MediaSource::Encrypted(Box::new(
+            EncryptedFileInit {
+                url: mxc_uri!("mxc://notareal.hs/file").to_owned(),
+                key: JsonWebKeyInit {
+                    kty: "oct".to_owned(),
+                    key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+                    alg: "A256CTR".to_owned(),
+                    k: Base64::new(vec![0; 32]),
+                    ext: true,
+                }
+                .into(),
+                iv: Base64::new(vec![0; 16]),
+                hashes: [("sha256".to_owned(), Base64::new(vec![0; 32]))].into(),
+                v: "v2".to_owned(),
+            }
+            .into(),
+        )),
+    );
+
+    assert_eq!(content.body, "Sticker (64x32)");
+
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(json["body"], "Sticker (64x32)");
+    assert_eq!(json["info"], json!({ "w": 64, "h": 32, "mimetype": "image/png", "size": 1024 }));
+    assert_eq!(json.get("url"), None);
+    assert_eq!(json["file"]["url"], "mxc://notareal.hs/file");
+    assert_eq!(json["file"]["v"], "v2");
+    assert_eq!(json["org.matrix.msc1767.file"]["url"], "mxc://notareal.hs/file");
+    assert_eq!(json["org.matrix.msc1767.file"]["v"], "v2");
+
+    let content = from_json_value::<StickerEventContent>(json).unwrap();
+    assert_eq!(content.body, "Sticker (64x32)");
+    let file = assert_matches!(content.source, MediaSource::Encrypted(file) => file);
+    assert_eq!(file.url, "mxc://notareal.hs/file");
+    assert!(content.file.unwrap().is_encrypted());
+}
+
+#[test]
+fn empty_body_is_kept() {
+    let content = StickerEventContent::new(
+        String::new(),
+        ImageInfo::new().with_dimensions(uint!(64), uint!(32)),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    assert_eq!(content.body, "");
+}
@@ -8,11 +8,13 @@ use js_int::uint;
 use ruma_common::{
     event_id,
     events::{
-        audio::AudioContent,
+        audio::{Amplitude, AudioContent, Waveform},
         file::{FileContent, FileContentInfo},
         relation::InReplyTo,
         room::{
-            message::{AudioMessageEventContent, MessageType, Relation, RoomMessageEventContent},
+            message::{
+                AudioInfo, AudioMessageEventContent, MessageType, Relation, RoomMessageEventContent,
+            },
             MediaSource,
         },
         voice::{VoiceContent, VoiceEventContent},
@@ -151,6 +153,53 @@ fn room_message_serialization() {
     );
 }
 
+#[test]
+fn room_message_voice_plain_serialization() {
+    let waveform = Waveform::try_from(vec![Amplitude::from(256); 30]).unwrap();
+    let message_event_content = RoomMessageEventContent::voice_plain(
+        "Upload: voice_message.ogg",
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        Some(Box::new(assign!(AudioInfo::new(), { duration: Some(Duration::from_secs(15)) }))),
+        Some(waveform),
+    );
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Upload: voice_message.ogg",
+            "url": "mxc://notareal.hs/file",
+            "info": {
+                "duration": 15_000,
+            },
+            "msgtype": "m.audio",
+            "org.matrix.msc1767.text": "Upload: voice_message.ogg",
+            "org.matrix.msc1767.file": {
+                "url": "mxc://notareal.hs/file",
+            },
+            "org.matrix.msc1767.audio": {
+                "duration": 15_000,
+                "waveform": vec![256; 30],
+            },
+            "org.matrix.msc3245.voice": {},
+        })
+    );
+}
+
+#[test]
+fn room_message_voice_plain_without_waveform() {
+    let message_event_content = RoomMessageEventContent::voice_plain(
+        "Upload: voice_message.ogg",
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        None,
+        None,
+    );
+
+    let content =
+        assert_matches!(message_event_content.msgtype, MessageType::Audio(content) => content);
+    assert!(content.voice.is_some());
+    assert!(content.audio.unwrap().waveform.is_none());
+}
+
 #[test]
 fn room_message_stable_deserialization() {
     let json_data = json!({
@@ -6,3 +6,98 @@ pub mod get_event_by_timestamp;
 pub mod get_missing_events;
 pub mod get_room_state;
 pub mod get_room_state_ids;
+
+use std::collections::BTreeSet;
+
+use ruma_common::{EventId, OwnedEventId};
+use serde::{de::IgnoredAny, Deserialize};
+use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+
+/// An error encountered when validating the events returned by a homeserver.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum EventValidationError {
+    /// A PDU couldn't be deserialized or its event ID couldn't be computed.
+    #[error("invalid PDU: {0}")]
+    InvalidPdu(String),
+
+    /// The returned event is not the event that was requested.
+    #[error("expected event {expected}, got {found}")]
+    UnexpectedEvent {
+        /// The ID of the requested event.
+        expected: OwnedEventId,
+
+        /// The ID of the returned event.
+        found: OwnedEventId,
+    },
+
+    /// An event ID is present several times in the same list.
+    #[error("duplicate event {0}")]
+    DuplicateEvent(OwnedEventId),
+
+    /// An event references an auth event that is not in the auth chain.
+    #[error("auth event {auth_event_id} of event {event_id} is missing from the auth chain")]
+    MissingAuthEvent {
+        /// The ID of the event referencing the missing auth event.
+        event_id: OwnedEventId,
+
+        /// The ID of the missing auth event.
+        auth_event_id: OwnedEventId,
+    },
+}
+
+/// The fields of a PDU that are needed to validate it.
+#[derive(Deserialize)]
+struct PduAuthEvents {
+    auth_events: Vec<AuthEventReference>,
+}
+
+/// A reference to an auth event in a PDU.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AuthEventReference {
+    /// An event ID, as used in room versions 3 and later.
+    EventId(OwnedEventId),
+
+    /// An event ID and its hashes, as used in room versions 1 and 2.
+    EventIdWithHashes(OwnedEventId, IgnoredAny),
+}
+
+impl AuthEventReference {
+    fn into_event_id(self) -> OwnedEventId {
+        match self {
+            Self::EventId(event_id) | Self::EventIdWithHashes(event_id, _) => event_id,
+        }
+    }
+}
+
+/// Get the ID of the given PDU with the given function, and the IDs of its auth events.
+fn parse_pdu(
+    pdu: &RawJsonValue,
+    event_id_of: &mut impl FnMut(&RawJsonValue) -> Option<OwnedEventId>,
+) -> Result<(OwnedEventId, Vec<OwnedEventId>), EventValidationError> {
+    let event_id = event_id_of(pdu).ok_or_else(|| {
+        EventValidationError::InvalidPdu("could not compute the event ID".to_owned())
+    })?;
+
+    let PduAuthEvents { auth_events } = from_json_str(pdu.get())
+        .map_err(|error| EventValidationError::InvalidPdu(error.to_string()))?;
+    let auth_events = auth_events.into_iter().map(AuthEventReference::into_event_id).collect();
+
+    Ok((event_id, auth_events))
+}
+
+/// Check that the given event IDs don't contain duplicates, and collect them.
+fn collect_unique<'a>(
+    event_ids: impl IntoIterator<Item = &'a EventId>,
+) -> Result<BTreeSet<&'a EventId>, EventValidationError> {
+    let mut set = BTreeSet::new();
+
+    for event_id in event_ids {
+        if !set.insert(event_id) {
+            return Err(EventValidationError::DuplicateEvent(event_id.to_owned()));
+        }
+    }
+
+    Ok(set)
+}
@@ -9,10 +9,12 @@ pub mod v1 {
 
     use ruma_common::{
         api::{request, response, Metadata},
-        metadata, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedServerName,
+        metadata, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedServerName,
     };
     use serde_json::value::RawValue as RawJsonValue;
 
+    use crate::event::{collect_unique, parse_pdu, EventValidationError};
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
@@ -60,5 +62,88 @@ pub mod v1 {
         ) -> Self {
             Self { origin, origin_server_ts, pdu }
         }
+
+        /// Check that the event in this response is the requested event.
+        ///
+        /// This checks that the ID of the event is `event_id`, and that its auth events don't
+        /// contain duplicates.
+        ///
+        /// `event_id_of` must return the ID of the given PDU, or `None` if it can't be computed.
+        /// Since room version 3, the ID of an event is not part of the PDU and must be computed
+        /// from its reference hash.
+        pub fn validate(
+            &self,
+            event_id: &EventId,
+            mut event_id_of: impl FnMut(&RawJsonValue) -> Option<OwnedEventId>,
+        ) -> Result<(), EventValidationError> {
+            let (found, auth_events) = parse_pdu(&self.pdu, &mut event_id_of)?;
+
+            if found != event_id {
+                return Err(EventValidationError::UnexpectedEvent {
+                    expected: event_id.to_owned(),
+                    found,
+                });
+            }
+
+            collect_unique(auth_events.iter().map(|id| &**id))?;
+            Ok(())
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use assert_matches::assert_matches;
+        use ruma_common::{event_id, server_name, MilliSecondsSinceUnixEpoch, OwnedEventId};
+        use serde::Deserialize;
+        use serde_json::{json, value::RawValue as RawJsonValue};
+
+        use super::Response;
+        use crate::event::EventValidationError;
+
+        fn response(event_id: &str, auth_events: &[&str]) -> Response {
+            let pdu = serde_json::value::to_raw_value(&json!({
+                "event_id": event_id,
+                "auth_events": auth_events,
+            }))
+            .unwrap();
+
+            Response::new(
+                server_name!("matrix.localhost").to_owned(),
+                MilliSecondsSinceUnixEpoch::now(),
+                pdu,
+            )
+        }
+
+        fn event_id_of(pdu: &RawJsonValue) -> Option<OwnedEventId> {
+            #[derive(Deserialize)]
+            struct Pdu {
+                event_id: OwnedEventId,
+            }
+
+            serde_json::from_str::<Pdu>(pdu.get()).ok().map(|pdu| pdu.event_id)
+        }
+
+        #[test]
+        fn validate_event() {
+            let event_id = event_id!("$topic");
+            response("$topic", &["$create", "$member"]).validate(event_id, event_id_of).unwrap();
+
+            // Mismatched event ID.
+            let err = response("$name", &["$create", "$member"])
+                .validate(event_id, event_id_of)
+                .unwrap_err();
+            let (expected, found) = assert_matches!(
+                err,
+                EventValidationError::UnexpectedEvent { expected, found } => (expected, found)
+            );
+            assert_eq!(expected, event_id);
+            assert_eq!(found, event_id!("$name"));
+
+            // Duplicate auth event.
+            let err = response("$topic", &["$create", "$member", "$create"])
+                .validate(event_id, event_id_of)
+                .unwrap_err();
+            let duplicate = assert_matches!(err, EventValidationError::DuplicateEvent(id) => id);
+            assert_eq!(duplicate, event_id!("$create"));
+        }
     }
 }
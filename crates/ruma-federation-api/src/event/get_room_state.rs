@@ -13,6 +13,8 @@ pub mod v1 {
     };
     use serde_json::value::RawValue as RawJsonValue;
 
+    use crate::event::{collect_unique, parse_pdu, EventValidationError};
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
@@ -57,5 +59,109 @@ pub mod v1 {
         pub fn new(auth_chain: Vec<Box<RawJsonValue>>, pdus: Vec<Box<RawJsonValue>>) -> Self {
             Self { auth_chain, pdus }
         }
+
+        /// Check that the auth chain and the state in this response are consistent.
+        ///
+        /// This checks that there are no duplicate events in `auth_chain` and in `pdus`, and that
+        /// all the auth events of the events in `auth_chain` and `pdus` are in `auth_chain`.
+        ///
+        /// `event_id_of` must return the ID of the given PDU, or `None` if it can't be computed.
+        /// Since room version 3, the ID of an event is not part of the PDU and must be computed
+        /// from its reference hash.
+        pub fn validate(
+            &self,
+            mut event_id_of: impl FnMut(&RawJsonValue) -> Option<OwnedEventId>,
+        ) -> Result<(), EventValidationError> {
+            let auth_chain = self
+                .auth_chain
+                .iter()
+                .map(|pdu| parse_pdu(pdu, &mut event_id_of))
+                .collect::<Result<Vec<_>, _>>()?;
+            let pdus = self
+                .pdus
+                .iter()
+                .map(|pdu| parse_pdu(pdu, &mut event_id_of))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let auth_chain_ids = collect_unique(auth_chain.iter().map(|(id, _)| &**id))?;
+            collect_unique(pdus.iter().map(|(id, _)| &**id))?;
+
+            for (event_id, auth_events) in auth_chain.iter().chain(&pdus) {
+                if let Some(auth_event_id) =
+                    auth_events.iter().find(|id| !auth_chain_ids.contains(&***id))
+                {
+                    return Err(EventValidationError::MissingAuthEvent {
+                        event_id: event_id.clone(),
+                        auth_event_id: auth_event_id.clone(),
+                    });
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use assert_matches::assert_matches;
+        use ruma_common::{event_id, OwnedEventId};
+        use serde::Deserialize;
+        use serde_json::{json, value::RawValue as RawJsonValue};
+
+        use super::Response;
+        use crate::event::EventValidationError;
+
+        fn pdu(event_id: &str, auth_events: &[&str]) -> Box<RawJsonValue> {
+            serde_json::value::to_raw_value(&json!({
+                "event_id": event_id,
+                "auth_events": auth_events,
+            }))
+            .unwrap()
+        }
+
+        fn event_id_of(pdu: &RawJsonValue) -> Option<OwnedEventId> {
+            #[derive(Deserialize)]
+            struct Pdu {
+                event_id: OwnedEventId,
+            }
+
+            serde_json::from_str::<Pdu>(pdu.get()).ok().map(|pdu| pdu.event_id)
+        }
+
+        #[test]
+        fn validate_state() {
+            let auth_chain = vec![pdu("$create", &[]), pdu("$member", &["$create"])];
+            let pdus = vec![pdu("$create", &[]), pdu("$topic", &["$create", "$member"])];
+            Response::new(auth_chain.clone(), pdus.clone()).validate(event_id_of).unwrap();
+
+            // Duplicate event.
+            let mut duplicate_pdus = pdus.clone();
+            duplicate_pdus.push(pdu("$topic", &["$create", "$member"]));
+            let err = Response::new(auth_chain.clone(), duplicate_pdus)
+                .validate(event_id_of)
+                .unwrap_err();
+            let event_id = assert_matches!(err, EventValidationError::DuplicateEvent(id) => id);
+            assert_eq!(event_id, event_id!("$topic"));
+
+            // Missing auth event.
+            let mut missing_auth_pdus = pdus;
+            missing_auth_pdus.push(pdu("$name", &["$create", "$power_levels"]));
+            let err =
+                Response::new(auth_chain, missing_auth_pdus).validate(event_id_of).unwrap_err();
+            let (event_id, auth_event_id) = assert_matches!(
+                err,
+                EventValidationError::MissingAuthEvent { event_id, auth_event_id } => {
+                    (event_id, auth_event_id)
+                }
+            );
+            assert_eq!(event_id, event_id!("$name"));
+            assert_eq!(auth_event_id, event_id!("$power_levels"));
+
+            // Invalid PDU.
+            let err = Response::new(vec![pdu("$create", &[])], vec![pdu("invalid", &[])])
+                .validate(event_id_of)
+                .unwrap_err();
+            assert_matches!(err, EventValidationError::InvalidPdu(_));
+        }
     }
 }
@@ -12,6 +12,8 @@ pub mod v1 {
         metadata, OwnedEventId, OwnedRoomId,
     };
 
+    use crate::event::{collect_unique, EventValidationError};
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
@@ -56,5 +58,51 @@ pub mod v1 {
         pub fn new(auth_chain_ids: Vec<OwnedEventId>, pdu_ids: Vec<OwnedEventId>) -> Self {
             Self { auth_chain_ids, pdu_ids }
         }
+
+        /// Check that the auth chain IDs and the state IDs in this response are consistent.
+        ///
+        /// This checks that there are no duplicate event IDs in `auth_chain_ids` and in `pdu_ids`.
+        /// The auth events of the events can only be checked once they were fetched, with the
+        /// `validate` method of the [`get_room_state`] response for example.
+        ///
+        /// [`get_room_state`]: crate::event::get_room_state
+        pub fn validate(&self) -> Result<(), EventValidationError> {
+            collect_unique(self.auth_chain_ids.iter().map(|id| &**id))?;
+            collect_unique(self.pdu_ids.iter().map(|id| &**id))?;
+            Ok(())
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use assert_matches::assert_matches;
+        use ruma_common::{event_id, OwnedEventId};
+
+        use super::Response;
+        use crate::event::EventValidationError;
+
+        fn event_ids(ids: &[&str]) -> Vec<OwnedEventId> {
+            ids.iter().map(|id| (*id).try_into().unwrap()).collect()
+        }
+
+        #[test]
+        fn validate_state_ids() {
+            let auth_chain_ids = event_ids(&["$create", "$member"]);
+            let pdu_ids = event_ids(&["$create", "$topic"]);
+            Response::new(auth_chain_ids.clone(), pdu_ids.clone()).validate().unwrap();
+
+            // Duplicate in the auth chain.
+            let err = Response::new(event_ids(&["$create", "$member", "$member"]), pdu_ids)
+                .validate()
+                .unwrap_err();
+            let event_id = assert_matches!(err, EventValidationError::DuplicateEvent(id) => id);
+            assert_eq!(event_id, event_id!("$member"));
+
+            // Duplicate in the state.
+            let err = Response::new(auth_chain_ids, event_ids(&["$create", "$topic", "$create"]))
+                .validate()
+                .unwrap_err();
+            let event_id = assert_matches!(err, EventValidationError::DuplicateEvent(id) => id);
+            assert_eq!(event_id, event_id!("$create"));
+        }
     }
 }
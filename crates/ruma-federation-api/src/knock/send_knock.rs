@@ -19,6 +19,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: PUT,
         rate_limited: false,
+        rate_limit_category: RoomJoin,
         authentication: ServerSignatures,
         history: {
             unstable => "/_matrix/federation/unstable/xyz.amorgan.knock/send_knock/:room_id/:event_id",
@@ -22,7 +22,11 @@ pub mod keys;
 pub mod knock;
 pub mod membership;
 pub mod openid;
+#[cfg(feature = "unstable-msc2444")]
+pub mod peek;
 pub mod query;
+#[cfg(feature = "unstable-msc3843")]
+pub mod room;
 pub mod space;
 pub mod thirdparty;
 pub mod transactions;
@@ -14,6 +14,7 @@ use serde_json::value::RawValue as RawJsonValue;
 const METADATA: Metadata = metadata! {
     method: PUT,
     rate_limited: false,
+    rate_limit_category: Invite,
     authentication: ServerSignatures,
     history: {
         1.0 => "/_matrix/federation/v2/invite/:room_id/:event_id",
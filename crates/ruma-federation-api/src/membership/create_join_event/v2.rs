@@ -13,6 +13,7 @@ use super::RoomState;
 const METADATA: Metadata = metadata! {
     method: PUT,
     rate_limited: false,
+    rate_limit_category: RoomJoin,
     authentication: ServerSignatures,
     history: {
         1.0 => "/_matrix/federation/v2/send_join/:room_id/:event_id",
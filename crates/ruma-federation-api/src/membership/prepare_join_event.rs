@@ -16,6 +16,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
+        rate_limit_category: RoomJoin,
         authentication: ServerSignatures,
         history: {
             1.0 => "/_matrix/federation/v1/make_join/:room_id/:user_id",
@@ -0,0 +1,6 @@
+//! Endpoints for peeking into rooms over federation ([MSC2444]).
+//!
+//! [MSC2444]: https://github.com/matrix-org/matrix-spec-proposals/pull/2444
+
+pub mod create_peek;
+pub mod delete_peek;
@@ -0,0 +1,104 @@
+//! `PUT /_matrix/federation/*/peek/{roomId}/{peekId}`
+//!
+//! Start peeking into a room, or renew an existing peek, to receive its events without joining
+//! it.
+
+pub mod unstable {
+    //! `/unstable/` ([spec])
+    //!
+    //! [spec]: https://github.com/matrix-org/matrix-spec-proposals/pull/2444
+
+    use std::time::Duration;
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata, OwnedRoomId, RoomVersionId,
+    };
+    use serde_json::value::RawValue as RawJsonValue;
+
+    const METADATA: Metadata = metadata! {
+        method: PUT,
+        rate_limited: false,
+        authentication: ServerSignatures,
+        history: {
+            unstable => "/_matrix/federation/unstable/peek/:room_id/:peek_id",
+        }
+    };
+
+    /// Request type for the `create_peek` endpoint.
+    #[request]
+    pub struct Request {
+        /// The room to peek into.
+        #[ruma_api(path)]
+        pub room_id: OwnedRoomId,
+
+        /// An opaque ID chosen by the peeking server to identify this peek.
+        ///
+        /// Reusing the ID of an existing peek renews it.
+        #[ruma_api(path)]
+        pub peek_id: String,
+
+        /// The room versions the sending server has support for.
+        ///
+        /// Defaults to `&[RoomVersionId::V1]`.
+        #[ruma_api(query)]
+        #[serde(default = "default_ver", skip_serializing_if = "is_default_ver")]
+        pub ver: Vec<RoomVersionId>,
+    }
+
+    /// Response type for the `create_peek` endpoint.
+    #[response]
+    pub struct Response {
+        /// The version of the room.
+        pub room_version: RoomVersionId,
+
+        /// The full set of authorization events that make up the state of the room, and their
+        /// authorization events, recursively.
+        pub auth_chain: Vec<Box<RawJsonValue>>,
+
+        /// The resolved state of the room at the `latest_event`.
+        pub state: Vec<Box<RawJsonValue>>,
+
+        /// The latest event of the room, from which the peeking server can start following it.
+        pub latest_event: Box<RawJsonValue>,
+
+        /// The duration after which the peek expires, unless it is renewed.
+        ///
+        /// If this is not set, the peeking server should renew the peek periodically at an
+        /// interval of its choosing.
+        #[serde(
+            with = "ruma_common::serde::duration::opt_ms",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub renewal_interval: Option<Duration>,
+    }
+
+    fn default_ver() -> Vec<RoomVersionId> {
+        vec![RoomVersionId::V1]
+    }
+
+    fn is_default_ver(ver: &[RoomVersionId]) -> bool {
+        *ver == [RoomVersionId::V1]
+    }
+
+    impl Request {
+        /// Creates a new `Request` with the given room ID and peek ID.
+        pub fn new(room_id: OwnedRoomId, peek_id: String) -> Self {
+            Self { room_id, peek_id, ver: vec![RoomVersionId::V1] }
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given room version, auth chain, state and latest
+        /// event.
+        pub fn new(
+            room_version: RoomVersionId,
+            auth_chain: Vec<Box<RawJsonValue>>,
+            state: Vec<Box<RawJsonValue>>,
+            latest_event: Box<RawJsonValue>,
+        ) -> Self {
+            Self { room_version, auth_chain, state, latest_event, renewal_interval: None }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! `DELETE /_matrix/federation/*/peek/{roomId}/{peekId}`
+//!
+//! Stop peeking into a room.
+
+pub mod unstable {
+    //! `/unstable/` ([spec])
+    //!
+    //! [spec]: https://github.com/matrix-org/matrix-spec-proposals/pull/2444
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata, OwnedRoomId,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: DELETE,
+        rate_limited: false,
+        authentication: ServerSignatures,
+        history: {
+            unstable => "/_matrix/federation/unstable/peek/:room_id/:peek_id",
+        }
+    };
+
+    /// Request type for the `delete_peek` endpoint.
+    #[request]
+    pub struct Request {
+        /// The room to stop peeking into.
+        #[ruma_api(path)]
+        pub room_id: OwnedRoomId,
+
+        /// The ID of the peek to stop.
+        #[ruma_api(path)]
+        pub peek_id: String,
+    }
+
+    /// Response type for the `delete_peek` endpoint.
+    #[response]
+    #[derive(Default)]
+    pub struct Response {}
+
+    impl Request {
+        /// Creates a new `Request` with the given room ID and peek ID.
+        pub fn new(room_id: OwnedRoomId, peek_id: String) -> Self {
+            Self { room_id, peek_id }
+        }
+    }
+
+    impl Response {
+        /// Creates an empty `Response`.
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+}
@@ -0,0 +1,3 @@
+//! Endpoints for room management.
+
+pub mod report_content;
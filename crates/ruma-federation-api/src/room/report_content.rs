@@ -0,0 +1,146 @@
+//! `POST /_matrix/federation/*/rooms/{roomId}/report/{eventId}`
+//!
+//! Report content to the server of the room's creator or of the event's sender.
+
+pub mod unstable {
+    //! `/unstable/` ([spec])
+    //!
+    //! [spec]: https://github.com/matrix-org/matrix-spec-proposals/pull/3843
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata, OwnedEventId, OwnedRoomId,
+    };
+    use serde::{Deserialize, Serialize};
+
+    const METADATA: Metadata = metadata! {
+        method: POST,
+        rate_limited: false,
+        authentication: ServerSignatures,
+        history: {
+            unstable => "/_matrix/federation/unstable/org.matrix.msc3843/rooms/:room_id/report/:event_id",
+        }
+    };
+
+    /// Request type for the `report_content` endpoint.
+    #[request]
+    pub struct Request {
+        /// The room in which the reported event is located.
+        #[ruma_api(path)]
+        pub room_id: OwnedRoomId,
+
+        /// The event to report.
+        #[ruma_api(path)]
+        pub event_id: OwnedEventId,
+
+        /// The content of the report.
+        #[ruma_api(body)]
+        pub content: ReportContent,
+    }
+
+    /// Response type for the `report_content` endpoint.
+    #[response]
+    #[derive(Default)]
+    pub struct Response {}
+
+    impl Request {
+        /// Creates a new `Request` with the given room ID, event ID and report content.
+        pub fn new(room_id: OwnedRoomId, event_id: OwnedEventId, content: ReportContent) -> Self {
+            Self { room_id, event_id, content }
+        }
+    }
+
+    impl Response {
+        /// Creates an empty `Response`.
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    /// The content of an abuse report sent over federation.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct ReportContent {
+        /// The reason the content is being reported.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+    }
+
+    impl ReportContent {
+        /// Creates a new `ReportContent` with the given reason.
+        pub fn new(reason: Option<String>) -> Self {
+            Self { reason }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[cfg(all(feature = "client", feature = "server"))]
+        use ruma_common::{
+            api::{IncomingRequest as _, MatrixVersion, OutgoingRequest, SendAccessToken},
+            event_id, room_id,
+        };
+        #[cfg(all(feature = "client", feature = "server"))]
+        use serde_json::{from_slice as from_json_slice, Value as JsonValue};
+        use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+        use super::ReportContent;
+        #[cfg(all(feature = "client", feature = "server"))]
+        use super::Request;
+
+        #[test]
+        fn report_content_serde_round_trip() {
+            let content = ReportContent::new(Some("Spam".to_owned()));
+            let json = json!({ "reason": "Spam" });
+            assert_eq!(to_json_value(&content).unwrap(), json);
+
+            let content = from_json_value::<ReportContent>(json).unwrap();
+            assert_eq!(content.reason.as_deref(), Some("Spam"));
+        }
+
+        #[test]
+        fn report_content_without_reason_serde_round_trip() {
+            let content = ReportContent::default();
+            let json = json!({});
+            assert_eq!(to_json_value(&content).unwrap(), json);
+
+            let content = from_json_value::<ReportContent>(json).unwrap();
+            assert_eq!(content.reason, None);
+        }
+
+        #[cfg(all(feature = "client", feature = "server"))]
+        #[test]
+        fn request_round_trip() {
+            let room_id = room_id!("!room:localhost");
+            let event_id = event_id!("$event:localhost");
+            let req = Request::new(
+                room_id.to_owned(),
+                event_id.to_owned(),
+                ReportContent::new(Some("Spam".to_owned())),
+            );
+
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://matrix.localhost",
+                    SendAccessToken::None,
+                    &[MatrixVersion::V1_4],
+                )
+                .unwrap();
+            assert_eq!(
+                http_req.uri(),
+                "https://matrix.localhost/_matrix/federation/unstable/org.matrix.msc3843/rooms/%21room%3Alocalhost/report/%24event%3Alocalhost"
+            );
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+                json!({ "reason": "Spam" })
+            );
+
+            let req =
+                Request::try_from_http_request(http_req, &[room_id.as_str(), event_id.as_str()])
+                    .unwrap();
+            assert_eq!(req.room_id, room_id);
+            assert_eq!(req.event_id, event_id);
+            assert_eq!(req.content.reason.as_deref(), Some("Spam"));
+        }
+    }
+}
@@ -7,14 +7,20 @@ use serde::{
     Deserialize, Serialize,
 };
 
+use crate::transactions::send_transaction_message::v1::{PduFailureKind, PduProcessingError};
+
 #[derive(Deserialize, Serialize)]
 struct WrappedError {
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+
+    #[cfg(feature = "unstable-unspecified")]
+    #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
+    retryable: bool,
 }
 
 pub fn serialize<S>(
-    response: &BTreeMap<OwnedEventId, Result<(), String>>,
+    response: &BTreeMap<OwnedEventId, Result<(), PduProcessingError>>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
 where
@@ -25,8 +31,10 @@ where
         let wrapped_error = WrappedError {
             error: match value {
                 Ok(_) => None,
-                Err(error) => Some(error.clone()),
+                Err(error) => Some(error.message.clone()),
             },
+            #[cfg(feature = "unstable-unspecified")]
+            retryable: matches!(value, Err(error) if error.kind == PduFailureKind::Retryable),
         };
         map.serialize_entry(&key, &wrapped_error)?;
     }
@@ -36,14 +44,14 @@ where
 #[allow(clippy::type_complexity)]
 pub fn deserialize<'de, D>(
     deserializer: D,
-) -> Result<BTreeMap<OwnedEventId, Result<(), String>>, D::Error>
+) -> Result<BTreeMap<OwnedEventId, Result<(), PduProcessingError>>, D::Error>
 where
     D: Deserializer<'de>,
 {
     struct PduProcessResponseVisitor;
 
     impl<'de> Visitor<'de> for PduProcessResponseVisitor {
-        type Value = BTreeMap<OwnedEventId, Result<(), String>>;
+        type Value = BTreeMap<OwnedEventId, Result<(), PduProcessingError>>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
             formatter.write_str("A map of EventIds to a map of optional errors")
@@ -58,7 +66,17 @@ where
             while let Some((key, value)) = access.next_entry::<OwnedEventId, WrappedError>()? {
                 let v = match value.error {
                     None => Ok(()),
-                    Some(error) => Err(error),
+                    Some(message) => Err(PduProcessingError {
+                        message,
+                        #[cfg(feature = "unstable-unspecified")]
+                        kind: if value.retryable {
+                            PduFailureKind::Retryable
+                        } else {
+                            PduFailureKind::Permanent
+                        },
+                        #[cfg(not(feature = "unstable-unspecified"))]
+                        kind: PduFailureKind::Permanent,
+                    }),
                 };
                 map.insert(key, v);
             }
@@ -77,13 +95,14 @@ mod tests {
     use serde_json::{json, value::Serializer as JsonSerializer};
 
     use super::{deserialize, serialize};
+    use crate::transactions::send_transaction_message::v1::PduProcessingError;
 
     #[test]
     fn serialize_error() {
-        let mut response: BTreeMap<OwnedEventId, Result<(), String>> = BTreeMap::new();
+        let mut response: BTreeMap<OwnedEventId, Result<(), PduProcessingError>> = BTreeMap::new();
         response.insert(
             event_id!("$someevent:matrix.org").to_owned(),
-            Err("Some processing error.".into()),
+            Err(PduProcessingError::new("Some processing error.".to_owned())),
         );
 
         let serialized = serialize(&response, JsonSerializer).unwrap();
@@ -93,9 +112,25 @@ mod tests {
         assert_eq!(serialized, json);
     }
 
+    #[test]
+    #[cfg(feature = "unstable-unspecified")]
+    fn serialize_retryable_error() {
+        let mut response: BTreeMap<OwnedEventId, Result<(), PduProcessingError>> = BTreeMap::new();
+        response.insert(
+            event_id!("$someevent:matrix.org").to_owned(),
+            Err(PduProcessingError::retryable("Some processing error.".to_owned())),
+        );
+
+        let serialized = serialize(&response, JsonSerializer).unwrap();
+        let json = json!({
+            "$someevent:matrix.org": { "error": "Some processing error.", "retryable": true }
+        });
+        assert_eq!(serialized, json);
+    }
+
     #[test]
     fn serialize_ok() {
-        let mut response: BTreeMap<OwnedEventId, Result<(), String>> = BTreeMap::new();
+        let mut response: BTreeMap<OwnedEventId, Result<(), PduProcessingError>> = BTreeMap::new();
         response.insert(event_id!("$someevent:matrix.org").to_owned(), Ok(()));
 
         let serialized = serialize(&response, serde_json::value::Serializer).unwrap();
@@ -115,7 +150,24 @@ mod tests {
         let event_id = event_id!("$someevent:matrix.org");
 
         let event_response = response.get(event_id).unwrap().clone().unwrap_err();
-        assert_eq!(event_response, "Some processing error.");
+        assert_eq!(event_response, PduProcessingError::new("Some processing error.".to_owned()));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-unspecified")]
+    fn deserialize_retryable_error() {
+        let json = json!({
+            "$someevent:matrix.org": { "error": "Some processing error.", "retryable": true }
+        });
+
+        let response = deserialize(json).unwrap();
+        let event_id = event_id!("$someevent:matrix.org");
+
+        let event_response = response.get(event_id).unwrap().clone().unwrap_err();
+        assert_eq!(
+            event_response,
+            PduProcessingError::retryable("Some processing error.".to_owned())
+        );
     }
 
     #[test]
@@ -140,7 +192,7 @@ mod tests {
         let event_id = event_id!("$someevent:matrix.org");
 
         let event_response = response.get(event_id).unwrap().clone().unwrap_err();
-        assert_eq!(event_response, "");
+        assert_eq!(event_response, PduProcessingError::new(String::new()));
     }
 
     #[test]
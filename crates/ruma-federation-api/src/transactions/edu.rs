@@ -5,11 +5,15 @@ use std::collections::BTreeMap;
 use js_int::UInt;
 use ruma_common::{
     encryption::{CrossSigningKey, DeviceKeys},
-    events::{receipt::Receipt, AnyToDeviceEventContent, ToDeviceEventType},
-    presence::PresenceState,
+    events::{
+        receipt::{Receipt, ReceiptEventContent, ReceiptType, Receipts},
+        AnyToDeviceEventContent, ToDeviceEventType,
+    },
+    presence::{self, PresenceState},
     serde::{from_raw_json_value, Raw},
     to_device::DeviceIdOrAllDevices,
-    OwnedDeviceId, OwnedEventId, OwnedRoomId, OwnedTransactionId, OwnedUserId,
+    MilliSecondsSinceUnixEpoch, OwnedDeviceId, OwnedEventId, OwnedRoomId, OwnedTransactionId,
+    OwnedUserId,
 };
 use serde::{de, Deserialize, Serialize};
 use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
@@ -129,6 +133,41 @@ impl PresenceUpdate {
             currently_active: false,
         }
     }
+
+    /// Convert this update, received at `received_at`, to a [`presence::PresenceUpdate`].
+    pub fn to_presence_update(
+        &self,
+        received_at: MilliSecondsSinceUnixEpoch,
+    ) -> presence::PresenceUpdate {
+        let mut update = presence::PresenceUpdate::with_last_active_ago(
+            self.presence.clone(),
+            Some(self.last_active_ago),
+            received_at,
+        );
+        update.status_msg = self.status_msg.clone();
+        update.currently_active = Some(self.currently_active);
+        update
+    }
+
+    /// Creates a new `PresenceUpdate` for the given user from the given
+    /// [`presence::PresenceUpdate`], to be sent at `now`.
+    ///
+    /// If the time of the last activity of the user is unknown, `last_active_ago` is set to `0`.
+    pub fn from_presence_update(
+        user_id: OwnedUserId,
+        update: &presence::PresenceUpdate,
+        now: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        Self {
+            status_msg: update.status_msg.clone(),
+            currently_active: update.currently_active.unwrap_or(false),
+            ..Self::new(
+                user_id,
+                update.presence.clone(),
+                update.last_active_ago(now).unwrap_or_default(),
+            )
+        }
+    }
 }
 
 /// The content for "m.receipt" Edu.
@@ -145,6 +184,65 @@ impl ReceiptContent {
     pub fn new(receipts: BTreeMap<OwnedRoomId, ReceiptMap>) -> Self {
         Self { receipts }
     }
+
+    /// Convert this EDU to the content of the `m.receipt` events to send to clients, for each
+    /// room.
+    pub fn into_event_contents(self) -> BTreeMap<OwnedRoomId, ReceiptEventContent> {
+        self.receipts.into_iter().map(|(room_id, map)| (room_id, map.into())).collect()
+    }
+
+    /// Merge the given receipt EDUs, keeping only the newest receipt of each user per room and
+    /// thread.
+    ///
+    /// Receipts are compared by their `ts`, a receipt without `ts` being older than any receipt
+    /// with one. When two receipts have the same `ts`, the one that comes last wins. This avoids
+    /// moving a receipt backwards when several transactions are processed at once.
+    ///
+    /// Like with [`ReceiptMap::from_event_content`], the receipts of the same user for different
+    /// threads are split into several EDUs. This returns an empty `Vec` if there are no receipts.
+    pub fn merge(edus: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut room_receipts: BTreeMap<OwnedRoomId, BTreeMap<OwnedUserId, Vec<ReceiptData>>> =
+            BTreeMap::new();
+
+        for edu in edus {
+            for (room_id, map) in edu.receipts {
+                let user_receipts = room_receipts.entry(room_id).or_default();
+
+                for (user_id, data) in map.read {
+                    let user_receipts = user_receipts.entry(user_id).or_default();
+
+                    match user_receipts.iter_mut().find(|d| d.data.thread == data.data.thread) {
+                        Some(newest) => {
+                            if data.data.ts >= newest.data.ts {
+                                *newest = data;
+                            }
+                        }
+                        None => user_receipts.push(data),
+                    }
+                }
+            }
+        }
+
+        let mut merged: Vec<Self> = Vec::new();
+        for (room_id, user_receipts) in room_receipts {
+            for (user_id, receipts) in user_receipts {
+                for (i, data) in receipts.into_iter().enumerate() {
+                    if merged.len() == i {
+                        merged.push(Self::new(BTreeMap::new()));
+                    }
+
+                    merged[i]
+                        .receipts
+                        .entry(room_id.clone())
+                        .or_insert_with(|| ReceiptMap::new(BTreeMap::new()))
+                        .read
+                        .insert(user_id.clone(), data);
+                }
+            }
+        }
+
+        merged
+    }
 }
 
 /// Mapping between user and `ReceiptData`.
@@ -161,6 +259,66 @@ impl ReceiptMap {
     pub fn new(read: BTreeMap<OwnedUserId, ReceiptData>) -> Self {
         Self { read }
     }
+
+    /// Create the receipt maps to send over federation from the content of an `m.receipt` event.
+    ///
+    /// Only public read receipts are federated, other receipt types are ignored.
+    ///
+    /// A `ReceiptMap` can only hold a single receipt per user, so the receipts of the same user
+    /// for different threads are split into several maps, which must be sent in separate EDUs.
+    /// This returns an empty `Vec` if there are no receipts to federate.
+    pub fn from_event_content(content: &ReceiptEventContent) -> Vec<Self> {
+        let mut user_receipts: BTreeMap<&OwnedUserId, Vec<ReceiptData>> = BTreeMap::new();
+
+        for (event_id, receipts) in content.iter() {
+            let read = match receipts.get(&ReceiptType::Read) {
+                Some(read) => read,
+                None => continue,
+            };
+
+            for (user_id, receipt) in read {
+                let user_receipts = user_receipts.entry(user_id).or_default();
+
+                match user_receipts.iter_mut().find(|data| data.data.thread == receipt.thread) {
+                    Some(data) => data.event_ids.push(event_id.clone()),
+                    None => user_receipts
+                        .push(ReceiptData::new(receipt.clone(), vec![event_id.clone()])),
+                }
+            }
+        }
+
+        let mut maps: Vec<Self> = Vec::new();
+        for (user_id, receipts) in user_receipts {
+            for (i, data) in receipts.into_iter().enumerate() {
+                if maps.len() == i {
+                    maps.push(Self::new(BTreeMap::new()));
+                }
+
+                maps[i].read.insert(user_id.clone(), data);
+            }
+        }
+
+        maps
+    }
+}
+
+impl From<ReceiptMap> for ReceiptEventContent {
+    fn from(map: ReceiptMap) -> Self {
+        let mut content: BTreeMap<OwnedEventId, Receipts> = BTreeMap::new();
+
+        for (user_id, ReceiptData { data, event_ids }) in map.read {
+            for event_id in event_ids {
+                content
+                    .entry(event_id)
+                    .or_default()
+                    .entry(ReceiptType::Read)
+                    .or_default()
+                    .insert(user_id.clone(), data.clone());
+            }
+        }
+
+        ReceiptEventContent(content)
+    }
 }
 
 /// Metadata about the event that was last read and when.
@@ -316,7 +474,11 @@ impl SigningKeyUpdateContent {
 mod test {
     use assert_matches::assert_matches;
     use js_int::uint;
-    use ruma_common::{room_id, user_id};
+    use ruma_common::{
+        event_id,
+        events::receipt::{ReceiptThread, ReceiptType},
+        room_id, user_id, MilliSecondsSinceUnixEpoch,
+    };
     use serde_json::json;
 
     use super::*;
@@ -442,6 +604,165 @@ mod test {
         assert_eq!(serde_json::to_value(&edu).unwrap(), json);
     }
 
+    #[test]
+    fn receipt_edu_to_event_content() {
+        let json = json!({
+            "!some_room:example.org": {
+                "m.read": {
+                    "@john:matrix.org": {
+                        "data": { "ts": 1_533_358, "thread_id": "main" },
+                        "event_ids": ["$first:matrix.org", "$second:matrix.org"]
+                    },
+                    "@jane:matrix.org": {
+                        "data": { "ts": 1_533_359 },
+                        "event_ids": ["$second:matrix.org"]
+                    }
+                }
+            }
+        });
+        let content = serde_json::from_value::<ReceiptContent>(json).unwrap();
+
+        let mut contents = content.into_event_contents();
+        let content = contents.remove(room_id!("!some_room:example.org")).unwrap();
+        assert!(contents.is_empty());
+        assert_eq!(content.len(), 2);
+
+        let (event_id, receipt) =
+            content.user_receipt(user_id!("@john:matrix.org"), ReceiptType::Read).unwrap();
+        assert!(event_id == "$first:matrix.org" || event_id == "$second:matrix.org");
+        assert_eq!(receipt.thread, ReceiptThread::Main);
+
+        let read = content[event_id!("$second:matrix.org")].get(&ReceiptType::Read).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(
+            read[user_id!("@jane:matrix.org")].ts,
+            Some(MilliSecondsSinceUnixEpoch(uint!(1_533_359)))
+        );
+    }
+
+    #[test]
+    fn receipt_event_content_to_edu() {
+        let content = serde_json::from_value::<ReceiptEventContent>(json!({
+            "$first:matrix.org": {
+                "m.read": {
+                    "@john:matrix.org": { "ts": 1_533_358 },
+                },
+                "m.read.private": {
+                    "@jane:matrix.org": { "ts": 1_533_358 },
+                },
+            },
+            "$second:matrix.org": {
+                "m.read": {
+                    "@john:matrix.org": { "ts": 1_533_359, "thread_id": "$thread:matrix.org" },
+                },
+            },
+        }))
+        .unwrap();
+
+        let maps = ReceiptMap::from_event_content(&content);
+        // John's receipts are in different threads so they must be sent separately, and Jane's
+        // private receipt must not be sent.
+        assert_eq!(maps.len(), 2);
+        for map in &maps {
+            assert_eq!(map.read.len(), 1);
+            assert!(!map.read.contains_key(user_id!("@jane:matrix.org")));
+        }
+
+        let mut threads: Vec<_> = maps
+            .iter()
+            .map(|map| {
+                let data = &map.read[user_id!("@john:matrix.org")];
+                (data.data.thread.as_str().map(ToOwned::to_owned), data.event_ids.clone())
+            })
+            .collect();
+        threads.sort();
+        assert_eq!(
+            threads,
+            [
+                (None, vec![event_id!("$first:matrix.org").to_owned()]),
+                (
+                    Some("$thread:matrix.org".to_owned()),
+                    vec![event_id!("$second:matrix.org").to_owned()]
+                ),
+            ]
+        );
+
+        assert!(ReceiptMap::from_event_content(&ReceiptEventContent(BTreeMap::new())).is_empty());
+    }
+
+    #[test]
+    fn merge_receipt_edus() {
+        let edu = |receipts| serde_json::from_value::<ReceiptContent>(receipts).unwrap();
+        let first = edu(json!({
+            "!room:matrix.org": {
+                "m.read": {
+                    "@john:matrix.org": {
+                        "data": { "ts": 1_533_360 },
+                        "event_ids": ["$third:matrix.org"],
+                    },
+                    "@jane:matrix.org": {
+                        "data": { "ts": 1_533_358 },
+                        "event_ids": ["$first:matrix.org"],
+                    },
+                },
+            },
+        }));
+        let second = edu(json!({
+            "!room:matrix.org": {
+                "m.read": {
+                    "@john:matrix.org": {
+                        "data": { "ts": 1_533_359 },
+                        "event_ids": ["$second:matrix.org"],
+                    },
+                    "@jane:matrix.org": {
+                        "data": { "ts": 1_533_359 },
+                        "event_ids": ["$second:matrix.org"],
+                    },
+                },
+            },
+        }));
+        let third = edu(json!({
+            "!room:matrix.org": {
+                "m.read": {
+                    "@john:matrix.org": {
+                        "data": { "ts": 1_533_358, "thread_id": "$thread:matrix.org" },
+                        "event_ids": ["$first:matrix.org"],
+                    },
+                },
+            },
+            "!other:matrix.org": {
+                "m.read": {
+                    "@jane:matrix.org": {
+                        "data": { "ts": 1_533_358 },
+                        "event_ids": ["$other:matrix.org"],
+                    },
+                },
+            },
+        }));
+
+        let merged = ReceiptContent::merge([first, second, third]);
+        // John's receipts are in different threads so they must be sent separately.
+        assert_eq!(merged.len(), 2);
+
+        let room = &merged[0].receipts[room_id!("!room:matrix.org")].read;
+        assert_eq!(room.len(), 2);
+        let john = &room[user_id!("@john:matrix.org")];
+        assert_eq!(john.data.ts, Some(MilliSecondsSinceUnixEpoch(uint!(1_533_360))));
+        assert_eq!(john.event_ids, [event_id!("$third:matrix.org")]);
+        let jane = &room[user_id!("@jane:matrix.org")];
+        assert_eq!(jane.event_ids, [event_id!("$second:matrix.org")]);
+        let other = &merged[0].receipts[room_id!("!other:matrix.org")].read;
+        assert_eq!(other[user_id!("@jane:matrix.org")].event_ids, [event_id!("$other:matrix.org")]);
+
+        let room = &merged[1].receipts[room_id!("!room:matrix.org")].read;
+        assert_eq!(room.len(), 1);
+        let john = &room[user_id!("@john:matrix.org")];
+        assert_eq!(john.data.thread.as_str(), Some("$thread:matrix.org"));
+        assert_eq!(john.event_ids, [event_id!("$first:matrix.org")]);
+
+        assert!(ReceiptContent::merge([]).is_empty());
+    }
+
     #[test]
     fn typing_edu() {
         let json = json!({
@@ -550,4 +871,26 @@ mod test {
 
         assert_eq!(serde_json::to_value(&edu).unwrap(), json);
     }
+
+    #[test]
+    fn presence_update_last_active_ago() {
+        let mut edu_update = PresenceUpdate::new(
+            user_id!("@alice:example.org").to_owned(),
+            PresenceState::Online,
+            uint!(5_000),
+        );
+        edu_update.currently_active = true;
+
+        let update = edu_update.to_presence_update(MilliSecondsSinceUnixEpoch(uint!(20_000)));
+        assert_eq!(update.last_active, Some(MilliSecondsSinceUnixEpoch(uint!(15_000))));
+        assert_eq!(update.currently_active, Some(true));
+
+        let edu_update = PresenceUpdate::from_presence_update(
+            user_id!("@alice:example.org").to_owned(),
+            &update,
+            MilliSecondsSinceUnixEpoch(uint!(21_000)),
+        );
+        assert_eq!(edu_update.last_active_ago, uint!(6_000));
+        assert!(edu_update.currently_active);
+    }
 }
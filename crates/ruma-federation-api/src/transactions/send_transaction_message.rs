@@ -7,13 +7,13 @@ pub mod v1 {
     //!
     //! [spec]: https://spec.matrix.org/v1.4/server-server-api/#put_matrixfederationv1sendtxnid
 
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, fmt};
 
     use ruma_common::{
         api::{request, response, Metadata},
         metadata,
         serde::Raw,
-        MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedServerName, OwnedTransactionId,
+        EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedServerName, OwnedTransactionId,
     };
     use serde_json::value::RawValue as RawJsonValue;
 
@@ -22,6 +22,7 @@ pub mod v1 {
     const METADATA: Metadata = metadata! {
         method: PUT,
         rate_limited: false,
+        rate_limit_category: Transaction,
         authentication: ServerSignatures,
         history: {
             1.0 => "/_matrix/federation/v1/send/:transaction_id",
@@ -71,7 +72,7 @@ pub mod v1 {
         /// See [MSC3618](https://github.com/matrix-org/matrix-spec-proposals/pull/3618).
         #[cfg_attr(feature = "unstable-msc3618", serde(default))]
         #[serde(with = "crate::serde::pdu_process_response")]
-        pub pdus: BTreeMap<OwnedEventId, Result<(), String>>,
+        pub pdus: BTreeMap<OwnedEventId, Result<(), PduProcessingError>>,
     }
 
     impl Request {
@@ -89,8 +90,128 @@ pub mod v1 {
 
     impl Response {
         /// Creates a new `Response` with the given PDUs.
-        pub fn new(pdus: BTreeMap<OwnedEventId, Result<(), String>>) -> Self {
+        pub fn new(pdus: BTreeMap<OwnedEventId, Result<(), PduProcessingError>>) -> Self {
             Self { pdus }
         }
+
+        /// Returns an iterator over the IDs of the PDUs that were processed successfully.
+        pub fn successful_pdus(&self) -> impl Iterator<Item = &EventId> {
+            self.pdus.iter().filter(|(_, result)| result.is_ok()).map(|(id, _)| &**id)
+        }
+
+        /// Returns an iterator over the IDs of the PDUs that could not be processed, with the
+        /// error returned by the receiving server.
+        pub fn failed_pdus(&self) -> impl Iterator<Item = (&EventId, &PduProcessingError)> {
+            self.pdus.iter().filter_map(|(id, result)| match result {
+                Ok(()) => None,
+                Err(error) => Some((&**id, error)),
+            })
+        }
+
+        /// Returns an iterator over the IDs of the PDUs that could not be processed because of a
+        /// temporary failure of the receiving server, with their error.
+        ///
+        /// These PDUs can be sent again in a later transaction.
+        pub fn retryable_pdus(&self) -> impl Iterator<Item = (&EventId, &PduProcessingError)> {
+            self.failed_pdus().filter(|(_, error)| error.kind == PduFailureKind::Retryable)
+        }
+
+        /// Returns an iterator over the IDs of the PDUs that were rejected by the receiving
+        /// server, with their error.
+        ///
+        /// These PDUs should not be sent again: the errors are about the PDUs themselves.
+        pub fn permanently_failed_pdus(
+            &self,
+        ) -> impl Iterator<Item = (&EventId, &PduProcessingError)> {
+            self.failed_pdus().filter(|(_, error)| error.kind == PduFailureKind::Permanent)
+        }
+    }
+
+    /// The error returned by the receiving server for a PDU of a transaction.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct PduProcessingError {
+        /// The description of the error.
+        pub message: String,
+
+        /// Whether the failure is temporary.
+        ///
+        /// The Matrix specification doesn't allow the receiving server to tell whether a PDU
+        /// can be retried, so this is only (de)serialized with the `unstable-unspecified`
+        /// feature, as the `retryable` field. Otherwise, failures are considered permanent.
+        pub kind: PduFailureKind,
+    }
+
+    impl PduProcessingError {
+        /// Creates a new permanent `PduProcessingError` with the given message.
+        pub fn new(message: String) -> Self {
+            Self { message, kind: PduFailureKind::Permanent }
+        }
+
+        /// Creates a new retryable `PduProcessingError` with the given message.
+        pub fn retryable(message: String) -> Self {
+            Self { message, kind: PduFailureKind::Retryable }
+        }
+    }
+
+    impl fmt::Display for PduProcessingError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.message)
+        }
+    }
+
+    impl std::error::Error for PduProcessingError {}
+
+    /// Whether a PDU that failed to be processed can be sent again.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub enum PduFailureKind {
+        /// The PDU was rejected, sending it again would fail the same way.
+        #[default]
+        Permanent,
+
+        /// The PDU could not be processed because of a temporary failure of the receiving
+        /// server, like a missing event that could not be fetched.
+        Retryable,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeMap;
+
+        use ruma_common::event_id;
+
+        use super::{PduProcessingError, Response};
+
+        #[test]
+        fn partition_pdus() {
+            let invalid = PduProcessingError::new("Invalid signature".to_owned());
+            let missing = PduProcessingError::retryable("Missing prev event".to_owned());
+            let response = Response::new(BTreeMap::from([
+                (event_id!("$ok:localhost").to_owned(), Ok(())),
+                (event_id!("$invalid:localhost").to_owned(), Err(invalid.clone())),
+                (event_id!("$missing:localhost").to_owned(), Err(missing.clone())),
+            ]));
+
+            assert_eq!(
+                response.successful_pdus().collect::<Vec<_>>(),
+                [event_id!("$ok:localhost")]
+            );
+            assert_eq!(
+                response.failed_pdus().collect::<Vec<_>>(),
+                [
+                    (event_id!("$invalid:localhost"), &invalid),
+                    (event_id!("$missing:localhost"), &missing)
+                ]
+            );
+            assert_eq!(
+                response.retryable_pdus().collect::<Vec<_>>(),
+                [(event_id!("$missing:localhost"), &missing)]
+            );
+            assert_eq!(
+                response.permanently_failed_pdus().collect::<Vec<_>>(),
+                [(event_id!("$invalid:localhost"), &invalid)]
+            );
+        }
     }
 }
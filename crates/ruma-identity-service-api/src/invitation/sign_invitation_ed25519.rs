@@ -46,6 +46,9 @@ pub mod v2 {
         pub sender: OwnedUserId,
 
         /// The signature of the mxid, sender and token.
+        ///
+        /// It can be verified with the public keys of the identity server by using
+        /// `ruma_signatures::verify_third_party_invite_signed`.
         pub signatures: ServerSignatures,
 
         /// The token for the invitation.
@@ -11,6 +11,7 @@ pub mod v2 {
         api::{request, response, Metadata},
         metadata,
         room::RoomType,
+        serde::Base64,
         thirdparty::Medium,
         OwnedMxcUri, OwnedRoomAliasId, OwnedRoomId, OwnedUserId,
     };
@@ -140,10 +141,10 @@ pub mod v2 {
     #[allow(clippy::exhaustive_structs)]
     pub struct PublicKeys {
         /// The server's long-term public key.
-        pub server_key: String,
+        pub server_key: PublicKey,
 
         /// The generated ephemeral public key.
-        pub ephemeral_key: String,
+        pub ephemeral_key: PublicKey,
     }
 
     impl<'de> Deserialize<'de> for PublicKeys {
@@ -151,7 +152,7 @@ pub mod v2 {
         where
             D: serde::Deserializer<'de>,
         {
-            let [server_key, ephemeral_key] = <[String; 2]>::deserialize(deserializer)?;
+            let [server_key, ephemeral_key] = <[PublicKey; 2]>::deserialize(deserializer)?;
 
             Ok(Self { server_key, ephemeral_key })
         }
@@ -170,4 +171,64 @@ pub mod v2 {
             seq.end()
         }
     }
+
+    /// A public key with which the invitation may be signed.
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct PublicKey {
+        /// The public key, encoded using unpadded base64.
+        pub public_key: Base64,
+
+        /// The URI of an endpoint where the validity of this key can be checked by passing it as
+        /// a `public_key` query parameter.
+        pub key_validity_url: String,
+    }
+
+    impl PublicKey {
+        /// Creates a new `PublicKey` with the given public key and key validity URL.
+        pub fn new(public_key: Base64, key_validity_url: String) -> Self {
+            Self { public_key, key_validity_url }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ruma_common::serde::Base64;
+        use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+        use super::{PublicKey, PublicKeys};
+
+        #[test]
+        fn public_keys_serde() {
+            let json = json!([
+                {
+                    "key_validity_url": "https://magic.forest/_matrix/identity/v2/pubkey/isvalid",
+                    "public_key": "c2VydmVyUHVibGljS2V5",
+                },
+                {
+                    "key_validity_url": "https://magic.forest/_matrix/identity/v2/pubkey/ephemeral/isvalid",
+                    "public_key": "ZXBoZW1lcmFsUHVibGljS2V5",
+                },
+            ]);
+
+            let keys = from_json_value::<PublicKeys>(json.clone()).unwrap();
+            assert_eq!(keys.server_key.public_key.as_bytes(), b"serverPublicKey");
+            assert_eq!(
+                keys.ephemeral_key.key_validity_url,
+                "https://magic.forest/_matrix/identity/v2/pubkey/ephemeral/isvalid"
+            );
+
+            let keys = PublicKeys {
+                server_key: PublicKey::new(
+                    Base64::new(b"serverPublicKey".to_vec()),
+                    "https://magic.forest/_matrix/identity/v2/pubkey/isvalid".to_owned(),
+                ),
+                ephemeral_key: PublicKey::new(
+                    Base64::new(b"ephemeralPublicKey".to_vec()),
+                    "https://magic.forest/_matrix/identity/v2/pubkey/ephemeral/isvalid".to_owned(),
+                ),
+            };
+            assert_eq!(to_json_value(keys).unwrap(), json);
+        }
+    }
 }
@@ -1,8 +1,10 @@
 //! Details of the `#[ruma_api(...)]` attributes.
 
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
-    Ident, Token, Type,
+    Ident, LitStr, Token, Type,
 };
 
 mod kw {
@@ -13,6 +15,7 @@ mod kw {
     syn::custom_keyword!(query_map);
     syn::custom_keyword!(header);
     syn::custom_keyword!(error);
+    syn::custom_keyword!(builder);
     syn::custom_keyword!(manual_body_serde);
 }
 
@@ -22,7 +25,7 @@ pub enum RequestMeta {
     Path,
     Query,
     QueryMap,
-    Header(Ident),
+    Header(HeaderName),
 }
 
 impl Parse for RequestMeta {
@@ -54,7 +57,8 @@ impl Parse for RequestMeta {
 }
 
 pub enum DeriveRequestMeta {
-    Error(Type),
+    Error(Box<Type>),
+    Builder,
 }
 
 impl Parse for DeriveRequestMeta {
@@ -63,7 +67,10 @@ impl Parse for DeriveRequestMeta {
         if lookahead.peek(kw::error) {
             let _: kw::error = input.parse()?;
             let _: Token![=] = input.parse()?;
-            input.parse().map(Self::Error)
+            input.parse().map(|ty| Self::Error(Box::new(ty)))
+        } else if lookahead.peek(kw::builder) {
+            let _: kw::builder = input.parse()?;
+            Ok(Self::Builder)
         } else {
             Err(lookahead.error())
         }
@@ -73,7 +80,7 @@ impl Parse for DeriveRequestMeta {
 pub enum ResponseMeta {
     NewtypeBody,
     RawBody,
-    Header(Ident),
+    Header(HeaderName),
 }
 
 impl Parse for ResponseMeta {
@@ -95,6 +102,73 @@ impl Parse for ResponseMeta {
     }
 }
 
+/// The name of the HTTP header of a field.
+pub enum HeaderName {
+    /// A `const` of type `http::header::HeaderName`, like `CONTENT_TYPE`.
+    Const(Ident),
+
+    /// A lowercase string literal, like `"x-custom-header"`.
+    Literal(LitStr),
+}
+
+impl HeaderName {
+    /// Produces code for the `http::header::HeaderName`.
+    pub fn expand(&self, http: &TokenStream) -> TokenStream {
+        match self {
+            Self::Const(ident) => quote! { #ident },
+            Self::Literal(lit) => quote! { #http::header::HeaderName::from_static(#lit) },
+        }
+    }
+
+    /// Produces code for the name of the header as a `&str`.
+    pub fn expand_str(&self) -> TokenStream {
+        match self {
+            Self::Const(ident) => quote! { #ident.as_str() },
+            Self::Literal(lit) => quote! { #lit },
+        }
+    }
+
+    /// The name of the header as it is written in the attribute.
+    pub fn to_name_string(&self) -> String {
+        match self {
+            Self::Const(ident) => ident.to_string(),
+            Self::Literal(lit) => lit.value(),
+        }
+    }
+}
+
+impl Parse for HeaderName {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            let value = lit.value();
+
+            // `HeaderName::from_static` panics if the name is not lowercase or contains invalid
+            // characters, so reject them at compile time.
+            let is_valid = !value.is_empty()
+                && value.bytes().all(|b| {
+                    b.is_ascii_lowercase()
+                        || b.is_ascii_digit()
+                        || b"!#$%&'*+-.^_`|~".contains(&b)
+                });
+            if !is_valid {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "header names must be non-empty and only contain lowercase ASCII letters, \
+                     digits and token symbols",
+                ));
+            }
+
+            Ok(Self::Literal(lit))
+        } else if lookahead.peek(Ident) {
+            input.parse().map(Self::Const)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum DeriveResponseMeta {
     ManualBodySerde,
@@ -5,12 +5,16 @@ use syn::parse::{Parse, ParseStream};
 mod kw {
     syn::custom_keyword!(None);
     syn::custom_keyword!(AccessToken);
+    syn::custom_keyword!(AccessTokenOptional);
+    syn::custom_keyword!(AppserviceToken);
     syn::custom_keyword!(ServerSignatures);
 }
 
 pub enum AuthScheme {
     None(kw::None),
     AccessToken(kw::AccessToken),
+    AccessTokenOptional(kw::AccessTokenOptional),
+    AppserviceToken(kw::AppserviceToken),
     ServerSignatures(kw::ServerSignatures),
 }
 
@@ -22,6 +26,10 @@ impl Parse for AuthScheme {
             input.parse().map(Self::None)
         } else if lookahead.peek(kw::AccessToken) {
             input.parse().map(Self::AccessToken)
+        } else if lookahead.peek(kw::AccessTokenOptional) {
+            input.parse().map(Self::AccessTokenOptional)
+        } else if lookahead.peek(kw::AppserviceToken) {
+            input.parse().map(Self::AppserviceToken)
         } else if lookahead.peek(kw::ServerSignatures) {
             input.parse().map(Self::ServerSignatures)
         } else {
@@ -35,6 +43,8 @@ impl ToTokens for AuthScheme {
         match self {
             AuthScheme::None(kw) => kw.to_tokens(tokens),
             AuthScheme::AccessToken(kw) => kw.to_tokens(tokens),
+            AuthScheme::AccessTokenOptional(kw) => kw.to_tokens(tokens),
+            AuthScheme::AppserviceToken(kw) => kw.to_tokens(tokens),
             AuthScheme::ServerSignatures(kw) => kw.to_tokens(tokens),
         }
     }
@@ -7,11 +7,13 @@ use syn::{
 };
 
 use super::{
-    attribute::{DeriveRequestMeta, RequestMeta},
+    attribute::{DeriveRequestMeta, HeaderName, RequestMeta},
     ensure_feature_presence,
 };
 use crate::util::import_ruma_common;
 
+mod builder;
+mod describe;
 mod incoming;
 mod outgoing;
 
@@ -21,10 +23,15 @@ pub fn expand_request(attr: RequestAttr, item: ItemStruct) -> TokenStream {
 
     let maybe_feature_error = ensure_feature_presence().map(syn::Error::to_compile_error);
 
-    let error_ty = attr.0.first().map_or_else(
-        || quote! { #ruma_common::api::error::MatrixError },
-        |DeriveRequestMeta::Error(ty)| quote! { #ty },
-    );
+    let mut error_ty = quote! { #ruma_common::api::error::MatrixError };
+    let mut builder_attr = None;
+
+    for meta in attr.0 {
+        match meta {
+            DeriveRequestMeta::Error(ty) => error_ty = quote! { #ty },
+            DeriveRequestMeta::Builder => builder_attr = Some(quote! { #[ruma_api(builder)] }),
+        }
+    }
 
     quote! {
         #maybe_feature_error
@@ -32,6 +39,7 @@ pub fn expand_request(attr: RequestAttr, item: ItemStruct) -> TokenStream {
         #[derive(Clone, Debug, #ruma_macros::Request, #ruma_common::serde::_FakeDeriveSerde)]
         #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
         #[ruma_api(error = #error_ty)]
+        #builder_attr
         #item
     }
 }
@@ -53,6 +61,7 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
     let fields = fields.into_iter().map(RequestField::try_from).collect::<syn::Result<_>>()?;
 
     let mut error_ty = None;
+    let mut builder = false;
 
     for attr in input.attrs {
         if !attr.path.is_ident("ruma_api") {
@@ -63,7 +72,8 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
             attr.parse_args_with(Punctuated::<DeriveRequestMeta, Token![,]>::parse_terminated)?;
         for meta in metas {
             match meta {
-                DeriveRequestMeta::Error(t) => error_ty = Some(t),
+                DeriveRequestMeta::Error(t) => error_ty = Some(*t),
+                DeriveRequestMeta::Builder => builder = true,
             }
         }
     }
@@ -73,14 +83,17 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
         generics: input.generics,
         fields,
         error_ty: error_ty.expect("missing error_ty attribute"),
+        builder,
     };
 
     let ruma_common = import_ruma_common();
     let test = request.check(&ruma_common)?;
     let types_impls = request.expand_all(&ruma_common);
+    let builder = request.builder.then(|| request.expand_builder()).transpose()?;
 
     Ok(quote! {
         #types_impls
+        #builder
         #test
     })
 }
@@ -91,6 +104,9 @@ struct Request {
     fields: Vec<RequestField>,
 
     error_ty: Type,
+
+    /// Whether to generate a builder for the request.
+    builder: bool,
 }
 
 impl Request {
@@ -120,7 +136,7 @@ impl Request {
         self.fields.iter().any(|f| matches!(&f.kind, RequestFieldKind::Query))
     }
 
-    fn header_fields(&self) -> impl Iterator<Item = (&Field, &Ident)> {
+    fn header_fields(&self) -> impl Iterator<Item = (&Field, &HeaderName)> {
         self.fields.iter().filter_map(RequestField::as_header_field)
     }
 
@@ -178,6 +194,7 @@ impl Request {
 
         let outgoing_request_impl = self.expand_outgoing(ruma_common);
         let incoming_request_impl = self.expand_incoming(ruma_common);
+        let describe_impls = self.expand_describe(ruma_common);
 
         quote! {
             #request_body_struct
@@ -185,6 +202,7 @@ impl Request {
 
             #outgoing_request_impl
             #incoming_request_impl
+            #describe_impls
         }
     }
 
@@ -280,7 +298,7 @@ pub(super) enum RequestFieldKind {
     Body,
 
     /// Data in an HTTP header.
-    Header(Ident),
+    Header(HeaderName),
 
     /// A specific data type in the body of the request.
     NewtypeBody,
@@ -354,8 +372,8 @@ impl RequestField {
         }
     }
 
-    /// Return the contained field and header ident if this request field is a header kind.
-    pub fn as_header_field(&self) -> Option<(&Field, &Ident)> {
+    /// Return the contained field and header name if this request field is a header kind.
+    pub fn as_header_field(&self) -> Option<(&Field, &HeaderName)> {
         match &self.kind {
             RequestFieldKind::Header(header_name) => Some((&self.inner, header_name)),
             _ => None,
@@ -0,0 +1,207 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Field, GenericArgument, Lit, Meta, MetaNameValue, NestedMeta, Path,
+    PathArguments, Type,
+};
+
+use super::Request;
+
+impl Request {
+    /// Generate a builder for the request.
+    ///
+    /// The required fields of the request are arguments of `Request::builder()`, and each
+    /// optional field gets a setter on the builder. A field is optional if its type is an `Option`
+    /// or if it has a `#[serde(default)]` attribute.
+    pub fn expand_builder(&self) -> syn::Result<TokenStream> {
+        if !self.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self.generics,
+                "Can't generate a builder for a request with generics",
+            ));
+        }
+
+        let ident = &self.ident;
+        let builder_ident = format_ident!("{}Builder", ident);
+        let builder_doc = format!("A builder for [`{ident}`].");
+
+        let fields: Vec<_> = self.fields.iter().map(|f| BuilderField::new(&f.inner)).collect();
+
+        let builder_fields = fields.iter().map(|f| {
+            let BuilderField { ident, ty, cfg_attrs, .. } = f;
+            quote! {
+                #( #cfg_attrs )*
+                #ident: #ty
+            }
+        });
+
+        let required_fields: Vec<_> = fields.iter().filter(|f| f.default.is_none()).collect();
+        let args = required_fields.iter().map(|f| {
+            let BuilderField { ident, ty, cfg_attrs, .. } = f;
+            quote! {
+                #( #cfg_attrs )*
+                #ident: #ty
+            }
+        });
+        let required_args_doc = if required_fields.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                ///
+                /// The required fields of the request must be passed to this method, the optional
+                /// fields can be set with the methods of the builder.
+            })
+        };
+
+        let initializers = fields.iter().map(|f| {
+            let BuilderField { ident, cfg_attrs, default, .. } = f;
+            let value = match default {
+                Some(FieldDefault::Default) => quote! { ::std::default::Default::default() },
+                Some(FieldDefault::Path(path)) => quote! { #path() },
+                None => quote! { #ident },
+            };
+
+            quote! {
+                #( #cfg_attrs )*
+                #ident: #value
+            }
+        });
+
+        let setters = fields.iter().filter(|f| f.default.is_some()).map(|f| {
+            let BuilderField { ident, ty, cfg_attrs, doc_attrs, .. } = f;
+
+            match option_inner_type(ty) {
+                Some(inner_ty) => quote! {
+                    #( #cfg_attrs )*
+                    #( #doc_attrs )*
+                    pub fn #ident(mut self, value: #inner_ty) -> Self {
+                        self.#ident = ::std::option::Option::Some(value);
+                        self
+                    }
+                },
+                None => quote! {
+                    #( #cfg_attrs )*
+                    #( #doc_attrs )*
+                    pub fn #ident(mut self, value: #ty) -> Self {
+                        self.#ident = value;
+                        self
+                    }
+                },
+            }
+        });
+
+        let field_idents = fields.iter().map(|f| {
+            let BuilderField { ident, cfg_attrs, .. } = f;
+            quote! {
+                #( #cfg_attrs )*
+                #ident: self.#ident
+            }
+        });
+
+        Ok(quote! {
+            #[doc = #builder_doc]
+            #[derive(Clone, Debug)]
+            #[must_use]
+            pub struct #builder_ident {
+                #( #builder_fields, )*
+            }
+
+            #[automatically_derived]
+            impl #ident {
+                /// Creates a builder for this request.
+                #required_args_doc
+                pub fn builder(#( #args ),*) -> #builder_ident {
+                    #builder_ident {
+                        #( #initializers, )*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #builder_ident {
+                #( #setters )*
+
+                /// Build the request.
+                pub fn build(self) -> #ident {
+                    #ident {
+                        #( #field_idents, )*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct BuilderField<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a Type,
+    cfg_attrs: Vec<&'a Attribute>,
+    doc_attrs: Vec<&'a Attribute>,
+    default: Option<FieldDefault>,
+}
+
+impl<'a> BuilderField<'a> {
+    fn new(field: &'a Field) -> Self {
+        let ident = field.ident.as_ref().expect("request fields must be named");
+        let cfg_attrs = field.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect();
+        let doc_attrs = field.attrs.iter().filter(|a| a.path.is_ident("doc")).collect();
+
+        let default = serde_default(field).or_else(|| {
+            option_inner_type(&field.ty).is_some().then_some(FieldDefault::Default)
+        });
+
+        Self { ident, ty: &field.ty, cfg_attrs, doc_attrs, default }
+    }
+}
+
+/// How the value of an optional field is initialized.
+enum FieldDefault {
+    /// With `Default::default()`.
+    Default,
+
+    /// With the function at the given path.
+    Path(Path),
+}
+
+/// The value of the `#[serde(default)]` attribute of the given field, if any.
+fn serde_default(field: &Field) -> Option<FieldDefault> {
+    field.attrs.iter().filter(|a| a.path.is_ident("serde")).find_map(|attr| {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => return None,
+        };
+
+        list.nested.into_iter().find_map(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => {
+                Some(FieldDefault::Default)
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. }))
+                if path.is_ident("default") =>
+            {
+                s.parse().ok().map(FieldDefault::Path)
+            }
+            _ => None,
+        })
+    })
+}
+
+/// The type inside the given `Option` type, if it is one.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(ty) if ty.qself.is_none() => &ty.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::{Request, RequestField};
+use crate::api::util::{field_descriptors, type_to_string};
+
+impl Request {
+    pub fn expand_describe(&self, ruma_common: &TokenStream) -> TokenStream {
+        let descriptor = quote! { #ruma_common::api::descriptor };
+
+        let path = field_descriptors(self.path_fields().map(|f| (f, None)), ruma_common);
+        let query = field_descriptors(
+            self.fields
+                .iter()
+                .filter_map(|f| f.as_query_field().or_else(|| f.as_query_map_field()))
+                .map(|f| (f, None)),
+            ruma_common,
+        );
+        let headers = field_descriptors(
+            self.header_fields().map(|(f, header_name)| (f, Some(header_name.expand_str()))),
+            ruma_common,
+        );
+
+        let body = if self.raw_body_field().is_some() {
+            quote! { #descriptor::BodyDescriptor::Raw }
+        } else if self.has_newtype_body() {
+            let ty = self.body_fields().next().map(|f| type_to_string(&f.ty));
+            quote! { #descriptor::BodyDescriptor::Newtype { ty: #ty } }
+        } else if self.has_body_fields() {
+            let fields = field_descriptors(
+                self.fields.iter().filter_map(RequestField::as_body_field).map(|f| (f, None)),
+                ruma_common,
+            );
+            quote! { #descriptor::BodyDescriptor::Fields { fields: #fields } }
+        } else {
+            quote! { #descriptor::BodyDescriptor::Empty }
+        };
+
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        quote! {
+            #ruma_common::__spec_gen! {
+                #[automatically_derived]
+                impl #impl_generics #descriptor::DescribeMessage for Request #ty_generics #where_clause {
+                    fn describe_message() -> #descriptor::MessageDescriptor {
+                        #descriptor::MessageDescriptor {
+                            type_name: ::std::any::type_name::<Self>(),
+                            path: #path,
+                            query: #query,
+                            headers: #headers,
+                            body: #body,
+                        }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #impl_generics #descriptor::DescribeRequest for Request #ty_generics #where_clause {
+                    type Response = Response;
+
+                    const METADATA: #ruma_common::api::Metadata = METADATA;
+                }
+            }
+        }
+    }
+}
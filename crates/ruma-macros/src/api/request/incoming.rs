@@ -74,7 +74,8 @@ impl Request {
                         field.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect::<Vec<_>>();
 
                     let field_name = &field.ident;
-                    let header_name_string = header_name.to_string();
+                    let header_name_string = header_name.to_name_string();
+                    let header_name = header_name.expand(&http);
 
                     let (some_case, none_case) = match &field.ty {
                         syn::Type::Path(syn::TypePath {
@@ -82,6 +83,28 @@ impl Request {
                         }) if segments.last().unwrap().ident == "Option" => {
                             (quote! { Some(str_value.to_owned()) }, quote! { None })
                         }
+                        syn::Type::Path(syn::TypePath {
+                            path: syn::Path { segments, .. }, ..
+                        }) if segments.last().unwrap().ident == "Vec" => {
+                            let decl = quote! {
+                                #( #cfg_attrs )*
+                                let #field_name = headers
+                                    .get_all(#header_name)
+                                    .iter()
+                                    .map(|header_value| {
+                                        header_value.to_str().map(|s| s.to_owned())
+                                    })
+                                    .collect::<::std::result::Result<_, _>>()?;
+                            };
+
+                            return (
+                                decl,
+                                quote! {
+                                    #( #cfg_attrs )*
+                                    #field_name
+                                },
+                            );
+                        }
                         _ => (
                             quote! { str_value.to_owned() },
                             quote! {
@@ -75,6 +75,9 @@ impl Request {
 
         header_kvs.extend(self.header_fields().map(|(field, header_name)| {
             let field_name = &field.ident;
+            let header_name = header_name.expand(&http);
+            let cfg_attrs =
+                field.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect::<Vec<_>>();
 
             match &field.ty {
                 syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -89,6 +92,19 @@ impl Request {
                         }
                     }
                 }
+                syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
+                    if segments.last().unwrap().ident == "Vec" =>
+                {
+                    quote! {
+                        #( #cfg_attrs )*
+                        for header_val in &self.#field_name {
+                            req_headers.append(
+                                #header_name,
+                                #http::header::HeaderValue::from_str(header_val)?,
+                            );
+                        }
+                    }
+                }
                 _ => quote! {
                     req_headers.insert(
                         #header_name,
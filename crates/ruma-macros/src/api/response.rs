@@ -10,11 +10,12 @@ use syn::{
 };
 
 use super::{
-    attribute::{DeriveResponseMeta, ResponseMeta},
+    attribute::{DeriveResponseMeta, HeaderName, ResponseMeta},
     ensure_feature_presence,
 };
 use crate::util::import_ruma_common;
 
+mod describe;
 mod incoming;
 mod outgoing;
 
@@ -146,12 +147,14 @@ impl Response {
 
         let outgoing_response_impl = self.expand_outgoing(&ruma_common);
         let incoming_response_impl = self.expand_incoming(&self.error_ty, &ruma_common);
+        let describe_impl = self.expand_describe(&ruma_common);
 
         quote! {
             #response_body_struct
 
             #outgoing_response_impl
             #incoming_response_impl
+            #describe_impl
         }
     }
 
@@ -203,7 +206,7 @@ enum ResponseFieldKind {
     Body,
 
     /// Data in an HTTP header.
-    Header(Ident),
+    Header(HeaderName),
 
     /// A specific data type in the body of the response.
     NewtypeBody,
@@ -241,8 +244,8 @@ impl ResponseField {
         }
     }
 
-    /// Return the contained field and HTTP header ident if this response field is a header kind.
-    fn as_header_field(&self) -> Option<(&Field, &Ident)> {
+    /// Return the contained field and HTTP header name if this response field is a header kind.
+    fn as_header_field(&self) -> Option<(&Field, &HeaderName)> {
         match &self.kind {
             ResponseFieldKind::Header(ident) => Some((&self.inner, ident)),
             _ => None,
@@ -0,0 +1,55 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::{Response, ResponseField};
+use crate::api::util::{field_descriptors, type_to_string};
+
+impl Response {
+    pub fn expand_describe(&self, ruma_common: &TokenStream) -> TokenStream {
+        let descriptor = quote! { #ruma_common::api::descriptor };
+
+        let headers = field_descriptors(
+            self.fields
+                .iter()
+                .filter_map(ResponseField::as_header_field)
+                .map(|(f, header_name)| (f, Some(header_name.expand_str()))),
+            ruma_common,
+        );
+
+        let body = if self.has_raw_body() {
+            quote! { #descriptor::BodyDescriptor::Raw }
+        } else if self.has_newtype_body() {
+            let ty = self
+                .fields
+                .iter()
+                .find_map(ResponseField::as_body_field)
+                .map(|f| type_to_string(&f.ty));
+            quote! { #descriptor::BodyDescriptor::Newtype { ty: #ty } }
+        } else if self.has_body_fields() {
+            let fields = field_descriptors(
+                self.fields.iter().filter_map(ResponseField::as_body_field).map(|f| (f, None)),
+                ruma_common,
+            );
+            quote! { #descriptor::BodyDescriptor::Fields { fields: #fields } }
+        } else {
+            quote! { #descriptor::BodyDescriptor::Empty }
+        };
+
+        quote! {
+            #ruma_common::__spec_gen! {
+                #[automatically_derived]
+                impl #descriptor::DescribeMessage for Response {
+                    fn describe_message() -> #descriptor::MessageDescriptor {
+                        #descriptor::MessageDescriptor {
+                            type_name: ::std::any::type_name::<Self>(),
+                            path: ::std::vec::Vec::new(),
+                            query: ::std::vec::Vec::new(),
+                            headers: #headers,
+                            body: #body,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -52,6 +52,7 @@ impl Response {
                         }
                     }
                     ResponseFieldKind::Header(header_name) => {
+                        let header_name = header_name.expand(&http);
                         let optional_header = match &field.ty {
                             syn::Type::Path(syn::TypePath {
                                 path: syn::Path { segments, .. },
@@ -66,6 +67,20 @@ impl Response {
                                     }
                                 }
                             }
+                            syn::Type::Path(syn::TypePath {
+                                path: syn::Path { segments, .. },
+                                ..
+                            }) if segments.last().unwrap().ident == "Vec" => {
+                                quote! {
+                                    #( #cfg_attrs )*
+                                    #field_name: {
+                                        headers.get_all(#header_name)
+                                            .iter()
+                                            .map(|h| h.to_str().map(|s| s.to_owned()))
+                                            .collect::<::std::result::Result<_, _>>()?
+                                    }
+                                }
+                            }
                             _ => quote! {
                                 #( #cfg_attrs )*
                                 #field_name: {
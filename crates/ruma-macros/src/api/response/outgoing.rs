@@ -12,6 +12,9 @@ impl Response {
             response_field.as_header_field().map(|(field, header_name)| {
                 let field_name =
                     field.ident.as_ref().expect("expected field to have an identifier");
+                let header_name = header_name.expand(&http);
+                let cfg_attrs =
+                    field.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect::<Vec<_>>();
 
                 match &field.ty {
                     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -26,6 +29,19 @@ impl Response {
                             }
                         }
                     }
+                    syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
+                        if segments.last().unwrap().ident == "Vec" =>
+                    {
+                        quote! {
+                            #( #cfg_attrs )*
+                            for header in self.#field_name {
+                                headers.append(
+                                    #header_name,
+                                    header.parse()?,
+                                );
+                            }
+                        }
+                    }
                     _ => quote! {
                         headers.insert(
                             #header_name,
@@ -2,6 +2,10 @@
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
+use syn::{
+    Field, GenericArgument, Lit, Meta, MetaNameValue, NestedMeta, Path, PathArguments, Type,
+    TypePath,
+};
 
 pub fn map_option_literal<T: ToTokens>(ver: &Option<T>) -> TokenStream {
     match ver {
@@ -13,3 +17,106 @@ pub fn map_option_literal<T: ToTokens>(ver: &Option<T>) -> TokenStream {
 pub fn is_valid_endpoint_path(string: &str) -> bool {
     string.as_bytes().iter().all(|b| (0x21..=0x7E).contains(b))
 }
+
+/// Produces code for a `Vec<FieldDescriptor>` of the given fields.
+///
+/// If a field has no explicit name, the name is the `#[serde(rename)]` of the field or its
+/// identifier.
+pub fn field_descriptors<'a>(
+    fields: impl IntoIterator<Item = (&'a Field, Option<TokenStream>)>,
+    ruma_common: &TokenStream,
+) -> TokenStream {
+    let pushes = fields.into_iter().map(|(field, name)| {
+        let cfg_attrs = field.attrs.iter().filter(|a| a.path.is_ident("cfg"));
+        let name = name.unwrap_or_else(|| {
+            let name = serde_rename(field).unwrap_or_else(|| {
+                field.ident.as_ref().expect("expected field to have an identifier").to_string()
+            });
+            quote! { #name }
+        });
+        let ty = type_to_string(&field.ty);
+
+        quote! {
+            #( #cfg_attrs )*
+            fields.push(#ruma_common::api::descriptor::FieldDescriptor {
+                name: ::std::string::ToString::to_string(#name),
+                ty: #ty,
+            });
+        }
+    });
+
+    quote! {{
+        #[allow(unused_mut)]
+        let mut fields = ::std::vec::Vec::new();
+        #( #pushes )*
+        fields
+    }}
+}
+
+/// The value of the `#[serde(rename = "...")]` attribute of the given field, if any.
+fn serde_rename(field: &Field) -> Option<String> {
+    field.attrs.iter().filter(|a| a.path.is_ident("serde")).find_map(|attr| {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => return None,
+        };
+
+        list.nested.into_iter().find_map(|meta| match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. }))
+                if path.is_ident("rename") =>
+            {
+                Some(s.value())
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Converts the given type to a string, formatted like it would be written in Rust code.
+pub fn type_to_string(ty: &Type) -> String {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path_to_string(path),
+        Type::Reference(reference) => {
+            let lifetime =
+                reference.lifetime.as_ref().map(|lt| format!("{lt} ")).unwrap_or_default();
+            let mutability = if reference.mutability.is_some() { "mut " } else { "" };
+            format!("&{lifetime}{mutability}{}", type_to_string(&reference.elem))
+        }
+        Type::Slice(slice) => format!("[{}]", type_to_string(&slice.elem)),
+        Type::Array(array) => {
+            format!("[{}; {}]", type_to_string(&array.elem), array.len.to_token_stream())
+        }
+        Type::Tuple(tuple) => {
+            let elems = tuple.elems.iter().map(type_to_string).collect::<Vec<_>>();
+            format!("({})", elems.join(", "))
+        }
+        Type::Paren(paren) => type_to_string(&paren.elem),
+        Type::Group(group) => type_to_string(&group.elem),
+        _ => ty.to_token_stream().to_string(),
+    }
+}
+
+/// Converts the given path to a string, formatted like it would be written in Rust code.
+fn path_to_string(path: &Path) -> String {
+    let segments = path.segments.iter().map(|segment| {
+        let ident = segment.ident.to_string();
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(arguments) => {
+                let args = arguments
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArgument::Type(ty) => type_to_string(ty),
+                        arg => arg.to_token_stream().to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{ident}<{}>", args.join(", "))
+            }
+            _ => ident,
+        }
+    });
+
+    let prefix = if path.leading_colon.is_some() { "::" } else { "" };
+    format!("{prefix}{}", segments.collect::<Vec<_>>().join("::"))
+}
@@ -668,6 +668,11 @@ fn generate_event_content_without_relation<'a>(
     })?;
 
     let without_relation_fields = other_fields.iter().flat_map(|f| &f.ident).collect::<Vec<_>>();
+    // Fields can be behind a cargo feature.
+    let without_relation_cfg_attrs = other_fields
+        .iter()
+        .map(|f| f.attrs.iter().filter(|a| a.path.is_ident("cfg")).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
     let without_relation_struct = if other_fields.is_empty() {
         quote! { ; }
     } else {
@@ -682,7 +687,10 @@ fn generate_event_content_without_relation<'a>(
         impl ::std::convert::From<#ident> for #without_relation_ident {
             fn from(c: #ident) -> Self {
                 Self {
-                    #( #without_relation_fields: c.#without_relation_fields, )*
+                    #(
+                        #( #without_relation_cfg_attrs )*
+                        #without_relation_fields: c.#without_relation_fields,
+                    )*
                 }
             }
         }
@@ -696,7 +704,10 @@ fn generate_event_content_without_relation<'a>(
             #[doc = #with_relation_fn_doc]
             pub fn with_relation(self, relates_to: #relates_to_type) -> #ident {
                 #ident {
-                    #( #without_relation_fields: self.#without_relation_fields, )*
+                    #(
+                        #( #without_relation_cfg_attrs )*
+                        #without_relation_fields: self.#without_relation_fields,
+                    )*
                     relates_to,
                 }
             }
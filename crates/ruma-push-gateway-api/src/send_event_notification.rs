@@ -7,12 +7,15 @@ pub mod v1 {
     //!
     //! [spec]: https://spec.matrix.org/v1.4/push-gateway-api/#post_matrixpushv1notify
 
+    #[cfg(feature = "unstable-unspecified")]
+    use std::collections::BTreeMap;
+
     use js_int::{uint, UInt};
     use ruma_common::{
         api::{request, response, Metadata},
         events::TimelineEventType,
         metadata,
-        push::{PushFormat, Tweak},
+        push::{Action, PushFormat, Tweak},
         serde::StringEnum,
         OwnedEventId, OwnedRoomAliasId, OwnedRoomId, OwnedUserId, SecondsSinceUnixEpoch,
     };
@@ -164,6 +167,25 @@ pub mod v1 {
         _Custom(PrivOwnedStr),
     }
 
+    impl NotificationPriority {
+        /// Computes the priority of a notification from the actions of the push rule that matched
+        /// the event.
+        ///
+        /// The notification has a high priority if the actions make a sound or highlight the
+        /// event, and a low priority otherwise.
+        pub fn from_actions(actions: &[Action]) -> Self {
+            let is_high = actions.iter().any(|action| {
+                matches!(action, Action::SetTweak(Tweak::Sound(_) | Tweak::Highlight(true)))
+            });
+
+            if is_high {
+                Self::High
+            } else {
+                Self::Low
+            }
+        }
+    }
+
     /// Type for passing information about notification counts.
     #[derive(Clone, Debug, Default, Deserialize, Serialize)]
     #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -177,17 +199,42 @@ pub mod v1 {
         /// which they are a member.
         #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
         pub missed_calls: UInt,
+
+        /// The number of unread messages in each thread of the room in which the event occurred.
+        ///
+        /// The keys are the IDs of the root events of the threads. This is only sent by
+        /// homeservers that count the notifications of threads separately, like for the
+        /// `unread_thread_notifications` of the sync endpoint.
+        // Not specified, follows the notifications for threads of MSC3773.
+        #[cfg(feature = "unstable-unspecified")]
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub unread_thread_notifications: BTreeMap<OwnedEventId, UInt>,
     }
 
     impl NotificationCounts {
         /// Create new notification counts from the given unread and missed call
         /// counts.
         pub fn new(unread: UInt, missed_calls: UInt) -> Self {
-            NotificationCounts { unread, missed_calls }
+            NotificationCounts {
+                unread,
+                missed_calls,
+                #[cfg(feature = "unstable-unspecified")]
+                unread_thread_notifications: BTreeMap::new(),
+            }
         }
 
         fn is_default(&self) -> bool {
-            self.unread == uint!(0) && self.missed_calls == uint!(0)
+            #[cfg(not(feature = "unstable-unspecified"))]
+            {
+                self.unread == uint!(0) && self.missed_calls == uint!(0)
+            }
+
+            #[cfg(feature = "unstable-unspecified")]
+            {
+                self.unread == uint!(0)
+                    && self.missed_calls == uint!(0)
+                    && self.unread_thread_notifications.is_empty()
+            }
         }
     }
 
@@ -231,6 +278,18 @@ pub mod v1 {
                 tweaks: Vec::new(),
             }
         }
+
+        /// Sets the tweaks of this device from the actions of the push rule that matched the
+        /// event.
+        pub fn set_tweaks_from_actions(&mut self, actions: &[Action]) {
+            self.tweaks = actions
+                .iter()
+                .filter_map(|action| match action {
+                    Action::SetTweak(tweak) => Some(tweak.clone()),
+                    _ => None,
+                })
+                .collect();
+        }
     }
 
     /// Information for the pusher implementation itself.
@@ -383,7 +442,9 @@ pub mod v1 {
             from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
         };
 
-        use super::{Device, Notification, NotificationCounts, NotificationPriority, Tweak};
+        use super::{
+            Action, Device, Notification, NotificationCounts, NotificationPriority, Tweak,
+        };
 
         #[test]
         fn serialize_request() {
@@ -453,5 +514,56 @@ pub mod v1 {
 
             assert_eq!(expected, to_json_value(notice).unwrap());
         }
+
+        #[test]
+        fn map_actions() {
+            let actions = [Action::Notify, Action::SetTweak(Tweak::Highlight(false))];
+            assert_eq!(NotificationPriority::from_actions(&actions), NotificationPriority::Low);
+
+            let actions = [
+                Action::Notify,
+                Action::SetTweak(Tweak::Sound("default".to_owned())),
+                Action::SetTweak(Tweak::Highlight(false)),
+            ];
+            assert_eq!(NotificationPriority::from_actions(&actions), NotificationPriority::High);
+
+            let mut device = Device::new("org.matrix.matrixConsole.ios".into(), "pushkey".into());
+            device.set_tweaks_from_actions(&actions);
+            assert_eq!(
+                to_json_value(device).unwrap(),
+                json!({
+                    "app_id": "org.matrix.matrixConsole.ios",
+                    "pushkey": "pushkey",
+                    "tweaks": {
+                        "sound": "default",
+                        "highlight": false,
+                    },
+                })
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "unstable-unspecified")]
+        fn thread_notification_counts() {
+            let mut counts = NotificationCounts::new(uint!(3), uint!(0));
+            counts
+                .unread_thread_notifications
+                .insert(event_id!("$thread_root").to_owned(), uint!(2));
+
+            let json = json!({
+                "unread": 3,
+                "unread_thread_notifications": {
+                    "$thread_root": 2,
+                },
+            });
+            assert_eq!(to_json_value(&counts).unwrap(), json);
+
+            let counts = from_json_value::<NotificationCounts>(json).unwrap();
+            assert_eq!(counts.unread, uint!(3));
+            assert_eq!(
+                counts.unread_thread_notifications.get(event_id!("$thread_root")),
+                Some(&uint!(2))
+            );
+        }
     }
 }
@@ -1,7 +1,7 @@
 use ruma_common::{
     canonical_json::{JsonType, RedactionError},
     serde::Base64DecodeError,
-    EventId, OwnedEventId, OwnedServerName, RoomVersionId,
+    EventId, OwnedEventId, OwnedServerName, OwnedUserId, RoomVersionId,
 };
 use thiserror::Error;
 
@@ -41,6 +41,13 @@ pub enum Error {
     /// PDU was too large
     #[error("PDU is larger than maximum of 65535 bytes")]
     PduSize,
+
+    /// The rules of the room version are unknown.
+    ///
+    /// The rules of custom room versions can be registered with
+    /// [`RoomVersionRules::register_custom()`](ruma_common::room_version_rules::RoomVersionRules::register_custom).
+    #[error("unsupported room version: {0}")]
+    UnsupportedRoomVersion(RoomVersionId),
 }
 
 impl From<RedactionError> for Error {
@@ -52,6 +59,9 @@ impl From<RedactionError> for Error {
             RedactionError::JsonFieldMissingFromObject(field) => {
                 JsonError::JsonFieldMissingFromObject(field).into()
             }
+            RedactionError::UnsupportedRoomVersion(version) => {
+                Self::UnsupportedRoomVersion(version)
+            }
             #[allow(unreachable_patterns)]
             _ => unreachable!(),
         }
@@ -150,6 +160,10 @@ pub enum VerificationError {
     #[error("Not signed with any of the given public keys")]
     UnknownPublicKeysForSignature,
 
+    /// For when the `mxid` of a third-party invite doesn't match the invitee.
+    #[error("Third-party invite is for another user: {0}")]
+    ThirdPartyInviteMxidMismatch(OwnedUserId),
+
     /// For when [`ed25519_dalek`] cannot verify a signature.
     #[error("Could not verify signature: {0}")]
     Signature(#[source] ed25519_dalek::SignatureError),
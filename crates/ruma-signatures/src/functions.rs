@@ -7,12 +7,19 @@ use std::{
 };
 
 use base64::{alphabet, encode_engine};
+#[cfg(feature = "events")]
+use ruma_common::events::room::{
+    member::ThirdPartyInvite, third_party_invite::RoomThirdPartyInviteEventContent,
+};
 use ruma_common::{
     canonical_json::{redact, JsonType},
+    room_version_rules::EventFormatVersion,
     serde::{base64::Standard, Base64},
     CanonicalJsonObject, CanonicalJsonValue, OwnedEventId, OwnedServerName, RoomVersionId, UserId,
 };
 use serde_json::{from_str as from_json_str, to_string as to_json_string};
+#[cfg(feature = "events")]
+use serde_json::{from_value as from_json_value, to_value as to_json_value};
 use sha2::{digest::Digest, Sha256};
 
 use crate::{
@@ -257,6 +264,106 @@ pub fn verify_json(
     Ok(())
 }
 
+/// Uses a list of public keys to verify the `signed` object of a third-party invite.
+///
+/// This object is returned by the identity server's `/sign-ed25519` endpoint and is included in
+/// the `third_party_invite` of the invitee's `m.room.member` event. Unlike `verify_json`, the
+/// public keys are not looked up by entity and key identifiers: as required by the authorization
+/// rules, the object is valid if one of its signatures can be verified with any of the public
+/// keys.
+///
+/// # Parameters
+///
+/// * public_keys: The public keys of the identity server. Generally, these are the `public_key`
+/// and `public_keys` of the `m.room.third_party_invite` event.
+/// * signed: The signed JSON object.
+///
+/// # Errors
+///
+/// Returns an error if the object is malformed or if none of its signatures can be verified with
+/// the given public keys.
+pub fn verify_third_party_invite_signed<'a>(
+    public_keys: impl IntoIterator<Item = &'a Base64>,
+    signed: &CanonicalJsonObject,
+) -> Result<(), Error> {
+    let signature_map = match signed.get("signatures") {
+        Some(CanonicalJsonValue::Object(signatures)) => signatures,
+        Some(_) => return Err(JsonError::not_of_type("signatures", JsonType::Object)),
+        None => return Err(JsonError::field_missing_from_object("signatures")),
+    };
+
+    let mut signatures = Vec::new();
+    for signature_set in signature_map.values() {
+        let signature_set = match signature_set {
+            CanonicalJsonValue::Object(set) => set,
+            _ => return Err(JsonError::not_multiples_of_type("signature sets", JsonType::Object)),
+        };
+
+        for signature in signature_set.values() {
+            let signature = match signature {
+                CanonicalJsonValue::String(s) => s,
+                _ => return Err(JsonError::not_of_type("signature", JsonType::String)),
+            };
+
+            signatures.push(
+                Base64::<Standard>::parse(signature)
+                    .map_err(|e| ParseError::base64("signature", signature, e))?,
+            );
+        }
+    }
+
+    let canonical_json = canonical_json(signed)?;
+    for public_key in public_keys {
+        let is_valid = signatures.iter().any(|signature| {
+            Ed25519Verifier
+                .verify_json(public_key.as_bytes(), signature.as_bytes(), canonical_json.as_bytes())
+                .is_ok()
+        });
+
+        if is_valid {
+            return Ok(());
+        }
+    }
+
+    Err(VerificationError::UnknownPublicKeysForSignature.into())
+}
+
+/// Verifies the `third_party_invite` of the content of an invitee's `m.room.member` event.
+///
+/// This checks that the invite is for the given user, and that its `signed` object was signed with
+/// one of the public keys of the `m.room.third_party_invite` event, as required by the
+/// authorization rules. Finding the `m.room.third_party_invite` event whose state key matches the
+/// `token` of the `signed` object is left to the caller.
+///
+/// # Parameters
+///
+/// * invitee: The user ID in the state key of the `m.room.member` event.
+/// * third_party_invite: The `third_party_invite` of the `m.room.member` event.
+/// * third_party_invite_event: The content of the matching `m.room.third_party_invite` event.
+///
+/// # Errors
+///
+/// Returns an error if the invite is for another user, or if the `signed` object can't be
+/// verified with the public keys of the `m.room.third_party_invite` event.
+#[cfg(feature = "events")]
+pub fn verify_third_party_invite(
+    invitee: &UserId,
+    third_party_invite: &ThirdPartyInvite,
+    third_party_invite_event: &RoomThirdPartyInviteEventContent,
+) -> Result<(), Error> {
+    let signed = &third_party_invite.signed;
+
+    if signed.mxid != invitee {
+        return Err(VerificationError::ThirdPartyInviteMxidMismatch(signed.mxid.clone()).into());
+    }
+
+    let signed: CanonicalJsonObject =
+        from_json_value(to_json_value(signed).map_err(JsonError::from)?)
+            .map_err(JsonError::from)?;
+
+    verify_third_party_invite_signed(third_party_invite_event.all_public_keys(), &signed)
+}
+
 /// Uses a public key to verify a signed JSON object.
 ///
 /// # Parameters
@@ -333,8 +440,9 @@ pub fn reference_hash(
 
     let hash = Sha256::digest(json.as_bytes());
 
-    let base64_alphabet = match version {
-        RoomVersionId::V1 | RoomVersionId::V2 | RoomVersionId::V3 => alphabet::STANDARD,
+    let rules = version.rules().ok_or_else(|| Error::UnsupportedRoomVersion(version.clone()))?;
+    let base64_alphabet = match rules.event_format {
+        EventFormatVersion::V1 | EventFormatVersion::V2 => alphabet::STANDARD,
         // Room versions higher than version 3 are url safe base64 encoded
         _ => alphabet::URL_SAFE,
     };
@@ -472,6 +580,72 @@ where
     Ok(())
 }
 
+/// Prepares a PDU to be sent over federation by adding its content hash and signature, and
+/// computing its event ID.
+///
+/// This does all the steps required after building the JSON of an event, in the order expected by
+/// the Matrix specification:
+///
+/// 1. The content hash is added under `hashes`.
+/// 2. The redacted event is signed and the signature is added under `signatures`.
+/// 3. The event ID is computed according to the room version.
+///
+/// In room versions 1 and 2, event IDs are generated by the origin server and must already be in
+/// the `event_id` field of `object`, since it is covered by the hashes and signatures. In later
+/// room versions, event IDs are derived from the reference hash of the event, so any `event_id`
+/// field is removed from `object` before it is hashed, as it must not be part of the PDU.
+///
+/// # Parameters
+///
+/// * room_version: The version of the room the event belongs to.
+/// * object: The JSON object of the event to be hashed and signed.
+/// * entity_id: The identifier of the entity creating the signature. Generally this means a
+///   homeserver, e.g. "example.com".
+/// * key_pair: A cryptographic key pair used to sign the event.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The event is too large.
+/// * `object` can't be hashed or signed, see [`hash_and_sign_event()`].
+/// * The room version is 1 or 2 and `object` is missing a valid `event_id` field.
+pub fn finalize_pdu<K>(
+    room_version: &RoomVersionId,
+    object: &mut CanonicalJsonObject,
+    entity_id: &str,
+    key_pair: &K,
+) -> Result<OwnedEventId, Error>
+where
+    K: KeyPair,
+{
+    let rules =
+        room_version.rules().ok_or_else(|| Error::UnsupportedRoomVersion(room_version.clone()))?;
+    let event_id = match rules.event_format {
+        EventFormatVersion::V1 => match object.get("event_id") {
+            Some(CanonicalJsonValue::String(event_id)) => {
+                Some(event_id.parse().map_err(|e| Error::from(ParseError::EventId(e)))?)
+            }
+            Some(_) => return Err(JsonError::not_of_type("event_id", JsonType::String)),
+            None => return Err(JsonError::field_missing_from_object("event_id")),
+        },
+        _ => {
+            object.remove("event_id");
+            None
+        }
+    };
+
+    hash_and_sign_event(entity_id, key_pair, object, room_version)?;
+
+    match event_id {
+        Some(event_id) => Ok(event_id),
+        None => {
+            let reference_hash = reference_hash(object, room_version)?;
+            format!("${reference_hash}").try_into().map_err(|e| ParseError::EventId(e).into())
+        }
+    }
+}
+
 /// Verifies that the signed event contains all the required valid signatures.
 ///
 /// Some room versions may require signatures from multiple homeservers, so this function takes a
@@ -678,8 +852,10 @@ fn servers_to_check_signatures(
         };
     }
 
-    match version {
-        RoomVersionId::V1 | RoomVersionId::V2 => match object.get("event_id") {
+    let rules = version.rules().ok_or_else(|| Error::UnsupportedRoomVersion(version.clone()))?;
+
+    if rules.event_format == EventFormatVersion::V1 {
+        match object.get("event_id") {
             Some(CanonicalJsonValue::String(raw_event_id)) => {
                 let event_id: OwnedEventId =
                     raw_event_id.parse().map_err(|e| Error::from(ParseError::EventId(e)))?;
@@ -694,29 +870,23 @@ fn servers_to_check_signatures(
             _ => {
                 return Err(JsonError::field_missing_from_object("event_id"));
             }
-        },
-        RoomVersionId::V3
-        | RoomVersionId::V4
-        | RoomVersionId::V5
-        | RoomVersionId::V6
-        | RoomVersionId::V7 => {}
-        // TODO: And for all future versions that have join_authorised_via_users_server
-        RoomVersionId::V8 | RoomVersionId::V9 | RoomVersionId::V10 => {
-            if let Some(authorized_user) = object
-                .get("content")
-                .and_then(|c| c.as_object())
-                .and_then(|c| c.get("join_authorised_via_users_server"))
-            {
-                let authorized_user = authorized_user.as_str().ok_or_else(|| {
-                    JsonError::not_of_type("join_authorised_via_users_server", JsonType::String)
-                })?;
-                let authorized_user = <&UserId>::try_from(authorized_user)
-                    .map_err(|e| Error::from(ParseError::UserId(e)))?;
+        }
+    }
 
-                servers_to_check.insert(authorized_user.server_name().to_owned());
-            }
+    if rules.restricted_join_rules {
+        if let Some(authorized_user) = object
+            .get("content")
+            .and_then(|c| c.as_object())
+            .and_then(|c| c.get("join_authorised_via_users_server"))
+        {
+            let authorized_user = authorized_user.as_str().ok_or_else(|| {
+                JsonError::not_of_type("join_authorised_via_users_server", JsonType::String)
+            })?;
+            let authorized_user = <&UserId>::try_from(authorized_user)
+                .map_err(|e| Error::from(ParseError::UserId(e)))?;
+
+            servers_to_check.insert(authorized_user.server_name().to_owned());
         }
-        _ => unimplemented!(),
     }
 
     Ok(servers_to_check)
@@ -735,15 +905,26 @@ mod tests {
     use std::collections::BTreeMap;
 
     use assert_matches::assert_matches;
+    #[cfg(feature = "events")]
     use ruma_common::{
-        serde::Base64, CanonicalJsonValue, RoomVersionId, ServerSigningKeyId, SigningKeyAlgorithm,
+        events::room::{
+            member::ThirdPartyInvite,
+            third_party_invite::{PublicKey, RoomThirdPartyInviteEventContent},
+        },
+        user_id,
+    };
+    use ruma_common::{
+        room_version_rules::RoomVersionRules, serde::Base64, CanonicalJsonValue, RoomVersionId,
+        ServerSigningKeyId, SigningKeyAlgorithm,
     };
     use serde_json::json;
 
     use super::canonical_json;
+    #[cfg(feature = "events")]
+    use crate::verify_third_party_invite;
     use crate::{
-        sign_json, verify_event, Ed25519KeyPair, Error, PublicKeyMap, PublicKeySet,
-        VerificationError, Verified,
+        reference_hash, sign_json, verify_event, verify_third_party_invite_signed, Ed25519KeyPair,
+        Error, PublicKeyMap, PublicKeySet, VerificationError, Verified,
     };
 
     #[test]
@@ -926,6 +1107,57 @@ mod tests {
         assert_eq!(server, "domain-authorized");
     }
 
+    #[test]
+    fn verify_event_with_custom_room_version() {
+        let key_pair_sender = generate_key_pair();
+        let mut signed_event = serde_json::from_str(
+            r#"{
+                "event_id": "$event_id:domain-event",
+                "auth_events": [],
+                "content": {},
+                "depth": 3,
+                "hashes": {
+                    "sha256": "5jM4wQpv6lnBo7CLIghJuHdW+s2CMBJPUOGOC89ncos"
+                },
+                "origin": "domain",
+                "origin_server_ts": 1000000,
+                "prev_events": [],
+                "room_id": "!x:domain",
+                "sender": "@name:domain-sender",
+                "type": "X",
+                "unsigned": {
+                    "age_ts": 1000000
+                }
+            }"#,
+        )
+        .unwrap();
+        sign_json("domain-sender", &key_pair_sender, &mut signed_event).unwrap();
+
+        let mut public_key_map = BTreeMap::new();
+        add_key_to_map(&mut public_key_map, "domain-sender", &key_pair_sender);
+
+        let room_version = RoomVersionId::try_from("org.example.signatures").unwrap();
+        let verification_result = verify_event(&public_key_map, &signed_event, &room_version);
+        let unsupported = assert_matches!(
+            verification_result,
+            Err(Error::UnsupportedRoomVersion(room_version)) => room_version
+        );
+        assert_eq!(unsupported, room_version);
+        assert_matches!(
+            reference_hash(&signed_event, &room_version),
+            Err(Error::UnsupportedRoomVersion(_))
+        );
+
+        // With the rules of room version 1, the server of the event ID must have signed the event.
+        RoomVersionRules::register_custom(room_version.clone(), RoomVersionRules::V1).unwrap();
+        let verification_result = verify_event(&public_key_map, &signed_event, &room_version);
+        let server = assert_matches!(
+            verification_result,
+            Err(Error::Verification(VerificationError::SignatureNotFound(server))) => server
+        );
+        assert_eq!(server, "domain-event");
+    }
+
     #[test]
     fn verification_fails_if_required_keys_are_not_given() {
         let key_pair_sender = generate_key_pair();
@@ -1011,6 +1243,92 @@ mod tests {
         assert!(format!("{error:?}").contains("Some(Verification equation was not satisfied)"));
     }
 
+    #[test]
+    fn verify_third_party_invite_signed_with_any_key() {
+        let key_pair = generate_key_pair();
+        let other_key_pair = generate_key_pair();
+
+        let mut signed = serde_json::from_str(
+            r#"{
+                "mxid": "@alice:example.org",
+                "sender": "@bob:example.org",
+                "token": "abc123"
+            }"#,
+        )
+        .unwrap();
+        sign_json("magic.forest", &key_pair, &mut signed).unwrap();
+
+        let public_key = Base64::new(key_pair.public_key().to_owned());
+        let other_public_key = Base64::new(other_key_pair.public_key().to_owned());
+
+        verify_third_party_invite_signed([&other_public_key, &public_key], &signed).unwrap();
+        assert_matches!(
+            verify_third_party_invite_signed([&other_public_key], &signed),
+            Err(Error::Verification(VerificationError::UnknownPublicKeysForSignature))
+        );
+
+        signed.insert("token".to_owned(), CanonicalJsonValue::String("def456".to_owned()));
+        assert_matches!(
+            verify_third_party_invite_signed([&public_key], &signed),
+            Err(Error::Verification(VerificationError::UnknownPublicKeysForSignature))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "events")]
+    fn verify_third_party_invite_from_member_content() {
+        let key_pair = generate_key_pair();
+        let other_key_pair = generate_key_pair();
+
+        let mut signed = serde_json::from_str(
+            r#"{
+                "mxid": "@alice:example.org",
+                "sender": "@bob:example.org",
+                "token": "abc123"
+            }"#,
+        )
+        .unwrap();
+        sign_json("magic.forest", &key_pair, &mut signed).unwrap();
+
+        let third_party_invite: ThirdPartyInvite = serde_json::from_value(json!({
+            "display_name": "alice",
+            "signed": signed,
+        }))
+        .unwrap();
+
+        let mut third_party_invite_event = RoomThirdPartyInviteEventContent::new(
+            "alice".to_owned(),
+            "https://magic.forest/verify_key".to_owned(),
+            Base64::new(other_key_pair.public_key().to_owned()),
+        );
+        assert_matches!(
+            verify_third_party_invite(
+                user_id!("@alice:example.org"),
+                &third_party_invite,
+                &third_party_invite_event
+            ),
+            Err(Error::Verification(VerificationError::UnknownPublicKeysForSignature))
+        );
+
+        third_party_invite_event.public_keys =
+            Some(vec![PublicKey::new(Base64::new(key_pair.public_key().to_owned()))]);
+        verify_third_party_invite(
+            user_id!("@alice:example.org"),
+            &third_party_invite,
+            &third_party_invite_event,
+        )
+        .unwrap();
+
+        assert_matches!(
+            verify_third_party_invite(
+                user_id!("@bob:example.org"),
+                &third_party_invite,
+                &third_party_invite_event
+            ),
+            Err(Error::Verification(VerificationError::ThirdPartyInviteMxidMismatch(_)))
+        );
+    }
+
     fn generate_key_pair() -> Ed25519KeyPair {
         let key_content = Ed25519KeyPair::generate().unwrap();
         Ed25519KeyPair::from_der(&key_content, "1".to_owned())
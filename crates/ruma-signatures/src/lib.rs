@@ -33,6 +33,9 @@
 //! is the same. To hash and sign an event, use the `hash_and_sign_event` function. See the
 //! documentation of this function for more details and a full example of use.
 //!
+//! To prepare a new PDU to be sent over federation, use the `finalize_pdu` function, that hashes
+//! and signs the event, and computes its event ID according to the room version.
+//!
 //! # Verifying signatures and hashes
 //!
 //! When a homeserver receives data from another homeserver via the federation, it's necessary to
@@ -41,15 +44,21 @@
 //! To verify a signature on arbitrary JSON, use the `verify_json` function. To verify the
 //! signatures and hashes on an event, use the `verify_event` function. See the documentation for
 //! these respective functions for more details and full examples of use.
+//!
+//! To verify the signature of a third-party invite made by an identity server, use the
+//! `verify_third_party_invite` function, available with the `events` feature, or the
+//! `verify_third_party_invite_signed` function if the `signed` object is only available as JSON.
 
 #![warn(missing_docs)]
 
 use ruma_common::serde::{AsRefStr, DisplayAsRefStr};
 
 pub use error::{Error, JsonError, ParseError, VerificationError};
+#[cfg(feature = "events")]
+pub use functions::verify_third_party_invite;
 pub use functions::{
-    canonical_json, content_hash, hash_and_sign_event, reference_hash, sign_json, verify_event,
-    verify_json,
+    canonical_json, content_hash, finalize_pdu, hash_and_sign_event, reference_hash, sign_json,
+    verify_event, verify_json, verify_third_party_invite_signed,
 };
 pub use keys::{Ed25519KeyPair, KeyPair, PublicKeyMap, PublicKeySet};
 pub use signatures::Signature;
@@ -103,6 +112,7 @@ fn split_id(id: &str) -> Result<(Algorithm, String), Error> {
 mod tests {
     use std::collections::BTreeMap;
 
+    use assert_matches::assert_matches;
     use pkcs8::{der::Decode, PrivateKeyInfo};
     use ruma_common::{
         serde::{base64::Standard, Base64},
@@ -111,7 +121,8 @@ mod tests {
     use serde_json::{from_str as from_json_str, to_string as to_json_string};
 
     use super::{
-        canonical_json, hash_and_sign_event, sign_json, verify_event, verify_json, Ed25519KeyPair,
+        canonical_json, finalize_pdu, hash_and_sign_event, reference_hash, sign_json, verify_event,
+        verify_json, Ed25519KeyPair, Error, JsonError,
     };
 
     fn pkcs8() -> Vec<u8> {
@@ -400,4 +411,67 @@ mod tests {
 
         verify_event(&public_key_map, &value, &RoomVersionId::V5).unwrap();
     }
+
+    #[test]
+    fn finalize_pdu_with_reference_hash_event_id() {
+        let key_pair = Ed25519KeyPair::from_der(&pkcs8(), "1".into()).unwrap();
+
+        let json = r#"{
+            "room_id": "!x:domain",
+            "sender": "@a:domain",
+            "origin": "domain",
+            "origin_server_ts": 1000000,
+            "event_id": "$should_be_removed:domain",
+            "type": "X",
+            "content": {},
+            "prev_events": [],
+            "auth_events": [],
+            "depth": 3
+        }"#;
+
+        let mut object = from_json_str(json).unwrap();
+        let event_id = finalize_pdu(&RoomVersionId::V5, &mut object, "domain", &key_pair).unwrap();
+
+        assert!(!object.contains_key("event_id"));
+        assert_eq!(
+            event_id.as_str(),
+            format!("${}", reference_hash(&object, &RoomVersionId::V5).unwrap())
+        );
+
+        let mut public_key_map = BTreeMap::new();
+        public_key_map
+            .insert("domain".into(), BTreeMap::from([("ed25519:1".into(), public_key_string())]));
+        verify_event(&public_key_map, &object, &RoomVersionId::V5).unwrap();
+    }
+
+    #[test]
+    fn finalize_pdu_with_origin_event_id() {
+        let key_pair = Ed25519KeyPair::from_der(&pkcs8(), "1".into()).unwrap();
+
+        let json = r#"{
+            "room_id": "!x:domain",
+            "sender": "@a:domain",
+            "origin": "domain",
+            "origin_server_ts": 1000000,
+            "type": "X",
+            "content": {},
+            "prev_events": [],
+            "auth_events": [],
+            "depth": 3
+        }"#;
+
+        let mut object = from_json_str(json).unwrap();
+        assert_matches!(
+            finalize_pdu(&RoomVersionId::V1, &mut object, "domain", &key_pair),
+            Err(Error::Json(JsonError::JsonFieldMissingFromObject(field))) if field == "event_id"
+        );
+
+        object.insert("event_id".to_owned(), "$0:domain".to_owned().into());
+        let event_id = finalize_pdu(&RoomVersionId::V1, &mut object, "domain", &key_pair).unwrap();
+
+        assert_eq!(event_id, "$0:domain");
+        assert!(object.contains_key("event_id"));
+        assert!(object.contains_key("hashes"));
+        assert!(object.contains_key("signatures"));
+    }
 }
@@ -27,12 +27,12 @@ use ruma_common::{
             join_rules::{JoinRule, RoomJoinRulesEventContent},
             member::{MembershipState, RoomMemberEventContent},
         },
-        StateEventType, TimelineEventType,
+        TimelineEventType,
     },
     room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, RoomVersionId,
     UserId,
 };
-use ruma_state_res::{self as state_res, Error, Event, Result, StateMap};
+use ruma_state_res::{self as state_res, Error, Event, Result, StateKey, StateMap, TypeStateKey};
 use serde_json::{
     json,
     value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
@@ -519,11 +519,11 @@ fn BAN_STATE_SET() -> HashMap<OwnedEventId, Arc<PduEvent>> {
 
 /// Convenience trait for adding event type plus state key to state maps.
 trait EventTypeExt {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String);
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey;
 }
 
 impl EventTypeExt for &TimelineEventType {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String) {
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey {
         (self.to_string().into(), state_key.into())
     }
 }
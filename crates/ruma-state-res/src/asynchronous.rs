@@ -0,0 +1,320 @@
+//! Variants of [`auth_check`] and [`resolve`] that fetch the state and the events asynchronously.
+//!
+//! These functions first load all the events needed by the algorithm with the async callbacks,
+//! and then run the synchronous algorithm with the loaded events.
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+};
+
+use ruma_common::{
+    events::{StateEventType, TimelineEventType},
+    RoomVersionId,
+};
+
+use crate::{
+    auth_check, auth_types_for_pdu, get_auth_chain_diff, resolve, separate, Error, Event,
+    EventTypeExt, Result, RoomVersion, StateKey, StateMap,
+};
+
+/// Authenticate the incoming `event`, fetching the state asynchronously.
+///
+/// This works like [`auth_check`], but the `fetch_state` callback returns a future. The state
+/// events needed to authenticate the event are all fetched before the checks are run.
+pub async fn auth_check_async<E, F, Fut>(
+    room_version: &RoomVersion,
+    incoming_event: impl Event,
+    current_third_party_invite: Option<impl Event>,
+    fetch_state: F,
+) -> Result<bool>
+where
+    E: Event,
+    F: Fn(StateEventType, StateKey) -> Fut,
+    Fut: Future<Output = Option<E>>,
+{
+    let mut keys = auth_types_for_pdu(&incoming_event, room_version);
+
+    // `auth_check` also looks at the join rules and the target of all membership changes.
+    if *incoming_event.event_type() == TimelineEventType::RoomMember {
+        if let Some(state_key) = incoming_event.state_key() {
+            for key in [
+                (StateEventType::RoomJoinRules, "".into()),
+                (StateEventType::RoomMember, state_key.into()),
+            ] {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+
+    let mut state = StateMap::new();
+    for (event_type, state_key) in keys {
+        if let Some(event) = fetch_state(event_type.clone(), state_key.clone()).await {
+            state.insert((event_type, state_key), event);
+        }
+    }
+
+    auth_check(room_version, incoming_event, current_third_party_invite, |ty, key| {
+        state.get(&ty.with_state_key(key))
+    })
+}
+
+/// Resolve sets of state events as they come in, fetching the events asynchronously.
+///
+/// This works like [`resolve`], but the `fetch_event` callback returns a future. The events
+/// needed by the algorithm are all fetched before the state is resolved: the events in the full
+/// conflicted set with their auth chains, and the unconflicted state events needed to
+/// authenticate them.
+pub async fn resolve_async<'a, E, SetIter, F, Fut>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: F,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+    F: Fn(E::Id) -> Fut,
+    Fut: Future<Output = Option<E>>,
+{
+    let state_sets = state_sets.into_iter();
+    let (clean, conflicting) = separate(state_sets.clone());
+
+    let mut loader = EventLoader::new(fetch_event);
+
+    let room_version_rules = room_version
+        .rules()
+        .ok_or_else(|| Error::Unsupported(format!("found version `{room_version}`")))?;
+    let all_conflicted: Vec<_> = get_auth_chain_diff(auth_chain_sets.clone())
+        .chain(conflicting.into_values().flatten())
+        .collect();
+    loader.load_with_auth_chain(all_conflicted.iter().cloned()).await;
+
+    // The unconflicted state events that the conflicted events are authenticated against.
+    let mut auth_keys = HashSet::from([(StateEventType::RoomPowerLevels, "".into())]);
+    for id in &all_conflicted {
+        if let Some(event) = loader.events.get::<E::Id>(id) {
+            auth_keys.extend(auth_types_for_pdu(event, &room_version_rules));
+        }
+    }
+
+    for key in auth_keys {
+        if let Some(id) = clean.get(&key) {
+            // The auth chain of the power levels is needed for the mainline ordering.
+            if key.0 == StateEventType::RoomPowerLevels {
+                loader.load_with_auth_chain([id.clone()]).await;
+            } else {
+                loader.load(id.clone()).await;
+            }
+        }
+    }
+
+    let events = loader.events;
+    resolve(room_version, state_sets, auth_chain_sets, |id| events.get(id).cloned())
+}
+
+/// Loads events with an async callback, and keeps them in memory.
+struct EventLoader<E: Event, F> {
+    fetch_event: F,
+    events: HashMap<E::Id, E>,
+    not_found: HashSet<E::Id>,
+}
+
+impl<E, F, Fut> EventLoader<E, F>
+where
+    E: Event,
+    F: Fn(E::Id) -> Fut,
+    Fut: Future<Output = Option<E>>,
+{
+    fn new(fetch_event: F) -> Self {
+        Self { fetch_event, events: HashMap::new(), not_found: HashSet::new() }
+    }
+
+    /// Load the event with the given ID, if it wasn't already.
+    ///
+    /// Returns the event if it was found.
+    async fn load(&mut self, id: E::Id) -> Option<&E> {
+        if !self.events.contains_key::<E::Id>(&id) && !self.not_found.contains::<E::Id>(&id) {
+            match (self.fetch_event)(id.clone()).await {
+                Some(event) => {
+                    self.events.insert(id.clone(), event);
+                }
+                None => {
+                    self.not_found.insert(id.clone());
+                }
+            }
+        }
+
+        self.events.get::<E::Id>(&id)
+    }
+
+    /// Load the events with the given IDs and their full auth chains.
+    async fn load_with_auth_chain(&mut self, ids: impl IntoIterator<Item = E::Id>) {
+        let mut stack: Vec<_> = ids.into_iter().collect();
+        let mut seen = HashSet::new();
+
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+
+            if let Some(event) = self.load(id).await {
+                stack.extend(event.auth_events().cloned());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    };
+
+    use futures_lite::future::block_on;
+    use ruma_common::{
+        events::{StateEventType, TimelineEventType},
+        OwnedEventId, RoomVersionId,
+    };
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+    use super::{auth_check_async, resolve_async};
+    use crate::{
+        test_utils::{
+            alice, bob, ella, event_id, member_content_join, room_id, to_pdu_event, PduEvent,
+            TestStore, INITIAL_EVENTS,
+        },
+        Event, EventTypeExt, RoomVersion, StateKey, StateMap,
+    };
+
+    fn state_map(
+        events: &HashMap<OwnedEventId, Arc<PduEvent>>,
+        ids: &[&str],
+    ) -> StateMap<OwnedEventId> {
+        ids.iter()
+            .map(|&id| {
+                let event = &events[&event_id(id)];
+                (event.event_type().with_state_key(event.state_key().unwrap()), event_id(id))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn auth_check() {
+        let events = INITIAL_EVENTS();
+        let state = state_map(&events, &["CREATE", "IMA", "IPOWER", "IJR", "IMB"]);
+        let fetched = RefCell::new(Vec::new());
+
+        let fetch_state = |ty: StateEventType, key: StateKey| {
+            fetched.borrow_mut().push((ty.clone(), key.clone()));
+            let event = state.get(&(ty, key)).map(|id| Arc::clone(&events[id]));
+            async move { event }
+        };
+
+        let join = to_pdu_event(
+            "IME",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            member_content_join(),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IMB"],
+        );
+        let allowed =
+            block_on(auth_check_async(&RoomVersion::V6, &join, None::<PduEvent>, fetch_state))
+                .unwrap();
+        assert!(allowed);
+        assert!(fetched.borrow().contains(&(StateEventType::RoomJoinRules, "".into())));
+
+        let ban = to_pdu_event(
+            "BAN",
+            bob(),
+            TimelineEventType::RoomMember,
+            Some(alice().as_str()),
+            to_raw_json_value(&json!({ "membership": "ban" })).unwrap(),
+            &["CREATE", "IMB", "IPOWER"],
+            &["IMB"],
+        );
+        let allowed =
+            block_on(auth_check_async(&RoomVersion::V6, &ban, None::<PduEvent>, fetch_state))
+                .unwrap();
+        assert!(!allowed);
+    }
+
+    #[test]
+    fn resolve() {
+        let mut events = INITIAL_EVENTS();
+        for event in [
+            to_pdu_event(
+                "T1",
+                alice(),
+                TimelineEventType::RoomTopic,
+                Some(""),
+                to_raw_json_value(&json!({ "topic": "T1" })).unwrap(),
+                &["CREATE", "IMA", "IPOWER"],
+                &["IMC"],
+            ),
+            to_pdu_event(
+                "PA",
+                alice(),
+                TimelineEventType::RoomPowerLevels,
+                Some(""),
+                to_raw_json_value(&json!({ "users": { alice(): 100, bob(): 50 } })).unwrap(),
+                &["CREATE", "IMA", "IPOWER"],
+                &["IMC"],
+            ),
+            to_pdu_event(
+                "T2",
+                bob(),
+                TimelineEventType::RoomTopic,
+                Some(""),
+                to_raw_json_value(&json!({ "topic": "T2" })).unwrap(),
+                &["CREATE", "IMB", "PA"],
+                &["PA"],
+            ),
+        ] {
+            events.insert(event.event_id().to_owned(), event);
+        }
+
+        let store = TestStore(events.clone());
+        let base = ["CREATE", "IMA", "IPOWER", "IJR", "IMB", "IMC"];
+        let state_sets = [
+            state_map(&events, &[&base[..], &["T1"]].concat()),
+            state_map(&events, &[&base[..], &["PA", "T2"]].concat()),
+        ];
+        let auth_chain_sets: Vec<_> = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+
+        let expected =
+            crate::resolve(&RoomVersionId::V6, &state_sets, auth_chain_sets.clone(), |id| {
+                events.get(id).cloned()
+            })
+            .unwrap();
+
+        let fetched = RefCell::new(HashSet::new());
+        let resolved = block_on(resolve_async(
+            &RoomVersionId::V6,
+            &state_sets,
+            auth_chain_sets,
+            |id: OwnedEventId| {
+                let event = events.get(&id).cloned();
+                fetched.borrow_mut().insert(id);
+                async move { event }
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(resolved, expected);
+        assert_eq!(resolved[&(StateEventType::RoomTopic, "".into())], event_id("T2"));
+        // Unconflicted events that are not needed to authenticate the conflicted events are
+        // not fetched.
+        assert!(!fetched.borrow().contains(&event_id("IMC")));
+    }
+}
@@ -0,0 +1,308 @@
+//! Authentication of a batch of events.
+
+use std::{borrow::Borrow, cell::RefCell, collections::HashMap, rc::Rc};
+
+use ruma_common::{
+    events::{
+        room::{
+            join_rules::RoomJoinRulesEventContent, member::RoomMemberEventContent,
+            power_levels::RoomPowerLevelsEventContent,
+        },
+        StateEventType, TimelineEventType,
+    },
+    EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, UserId,
+};
+use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+
+use crate::{
+    auth_check_detailed, power_levels::deserialize_power_levels, AuthResult, Event, Result,
+    RoomVersion,
+};
+
+/// Authenticate a batch of events, like the events received during a backfill.
+///
+/// This works like calling [`auth_check_detailed`] for every event, but the content of the
+/// `m.room.power_levels`, `m.room.member` and `m.room.join_rules` events of the state is only
+/// deserialized once for the whole batch, unless the events already provide it with
+/// [`Event::power_levels_content()`], [`Event::member_content()`] or
+/// [`Event::join_rules_content()`].
+///
+/// `events` must be sorted topologically, from the oldest to the most recent event. The content
+/// of the state events of the batch is deserialized when they are checked, so it is reused when
+/// they are part of the state of a more recent event.
+///
+/// `fetch_state` is called with the ID of the event being authenticated, and returns the state
+/// event with the given type and state key in the state before this event. For an
+/// `m.room.member` event with a third-party invite, it is also used to get the
+/// `m.room.third_party_invite` event matching the token of the invite.
+///
+/// Returns the result of the check of every event, in the same order as `events`. An error only
+/// applies to the event that caused it, the next events of the batch are still checked.
+pub fn auth_check_batch<E, S>(
+    room_version: &RoomVersion,
+    events: &[E],
+    fetch_state: impl Fn(&EventId, &StateEventType, &str) -> Option<S>,
+) -> Vec<Result<AuthResult>>
+where
+    E: Event,
+    S: Event,
+{
+    let cache = ContentCache::default();
+
+    events
+        .iter()
+        .map(|event| {
+            let event_id = event.event_id().borrow();
+            let event = cache.prepare(event, room_version);
+
+            let current_third_party_invite = event
+                .member_content()
+                .and_then(|content| content.third_party_invite.as_ref())
+                .and_then(|invite| {
+                    fetch_state(
+                        event_id,
+                        &StateEventType::RoomThirdPartyInvite,
+                        &invite.signed.token,
+                    )
+                });
+
+            auth_check_detailed(room_version, &event, current_third_party_invite, |ty, key| {
+                fetch_state(event_id, ty, key).map(|ev| cache.prepare(ev, room_version))
+            })
+        })
+        .collect()
+}
+
+/// The deserialized content of an event that is used during the authorization checks.
+enum PreparedContent {
+    PowerLevels(RoomPowerLevelsEventContent),
+    Member(RoomMemberEventContent),
+    JoinRules(RoomJoinRulesEventContent),
+    None,
+}
+
+/// A cache of the deserialized content of events, by event ID.
+#[derive(Default)]
+struct ContentCache {
+    contents: RefCell<HashMap<OwnedEventId, Rc<PreparedContent>>>,
+}
+
+impl ContentCache {
+    /// Wrap the given event with its deserialized content, deserializing it if it is not in the
+    /// cache yet.
+    fn prepare<E: Event>(&self, event: E, room_version: &RoomVersion) -> PreparedEvent<E> {
+        let event_id: &EventId = event.event_id().borrow();
+
+        let cached = self.contents.borrow().get(event_id).cloned();
+        let content = match cached {
+            Some(content) => content,
+            None => {
+                let content = Rc::new(PreparedContent::new(&event, room_version));
+                self.contents.borrow_mut().insert(event_id.to_owned(), Rc::clone(&content));
+                content
+            }
+        };
+
+        PreparedEvent { event, content }
+    }
+}
+
+impl PreparedContent {
+    fn new(event: &impl Event, room_version: &RoomVersion) -> Self {
+        // Don't deserialize the content again if the event already provides it, and fall back to
+        // the checks of the JSON if it is invalid, so the errors are the same as `auth_check`.
+        match event.event_type() {
+            TimelineEventType::RoomPowerLevels if event.power_levels_content().is_none() => {
+                deserialize_power_levels(event.content(), room_version)
+                    .map_or(Self::None, Self::PowerLevels)
+            }
+            TimelineEventType::RoomMember if event.member_content().is_none() => {
+                from_json_str(event.content().get()).map_or(Self::None, Self::Member)
+            }
+            TimelineEventType::RoomJoinRules if event.join_rules_content().is_none() => {
+                from_json_str(event.content().get()).map_or(Self::None, Self::JoinRules)
+            }
+            _ => Self::None,
+        }
+    }
+}
+
+/// An event with its deserialized content.
+struct PreparedEvent<E> {
+    event: E,
+    content: Rc<PreparedContent>,
+}
+
+impl<E: Event> Event for PreparedEvent<E> {
+    type Id = E::Id;
+
+    fn event_id(&self) -> &Self::Id {
+        self.event.event_id()
+    }
+
+    fn room_id(&self) -> &RoomId {
+        self.event.room_id()
+    }
+
+    fn sender(&self) -> &UserId {
+        self.event.sender()
+    }
+
+    fn origin_server_ts(&self) -> MilliSecondsSinceUnixEpoch {
+        self.event.origin_server_ts()
+    }
+
+    fn event_type(&self) -> &TimelineEventType {
+        self.event.event_type()
+    }
+
+    fn content(&self) -> &RawJsonValue {
+        self.event.content()
+    }
+
+    fn state_key(&self) -> Option<&str> {
+        self.event.state_key()
+    }
+
+    fn prev_events(&self) -> Box<dyn DoubleEndedIterator<Item = &Self::Id> + '_> {
+        self.event.prev_events()
+    }
+
+    fn auth_events(&self) -> Box<dyn DoubleEndedIterator<Item = &Self::Id> + '_> {
+        self.event.auth_events()
+    }
+
+    fn redacts(&self) -> Option<&Self::Id> {
+        self.event.redacts()
+    }
+
+    fn power_levels_content(&self) -> Option<&RoomPowerLevelsEventContent> {
+        match &*self.content {
+            PreparedContent::PowerLevels(content) => Some(content),
+            _ => self.event.power_levels_content(),
+        }
+    }
+
+    fn member_content(&self) -> Option<&RoomMemberEventContent> {
+        match &*self.content {
+            PreparedContent::Member(content) => Some(content),
+            _ => self.event.member_content(),
+        }
+    }
+
+    fn join_rules_content(&self) -> Option<&RoomJoinRulesEventContent> {
+        match &*self.content {
+            PreparedContent::JoinRules(content) => Some(content),
+            _ => self.event.join_rules_content(),
+        }
+    }
+
+    fn rejected(&self) -> bool {
+        self.event.rejected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use js_int::int;
+    use ruma_common::{
+        events::{room::member::MembershipState, StateEventType, TimelineEventType},
+        EventId, OwnedEventId,
+    };
+
+    use super::auth_check_batch;
+    use crate::{
+        auth_check_detailed,
+        test_utils::{
+            alice, bob, charlie, init_member_event, init_power_levels_event, member_event_builder,
+            PduBuilder, PduEvent, TestRoom,
+        },
+        Event, EventTypeExt, RoomVersion, StateMap,
+    };
+
+    #[test]
+    fn auth_check_batch_chain() {
+        let mut room = TestRoom::bootstrap(alice(), &[bob()]);
+        let mut state_before: HashMap<OwnedEventId, StateMap<OwnedEventId>> = HashMap::new();
+        let mut events = Vec::new();
+
+        let mut add_event = |room: &mut TestRoom, builder| {
+            let state = room.state.clone();
+            let event: Arc<PduEvent> = room.add_event(builder);
+            state_before.insert(event.event_id().clone(), state);
+            events.push(event);
+        };
+
+        add_event(
+            &mut room,
+            PduBuilder::new("MSG_bob", bob(), TimelineEventType::RoomMessage)
+                .serialized_content(&serde_json::json!({ "body": "Hi" })),
+        );
+        add_event(
+            &mut room,
+            member_event_builder("IM_charlie", charlie(), charlie(), MembershipState::Join),
+        );
+        add_event(
+            &mut room,
+            PduBuilder::new("MSG_charlie", charlie(), TimelineEventType::RoomMessage)
+                .serialized_content(&serde_json::json!({ "body": "Hello" })),
+        );
+        add_event(&mut room, member_event_builder("BAN", bob(), charlie(), MembershipState::Ban));
+
+        let results = auth_check_batch(&RoomVersion::V6, &events, |id, ty, key| {
+            let event_id = state_before[id].get(&ty.with_state_key(key))?;
+            room.store.0.get::<EventId>(event_id).cloned()
+        });
+        let results = results.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_allowed());
+        assert!(results[1].is_allowed());
+        assert!(results[2].is_allowed());
+        // Bob doesn't have the power level to ban.
+        assert!(!results[3].is_allowed());
+    }
+
+    #[test]
+    fn auth_check_batch_same_as_auth_check() {
+        let room = TestRoom::bootstrap(alice(), &[bob()]);
+        let events = [
+            init_power_levels_event("PA", bob(), &[(alice(), int!(100)), (bob(), int!(100))]),
+            init_member_event("MC", charlie(), charlie(), MembershipState::Invite),
+        ]
+        .into_iter()
+        .map(|event| {
+            let auth_events = [
+                (StateEventType::RoomCreate, ""),
+                (StateEventType::RoomPowerLevels, ""),
+                (StateEventType::RoomMember, bob().as_str()),
+            ]
+            .into_iter()
+            .map(|(ty, key)| room.state[&ty.with_state_key(key)].to_string())
+            .collect::<Vec<_>>();
+
+            PduBuilder::new(event.event_id().as_str(), event.sender(), event.event_type().clone())
+                .state_key(event.state_key().unwrap())
+                .content(event.content().to_owned())
+                .auth_events(&auth_events)
+                .prev_events(&[room.latest_event.as_str()])
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+        let fetch_state = |ty: &StateEventType, key: &str| {
+            let event_id = room.state.get(&ty.with_state_key(key))?;
+            room.store.0.get::<EventId>(event_id).cloned()
+        };
+        let results =
+            auth_check_batch(&RoomVersion::V6, &events, |_, ty, key| fetch_state(ty, key));
+
+        for (event, result) in events.iter().zip(results) {
+            let expected =
+                auth_check_detailed(&RoomVersion::V6, event, None::<PduEvent>, fetch_state);
+            assert_eq!(result.unwrap(), expected.unwrap());
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use js_int::Int;
+use ruma_common::EventId;
+
+use crate::{Error, Event, Result};
+
+/// A cache for the intermediate results of state resolution.
+///
+/// Servers often resolve state sets that are almost identical. The results cached by this trait
+/// only depend on the events, so they can be kept between calls to
+/// [`resolve_with_cache`](crate::resolve_with_cache) and [`auth_chain_with_cache`] instead of
+/// being computed again every time.
+///
+/// All the methods have a default implementation that doesn't cache anything.
+pub trait StateResolutionCache<Id> {
+    /// The full auth chain of the event with the given ID, if it is cached.
+    fn auth_chain(&self, _event_id: &EventId) -> Option<Arc<HashSet<Id>>> {
+        None
+    }
+
+    /// Cache the full auth chain of the event with the given ID.
+    fn insert_auth_chain(&mut self, _event_id: Id, _auth_chain: Arc<HashSet<Id>>) {}
+
+    /// The power level of the sender of the event with the given ID, according to the
+    /// `m.room.power_levels` event in its `auth_events`, if it is cached.
+    fn sender_power_level(&self, _event_id: &EventId) -> Option<Int> {
+        None
+    }
+
+    /// Cache the power level of the sender of the event with the given ID.
+    fn insert_sender_power_level(&mut self, _event_id: Id, _power_level: Int) {}
+
+    /// The depth of the event with the given ID in the mainline of the given
+    /// `m.room.power_levels` event, if it is cached.
+    fn mainline_depth(
+        &self,
+        _power_levels_event_id: &EventId,
+        _event_id: &EventId,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Cache the depth of the event with the given ID in the mainline of the given
+    /// `m.room.power_levels` event.
+    fn insert_mainline_depth(&mut self, _power_levels_event_id: Id, _event_id: Id, _depth: usize) {}
+}
+
+/// A [`StateResolutionCache`] that doesn't cache anything.
+pub(crate) struct NoCache;
+
+impl<Id> StateResolutionCache<Id> for NoCache {}
+
+/// A [`StateResolutionCache`] that keeps all the results in memory.
+///
+/// The cache grows without limit, so it should be cleared or dropped regularly.
+#[derive(Clone, Debug)]
+pub struct InMemoryStateResolutionCache<Id> {
+    auth_chains: HashMap<Id, Arc<HashSet<Id>>>,
+    sender_power_levels: HashMap<Id, Int>,
+    mainline_depths: HashMap<Id, HashMap<Id, usize>>,
+}
+
+impl<Id> InMemoryStateResolutionCache<Id> {
+    /// Creates an empty `InMemoryStateResolutionCache`.
+    pub fn new() -> Self {
+        Self {
+            auth_chains: HashMap::new(),
+            sender_power_levels: HashMap::new(),
+            mainline_depths: HashMap::new(),
+        }
+    }
+
+    /// Remove all the cached results.
+    pub fn clear(&mut self) {
+        self.auth_chains.clear();
+        self.sender_power_levels.clear();
+        self.mainline_depths.clear();
+    }
+}
+
+impl<Id> Default for InMemoryStateResolutionCache<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id> StateResolutionCache<Id> for InMemoryStateResolutionCache<Id>
+where
+    Id: Borrow<EventId> + Eq + Hash,
+{
+    fn auth_chain(&self, event_id: &EventId) -> Option<Arc<HashSet<Id>>> {
+        self.auth_chains.get(event_id).cloned()
+    }
+
+    fn insert_auth_chain(&mut self, event_id: Id, auth_chain: Arc<HashSet<Id>>) {
+        self.auth_chains.insert(event_id, auth_chain);
+    }
+
+    fn sender_power_level(&self, event_id: &EventId) -> Option<Int> {
+        self.sender_power_levels.get(event_id).copied()
+    }
+
+    fn insert_sender_power_level(&mut self, event_id: Id, power_level: Int) {
+        self.sender_power_levels.insert(event_id, power_level);
+    }
+
+    fn mainline_depth(&self, power_levels_event_id: &EventId, event_id: &EventId) -> Option<usize> {
+        self.mainline_depths.get(power_levels_event_id)?.get(event_id).copied()
+    }
+
+    fn insert_mainline_depth(&mut self, power_levels_event_id: Id, event_id: Id, depth: usize) {
+        self.mainline_depths.entry(power_levels_event_id).or_default().insert(event_id, depth);
+    }
+}
+
+/// Compute the full auth chain of the given events, using and populating the given cache.
+///
+/// The auth chain of an event is the set of its `auth_events`, and of their `auth_events`
+/// recursively. The given events are not part of the result, unless they are in the auth chain of
+/// another one of the given events.
+///
+/// The auth chain of every event that is walked is cached, so computing the auth chains of the
+/// state sets of a room again only needs to fetch the new events.
+pub fn auth_chain_with_cache<E: Event>(
+    event_ids: impl IntoIterator<Item = E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    cache: &mut impl StateResolutionCache<E::Id>,
+) -> Result<HashSet<E::Id>> {
+    let mut auth_chain = HashSet::new();
+
+    for event_id in event_ids {
+        let event_auth_chain = event_auth_chain(event_id, &fetch_event, cache)?;
+        auth_chain.extend(event_auth_chain.iter().cloned());
+    }
+
+    Ok(auth_chain)
+}
+
+/// Compute the auth chain of a single event.
+fn event_auth_chain<E: Event>(
+    event_id: E::Id,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    cache: &mut impl StateResolutionCache<E::Id>,
+) -> Result<Arc<HashSet<E::Id>>> {
+    // The auth chains computed during this call, in case the cache doesn't keep them.
+    let mut computed: HashMap<E::Id, Arc<HashSet<E::Id>>> = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let mut stack = vec![(event_id.clone(), false)];
+
+    while let Some((id, auth_events_done)) = stack.pop() {
+        if cached_auth_chain(&computed, cache, &id).is_some() {
+            continue;
+        }
+
+        let event = fetch_event(id.borrow())
+            .ok_or_else(|| Error::NotFound(format!("Failed to find {id}")))?;
+
+        if !auth_events_done {
+            // Compute the auth chains of the auth events first. Events that are already being
+            // computed are part of a cycle and are skipped.
+            in_progress.insert(id.clone());
+            stack.push((id, true));
+            for auth_event_id in event.auth_events() {
+                if !in_progress.contains::<E::Id>(auth_event_id) {
+                    stack.push((auth_event_id.clone(), false));
+                }
+            }
+            continue;
+        }
+
+        let mut auth_chain = HashSet::new();
+        for auth_event_id in event.auth_events() {
+            auth_chain.insert(auth_event_id.clone());
+            if let Some(chain) = cached_auth_chain(&computed, cache, auth_event_id) {
+                auth_chain.extend(chain.iter().cloned());
+            }
+        }
+
+        let auth_chain = Arc::new(auth_chain);
+        cache.insert_auth_chain(id.clone(), Arc::clone(&auth_chain));
+        computed.insert(id, auth_chain);
+    }
+
+    Ok(cached_auth_chain(&computed, cache, &event_id).unwrap_or_default())
+}
+
+/// The auth chain of the given event computed during this call, or in the cache.
+fn cached_auth_chain<Id>(
+    computed: &HashMap<Id, Arc<HashSet<Id>>>,
+    cache: &impl StateResolutionCache<Id>,
+    event_id: &Id,
+) -> Option<Arc<HashSet<Id>>>
+where
+    Id: Borrow<EventId> + Eq + Hash,
+{
+    computed.get::<Id>(event_id).cloned().or_else(|| cache.auth_chain(event_id.borrow()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc};
+
+    use ruma_common::{
+        events::{StateEventType, TimelineEventType},
+        OwnedEventId, RoomVersionId,
+    };
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+    use super::{
+        auth_chain_with_cache, InMemoryStateResolutionCache, NoCache, StateResolutionCache,
+    };
+    use crate::{
+        resolve_with_cache,
+        test_utils::{alice, bob, event_id, to_pdu_event, INITIAL_EVENTS},
+        Event, EventTypeExt, ResolverConfig, StateMap,
+    };
+
+    #[test]
+    fn auth_chain() {
+        let events = INITIAL_EVENTS();
+        let fetch_event = |id: &_| events.get(id).map(Arc::clone);
+        let expected = HashSet::from([
+            event_id("CREATE"),
+            event_id("IMA"),
+            event_id("IPOWER"),
+            event_id("IJR"),
+        ]);
+
+        let auth_chain =
+            auth_chain_with_cache([event_id("IMC")], fetch_event, &mut NoCache).unwrap();
+        assert_eq!(auth_chain, expected);
+
+        let mut cache = InMemoryStateResolutionCache::new();
+        let auth_chain = auth_chain_with_cache([event_id("IMC")], fetch_event, &mut cache).unwrap();
+        assert_eq!(auth_chain, expected);
+        assert_eq!(
+            *cache.auth_chain(&event_id("IPOWER")).unwrap(),
+            HashSet::from([event_id("CREATE"), event_id("IMA")])
+        );
+
+        // Only the new event needs to be fetched once the rest of the auth chain is cached.
+        let imb = Arc::clone(&events[&event_id("IMB")]);
+        let auth_chain = auth_chain_with_cache(
+            [event_id("IMB")],
+            |id: &_| (*id == event_id("IMB")).then(|| Arc::clone(&imb)),
+            &mut cache,
+        )
+        .unwrap();
+        assert_eq!(auth_chain, expected);
+    }
+
+    #[test]
+    fn resolve() {
+        let mut events = INITIAL_EVENTS();
+        for event in [
+            to_pdu_event(
+                "T1",
+                alice(),
+                TimelineEventType::RoomTopic,
+                Some(""),
+                to_raw_json_value(&json!({ "topic": "T1" })).unwrap(),
+                &["CREATE", "IMA", "IPOWER"],
+                &["IMC"],
+            ),
+            to_pdu_event(
+                "PA",
+                alice(),
+                TimelineEventType::RoomPowerLevels,
+                Some(""),
+                to_raw_json_value(&json!({ "users": { alice(): 100, bob(): 50 } })).unwrap(),
+                &["CREATE", "IMA", "IPOWER"],
+                &["IMC"],
+            ),
+            to_pdu_event(
+                "T2",
+                bob(),
+                TimelineEventType::RoomTopic,
+                Some(""),
+                to_raw_json_value(&json!({ "topic": "T2" })).unwrap(),
+                &["CREATE", "IMB", "PA"],
+                &["PA"],
+            ),
+        ] {
+            events.insert(event.event_id().to_owned(), event);
+        }
+        let fetch_event = |id: &_| events.get(id).map(Arc::clone);
+
+        let state_map = |ids: &[&str]| -> StateMap<OwnedEventId> {
+            ids.iter()
+                .map(|&id| {
+                    let event = &events[&event_id(id)];
+                    (event.event_type().with_state_key(event.state_key().unwrap()), event_id(id))
+                })
+                .collect()
+        };
+        let base = ["CREATE", "IMA", "IPOWER", "IJR", "IMB", "IMC"];
+        let state_sets = [
+            state_map(&[&base[..], &["T1"]].concat()),
+            state_map(&[&base[..], &["PA", "T2"]].concat()),
+        ];
+
+        let mut cache = InMemoryStateResolutionCache::new();
+        let auth_chain_sets: Vec<_> = state_sets
+            .iter()
+            .map(|state| {
+                auth_chain_with_cache(state.values().cloned(), fetch_event, &mut cache).unwrap()
+            })
+            .collect();
+
+        let resolve = |cache: &mut InMemoryStateResolutionCache<_>| {
+            resolve_with_cache(
+                &RoomVersionId::V6,
+                &state_sets,
+                auth_chain_sets.clone(),
+                fetch_event,
+                &ResolverConfig::new(),
+                cache,
+            )
+            .unwrap()
+        };
+
+        let expected =
+            crate::resolve(&RoomVersionId::V6, &state_sets, auth_chain_sets.clone(), fetch_event)
+                .unwrap();
+        let resolved = resolve(&mut cache);
+        assert_eq!(resolved, expected);
+        assert_eq!(resolved[&(StateEventType::RoomTopic, "".into())], event_id("T2"));
+
+        assert!(cache.sender_power_level(&event_id("PA")).is_some());
+        assert!(cache.mainline_depth(&event_id("PA"), &event_id("T2")).is_some());
+
+        // Resolving again with the cache gives the same result.
+        assert_eq!(resolve(&mut cache), expected);
+    }
+}
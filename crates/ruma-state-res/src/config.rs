@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// Limits applied during state resolution.
+///
+/// State resolution walks the auth chains and the power level history of a room, which can be
+/// arbitrarily large in rooms crafted by a malicious server. Set these limits to bound the memory
+/// and CPU used by [`resolve_with_config`](crate::resolve_with_config).
+///
+/// The default configuration doesn't enforce any limit.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ResolverConfig {
+    /// The maximum number of events in a single auth chain.
+    pub max_auth_chain_length: usize,
+
+    /// The maximum number of events in the full conflicted set.
+    pub max_conflicted_events: usize,
+
+    /// The maximum number of power level events to follow when walking back the power level
+    /// history of an event.
+    pub max_recursion_depth: usize,
+}
+
+impl ResolverConfig {
+    /// Creates a new `ResolverConfig` without any limit.
+    pub fn new() -> Self {
+        Self {
+            max_auth_chain_length: usize::MAX,
+            max_conflicted_events: usize::MAX,
+            max_recursion_depth: usize::MAX,
+        }
+    }
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A limit of [`ResolverConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum ResolverLimit {
+    /// [`ResolverConfig::max_auth_chain_length`].
+    AuthChainLength,
+
+    /// [`ResolverConfig::max_conflicted_events`].
+    ConflictedEvents,
+
+    /// [`ResolverConfig::max_recursion_depth`].
+    RecursionDepth,
+}
+
+impl fmt::Display for ResolverLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::AuthChainLength => "auth chain length",
+            Self::ConflictedEvents => "number of conflicted events",
+            Self::RecursionDepth => "recursion depth",
+        };
+
+        f.write_str(s)
+    }
+}
@@ -0,0 +1,161 @@
+//! Helpers to maintain the DAG of the events of a room.
+
+use std::collections::{HashMap, HashSet};
+
+use js_int::{uint, UInt};
+
+use crate::Event;
+
+/// Computes the `depth` of a new event from the `depth` of its `prev_events`.
+///
+/// This is the maximum depth of the `prev_events` plus one, saturating at the maximum value of a
+/// canonical JSON integer. An event without `prev_events` has a depth of `1`.
+pub fn depth_from_prev_events(prev_depths: impl IntoIterator<Item = UInt>) -> UInt {
+    match prev_depths.into_iter().max() {
+        Some(depth) => depth.checked_add(uint!(1)).unwrap_or(UInt::MAX),
+        None => uint!(1),
+    }
+}
+
+/// Whether the given `depth` of an event is consistent with the `depth` of its `prev_events`.
+///
+/// The depth of an event must be greater than the depth of all its `prev_events`, unless it
+/// already reached the maximum value of a canonical JSON integer.
+pub fn is_valid_depth(depth: UInt, prev_depths: impl IntoIterator<Item = UInt>) -> bool {
+    prev_depths.into_iter().all(|prev_depth| depth > prev_depth || depth == UInt::MAX)
+}
+
+/// Find a cycle in the `prev_events` of the given events.
+///
+/// Only the edges between the given events are followed, `prev_events` that are not part of the
+/// set are ignored.
+///
+/// Returns the IDs of the events forming the cycle, starting from the most recent, if one was
+/// found.
+pub fn find_prev_events_cycle<E: Event>(events: impl IntoIterator<Item = E>) -> Option<Vec<E::Id>> {
+    let graph: HashMap<E::Id, Vec<E::Id>> = events
+        .into_iter()
+        .map(|event| (event.event_id().clone(), event.prev_events().cloned().collect()))
+        .collect();
+
+    // The events whose `prev_events` have all been visited, and cannot be part of a cycle.
+    let mut done: HashSet<&E::Id> = HashSet::new();
+
+    for start in graph.keys() {
+        if done.contains::<E::Id>(start) {
+            continue;
+        }
+
+        // Iterative DFS, the stack contains the current path with the index of the next
+        // `prev_event` to visit for each event.
+        let mut stack = vec![(start, 0)];
+
+        while let Some(&(id, next)) = stack.last() {
+            match graph.get::<E::Id>(id).and_then(|prev_events| prev_events.get(next)) {
+                Some(prev_id) => {
+                    stack.last_mut().unwrap().1 += 1;
+
+                    if !graph.contains_key::<E::Id>(prev_id) || done.contains::<E::Id>(prev_id) {
+                        continue;
+                    }
+
+                    if let Some(pos) = stack.iter().position(|&(id, _)| id == prev_id) {
+                        return Some(stack[pos..].iter().map(|&(id, _)| id.clone()).collect());
+                    }
+
+                    stack.push((prev_id, 0));
+                }
+                None => {
+                    done.insert(id);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Compute the forward extremities of the given events.
+///
+/// These are the events that are not referenced in the `prev_events` of any of the given events,
+/// i.e. the candidates for the `prev_events` of a new event.
+pub fn forward_extremities<E: Event>(events: impl IntoIterator<Item = E>) -> HashSet<E::Id> {
+    let mut extremities = HashSet::new();
+    let mut referenced: HashSet<E::Id> = HashSet::new();
+
+    for event in events {
+        referenced.extend(event.prev_events().cloned());
+        extremities.insert(event.event_id().clone());
+    }
+
+    extremities.retain(|id| !referenced.contains::<E::Id>(id));
+    extremities
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc};
+
+    use js_int::{uint, UInt};
+    use ruma_common::events::TimelineEventType;
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+    use super::{
+        depth_from_prev_events, find_prev_events_cycle, forward_extremities, is_valid_depth,
+    };
+    use crate::test_utils::{alice, event_id, to_pdu_event, PduEvent};
+
+    fn message(id: &str, prev_events: &[&str]) -> Arc<PduEvent> {
+        to_pdu_event(
+            id,
+            alice(),
+            TimelineEventType::RoomMessage,
+            None,
+            to_raw_json_value(&json!({})).unwrap(),
+            &[],
+            prev_events,
+        )
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(depth_from_prev_events([]), uint!(1));
+        assert_eq!(depth_from_prev_events([uint!(3), uint!(7), uint!(5)]), uint!(8));
+        assert_eq!(depth_from_prev_events([UInt::MAX]), UInt::MAX);
+
+        assert!(is_valid_depth(uint!(8), [uint!(3), uint!(7)]));
+        assert!(!is_valid_depth(uint!(7), [uint!(3), uint!(7)]));
+        assert!(is_valid_depth(UInt::MAX, [UInt::MAX]));
+    }
+
+    #[test]
+    fn cycle() {
+        let events = [message("A", &[]), message("B", &["A"]), message("C", &["B", "MISSING"])];
+        assert_eq!(find_prev_events_cycle(&events), None);
+
+        let events = [
+            message("A", &["C"]),
+            message("B", &["A"]),
+            message("C", &["B"]),
+            message("D", &["C"]),
+        ];
+        let cycle = find_prev_events_cycle(&events).unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(
+            cycle.into_iter().collect::<HashSet<_>>(),
+            HashSet::from([event_id("A"), event_id("B"), event_id("C")])
+        );
+
+        let events = [message("A", &["A"])];
+        assert_eq!(find_prev_events_cycle(&events), Some(vec![event_id("A")]));
+    }
+
+    #[test]
+    fn extremities() {
+        let events =
+            [message("A", &[]), message("B", &["A"]), message("C", &["A"]), message("D", &["B"])];
+
+        assert_eq!(forward_extremities(&events), HashSet::from([event_id("C"), event_id("D")]));
+    }
+}
@@ -1,6 +1,8 @@
 use serde_json::Error as JsonError;
 use thiserror::Error;
 
+use crate::ResolverLimit;
+
 /// Result type for state resolution.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -24,6 +26,16 @@ pub enum Error {
     #[error("Invalid PDU: {0}")]
     InvalidPdu(String),
 
+    /// A limit of the [`ResolverConfig`](crate::ResolverConfig) was exceeded.
+    #[error("State resolution limit exceeded: {kind} is greater than {max}")]
+    LimitExceeded {
+        /// The limit that was exceeded.
+        kind: ResolverLimit,
+
+        /// The maximum value allowed by the configuration.
+        max: usize,
+    },
+
     /// A custom error.
     #[error("{0}")]
     Custom(Box<dyn std::error::Error>),
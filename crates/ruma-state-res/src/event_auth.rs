@@ -1,22 +1,25 @@
-use std::{borrow::Borrow, collections::BTreeSet};
+use std::{
+    borrow::{Borrow, Cow},
+    collections::BTreeSet,
+};
 
 use js_int::{int, Int};
 use ruma_common::{
     events::{
         room::{
-            create::RoomCreateEventContent,
+            create::{PreviousRoom, RoomCreateEventContent},
             join_rules::{JoinRule, RoomJoinRulesEventContent},
-            member::{MembershipState, ThirdPartyInvite},
+            member::{MembershipState, RoomMemberEventContent, ThirdPartyInvite},
             power_levels::RoomPowerLevelsEventContent,
             third_party_invite::RoomThirdPartyInviteEventContent,
         },
         StateEventType, TimelineEventType,
     },
-    serde::{Base64, Raw},
+    serde::Raw,
     OwnedUserId, RoomVersionId, UserId,
 };
 use serde::{de::IgnoredAny, Deserialize};
-use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+use serde_json::from_str as from_json_str;
 use tracing::{debug, error, info, warn};
 
 use crate::{
@@ -25,7 +28,7 @@ use crate::{
         deserialize_power_levels_content_invite, deserialize_power_levels_content_redact,
     },
     room_version::RoomVersion,
-    Error, Event, Result,
+    Error, Event, Result, SignatureVerifier, TypeStateKey,
 };
 
 // FIXME: field extracting could be bundled for `content`
@@ -37,79 +40,184 @@ struct GetMembership {
 #[derive(Deserialize)]
 struct RoomMemberContentFields {
     membership: Option<Raw<MembershipState>>,
+    third_party_invite: Option<Raw<ThirdPartyInvite>>,
     join_authorised_via_users_server: Option<Raw<OwnedUserId>>,
 }
 
+impl RoomMemberContentFields {
+    /// Get the content of an `m.room.member` event with only the fields used for authorization,
+    /// ignoring invalid values of the other fields.
+    ///
+    /// Returns `None` if the content doesn't have a valid `membership`.
+    fn lenient_content(content: &str) -> Option<RoomMemberEventContent> {
+        let fields: Self = from_json_str(content).ok()?;
+
+        let mut content = RoomMemberEventContent::new(fields.membership?.deserialize().ok()?);
+        content.third_party_invite = fields.third_party_invite.and_then(|t| t.deserialize().ok());
+        content.join_authorized_via_users_server =
+            fields.join_authorised_via_users_server.and_then(|u| u.deserialize().ok());
+
+        Some(content)
+    }
+}
+
+/// Get the membership of the given `m.room.member` event.
+///
+/// Uses [`Event::member_content()`] if it is available.
+fn get_membership(member_event: impl Event) -> serde_json::Result<MembershipState> {
+    match member_event.member_content() {
+        Some(content) => Ok(content.membership.clone()),
+        None => Ok(from_json_str::<GetMembership>(member_event.content().get())?.membership),
+    }
+}
+
+/// Get the content of the given `m.room.power_levels` event.
+///
+/// Uses [`Event::power_levels_content()`] if it is available.
+fn get_power_levels_content(
+    power_levels_event: &impl Event,
+) -> serde_json::Result<Cow<'_, RoomPowerLevelsEventContent>> {
+    match power_levels_event.power_levels_content() {
+        Some(content) => Ok(Cow::Borrowed(content)),
+        None => from_json_str(power_levels_event.content().get()).map(Cow::Owned),
+    }
+}
+
+/// Get the power level of the given user according to the given `m.room.power_levels` event.
+///
+/// Uses [`Event::power_levels_content()`] if it is available.
+fn get_user_power_level(
+    power_levels_event: impl Event,
+    user_id: &UserId,
+    room_version: &RoomVersion,
+) -> serde_json::Result<Int> {
+    let (users, users_default) = match power_levels_event.power_levels_content() {
+        Some(content) => (Cow::Borrowed(&content.users), content.users_default),
+        None => {
+            let content = deserialize_power_levels_content_fields(
+                power_levels_event.content().get(),
+                room_version,
+            )?;
+            (Cow::Owned(content.users), content.users_default)
+        }
+    };
+
+    Ok(users.get(user_id).copied().unwrap_or(users_default))
+}
+
+/// Get the level required to invite users according to the given `m.room.power_levels` event.
+///
+/// Uses [`Event::power_levels_content()`] if it is available.
+fn get_invite_level(
+    power_levels_event: impl Event,
+    room_version: &RoomVersion,
+) -> serde_json::Result<Int> {
+    match power_levels_event.power_levels_content() {
+        Some(content) => Ok(content.invite),
+        None => Ok(deserialize_power_levels_content_invite(
+            power_levels_event.content().get(),
+            room_version,
+        )?
+        .invite),
+    }
+}
+
 /// For the given event `kind` what are the relevant auth events that are needed to authenticate
-/// this `content`.
+/// this event.
 ///
-/// # Errors
+/// `member_content` is the content of the event if it is an `m.room.member` event, it is ignored
+/// for other event types. In room versions that support restricted join rules, the
+/// `m.room.member` event of the `join_authorised_via_users_server` of a join is also needed.
 ///
-/// This function will return an error if the supplied `content` is not a JSON object.
+/// Use [`auth_types_for_pdu`] to get the content from an [`Event`].
 pub fn auth_types_for_event(
     kind: &TimelineEventType,
     sender: &UserId,
     state_key: Option<&str>,
-    content: &RawJsonValue,
-) -> serde_json::Result<Vec<(StateEventType, String)>> {
+    member_content: Option<&RoomMemberEventContent>,
+    room_version: &RoomVersion,
+) -> Vec<TypeStateKey> {
     if kind == &TimelineEventType::RoomCreate {
-        return Ok(vec![]);
+        return vec![];
     }
 
     let mut auth_types = vec![
-        (StateEventType::RoomPowerLevels, "".to_owned()),
-        (StateEventType::RoomMember, sender.to_string()),
-        (StateEventType::RoomCreate, "".to_owned()),
+        (StateEventType::RoomPowerLevels, "".into()),
+        (StateEventType::RoomMember, sender.as_str().into()),
+        (StateEventType::RoomCreate, "".into()),
     ];
 
     if kind == &TimelineEventType::RoomMember {
-        #[derive(Deserialize)]
-        struct RoomMemberContentFields {
-            membership: Option<Raw<MembershipState>>,
-            third_party_invite: Option<Raw<ThirdPartyInvite>>,
-            join_authorised_via_users_server: Option<Raw<OwnedUserId>>,
-        }
+        if let (Some(state_key), Some(content)) = (state_key, member_content) {
+            let membership = &content.membership;
 
-        if let Some(state_key) = state_key {
-            let content: RoomMemberContentFields = from_json_str(content.get())?;
+            if [MembershipState::Join, MembershipState::Invite, MembershipState::Knock]
+                .contains(membership)
+            {
+                let key = (StateEventType::RoomJoinRules, "".into());
+                if !auth_types.contains(&key) {
+                    auth_types.push(key);
+                }
+            }
 
-            if let Some(Ok(membership)) = content.membership.map(|m| m.deserialize()) {
-                if [MembershipState::Join, MembershipState::Invite, MembershipState::Knock]
-                    .contains(&membership)
-                {
-                    let key = (StateEventType::RoomJoinRules, "".to_owned());
+            if *membership == MembershipState::Join && room_version.restricted_join_rules {
+                if let Some(u) = &content.join_authorized_via_users_server {
+                    let key = (StateEventType::RoomMember, u.as_str().into());
                     if !auth_types.contains(&key) {
                         auth_types.push(key);
                     }
-
-                    if let Some(Ok(u)) =
-                        content.join_authorised_via_users_server.map(|m| m.deserialize())
-                    {
-                        let key = (StateEventType::RoomMember, u.to_string());
-                        if !auth_types.contains(&key) {
-                            auth_types.push(key);
-                        }
-                    }
                 }
+            }
 
-                let key = (StateEventType::RoomMember, state_key.to_owned());
-                if !auth_types.contains(&key) {
-                    auth_types.push(key);
-                }
+            let key = (StateEventType::RoomMember, state_key.into());
+            if !auth_types.contains(&key) {
+                auth_types.push(key);
+            }
 
-                if membership == MembershipState::Invite {
-                    if let Some(Ok(t_id)) = content.third_party_invite.map(|t| t.deserialize()) {
-                        let key = (StateEventType::RoomThirdPartyInvite, t_id.signed.token);
-                        if !auth_types.contains(&key) {
-                            auth_types.push(key);
-                        }
+            if *membership == MembershipState::Invite {
+                if let Some(t_id) = &content.third_party_invite {
+                    let key =
+                        (StateEventType::RoomThirdPartyInvite, t_id.signed.token.as_str().into());
+                    if !auth_types.contains(&key) {
+                        auth_types.push(key);
                     }
                 }
             }
         }
     }
 
-    Ok(auth_types)
+    auth_types
+}
+
+/// What are the relevant auth events that are needed to authenticate the given event.
+///
+/// This calls [`auth_types_for_event`] with the content of the event, using
+/// [`Event::member_content()`] if it is available. If the content of an `m.room.member` event
+/// can't be fully deserialized, only the fields used for authorization are read, like
+/// [`auth_check`] does.
+pub fn auth_types_for_pdu(event: &impl Event, room_version: &RoomVersion) -> Vec<TypeStateKey> {
+    let member_content = if *event.event_type() == TimelineEventType::RoomMember {
+        match event.member_content() {
+            Some(content) => Some(Cow::Borrowed(content)),
+            None => match from_json_str(event.content().get()) {
+                Ok(content) => Some(Cow::Owned(content)),
+                Err(e) => {
+                    warn!("invalid m.room.member content in {}: {e}", event.event_id());
+                    RoomMemberContentFields::lenient_content(event.content().get()).map(Cow::Owned)
+                }
+            },
+        }
+    } else {
+        None
+    };
+
+    auth_types_for_event(
+        event.event_type(),
+        event.sender(),
+        event.state_key(),
+        member_content.as_deref(),
+        room_version,
+    )
 }
 
 /// Authenticate the incoming `event`.
@@ -121,12 +229,52 @@ pub fn auth_types_for_event(
 ///
 /// The `fetch_state` closure should gather state from a state snapshot. We need to know if the
 /// event passes auth against some state not a recursive collection of auth_events fields.
+///
+/// Use [`auth_check_detailed`] to know why an event was rejected.
 pub fn auth_check<E: Event>(
     room_version: &RoomVersion,
     incoming_event: impl Event,
     current_third_party_invite: Option<impl Event>,
     fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
 ) -> Result<bool> {
+    auth_check_detailed(room_version, incoming_event, current_third_party_invite, fetch_state)
+        .map(|result| result.is_allowed())
+}
+
+/// Authenticate the incoming `event` after verifying its signatures with `verifier`.
+///
+/// This works like [`auth_check_detailed`], but the event is rejected with
+/// [`RejectionReason::InvalidSignature`] if `verifier` fails to verify its signatures. The
+/// signatures of the `signed` object of third-party invites are verified with the public keys of
+/// the `m.room.third_party_invite` event in any case.
+///
+/// This should be used for events received over federation. The events used during state
+/// resolution have already been verified when they were received, so [`resolve`](crate::resolve)
+/// doesn't verify their signatures again.
+pub fn auth_check_with_verifier<E: Event>(
+    room_version: &RoomVersion,
+    incoming_event: impl Event,
+    current_third_party_invite: Option<impl Event>,
+    fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
+    verifier: &impl SignatureVerifier,
+) -> Result<AuthResult> {
+    if !verifier.verify_event(incoming_event.event_id().borrow()) {
+        warn!("signatures of {} are invalid", incoming_event.event_id());
+        return Ok(AuthResult::Rejected(RejectionReason::InvalidSignature));
+    }
+
+    auth_check_detailed(room_version, incoming_event, current_third_party_invite, fetch_state)
+}
+
+/// Authenticate the incoming `event`, and return the reason why it was rejected, if it was.
+///
+/// This works like [`auth_check`], but returns an [`AuthResult`] instead of a `bool`.
+pub fn auth_check_detailed<E: Event>(
+    room_version: &RoomVersion,
+    incoming_event: impl Event,
+    current_third_party_invite: Option<impl Event>,
+    fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
+) -> Result<AuthResult> {
     info!(
         "auth_check beginning for {} ({})",
         incoming_event.event_id(),
@@ -135,7 +283,7 @@ pub fn auth_check<E: Event>(
 
     // [synapse] check that all the events are in the same room as `incoming_event`
 
-    // [synapse] do_sig_check check the event has valid signatures for member events
+    // [synapse] do_sig_check is done by `auth_check_with_verifier`
 
     // TODO do_size_check is false when called by `iterative_auth_check`
     // do_size_check is also mostly accomplished by ruma with the exception of checking event_type,
@@ -158,13 +306,13 @@ pub fn auth_check<E: Event>(
         // If it has any previous events, reject
         if incoming_event.prev_events().next().is_some() {
             warn!("the room creation event had previous events");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::CreateEventHasPrevEvents));
         }
 
         // If the domain of the room_id does not match the domain of the sender, reject
         if incoming_event.room_id().server_name() != sender.server_name() {
             warn!("creation events server does not match sender");
-            return Ok(false); // creation events room id does not match senders
+            return Ok(AuthResult::Rejected(RejectionReason::CreateEventServerMismatch));
         }
 
         let content: RoomCreateContentFields = from_json_str(incoming_event.content().get())?;
@@ -172,17 +320,17 @@ pub fn auth_check<E: Event>(
         // If content.room_version is present and is not a recognized version, reject
         if content.room_version.map(|v| v.deserialize().is_err()).unwrap_or(false) {
             warn!("invalid room version found in m.room.create event");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::InvalidRoomVersion));
         }
 
         // If content has no creator field, reject
         if content.creator.is_none() {
             warn!("no creator field found in m.room.create content");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::CreateEventMissingCreator));
         }
 
         info!("m.room.create event was allowed");
-        return Ok(true);
+        return Ok(AuthResult::Allowed);
     }
 
     /*
@@ -213,7 +361,7 @@ pub fn auth_check<E: Event>(
     let room_create_event = match fetch_state(&StateEventType::RoomCreate, "") {
         None => {
             warn!("no m.room.create event in auth chain");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::MissingCreateEvent));
         }
         Some(e) => e,
     };
@@ -222,23 +370,17 @@ pub fn auth_check<E: Event>(
     if !incoming_event.auth_events().any(|id| id.borrow() == room_create_event.event_id().borrow())
     {
         warn!("no m.room.create event in auth events");
-        return Ok(false);
+        return Ok(AuthResult::Rejected(RejectionReason::CreateEventNotInAuthEvents));
     }
 
     // If the create event content has the field m.federate set to false and the sender domain of
     // the event does not match the sender domain of the create event, reject.
-    #[derive(Deserialize)]
-    struct RoomCreateContentFederate {
-        #[serde(rename = "m.federate", default = "ruma_common::serde::default_true")]
-        federate: bool,
-    }
-    let room_create_content: RoomCreateContentFederate =
-        from_json_str(room_create_event.content().get())?;
-    if !room_create_content.federate
+    let room_create = RoomCreateView::new(&room_create_event)?;
+    if !room_create.federate()
         && room_create_event.sender().server_name() != incoming_event.sender().server_name()
     {
         warn!("room is not federated and event's sender domain does not match create event's sender domain");
-        return Ok(false);
+        return Ok(AuthResult::Rejected(RejectionReason::RoomNotFederated));
     }
 
     // Only in some room versions 6 and below
@@ -250,11 +392,11 @@ pub fn auth_check<E: Event>(
             // If sender's domain doesn't matches state_key, reject
             if incoming_event.state_key() != Some(sender.server_name().as_str()) {
                 warn!("state_key does not match sender");
-                return Ok(false);
+                return Ok(AuthResult::Rejected(RejectionReason::AliasesStateKeyMismatch));
             }
 
             info!("m.room.aliases event was allowed");
-            return Ok(true);
+            return Ok(AuthResult::Allowed);
         }
     }
 
@@ -267,7 +409,7 @@ pub fn auth_check<E: Event>(
         let state_key = match incoming_event.state_key() {
             None => {
                 warn!("no statekey in member event");
-                return Ok(false);
+                return Ok(AuthResult::Rejected(RejectionReason::MissingStateKey));
             }
             Some(s) => s,
         };
@@ -275,7 +417,7 @@ pub fn auth_check<E: Event>(
         let content: RoomMemberContentFields = from_json_str(incoming_event.content().get())?;
         if content.membership.as_ref().and_then(|m| m.deserialize().ok()).is_none() {
             warn!("no valid membership field found for m.room.member event content");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::InvalidMembership));
         }
 
         let target_user =
@@ -287,11 +429,10 @@ pub fn auth_check<E: Event>(
         let user_for_join_auth_membership = user_for_join_auth
             .as_ref()
             .and_then(|auth_user| fetch_state(&StateEventType::RoomMember, auth_user.as_str()))
-            .and_then(|mem| from_json_str::<GetMembership>(mem.content().get()).ok())
-            .map(|mem| mem.membership)
+            .and_then(|mem| get_membership(mem).ok())
             .unwrap_or(MembershipState::Leave);
 
-        if !valid_membership_change(
+        let result = valid_membership_change(
             room_version,
             target_user,
             fetch_state(&StateEventType::RoomMember, target_user.as_str()).as_ref(),
@@ -304,12 +445,12 @@ pub fn auth_check<E: Event>(
             user_for_join_auth.as_deref(),
             &user_for_join_auth_membership,
             room_create_event,
-        )? {
-            return Ok(false);
-        }
+        )?;
 
-        info!("m.room.member event was allowed");
-        return Ok(true);
+        if result.is_allowed() {
+            info!("m.room.member event was allowed");
+        }
+        return Ok(result);
     }
 
     // If the sender's current membership state is not join, reject
@@ -317,63 +458,54 @@ pub fn auth_check<E: Event>(
         Some(mem) => mem,
         None => {
             warn!("sender not found in room");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::SenderNotJoined));
         }
     };
 
-    let sender_membership_event_content: RoomMemberContentFields =
-        from_json_str(sender_member_event.content().get())?;
-    let membership_state = sender_membership_event_content
-        .membership
-        .expect("we should test before that this field exists")
-        .deserialize()?;
-
-    if !matches!(membership_state, MembershipState::Join) {
+    if !matches!(get_membership(&sender_member_event)?, MembershipState::Join) {
         warn!("sender's membership is not join");
-        return Ok(false);
+        return Ok(AuthResult::Rejected(RejectionReason::SenderNotJoined));
     }
 
     // If type is m.room.third_party_invite
     let sender_power_level = if let Some(pl) = &power_levels_event {
-        let content = deserialize_power_levels_content_fields(pl.content().get(), room_version)?;
-        if let Some(level) = content.users.get(sender) {
-            *level
-        } else {
-            content.users_default
-        }
+        get_user_power_level(pl, sender, room_version)?
     } else {
         // If no power level event found the creator gets 100 everyone else gets 0
-        from_json_str::<RoomCreateEventContent>(room_create_event.content().get())
-            .ok()
-            .and_then(|create| (create.creator == *sender).then(|| int!(100)))
-            .unwrap_or_default()
+        if room_create.creator() == sender {
+            int!(100)
+        } else {
+            int!(0)
+        }
     };
 
     // Allow if and only if sender's current power level is greater than
     // or equal to the invite level
     if *incoming_event.event_type() == TimelineEventType::RoomThirdPartyInvite {
         let invite_level = match &power_levels_event {
-            Some(power_levels) => {
-                deserialize_power_levels_content_invite(power_levels.content().get(), room_version)?
-                    .invite
-            }
+            Some(power_levels) => get_invite_level(power_levels, room_version)?,
             None => int!(0),
         };
 
         if sender_power_level < invite_level {
             warn!("sender's cannot send invites in this room");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::InsufficientPowerLevel {
+                required: invite_level,
+                actual: sender_power_level,
+            }));
         }
 
         info!("m.room.third_party_invite event was allowed");
-        return Ok(true);
+        return Ok(AuthResult::Allowed);
     }
 
     // If the event type's required power level is greater than the sender's power level, reject
     // If the event has a state_key that starts with an @ and does not match the sender, reject.
-    if !can_send_event(&incoming_event, power_levels_event.as_ref(), sender_power_level) {
+    if let AuthResult::Rejected(reason) =
+        can_send_event(&incoming_event, power_levels_event.as_ref(), sender_power_level)
+    {
         warn!("user cannot send event");
-        return Ok(false);
+        return Ok(AuthResult::Rejected(reason));
     }
 
     // If type is m.room.power_levels
@@ -388,11 +520,11 @@ pub fn auth_check<E: Event>(
         ) {
             if !required_pwr_lvl {
                 warn!("power level was not allowed");
-                return Ok(false);
+                return Ok(AuthResult::Rejected(RejectionReason::PowerLevelsChangeNotAllowed));
             }
         } else {
             warn!("power level was not allowed");
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::InvalidPowerLevels));
         }
         info!("power levels event allowed");
     }
@@ -408,23 +540,25 @@ pub fn auth_check<E: Event>(
         && *incoming_event.event_type() == TimelineEventType::RoomRedaction
     {
         let redact_level = match power_levels_event {
-            Some(pl) => {
-                deserialize_power_levels_content_redact(pl.content().get(), room_version)?.redact
-            }
+            Some(pl) => match pl.power_levels_content() {
+                Some(content) => content.redact,
+                None => {
+                    deserialize_power_levels_content_redact(pl.content().get(), room_version)?
+                        .redact
+                }
+            },
             None => int!(50),
         };
 
         if !check_redaction(room_version, incoming_event, sender_power_level, redact_level)? {
-            return Ok(false);
+            return Ok(AuthResult::Rejected(RejectionReason::RedactionNotAllowed));
         }
     }
 
     info!("allowing event passed all checks");
-    Ok(true)
+    Ok(AuthResult::Allowed)
 }
 
-// TODO deserializing the member, power, join_rules event contents is done in conduit
-// just before this is called. Could they be passed in?
 /// Does the user who sent this member event have required power levels to do so.
 ///
 /// * `user` - Information about the membership event and user making the request.
@@ -446,31 +580,31 @@ fn valid_membership_change(
     user_for_join_auth: Option<&UserId>,
     user_for_join_auth_membership: &MembershipState,
     create_room: impl Event,
-) -> Result<bool> {
+) -> Result<AuthResult> {
     #[derive(Deserialize)]
     struct GetThirdPartyInvite {
         third_party_invite: Option<Raw<ThirdPartyInvite>>,
     }
     let content = current_event.content();
 
-    let target_membership = from_json_str::<GetMembership>(content.get())?.membership;
+    let target_membership = get_membership(&current_event)?;
     let third_party_invite =
         from_json_str::<GetThirdPartyInvite>(content.get())?.third_party_invite;
 
     let sender_membership = match &sender_membership_event {
-        Some(pdu) => from_json_str::<GetMembership>(pdu.content().get())?.membership,
+        Some(pdu) => get_membership(pdu)?,
         None => MembershipState::Leave,
     };
     let sender_is_joined = sender_membership == MembershipState::Join;
 
     let target_user_current_membership = match &target_user_membership_event {
-        Some(pdu) => from_json_str::<GetMembership>(pdu.content().get())?.membership,
+        Some(pdu) => get_membership(pdu)?,
         None => MembershipState::Leave,
     };
 
-    let power_levels: RoomPowerLevelsEventContent = match &power_levels_event {
-        Some(ev) => from_json_str(ev.content().get())?,
-        None => RoomPowerLevelsEventContent::default(),
+    let power_levels = match &power_levels_event {
+        Some(ev) => get_power_levels_content(ev)?,
+        None => Cow::Owned(RoomPowerLevelsEventContent::default()),
     };
 
     let sender_power = power_levels
@@ -484,7 +618,10 @@ fn valid_membership_change(
 
     let mut join_rules = JoinRule::Invite;
     if let Some(jr) = &join_rules_event {
-        join_rules = from_json_str::<RoomJoinRulesEventContent>(jr.content().get())?.join_rule;
+        join_rules = match jr.join_rules_content() {
+            Some(content) => content.join_rule.clone(),
+            None => from_json_str::<RoomJoinRulesEventContent>(jr.content().get())?.join_rule,
+        };
     }
 
     let power_levels_event_id = power_levels_event.as_ref().map(|e| e.event_id());
@@ -492,20 +629,20 @@ fn valid_membership_change(
     let target_user_membership_event_id =
         target_user_membership_event.as_ref().map(|e| e.event_id());
 
+    // Only used when the sender is joined, so it falls back to `users_default`.
+    let sender_level = sender_power.copied().unwrap_or(power_levels.users_default);
+    let insufficient_power = |required: Int| {
+        AuthResult::Rejected(RejectionReason::InsufficientPowerLevel {
+            required,
+            actual: sender_level,
+        })
+    };
+
     let user_for_join_auth_is_valid = if let Some(user_for_join_auth) = user_for_join_auth {
         // Is the authorised user allowed to invite users into this room
         let (auth_user_pl, invite_level) = if let Some(pl) = &power_levels_event {
-            // TODO Refactor all powerlevel parsing
-            let invite =
-                deserialize_power_levels_content_invite(pl.content().get(), room_version)?.invite;
-
-            let content =
-                deserialize_power_levels_content_fields(pl.content().get(), room_version)?;
-            let user_pl = if let Some(level) = content.users.get(user_for_join_auth) {
-                *level
-            } else {
-                content.users_default
-            };
+            let invite = get_invite_level(pl, room_version)?;
+            let user_pl = get_user_power_level(pl, user_for_join_auth, room_version)?;
 
             (user_pl, invite)
         } else {
@@ -530,29 +667,28 @@ fn valid_membership_change(
             let no_more_prev_events = prev_events.next().is_none();
 
             if prev_event_is_create_event && no_more_prev_events {
-                let create_content =
-                    from_json_str::<RoomCreateEventContent>(create_room.content().get())?;
+                let creator = RoomCreateView::new(&create_room)?.content.creator;
 
-                if create_content.creator == sender && create_content.creator == target_user {
-                    return Ok(true);
+                if creator == sender && creator == target_user {
+                    return Ok(AuthResult::Allowed);
                 }
             }
 
             if sender != target_user {
                 // If the sender does not match state_key, reject.
                 warn!("Can't make other user join");
-                false
+                AuthResult::Rejected(RejectionReason::SenderNotTarget)
             } else if let MembershipState::Ban = target_user_current_membership {
                 // If the sender is banned, reject.
                 warn!(?target_user_membership_event_id, "Banned user can't join");
-                false
+                AuthResult::Rejected(RejectionReason::TargetBanned)
             } else if (join_rules == JoinRule::Invite
                     || room_version.allow_knocking && join_rules == JoinRule::Knock)
                 // If the join_rule is invite then allow if membership state is invite or join
                     && (target_user_current_membership == MembershipState::Join
                         || target_user_current_membership == MembershipState::Invite)
             {
-                true
+                AuthResult::Allowed
             } else if room_version.restricted_join_rules
                 && matches!(join_rules, JoinRule::Restricted(_))
                 || room_version.knock_restricted_join_rule
@@ -564,17 +700,27 @@ fn valid_membership_change(
                     MembershipState::Invite | MembershipState::Join
                 ) {
                     // If membership state is join or invite, allow.
-                    true
+                    AuthResult::Allowed
                 } else {
                     // If the join_authorised_via_users_server key in content is not a user with
                     // sufficient permission to invite other users, reject.
                     // Otherwise, allow.
-                    user_for_join_auth_is_valid
+                    if user_for_join_auth_is_valid {
+                        AuthResult::Allowed
+                    } else {
+                        warn!("Join was not authorised by a user that can invite");
+                        AuthResult::Rejected(RejectionReason::InvalidJoinAuthorisation)
+                    }
                 }
             } else {
                 // If the join_rule is public, allow.
                 // Otherwise, reject.
-                join_rules == JoinRule::Public
+                if join_rules == JoinRule::Public {
+                    AuthResult::Allowed
+                } else {
+                    warn!("Join rule doesn't allow joining");
+                    AuthResult::Rejected(RejectionReason::JoinRuleNotSatisfied)
+                }
             }
         }
         MembershipState::Invite => {
@@ -582,7 +728,7 @@ fn valid_membership_change(
             if let Some(tp_id) = third_party_invite.and_then(|i| i.deserialize().ok()) {
                 if target_user_current_membership == MembershipState::Ban {
                     warn!(?target_user_membership_event_id, "Can't invite banned user");
-                    false
+                    AuthResult::Rejected(RejectionReason::TargetBanned)
                 } else {
                     let allow = verify_third_party_invite(
                         Some(target_user),
@@ -590,10 +736,12 @@ fn valid_membership_change(
                         &tp_id,
                         current_third_party_invite,
                     );
-                    if !allow {
+                    if allow {
+                        AuthResult::Allowed
+                    } else {
                         warn!("Third party invite invalid");
+                        AuthResult::Rejected(RejectionReason::InvalidThirdPartyInvite)
                     }
-                    allow
                 }
             } else if !sender_is_joined
                 || target_user_current_membership == MembershipState::Join
@@ -605,27 +753,36 @@ fn valid_membership_change(
                     "Can't invite user if sender not joined or the user is currently joined or \
                      banned",
                 );
-                false
-            } else {
-                let allow = sender_power.filter(|&p| p >= &power_levels.invite).is_some();
-                if !allow {
-                    warn!(
-                        ?target_user_membership_event_id,
-                        ?power_levels_event_id,
-                        "User does not have enough power to invite",
-                    );
+                if !sender_is_joined {
+                    AuthResult::Rejected(RejectionReason::SenderNotJoined)
+                } else {
+                    AuthResult::Rejected(RejectionReason::InvalidTargetMembership(
+                        target_user_current_membership,
+                    ))
                 }
-                allow
+            } else if sender_power.filter(|&p| p >= &power_levels.invite).is_some() {
+                AuthResult::Allowed
+            } else {
+                warn!(
+                    ?target_user_membership_event_id,
+                    ?power_levels_event_id,
+                    "User does not have enough power to invite",
+                );
+                insufficient_power(power_levels.invite)
             }
         }
         MembershipState::Leave => {
             if sender == target_user {
-                let allow = target_user_current_membership == MembershipState::Join
-                    || target_user_current_membership == MembershipState::Invite;
-                if !allow {
+                if target_user_current_membership == MembershipState::Join
+                    || target_user_current_membership == MembershipState::Invite
+                {
+                    AuthResult::Allowed
+                } else {
                     warn!(?target_user_membership_event_id, "Can't leave if not invited or joined");
+                    AuthResult::Rejected(RejectionReason::InvalidTargetMembership(
+                        target_user_current_membership,
+                    ))
                 }
-                allow
             } else if !sender_is_joined
                 || target_user_current_membership == MembershipState::Ban
                     && sender_power.filter(|&p| p < &power_levels.ban).is_some()
@@ -635,45 +792,60 @@ fn valid_membership_change(
                     ?sender_membership_event_id,
                     "Can't kick if sender not joined or user is already banned",
                 );
-                false
-            } else {
-                let allow = sender_power.filter(|&p| p >= &power_levels.kick).is_some()
-                    && target_power < sender_power;
-                if !allow {
-                    warn!(
-                        ?target_user_membership_event_id,
-                        ?power_levels_event_id,
-                        "User does not have enough power to kick",
-                    );
+                if !sender_is_joined {
+                    AuthResult::Rejected(RejectionReason::SenderNotJoined)
+                } else {
+                    insufficient_power(power_levels.ban)
                 }
-                allow
+            } else if sender_power.filter(|&p| p >= &power_levels.kick).is_none() {
+                warn!(
+                    ?target_user_membership_event_id,
+                    ?power_levels_event_id,
+                    "User does not have enough power to kick",
+                );
+                insufficient_power(power_levels.kick)
+            } else if target_power >= sender_power {
+                warn!(
+                    ?target_user_membership_event_id,
+                    ?power_levels_event_id,
+                    "User does not have more power than the target to kick",
+                );
+                AuthResult::Rejected(RejectionReason::TargetPowerLevelTooHigh)
+            } else {
+                AuthResult::Allowed
             }
         }
         MembershipState::Ban => {
             if !sender_is_joined {
                 warn!(?sender_membership_event_id, "Can't ban user if sender is not joined");
-                false
+                AuthResult::Rejected(RejectionReason::SenderNotJoined)
+            } else if sender_power.filter(|&p| p >= &power_levels.ban).is_none() {
+                warn!(
+                    ?target_user_membership_event_id,
+                    ?power_levels_event_id,
+                    "User does not have enough power to ban",
+                );
+                insufficient_power(power_levels.ban)
+            } else if target_power >= sender_power {
+                warn!(
+                    ?target_user_membership_event_id,
+                    ?power_levels_event_id,
+                    "User does not have more power than the target to ban",
+                );
+                AuthResult::Rejected(RejectionReason::TargetPowerLevelTooHigh)
             } else {
-                let allow = sender_power.filter(|&p| p >= &power_levels.ban).is_some()
-                    && target_power < sender_power;
-                if !allow {
-                    warn!(
-                        ?target_user_membership_event_id,
-                        ?power_levels_event_id,
-                        "User does not have enough power to ban",
-                    );
-                }
-                allow
+                AuthResult::Allowed
             }
         }
         MembershipState::Knock if room_version.allow_knocking => {
             // 1. If the `join_rule` is anything other than `knock` or `knock_restricted`, reject.
-            if join_rules != JoinRule::Knock
+            let knocking_allowed = join_rules == JoinRule::Knock
                 || room_version.knock_restricted_join_rule
-                    && matches!(join_rules, JoinRule::KnockRestricted(_))
-            {
+                    && matches!(join_rules, JoinRule::KnockRestricted(_));
+
+            if !knocking_allowed {
                 warn!("Join rule is not set to knock or knock_restricted, knocking is not allowed");
-                false
+                AuthResult::Rejected(RejectionReason::JoinRuleNotSatisfied)
             } else {
                 // 2. If `sender` does not match `state_key`, reject.
                 // 3. If the `sender`'s current membership is not `ban` or `join`, allow.
@@ -684,22 +856,24 @@ fn valid_membership_change(
                         ?target_user,
                         "Can't make another user join, sender did not match target"
                     );
-                    false
+                    AuthResult::Rejected(RejectionReason::SenderNotTarget)
                 } else if matches!(sender_membership, MembershipState::Ban | MembershipState::Join)
                 {
                     warn!(
                         ?target_user_membership_event_id,
                         "Membership state of ban or join are invalid",
                     );
-                    false
+                    AuthResult::Rejected(RejectionReason::InvalidTargetMembership(
+                        sender_membership,
+                    ))
                 } else {
-                    true
+                    AuthResult::Allowed
                 }
             }
         }
         _ => {
             warn!("Unknown membership transition");
-            false
+            AuthResult::Rejected(RejectionReason::UnknownMembershipTransition)
         }
     })
 }
@@ -707,22 +881,26 @@ fn valid_membership_change(
 /// Is the user allowed to send a specific event based on the rooms power levels.
 ///
 /// Does the event have the correct userId as its state_key if it's not the "" state_key.
-fn can_send_event(event: impl Event, ple: Option<impl Event>, user_level: Int) -> bool {
+fn can_send_event(event: impl Event, ple: Option<impl Event>, user_level: Int) -> AuthResult {
     let event_type_power_level = get_send_level(event.event_type(), event.state_key(), ple);
 
     debug!("{} ev_type {event_type_power_level} usr {user_level}", event.event_id());
 
     if user_level < event_type_power_level {
-        return false;
+        return AuthResult::Rejected(RejectionReason::InsufficientPowerLevel {
+            required: event_type_power_level,
+            actual: user_level,
+        });
     }
 
     if event.state_key().map_or(false, |k| k.starts_with('@'))
         && event.state_key() != Some(event.sender().as_str())
     {
-        return false; // permission required to post in this room
+        // permission required to post in this room
+        return AuthResult::Rejected(RejectionReason::StateKeyNotSender);
     }
 
-    true
+    AuthResult::Allowed
 }
 
 /// Confirm that the event sender has the required power levels.
@@ -750,8 +928,10 @@ fn check_power_levels(
     //   with values that are integers, reject.
     // - If users key in content is not a dictionary with keys that are valid user IDs with values
     //   that are integers, reject.
-    let user_content: RoomPowerLevelsEventContent =
-        deserialize_power_levels(power_event.content().get(), room_version)?;
+    let user_content = match power_event.power_levels_content() {
+        Some(content) => Cow::Borrowed(content),
+        None => Cow::Owned(deserialize_power_levels(power_event.content(), room_version)?),
+    };
 
     // Validation of users is done in Ruma, synapse for loops validating user_ids and integers here
     info!("validation of power event finished");
@@ -762,8 +942,10 @@ fn check_power_levels(
         None => return Some(true),
     };
 
-    let current_content: RoomPowerLevelsEventContent =
-        deserialize_power_levels(current_state.content().get(), room_version)?;
+    let current_content = match current_state.power_levels_content() {
+        Some(content) => Cow::Borrowed(content),
+        None => Cow::Owned(deserialize_power_levels(current_state.content(), room_version)?),
+    };
 
     let mut user_levels_to_check = BTreeSet::new();
     let old_list = &current_content.users;
@@ -832,15 +1014,28 @@ fn check_power_levels(
         }
     }
 
-    // Notifications, currently there is only @room
+    // Notifications loop
     if room_version.limit_notifications_power_levels {
-        let old_level = old_state.notifications.room;
-        let new_level = new_state.notifications.room;
-        if old_level != new_level {
+        let notification_levels_to_check: BTreeSet<_> = old_state
+            .notifications
+            .iter()
+            .chain(new_state.notifications.iter())
+            .map(|(key, _)| key)
+            .collect();
+
+        debug!("notifications to check {notification_levels_to_check:?}");
+
+        for key in notification_levels_to_check {
+            let old_level = old_state.notifications.get(key);
+            let new_level = new_state.notifications.get(key);
+            if old_level == new_level {
+                continue;
+            }
+
             // If the current value is higher than the sender's current power level, reject
             // If the new value is higher than the sender's current power level, reject
-            let old_level_too_big = old_level > user_level;
-            let new_level_too_big = new_level > user_level;
+            let old_level_too_big = old_level > Some(&user_level);
+            let new_level_too_big = new_level > Some(&user_level);
             if old_level_too_big || new_level_too_big {
                 warn!("m.room.power_level failed to add ops > than own");
                 return Some(false); // cannot add ops greater than own
@@ -911,7 +1106,7 @@ fn get_send_level(
 ) -> Int {
     power_lvl
         .and_then(|ple| {
-            from_json_str::<RoomPowerLevelsEventContent>(ple.content().get())
+            get_power_levels_content(&ple)
                 .map(|content| {
                     content.events.get(e_type).copied().unwrap_or_else(|| {
                         if state_key.is_some() {
@@ -963,43 +1158,229 @@ fn verify_third_party_invite(
             Err(_) => return false,
         };
 
-    let decoded_invite_token = match Base64::parse(&tp_id.signed.token) {
-        Ok(tok) => tok,
-        // FIXME: Log a warning?
-        Err(_) => return false,
-    };
+    match ruma_signatures::verify_third_party_invite(&tp_id.signed.mxid, tp_id, &tpid_ev) {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("Failed to verify third-party invite: {e}");
+            false
+        }
+    }
+}
+
+/// The result of the authorization of an event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum AuthResult {
+    /// The event is allowed.
+    Allowed,
 
-    // A list of public keys in the public_keys field
-    for key in tpid_ev.public_keys.unwrap_or_default() {
-        if key.public_key == decoded_invite_token {
-            return true;
+    /// The event is rejected.
+    Rejected(RejectionReason),
+}
+
+impl AuthResult {
+    /// Whether the event is allowed.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allowed)
+    }
+
+    /// The reason why the event was rejected, if it was.
+    pub fn rejection_reason(&self) -> Option<&RejectionReason> {
+        match self {
+            Self::Allowed => None,
+            Self::Rejected(reason) => Some(reason),
         }
     }
+}
+
+/// The authorization rule that rejected an event.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RejectionReason {
+    /// The event is not signed by all the servers that must sign it.
+    #[error("the event is not signed by all the servers that must sign it")]
+    InvalidSignature,
+
+    /// The `m.room.create` event has `prev_events`.
+    #[error("the m.room.create event has prev_events")]
+    CreateEventHasPrevEvents,
+
+    /// The server of the room ID of the `m.room.create` event doesn't match the server of the
+    /// sender.
+    #[error("the server of the room ID doesn't match the server of the m.room.create sender")]
+    CreateEventServerMismatch,
+
+    /// The room version of the `m.room.create` event is invalid.
+    #[error("the room version of the m.room.create event is invalid")]
+    InvalidRoomVersion,
+
+    /// The `m.room.create` event doesn't have a `creator`.
+    #[error("the m.room.create event doesn't have a creator")]
+    CreateEventMissingCreator,
+
+    /// There is no `m.room.create` event in the state.
+    #[error("no m.room.create event in the state")]
+    MissingCreateEvent,
+
+    /// The `m.room.create` event is not in the `auth_events` of the event.
+    #[error("the m.room.create event is not in the auth_events")]
+    CreateEventNotInAuthEvents,
+
+    /// The room is not federated and the sender is not on the server of the room creator.
+    #[error("the room is not federated and the sender is on another server")]
+    RoomNotFederated,
+
+    /// The state key of an `m.room.aliases` event doesn't match the server of the sender.
+    #[error("the state key of the m.room.aliases event doesn't match the sender's server")]
+    AliasesStateKeyMismatch,
+
+    /// The `m.room.member` event doesn't have a state key.
+    #[error("the m.room.member event doesn't have a state key")]
+    MissingStateKey,
+
+    /// The `m.room.member` event doesn't have a valid `membership`.
+    #[error("the m.room.member event doesn't have a valid membership")]
+    InvalidMembership,
+
+    /// The sender is not allowed to change the membership of another user this way.
+    #[error("the sender can't change the membership of another user this way")]
+    SenderNotTarget,
+
+    /// The target user is banned.
+    #[error("the target user is banned")]
+    TargetBanned,
+
+    /// The current membership of the target user doesn't allow this membership change.
+    #[error("the current membership of the target user ({0}) doesn't allow this change")]
+    InvalidTargetMembership(MembershipState),
+
+    /// The join rule of the room doesn't allow this membership change.
+    #[error("the join rule of the room doesn't allow this membership change")]
+    JoinRuleNotSatisfied,
+
+    /// The user in `join_authorised_via_users_server` is not allowed to authorise the join.
+    #[error("the join was not authorised by a joined user that can invite")]
+    InvalidJoinAuthorisation,
+
+    /// The third-party invite doesn't match the `m.room.third_party_invite` event.
+    #[error("the third-party invite is invalid")]
+    InvalidThirdPartyInvite,
+
+    /// The membership change is not supported in this room version.
+    #[error("unknown membership transition")]
+    UnknownMembershipTransition,
+
+    /// The sender is not joined to the room.
+    #[error("the sender is not joined to the room")]
+    SenderNotJoined,
+
+    /// The power level of the sender is lower than the required power level.
+    #[error("the sender's power level {actual} is lower than the required power level {required}")]
+    InsufficientPowerLevel {
+        /// The required power level.
+        required: Int,
+
+        /// The power level of the sender.
+        actual: Int,
+    },
+
+    /// The power level of the target user is higher than or equal to the one of the sender.
+    #[error("the target user's power level is not lower than the sender's power level")]
+    TargetPowerLevelTooHigh,
+
+    /// The state key of the event is a user ID that doesn't match the sender.
+    #[error("the state key is a user ID that doesn't match the sender")]
+    StateKeyNotSender,
+
+    /// The `m.room.power_levels` event is invalid.
+    #[error("the m.room.power_levels event is invalid")]
+    InvalidPowerLevels,
+
+    /// The sender is not allowed to make these changes to the power levels.
+    #[error("the sender can't make these changes to the power levels")]
+    PowerLevelsChangeNotAllowed,
+
+    /// The sender is not allowed to redact the event.
+    #[error("the sender can't redact the event")]
+    RedactionNotAllowed,
+}
+
+/// A typed view of the content of an `m.room.create` event.
+#[derive(Clone, Debug)]
+pub struct RoomCreateView {
+    content: RoomCreateEventContent,
+}
+
+impl RoomCreateView {
+    /// Deserialize the content of the given `m.room.create` event.
+    pub fn new(room_create_event: impl Event) -> serde_json::Result<Self> {
+        Ok(Self { content: from_json_str(room_create_event.content().get())? })
+    }
+
+    /// The user who created the room.
+    pub fn creator(&self) -> &UserId {
+        &self.content.creator
+    }
+
+    /// Whether users on other servers can join the room.
+    ///
+    /// This is the value of the `m.federate` field, which defaults to `true`.
+    pub fn federate(&self) -> bool {
+        self.content.federate
+    }
+
+    /// The version of the room.
+    pub fn room_version(&self) -> &RoomVersionId {
+        &self.content.room_version
+    }
+
+    /// The room this room replaces, if it was upgraded.
+    pub fn predecessor(&self) -> Option<&PreviousRoom> {
+        self.content.predecessor.as_ref()
+    }
 
-    // A single public key in the public_key field
-    tpid_ev.public_key == decoded_invite_token
+    /// Get the full deserialized content.
+    pub fn into_content(self) -> RoomCreateEventContent {
+        self.content
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use ruma_common::events::{
-        room::{
-            join_rules::{
-                AllowRule, JoinRule, Restricted, RoomJoinRulesEventContent, RoomMembership,
+    use js_int::int;
+    use ruma_common::{
+        events::{
+            room::{
+                join_rules::{
+                    AllowRule, JoinRule, Restricted, RoomJoinRulesEventContent, RoomMembership,
+                },
+                member::{MembershipState, RoomMemberEventContent},
+                power_levels::RoomPowerLevelsEventContent,
+                third_party_invite::RoomThirdPartyInviteEventContent,
             },
-            member::{MembershipState, RoomMemberEventContent},
+            StateEventType, TimelineEventType,
         },
-        StateEventType, TimelineEventType,
+        serde::Base64,
+        CanonicalJsonObject, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, RoomVersionId,
+        UserId,
+    };
+    use ruma_signatures::{sign_json, Ed25519KeyPair};
+    use serde_json::{
+        json,
+        value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
     };
-    use serde_json::value::to_raw_value as to_raw_json_value;
 
     use crate::{
-        event_auth::valid_membership_change,
+        event_auth::{
+            auth_check, auth_check_detailed, auth_types_for_event, auth_types_for_pdu,
+            check_power_levels, valid_membership_change, AuthResult, RejectionReason,
+            RoomCreateView,
+        },
         test_utils::{
-            alice, charlie, ella, event_id, member_content_ban, member_content_join, room_id,
-            to_pdu_event, PduEvent, INITIAL_EVENTS, INITIAL_EVENTS_CREATE_ROOM,
+            alice, bob, charlie, ella, event_id, member_content_ban, member_content_join, room_id,
+            to_init_pdu_event, to_pdu_event, PduEvent, INITIAL_EVENTS, INITIAL_EVENTS_CREATE_ROOM,
         },
         Event, EventTypeExt, RoomVersion, StateMap,
     };
@@ -1032,18 +1413,19 @@ mod tests {
         assert!(valid_membership_change(
             &RoomVersion::V6,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             None,
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
     }
 
     #[test]
@@ -1074,18 +1456,19 @@ mod tests {
         assert!(!valid_membership_change(
             &RoomVersion::V6,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             None,
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
     }
 
     #[test]
@@ -1116,18 +1499,19 @@ mod tests {
         assert!(valid_membership_change(
             &RoomVersion::V6,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             None,
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
     }
 
     #[test]
@@ -1158,18 +1542,19 @@ mod tests {
         assert!(!valid_membership_change(
             &RoomVersion::V6,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             None,
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
     }
 
     #[test]
@@ -1217,34 +1602,36 @@ mod tests {
         assert!(valid_membership_change(
             &RoomVersion::V9,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             Some(alice()),
             &MembershipState::Join,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
 
         assert!(!valid_membership_change(
             &RoomVersion::V9,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             Some(ella()),
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_allowed());
     }
 
     #[test]
@@ -1284,17 +1671,500 @@ mod tests {
         assert!(valid_membership_change(
             &RoomVersion::V7,
             target_user,
-            fetch_state(StateEventType::RoomMember, target_user.to_string()),
+            fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
             sender,
-            fetch_state(StateEventType::RoomMember, sender.to_string()),
+            fetch_state(StateEventType::RoomMember, sender.as_str().into()),
             &requester,
             None::<PduEvent>,
-            fetch_state(StateEventType::RoomPowerLevels, "".to_owned()),
-            fetch_state(StateEventType::RoomJoinRules, "".to_owned()),
+            fetch_state(StateEventType::RoomPowerLevels, "".into()),
+            fetch_state(StateEventType::RoomJoinRules, "".into()),
             None,
             &MembershipState::Leave,
-            fetch_state(StateEventType::RoomCreate, "".to_owned()).unwrap(),
+            fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
+        )
+        .unwrap()
+        .is_allowed());
+    }
+
+    #[test]
+    fn test_knock_restricted_join_rule() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+        let mut events = INITIAL_EVENTS();
+        *events.get_mut(&event_id("IJR")).unwrap() = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::knock_restricted(vec![
+                AllowRule::RoomMembership(RoomMembership::new(room_id().to_owned())),
+            ]))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+
+        let mut member = RoomMemberEventContent::new(MembershipState::Join);
+        member.join_authorized_via_users_server = Some(alice().to_owned());
+        let joiner = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&member).unwrap(),
+            &["CREATE", "IJR", "IPOWER", "new"],
+            &["new"],
+        );
+        let knocker = to_pdu_event(
+            "HELLO",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Knock)).unwrap(),
+            &[],
+            &["IMC"],
+        );
+
+        let fetch_state = |ty, key| auth_events.get(&(ty, key)).cloned();
+        let target_user = ella();
+        let sender = ella();
+
+        let check = |room_version: &RoomVersion, event: &Arc<PduEvent>, authorising_user| {
+            valid_membership_change(
+                room_version,
+                target_user,
+                fetch_state(StateEventType::RoomMember, target_user.as_str().into()),
+                sender,
+                fetch_state(StateEventType::RoomMember, sender.as_str().into()),
+                event,
+                None::<PduEvent>,
+                fetch_state(StateEventType::RoomPowerLevels, "".into()),
+                fetch_state(StateEventType::RoomJoinRules, "".into()),
+                authorising_user,
+                &MembershipState::Join,
+                fetch_state(StateEventType::RoomCreate, "".into()).unwrap(),
+            )
+            .unwrap()
+            .is_allowed()
+        };
+
+        // Joining is allowed with a user that can invite since room version 10.
+        assert!(check(&RoomVersion::V10, &joiner, Some(alice())));
+        assert!(!check(&RoomVersion::V10, &joiner, None));
+        assert!(!check(&RoomVersion::V9, &joiner, Some(alice())));
+
+        // Knocking is allowed since room version 10.
+        assert!(check(&RoomVersion::V10, &knocker, None));
+        assert!(!check(&RoomVersion::V9, &knocker, None));
+    }
+
+    #[test]
+    fn auth_check_detailed_reasons() {
+        let events = INITIAL_EVENTS();
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+        let fetch_state =
+            |ty: &StateEventType, key: &str| auth_events.get(&(ty.clone(), key.into())).cloned();
+
+        let topic = to_pdu_event(
+            "TOPIC",
+            bob(),
+            TimelineEventType::RoomTopic,
+            Some(""),
+            to_raw_json_value(&json!({ "topic": "Bob's topic" })).unwrap(),
+            &["CREATE", "IMB", "IPOWER"],
+            &["IMC"],
+        );
+        assert_eq!(
+            auth_check_detailed(&RoomVersion::V6, &topic, None::<PduEvent>, fetch_state).unwrap(),
+            AuthResult::Rejected(RejectionReason::InsufficientPowerLevel {
+                required: int!(50),
+                actual: int!(0),
+            })
+        );
+
+        let message = to_pdu_event(
+            "MESSAGE",
+            ella(),
+            TimelineEventType::RoomMessage,
+            None,
+            to_raw_json_value(&json!({ "body": "Hello", "msgtype": "m.text" })).unwrap(),
+            &["CREATE", "IPOWER"],
+            &["IMC"],
+        );
+        let result =
+            auth_check_detailed(&RoomVersion::V6, &message, None::<PduEvent>, fetch_state).unwrap();
+        assert_eq!(result.rejection_reason(), Some(&RejectionReason::SenderNotJoined));
+
+        let message = to_pdu_event(
+            "MESSAGE",
+            alice(),
+            TimelineEventType::RoomMessage,
+            None,
+            to_raw_json_value(&json!({ "body": "Hello", "msgtype": "m.text" })).unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IMC"],
+        );
+        assert!(auth_check_detailed(&RoomVersion::V6, &message, None::<PduEvent>, fetch_state)
+            .unwrap()
+            .is_allowed());
+    }
+
+    fn third_party_invite_event(key_pair: &Ed25519KeyPair) -> Arc<PduEvent> {
+        let content = RoomThirdPartyInviteEventContent::new(
+            "e...@example.org".to_owned(),
+            "https://identity.example.org/_matrix/identity/v2/pubkey/isvalid".to_owned(),
+            Base64::new(key_pair.public_key().to_vec()),
+        );
+
+        to_pdu_event(
+            "THIRDPARTY",
+            alice(),
+            TimelineEventType::RoomThirdPartyInvite,
+            Some("token"),
+            to_raw_json_value(&content).unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IMC"],
+        )
+    }
+
+    fn third_party_invite_member_event(
+        key_pair: &Ed25519KeyPair,
+        mxid: &UserId,
+        token: &str,
+    ) -> Arc<PduEvent> {
+        let mut signed: CanonicalJsonObject =
+            serde_json::from_value(json!({ "mxid": mxid, "token": token })).unwrap();
+        sign_json("identity.example.org", key_pair, &mut signed).unwrap();
+
+        to_pdu_event(
+            "TPINVITE",
+            alice(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&json!({
+                "membership": "invite",
+                "third_party_invite": {
+                    "display_name": "e...@example.org",
+                    "signed": signed,
+                },
+            }))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER", "THIRDPARTY"],
+            &["THIRDPARTY"],
+        )
+    }
+
+    #[test]
+    fn third_party_invite() {
+        let events = INITIAL_EVENTS();
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+        let fetch_state =
+            |ty: &StateEventType, key: &str| auth_events.get(&(ty.clone(), key.into())).cloned();
+
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".into()).unwrap();
+        let other_key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "0".into()).unwrap();
+        let third_party_invite = third_party_invite_event(&key_pair);
+
+        // Valid signed block.
+        let invite = third_party_invite_member_event(&key_pair, ella(), "token");
+        assert!(auth_check(
+            &RoomVersion::V6,
+            &invite,
+            Some(Arc::clone(&third_party_invite)),
+            fetch_state
         )
         .unwrap());
+
+        // Signed with a key that is not in the `m.room.third_party_invite` event.
+        let invite = third_party_invite_member_event(&other_key_pair, ella(), "token");
+        assert_eq!(
+            auth_check_detailed(
+                &RoomVersion::V6,
+                &invite,
+                Some(Arc::clone(&third_party_invite)),
+                fetch_state
+            )
+            .unwrap(),
+            AuthResult::Rejected(RejectionReason::InvalidThirdPartyInvite)
+        );
+
+        // The mxid doesn't match the state key.
+        let invite = third_party_invite_member_event(&key_pair, bob(), "token");
+        assert_eq!(
+            auth_check_detailed(
+                &RoomVersion::V6,
+                &invite,
+                Some(Arc::clone(&third_party_invite)),
+                fetch_state
+            )
+            .unwrap(),
+            AuthResult::Rejected(RejectionReason::InvalidThirdPartyInvite)
+        );
+
+        // The token doesn't match the state key of the `m.room.third_party_invite` event.
+        let invite = third_party_invite_member_event(&key_pair, ella(), "other_token");
+        assert_eq!(
+            auth_check_detailed(&RoomVersion::V6, &invite, Some(third_party_invite), fetch_state)
+                .unwrap(),
+            AuthResult::Rejected(RejectionReason::InvalidThirdPartyInvite)
+        );
+    }
+
+    #[test]
+    fn room_create_view() {
+        let create = to_init_pdu_event(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            Some(""),
+            to_raw_json_value(&json!({
+                "creator": alice(),
+                "m.federate": false,
+                "room_version": "9",
+                "predecessor": {
+                    "room_id": room_id(),
+                    "event_id": event_id("TOMBSTONE"),
+                },
+            }))
+            .unwrap(),
+        );
+
+        let view = RoomCreateView::new(&create).unwrap();
+        assert_eq!(view.creator(), alice());
+        assert!(!view.federate());
+        assert_eq!(*view.room_version(), RoomVersionId::V9);
+        assert_eq!(view.predecessor().unwrap().event_id, event_id("TOMBSTONE"));
+
+        let create = to_init_pdu_event(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            Some(""),
+            to_raw_json_value(&json!({ "creator": alice() })).unwrap(),
+        );
+
+        let view = RoomCreateView::new(&create).unwrap();
+        assert!(view.federate());
+        assert_eq!(*view.room_version(), RoomVersionId::V1);
+        assert!(view.predecessor().is_none());
+    }
+
+    #[test]
+    fn restricted_join_auth_types() {
+        let join = to_init_pdu_event(
+            "JOIN",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&json!({
+                "membership": "join",
+                "join_authorised_via_users_server": bob(),
+            }))
+            .unwrap(),
+        );
+        let authorising_member = (StateEventType::RoomMember, bob().as_str().into());
+
+        let auth_types = auth_types_for_pdu(&join, &RoomVersion::V8);
+        assert!(auth_types.contains(&(StateEventType::RoomJoinRules, "".into())));
+        assert!(auth_types.contains(&(StateEventType::RoomMember, ella().as_str().into())));
+        assert!(auth_types.contains(&authorising_member));
+
+        // Restricted join rules are not supported before room version 8.
+        let auth_types = auth_types_for_pdu(&join, &RoomVersion::V7);
+        assert!(!auth_types.contains(&authorising_member));
+
+        let content = RoomMemberEventContent::new(MembershipState::Leave);
+        let auth_types = auth_types_for_event(
+            &TimelineEventType::RoomMember,
+            ella(),
+            Some(ella().as_str()),
+            Some(&content),
+            &RoomVersion::V8,
+        );
+        assert!(!auth_types.contains(&(StateEventType::RoomJoinRules, "".into())));
+    }
+
+    #[test]
+    fn auth_types_for_member_with_invalid_field() {
+        // An invalid field that is not used for authorization.
+        let join = to_init_pdu_event(
+            "JOIN",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&json!({
+                "membership": "join",
+                "displayname": 5,
+                "join_authorised_via_users_server": bob(),
+            }))
+            .unwrap(),
+        );
+
+        let auth_types = auth_types_for_pdu(&join, &RoomVersion::V8);
+        assert!(auth_types.contains(&(StateEventType::RoomJoinRules, "".into())));
+        assert!(auth_types.contains(&(StateEventType::RoomMember, ella().as_str().into())));
+        assert!(auth_types.contains(&(StateEventType::RoomMember, bob().as_str().into())));
+    }
+
+    #[test]
+    fn custom_notification_power_levels() {
+        let power_levels = |notifications| {
+            to_init_pdu_event(
+                "IPOWER",
+                alice(),
+                TimelineEventType::RoomPowerLevels,
+                Some(""),
+                to_raw_json_value(&json!({
+                    "users": { alice(): 100, charlie(): 50 },
+                    "notifications": notifications,
+                }))
+                .unwrap(),
+            )
+        };
+        let previous = power_levels(json!({ "room": 50 }));
+
+        let new = power_levels(json!({ "room": 50, "org.example.ping": 40 }));
+        assert_eq!(
+            check_power_levels(&RoomVersion::V6, &new, Some(&previous), int!(50)),
+            Some(true)
+        );
+
+        let new = power_levels(json!({ "room": 50, "org.example.ping": 100 }));
+        assert_eq!(
+            check_power_levels(&RoomVersion::V6, &new, Some(&previous), int!(50)),
+            Some(false)
+        );
+        assert_eq!(
+            check_power_levels(&RoomVersion::V6, &new, Some(&previous), int!(100)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn stringified_power_levels() {
+        let power_levels = |ban| {
+            to_init_pdu_event(
+                "IPOWER",
+                alice(),
+                TimelineEventType::RoomPowerLevels,
+                Some(""),
+                to_raw_json_value(&json!({ "users": { alice(): 100 }, "ban": ban })).unwrap(),
+            )
+        };
+        let previous = power_levels(json!(50));
+        let new = power_levels(json!("50"));
+
+        assert_eq!(
+            check_power_levels(&RoomVersion::V6, &new, Some(&previous), int!(100)),
+            Some(true)
+        );
+        assert_eq!(check_power_levels(&RoomVersion::V10, &new, Some(&previous), int!(100)), None);
+    }
+
+    /// An event that keeps its deserialized power levels content.
+    struct DeserializedEvent {
+        event: Arc<PduEvent>,
+        power_levels: Option<RoomPowerLevelsEventContent>,
+    }
+
+    impl Event for DeserializedEvent {
+        type Id = OwnedEventId;
+
+        fn event_id(&self) -> &Self::Id {
+            self.event.event_id()
+        }
+
+        fn room_id(&self) -> &RoomId {
+            self.event.room_id()
+        }
+
+        fn sender(&self) -> &UserId {
+            self.event.sender()
+        }
+
+        fn origin_server_ts(&self) -> MilliSecondsSinceUnixEpoch {
+            self.event.origin_server_ts()
+        }
+
+        fn event_type(&self) -> &TimelineEventType {
+            self.event.event_type()
+        }
+
+        fn content(&self) -> &RawJsonValue {
+            self.event.content()
+        }
+
+        fn state_key(&self) -> Option<&str> {
+            self.event.state_key()
+        }
+
+        fn prev_events(&self) -> Box<dyn DoubleEndedIterator<Item = &Self::Id> + '_> {
+            self.event.prev_events()
+        }
+
+        fn auth_events(&self) -> Box<dyn DoubleEndedIterator<Item = &Self::Id> + '_> {
+            self.event.auth_events()
+        }
+
+        fn redacts(&self) -> Option<&Self::Id> {
+            self.event.redacts()
+        }
+
+        fn power_levels_content(&self) -> Option<&RoomPowerLevelsEventContent> {
+            self.power_levels.as_ref()
+        }
+    }
+
+    #[test]
+    fn deserialized_power_levels_content() {
+        let events = INITIAL_EVENTS();
+        let auth_events = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+
+        let name = to_pdu_event(
+            "NAME",
+            bob(),
+            TimelineEventType::RoomName,
+            Some(""),
+            to_raw_json_value(&json!({ "name": "New name" })).unwrap(),
+            &["CREATE", "IMB", "IPOWER"],
+            &["IMC"],
+        );
+
+        // Bob doesn't have the power level to change the name of the room.
+        let fetch_state = |ty: &StateEventType, key: &str| {
+            auth_events
+                .get(&ty.with_state_key(key))
+                .map(|event| DeserializedEvent { event: Arc::clone(event), power_levels: None })
+        };
+        assert!(!auth_check(&RoomVersion::V6, &name, None::<PduEvent>, fetch_state).unwrap());
+
+        // The deserialized content is used instead of the JSON of the event.
+        let mut power_levels = RoomPowerLevelsEventContent::new();
+        power_levels.users.insert(alice().to_owned(), int!(100));
+        power_levels.users.insert(bob().to_owned(), int!(50));
+        let fetch_state = |ty: &StateEventType, key: &str| {
+            auth_events.get(&ty.with_state_key(key)).map(|event| DeserializedEvent {
+                event: Arc::clone(event),
+                power_levels: (*ty == StateEventType::RoomPowerLevels)
+                    .then(|| power_levels.clone()),
+            })
+        };
+        assert!(auth_check(&RoomVersion::V6, &name, None::<PduEvent>, fetch_state).unwrap());
     }
 }
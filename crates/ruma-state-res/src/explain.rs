@@ -0,0 +1,205 @@
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+
+use crate::{StateMap, TypeStateKey};
+
+/// A report of the decisions taken while resolving conflicted state.
+///
+/// This is filled by [`resolve_with_trace`](crate::resolve_with_trace) and returned by
+/// [`resolve_with_report`](crate::resolve_with_report). It is meant to help debugging unexpected
+/// resolved state, like state resets, or the divergence of the state of a room between servers.
+///
+/// The report can be serialized, to compare the reports of several servers.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ResolutionReport<Id> {
+    /// The full conflicted set: the conflicted events of the state sets and the events of the
+    /// auth chain difference, sorted by event ID.
+    pub conflicted_events: Vec<Id>,
+
+    /// The conflicted power events, in the reverse topological power ordering.
+    pub power_order: Vec<Id>,
+
+    /// The mainline of the resolved `m.room.power_levels` event, from the oldest to the newest
+    /// event.
+    ///
+    /// Empty if all the conflicted events are power events, since the mainline is not needed.
+    pub mainline: Vec<Id>,
+
+    /// The other conflicted events, in the mainline ordering.
+    pub mainline_order: Vec<Id>,
+
+    /// The authorization checks of the conflicted events, in the order they were performed.
+    pub auth_checks: Vec<AuthCheckReport<Id>>,
+
+    /// The conflicted state keys, with the events that competed for them.
+    ///
+    /// This includes the state keys of events that are only in the auth chain difference of the
+    /// state sets.
+    #[serde(serialize_with = "serialize_conflicts")]
+    pub conflicts: StateMap<ConflictReport<Id>>,
+}
+
+impl<Id> ResolutionReport<Id> {
+    /// Creates an empty `ResolutionReport`.
+    pub fn new() -> Self {
+        Self {
+            conflicted_events: Vec::new(),
+            power_order: Vec::new(),
+            mainline: Vec::new(),
+            mainline_order: Vec::new(),
+            auth_checks: Vec::new(),
+            conflicts: StateMap::new(),
+        }
+    }
+}
+
+impl<Id> Default for ResolutionReport<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: Clone + PartialEq> ResolutionReport<Id> {
+    fn candidate(&mut self, key: TypeStateKey, event_id: &Id) -> &mut CandidateReport<Id> {
+        let conflict = self.conflicts.entry(key).or_insert_with(ConflictReport::new);
+
+        let pos = match conflict.candidates.iter().position(|c| c.event_id == *event_id) {
+            Some(pos) => pos,
+            None => {
+                conflict.candidates.push(CandidateReport::new(event_id.clone()));
+                conflict.candidates.len() - 1
+            }
+        };
+
+        &mut conflict.candidates[pos]
+    }
+
+    pub(crate) fn record_candidate(&mut self, key: TypeStateKey, event_id: &Id) {
+        self.candidate(key, event_id);
+    }
+
+    pub(crate) fn record_power_order(&mut self, key: TypeStateKey, event_id: &Id, index: usize) {
+        self.candidate(key, event_id).power_order = Some(index);
+    }
+
+    pub(crate) fn record_mainline_order(&mut self, key: TypeStateKey, event_id: &Id, index: usize) {
+        self.candidate(key, event_id).mainline_order = Some(index);
+    }
+
+    pub(crate) fn record_auth(&mut self, key: TypeStateKey, event_id: &Id, allowed: bool) {
+        let decision = if allowed { AuthDecision::Allowed } else { AuthDecision::Rejected };
+        self.candidate(key, event_id).auth = decision;
+        self.auth_checks.push(AuthCheckReport { event_id: event_id.clone(), decision });
+    }
+
+    pub(crate) fn record_resolved(&mut self, resolved_state: &StateMap<Id>) {
+        for (key, conflict) in &mut self.conflicts {
+            conflict.resolved = resolved_state.get(key).cloned();
+        }
+    }
+}
+
+/// Serialize the conflicts as a list, since the keys of a `StateMap` can't be the keys of a map in
+/// most formats.
+fn serialize_conflicts<Id, S>(
+    conflicts: &StateMap<ConflictReport<Id>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    Id: Serialize,
+    S: Serializer,
+{
+    #[derive(Serialize)]
+    struct Conflict<'a, Id> {
+        #[serde(rename = "type")]
+        event_type: &'a str,
+        state_key: &'a str,
+        #[serde(flatten)]
+        conflict: &'a ConflictReport<Id>,
+    }
+
+    // Sort the conflicts so the output is deterministic.
+    let mut conflicts: Vec<_> = conflicts.iter().collect();
+    conflicts.sort_unstable_by_key(|(key, _)| *key);
+
+    let mut seq = serializer.serialize_seq(Some(conflicts.len()))?;
+    for ((event_type, state_key), conflict) in conflicts {
+        seq.serialize_element(&Conflict {
+            event_type: event_type.to_string().as_str(),
+            state_key,
+            conflict,
+        })?;
+    }
+    seq.end()
+}
+
+/// The result of the authorization check of a conflicted event.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct AuthCheckReport<Id> {
+    /// The ID of the event.
+    pub event_id: Id,
+
+    /// The result of the authorization check of the event against the partially resolved state.
+    pub decision: AuthDecision,
+}
+
+/// The events that competed for a conflicted state key.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ConflictReport<Id> {
+    /// The events for this state key, in the order they were checked.
+    pub candidates: Vec<CandidateReport<Id>>,
+
+    /// The event that ended up in the resolved state for this state key, if any.
+    pub resolved: Option<Id>,
+}
+
+impl<Id> ConflictReport<Id> {
+    fn new() -> Self {
+        Self { candidates: Vec::new(), resolved: None }
+    }
+}
+
+/// How an event was handled during state resolution.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct CandidateReport<Id> {
+    /// The ID of the event.
+    pub event_id: Id,
+
+    /// The position of the event in the reverse topological power ordering, if it is a power
+    /// event.
+    ///
+    /// Power events are authorized in this order, before all other events.
+    pub power_order: Option<usize>,
+
+    /// The position of the event in the mainline ordering, if it is not a power event.
+    pub mainline_order: Option<usize>,
+
+    /// The result of the authorization check of the event against the partially resolved state.
+    pub auth: AuthDecision,
+}
+
+impl<Id> CandidateReport<Id> {
+    fn new(event_id: Id) -> Self {
+        Self { event_id, power_order: None, mainline_order: None, auth: AuthDecision::NotChecked }
+    }
+}
+
+/// The result of the authorization check of an event during state resolution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthDecision {
+    /// The event passed the authorization rules.
+    ///
+    /// It can still have been replaced by a later event for the same state key.
+    Allowed,
+
+    /// The event failed the authorization rules.
+    Rejected,
+
+    /// The event was not checked, because its position in the mainline could not be computed.
+    NotChecked,
+}
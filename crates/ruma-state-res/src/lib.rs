@@ -1,6 +1,6 @@
 use std::{
-    borrow::Borrow,
-    cmp::Reverse,
+    borrow::{Borrow, Cow},
+    cmp::{Ordering, Reverse},
     collections::{BinaryHeap, HashMap, HashSet},
     hash::Hash,
 };
@@ -17,22 +17,45 @@ use ruma_common::{
 use serde_json::from_str as from_json_str;
 use tracing::{debug, info, trace, warn};
 
+#[cfg(feature = "async")]
+mod asynchronous;
+mod batch;
+mod cache;
+mod config;
+pub mod dag;
 mod error;
 pub mod event_auth;
+mod explain;
 mod power_levels;
 pub mod room_version;
+mod signature_verifier;
 mod state_event;
-#[cfg(test)]
-mod test_utils;
-
+mod state_key;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+#[cfg(feature = "async")]
+pub use asynchronous::{auth_check_async, resolve_async};
+pub use batch::auth_check_batch;
+use cache::NoCache;
+pub use cache::{auth_chain_with_cache, InMemoryStateResolutionCache, StateResolutionCache};
+pub use config::{ResolverConfig, ResolverLimit};
 pub use error::{Error, Result};
-pub use event_auth::{auth_check, auth_types_for_event};
+pub use event_auth::{
+    auth_check, auth_check_detailed, auth_check_with_verifier, auth_types_for_event,
+    auth_types_for_pdu, AuthResult, RejectionReason, RoomCreateView,
+};
+pub use explain::{
+    AuthCheckReport, AuthDecision, CandidateReport, ConflictReport, ResolutionReport,
+};
 use power_levels::PowerLevelsContentFields;
 pub use room_version::RoomVersion;
+pub use signature_verifier::{PublicKeyVerifier, SignatureVerifier};
 pub use state_event::Event;
+pub use state_key::{StateKey, StateKeyInterner, TypeStateKey};
 
 /// A mapping of event type and state_key to some value `T`, usually an `EventId`.
-pub type StateMap<T> = HashMap<(StateEventType, String), T>;
+pub type StateMap<T> = HashMap<TypeStateKey, T>;
 
 /// Resolve sets of state events as they come in.
 ///
@@ -48,7 +71,10 @@ pub type StateMap<T> = HashMap<(StateEventType, String), T>;
 ///   `state_sets`.
 ///
 /// * `fetch_event` - Any event not found in the `event_map` will defer to this closure to find the
-///   event.
+///   event. The events it returns must report whether they were rejected with
+///   [`Event::rejected()`]: rejected events are excluded from the conflicted state, and are never
+///   used to authorize other events. Soft-failed events are not rejected and must be treated
+///   normally.
 ///
 /// ## Invariants
 ///
@@ -64,12 +90,239 @@ where
     E: Event + Clone,
     E::Id: 'a,
     SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_with_config(
+        room_version,
+        state_sets,
+        auth_chain_sets,
+        fetch_event,
+        &ResolverConfig::default(),
+    )
+}
+
+/// Resolve sets of state events as they come in, within the limits of the given
+/// [`ResolverConfig`].
+///
+/// See [`resolve`] for the arguments and invariants.
+///
+/// Returns [`Error::LimitExceeded`] as soon as one of the limits of `config` is exceeded.
+pub fn resolve_with_config<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_inner(
+        room_version,
+        state_sets,
+        auth_chain_sets,
+        fetch_event,
+        config,
+        &mut NoCache,
+        None,
+    )
+}
+
+/// Resolve sets of state events as they come in, using and populating the given cache.
+///
+/// This works like [`resolve_with_config`], but the power levels of the senders of the events and
+/// their positions in the mainline of the resolved power levels are looked up in `cache` before
+/// being computed, and are added to it otherwise.
+///
+/// Use [`auth_chain_with_cache`] with the same cache to compute the `auth_chain_sets`.
+pub fn resolve_with_cache<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+    cache: &mut impl StateResolutionCache<E::Id>,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_inner(room_version, state_sets, auth_chain_sets, fetch_event, config, cache, None)
+}
+
+/// Resolve sets of state events as they come in, and explain how the conflicted state was
+/// resolved.
+///
+/// This works like [`resolve_with_config`], but also returns a [`ResolutionReport`] with the
+/// ordering and the result of the authorization check of every conflicted event. This is slower,
+/// so it should only be used to debug unexpected results.
+#[allow(clippy::type_complexity)]
+pub fn resolve_with_report<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+) -> Result<(StateMap<E::Id>, ResolutionReport<E::Id>)>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    let mut report = ResolutionReport::new();
+    let state = resolve_with_trace(
+        room_version,
+        state_sets,
+        auth_chain_sets,
+        fetch_event,
+        config,
+        &mut NoCache,
+        &mut report,
+    )?;
+
+    Ok((state, report))
+}
+
+/// Resolve sets of state events as they come in, and record how the conflicted state was resolved
+/// in the given [`ResolutionReport`].
+///
+/// This works like [`resolve_with_cache`], but also records the full conflicted set, the power
+/// ordering, the mainline of the resolved power levels, the mainline ordering and every
+/// authorization check in `report`. The report is filled even if the resolution fails, to help
+/// finding out where it failed.
+pub fn resolve_with_trace<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+    cache: &mut impl StateResolutionCache<E::Id>,
+    report: &mut ResolutionReport<E::Id>,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_inner(
+        room_version,
+        state_sets,
+        auth_chain_sets,
+        fetch_event,
+        config,
+        cache,
+        Some(report),
+    )
+}
+
+/// Resolve sets of state events as they come in, without keeping all the state sets in memory.
+///
+/// This works like [`resolve_with_cache`], except that `state_sets` is consumed one state set at a
+/// time, so the state sets can be loaded lazily, for example from a database. Only the unconflicted
+/// state and the conflicted candidates are kept in memory.
+///
+/// This doesn't bound the memory used by the resolution of the conflicted events:
+/// `auth_chain_sets`, the graph of the conflicted events and their sort keys are all kept in
+/// memory.
+pub fn resolve_streaming<E>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<Item = StateMap<E::Id>>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+    cache: &mut impl StateResolutionCache<E::Id>,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
 {
     info!("State resolution starting");
 
+    check_auth_chain_length(&auth_chain_sets, config)?;
+
+    // Split non-conflicting and conflicting state
+    let (clean, conflicting) = separate_streaming(state_sets);
+
+    resolve_separated(
+        room_version,
+        clean,
+        conflicting,
+        auth_chain_sets,
+        fetch_event,
+        config,
+        cache,
+        None,
+    )
+}
+
+fn resolve_inner<'a, E, SetIter>(
+    room_version: &RoomVersionId,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+    cache: &mut dyn StateResolutionCache<E::Id>,
+    report: Option<&mut ResolutionReport<E::Id>>,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    info!("State resolution starting");
+
+    check_auth_chain_length(&auth_chain_sets, config)?;
+
     // Split non-conflicting and conflicting state
     let (clean, conflicting) = separate(state_sets.into_iter());
 
+    resolve_separated(
+        room_version,
+        clean,
+        conflicting,
+        auth_chain_sets,
+        fetch_event,
+        config,
+        cache,
+        report,
+    )
+}
+
+/// Check that none of the auth chains is longer than the limit of the given config.
+fn check_auth_chain_length<Id>(
+    auth_chain_sets: &[HashSet<Id>],
+    config: &ResolverConfig,
+) -> Result<()> {
+    if auth_chain_sets.iter().any(|chain| chain.len() > config.max_auth_chain_length) {
+        return Err(Error::LimitExceeded {
+            kind: ResolverLimit::AuthChainLength,
+            max: config.max_auth_chain_length,
+        });
+    }
+
+    Ok(())
+}
+
+/// Resolve the conflicted state, once it was separated from the unconflicted state.
+#[allow(clippy::too_many_arguments)]
+fn resolve_separated<E>(
+    room_version: &RoomVersionId,
+    clean: StateMap<E::Id>,
+    conflicting: StateMap<Vec<E::Id>>,
+    auth_chain_sets: Vec<HashSet<E::Id>>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    config: &ResolverConfig,
+    cache: &mut dyn StateResolutionCache<E::Id>,
+    mut report: Option<&mut ResolutionReport<E::Id>>,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+{
+    // Reuse the state keys of the state sets for the resolved state
+    let mut interner = StateKeyInterner::new();
+    interner.extend(clean.keys().chain(conflicting.keys()).map(|(_, state_key)| state_key.clone()));
+
     info!("non conflicting events: {}", clean.len());
     trace!("{clean:?}");
 
@@ -85,13 +338,25 @@ where
     // synapse says `full_set = {eid for eid in full_conflicted_set if eid in event_map}`
     let all_conflicted: HashSet<_> = get_auth_chain_diff(auth_chain_sets)
         .chain(conflicting.into_values().flatten())
-        // Don't honor events we cannot "verify"
-        .filter(|id| fetch_event(id.borrow()).is_some())
+        // Don't honor events we cannot "verify", or that were rejected
+        .filter(|id| fetch_event(id.borrow()).map_or(false, |event| !event.rejected()))
         .collect();
 
     info!("full conflicted set: {}", all_conflicted.len());
     debug!("{all_conflicted:?}");
 
+    if let Some(report) = report.as_deref_mut() {
+        report.conflicted_events = all_conflicted.iter().cloned().collect();
+        report.conflicted_events.sort_unstable();
+    }
+
+    if all_conflicted.len() > config.max_conflicted_events {
+        return Err(Error::LimitExceeded {
+            kind: ResolverLimit::ConflictedEvents,
+            max: config.max_conflicted_events,
+        });
+    }
+
     // We used to check that all events are events from the correct room
     // this is now a check the caller of `resolve` must make.
 
@@ -104,15 +369,30 @@ where
 
     // Sort the control events based on power_level/clock/event_id and outgoing/incoming edges
     let sorted_control_levels =
-        reverse_topological_power_sort(control_events, &all_conflicted, &fetch_event)?;
+        reverse_topological_power_sort_inner(control_events, &all_conflicted, &fetch_event, cache)?;
 
     debug!("sorted control events: {}", sorted_control_levels.len());
     trace!("{sorted_control_levels:?}");
 
-    let room_version = RoomVersion::new(room_version)?;
+    if let Some(report) = report.as_deref_mut() {
+        report.power_order = sorted_control_levels.clone();
+        record_order(report, &sorted_control_levels, &fetch_event, |report, key, id, idx| {
+            report.record_power_order(key, id, idx);
+        });
+    }
+
+    let room_version = &room_version
+        .rules()
+        .ok_or_else(|| Error::Unsupported(format!("found version `{room_version}`")))?;
     // Sequentially auth check each control event.
-    let resolved_control =
-        iterative_auth_check(&room_version, &sorted_control_levels, clean.clone(), &fetch_event)?;
+    let resolved_control = iterative_auth_check(
+        room_version,
+        &sorted_control_levels,
+        clean.clone(),
+        &fetch_event,
+        &mut interner,
+        report.as_deref_mut(),
+    )?;
 
     debug!("resolved control events: {}", resolved_control.len());
     trace!("{resolved_control:?}");
@@ -137,23 +417,67 @@ where
 
     debug!("power event: {power_event:?}");
 
-    let sorted_left_events = mainline_sort(&events_to_resolve, power_event.cloned(), &fetch_event)?;
+    let sorted_left_events = mainline_sort_inner(
+        &events_to_resolve,
+        power_event.cloned(),
+        &fetch_event,
+        config.max_recursion_depth,
+        cache,
+        report.as_deref_mut().map(|report| &mut report.mainline),
+    )?;
 
     trace!("events left, sorted: {sorted_left_events:?}");
 
+    if let Some(report) = report.as_deref_mut() {
+        report.mainline_order = sorted_left_events.clone();
+        record_order(report, &sorted_left_events, &fetch_event, |report, key, id, idx| {
+            report.record_mainline_order(key, id, idx);
+        });
+    }
+
     let mut resolved_state = iterative_auth_check(
-        &room_version,
+        room_version,
         &sorted_left_events,
         resolved_control, // The control events are added to the final resolved state
         &fetch_event,
+        &mut interner,
+        report.as_deref_mut(),
     )?;
 
     // Add unconflicted state to the resolved state
     // We priorities the unconflicting state
     resolved_state.extend(clean);
+
+    if let Some(report) = report {
+        for id in &all_conflicted {
+            if let Some(ev) = fetch_event(id.borrow()) {
+                if let Some(state_key) = ev.state_key() {
+                    report.record_candidate(ev.event_type().with_state_key(state_key), id);
+                }
+            }
+        }
+        report.record_resolved(&resolved_state);
+    }
+
     Ok(resolved_state)
 }
 
+/// Record the position of the given sorted events in the report with `record`.
+fn record_order<E: Event>(
+    report: &mut ResolutionReport<E::Id>,
+    sorted: &[E::Id],
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    record: impl Fn(&mut ResolutionReport<E::Id>, TypeStateKey, &E::Id, usize),
+) {
+    for (idx, id) in sorted.iter().enumerate() {
+        if let Some(state_key) = fetch_event(id.borrow()).and_then(|ev| {
+            ev.state_key().map(|state_key| ev.event_type().with_state_key(state_key))
+        }) {
+            record(report, state_key, id, idx);
+        }
+    }
+}
+
 /// Split the events that have no conflicts from those that are conflicting.
 ///
 /// The return tuple looks like `(unconflicted, conflicted)`.
@@ -189,6 +513,57 @@ where
     (unconflicted_state, conflicted_state)
 }
 
+/// Split the events that have no conflicts from those that are conflicting, consuming the state
+/// sets one at a time.
+///
+/// This gives the same result as [`separate`], except that the conflicted event IDs are
+/// deduplicated.
+fn separate_streaming<Id>(
+    state_sets: impl IntoIterator<Item = StateMap<Id>>,
+) -> (StateMap<Id>, StateMap<Vec<Id>>)
+where
+    Id: Clone + Eq,
+{
+    let mut unconflicted_state: StateMap<Id> = StateMap::new();
+    let mut conflicted_state: StateMap<Vec<Id>> = StateMap::new();
+
+    for (i, mut state_set) in state_sets.into_iter().enumerate() {
+        if i == 0 {
+            unconflicted_state = state_set;
+            continue;
+        }
+
+        // The unconflicted state that is missing from this state set is now conflicting.
+        let missing = unconflicted_state
+            .keys()
+            .filter(|key| !state_set.contains_key(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in missing {
+            let id = unconflicted_state.remove(&key).expect("missing key is unconflicted");
+            conflicted_state.insert(key, vec![id]);
+        }
+
+        for (key, id) in state_set.drain() {
+            if let Some(ids) = conflicted_state.get_mut(&key) {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            } else if let Some(unconflicted_id) = unconflicted_state.get(&key) {
+                if *unconflicted_id != id {
+                    let unconflicted_id = unconflicted_state.remove(&key).unwrap();
+                    conflicted_state.insert(key, vec![unconflicted_id, id]);
+                }
+            } else {
+                // The key is missing from the previous state sets.
+                conflicted_state.insert(key, vec![id]);
+            }
+        }
+    }
+
+    (unconflicted_state, conflicted_state)
+}
+
 /// Returns a Vec of deduped EventIds that appear in some chains but not others.
 fn get_auth_chain_diff<Id>(auth_chain_sets: Vec<HashSet<Id>>) -> impl Iterator<Item = Id>
 where
@@ -204,6 +579,78 @@ where
     id_counts.into_iter().filter_map(move |(id, count)| (count < num_sets).then_some(id))
 }
 
+/// The key used to break ties between events in [`reverse_topological_power_sort`].
+///
+/// Keys are ordered by descending power level of the sender, then by ascending
+/// `origin_server_ts`, then by event ID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct PowerSortKey<Id> {
+    /// The power level of the sender of the event, according to the `m.room.power_levels` event
+    /// in its `auth_events`.
+    pub sender_power_level: Int,
+
+    /// The `origin_server_ts` of the event.
+    pub origin_server_ts: MilliSecondsSinceUnixEpoch,
+
+    /// The ID of the event.
+    pub event_id: Id,
+}
+
+impl<Id: Ord> Ord for PowerSortKey<Id> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Reverse(self.sender_power_level)
+            .cmp(&Reverse(other.sender_power_level))
+            .then_with(|| self.origin_server_ts.cmp(&other.origin_server_ts))
+            .then_with(|| self.event_id.cmp(&other.event_id))
+    }
+}
+
+impl<Id: Ord> PartialOrd for PowerSortKey<Id> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compute the key used to break ties between the given event and other events in
+/// [`reverse_topological_power_sort`].
+pub fn power_sort_key<E: Event>(
+    event_id: &EventId,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<PowerSortKey<E::Id>> {
+    let event = fetch_event(event_id)
+        .ok_or_else(|| Error::NotFound(format!("Failed to find {event_id}")))?;
+
+    Ok(PowerSortKey {
+        sender_power_level: get_power_level_for_sender(event_id, &fetch_event)?,
+        origin_server_ts: event.origin_server_ts(),
+        event_id: event.event_id().clone(),
+    })
+}
+
+/// Sort the given power events and the events of their auth chains that are in `auth_diff`, from
+/// "earliest" to "latest".
+///
+/// This is the ordering used by state resolution to authenticate the conflicted power events.
+/// Events are sorted topologically by their `auth_events`, and events that are not ordered by the
+/// graph are sorted by their [`PowerSortKey`].
+///
+/// ## Arguments
+///
+/// * `events_to_sort` - The power events to sort.
+///
+/// * `auth_diff` - The auth difference of the state sets. The events of the auth chains of
+///   `events_to_sort` that are not in this set are not part of the result.
+///
+/// * `fetch_event` - Function to fetch an event by its ID.
+pub fn reverse_topological_power_sort<E: Event>(
+    events_to_sort: Vec<E::Id>,
+    auth_diff: &HashSet<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<Vec<E::Id>> {
+    reverse_topological_power_sort_inner(events_to_sort, auth_diff, fetch_event, &mut NoCache)
+}
+
 /// Events are sorted from "earliest" to "latest".
 ///
 /// They are compared using the negative power level (reverse topological ordering), the origin
@@ -211,10 +658,11 @@ where
 ///
 /// The power level is negative because a higher power level is equated to an earlier (further back
 /// in time) origin server timestamp.
-fn reverse_topological_power_sort<E: Event>(
+fn reverse_topological_power_sort_inner<E: Event>(
     events_to_sort: Vec<E::Id>,
     auth_diff: &HashSet<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    cache: &mut dyn StateResolutionCache<E::Id>,
 ) -> Result<Vec<E::Id>> {
     debug!("reverse topological sort of power events");
 
@@ -230,7 +678,14 @@ fn reverse_topological_power_sort<E: Event>(
     // This is used in the `key_fn` passed to the lexico_topo_sort fn
     let mut event_to_pl = HashMap::new();
     for event_id in graph.keys() {
-        let pl = get_power_level_for_sender(event_id.borrow(), &fetch_event)?;
+        let pl = match cache.sender_power_level(event_id.borrow()) {
+            Some(pl) => pl,
+            None => {
+                let pl = get_power_level_for_sender(event_id.borrow(), &fetch_event)?;
+                cache.insert_sender_power_level(event_id.clone(), pl);
+                pl
+            }
+        };
         info!("{event_id} power level {pl}");
 
         event_to_pl.insert(event_id.clone(), pl);
@@ -359,19 +814,26 @@ fn get_power_level_for_sender<E: Event>(
         }
     }
 
-    let content: PowerLevelsContentFields = match pl {
+    let pl = match pl {
         None => return Ok(int!(0)),
-        Some(ev) => from_json_str(ev.content().get())?,
+        Some(ev) => ev,
+    };
+    let (users, users_default) = match pl.power_levels_content() {
+        Some(content) => (Cow::Borrowed(&content.users), content.users_default),
+        None => {
+            let content: PowerLevelsContentFields = from_json_str(pl.content().get())?;
+            (Cow::Owned(content.users), content.users_default)
+        }
     };
 
     if let Some(ev) = event {
-        if let Some(&user_level) = content.users.get(ev.sender()) {
+        if let Some(&user_level) = users.get(ev.sender()) {
             debug!("found {} at power_level {user_level}", ev.sender());
             return Ok(user_level);
         }
     }
 
-    Ok(content.users_default)
+    Ok(users_default)
 }
 
 /// Check the that each event is authenticated based on the events before it.
@@ -388,6 +850,8 @@ fn iterative_auth_check<E: Event + Clone>(
     events_to_check: &[E::Id],
     unconflicted_state: StateMap<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    interner: &mut StateKeyInterner,
+    mut report: Option<&mut ResolutionReport<E::Id>>,
 ) -> Result<StateMap<E::Id>> {
     info!("starting iterative auth check");
 
@@ -405,12 +869,16 @@ fn iterative_auth_check<E: Event + Clone>(
         let mut auth_events = StateMap::new();
         for aid in event.auth_events() {
             if let Some(ev) = fetch_event(aid.borrow()) {
-                // TODO synapse check "rejected_reason" which is most likely
-                // related to soft-failing
+                if ev.rejected() {
+                    debug!("ignoring rejected auth event {aid} of {event_id}");
+                    continue;
+                }
+
+                let state_key = ev
+                    .state_key()
+                    .ok_or_else(|| Error::InvalidPdu("State event had no state key".to_owned()))?;
                 auth_events.insert(
-                    ev.event_type().with_state_key(ev.state_key().ok_or_else(|| {
-                        Error::InvalidPdu("State event had no state key".to_owned())
-                    })?),
+                    interner.type_state_key(ev.event_type().to_string().into(), state_key),
                     ev,
                 );
             } else {
@@ -418,16 +886,10 @@ fn iterative_auth_check<E: Event + Clone>(
             }
         }
 
-        for key in auth_types_for_event(
-            event.event_type(),
-            event.sender(),
-            Some(state_key),
-            event.content(),
-        )? {
-            if let Some(ev_id) = resolved_state.get(&key) {
-                if let Some(event) = fetch_event(ev_id.borrow()) {
-                    // TODO synapse checks `rejected_reason` is None here
-                    auth_events.insert(key.to_owned(), event);
+        for key in auth_types_for_pdu(&event, room_version) {
+            if let Some((key, ev_id)) = resolved_state.get_key_value(&key) {
+                if let Some(event) = fetch_event(ev_id.borrow()).filter(|ev| !ev.rejected()) {
+                    auth_events.insert(key.clone(), event);
                 }
             }
         }
@@ -440,11 +902,20 @@ fn iterative_auth_check<E: Event + Clone>(
             (*pdu.event_type() == TimelineEventType::RoomThirdPartyInvite).then_some(pdu)
         });
 
-        if auth_check(room_version, &event, current_third_party, |ty, key| {
-            auth_events.get(&ty.with_state_key(key))
-        })? {
+        // All the keys of `auth_events` are interned, so a key that isn't interned can't be found
+        let allowed = auth_check(room_version, &event, current_third_party, |ty, key| {
+            auth_events.get(&(ty.clone(), interner.get(key)?))
+        })?;
+
+        let key = interner.type_state_key(event.event_type().to_string().into(), state_key);
+
+        if let Some(report) = report.as_deref_mut() {
+            report.record_auth(key.clone(), event_id, allowed);
+        }
+
+        if allowed {
             // add event to resolved state map
-            resolved_state.insert(event.event_type().with_state_key(state_key), event_id.clone());
+            resolved_state.insert(key, event_id.clone());
         } else {
             // synapse passes here on AuthError. We do not add this event to resolved_state.
             warn!("event {event_id} failed the authentication check");
@@ -457,6 +928,72 @@ fn iterative_auth_check<E: Event + Clone>(
     Ok(resolved_state)
 }
 
+/// The key used to order events in [`mainline_sort`].
+///
+/// Keys are ordered by ascending mainline depth, then by ascending `origin_server_ts`, then by
+/// event ID.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::exhaustive_structs)]
+pub struct MainlineSortKey<Id> {
+    /// The position in the mainline of the closest `m.room.power_levels` event in the auth chain
+    /// of the event.
+    ///
+    /// The oldest event of the mainline is at position `0`, like events that don't have any event
+    /// of the mainline in their auth chain.
+    pub mainline_depth: usize,
+
+    /// The `origin_server_ts` of the event.
+    pub origin_server_ts: MilliSecondsSinceUnixEpoch,
+
+    /// The ID of the event.
+    pub event_id: Id,
+}
+
+/// Compute the key used to order the given event in [`mainline_sort`].
+///
+/// See [`mainline_sort`] for the arguments.
+pub fn mainline_sort_key<E: Event>(
+    event_id: &EventId,
+    resolved_power_level: Option<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<MainlineSortKey<E::Id>> {
+    let event = fetch_event(event_id)
+        .ok_or_else(|| Error::NotFound(format!("Failed to find {event_id}")))?;
+    let mainline_map = get_mainline_map(resolved_power_level, &fetch_event, usize::MAX)?;
+
+    Ok(MainlineSortKey {
+        origin_server_ts: event.origin_server_ts(),
+        event_id: event.event_id().clone(),
+        mainline_depth: get_mainline_depth(Some(event), &mainline_map, &fetch_event, usize::MAX)?,
+    })
+}
+
+/// Sort the given events by their position relative to the mainline of the given
+/// `m.room.power_levels` event, from "earliest" to "latest".
+///
+/// This is the ordering used by state resolution to authenticate the conflicted events that are
+/// not power events. The mainline of a power levels event is the list of the power levels events
+/// that are found by following the `m.room.power_levels` event in their `auth_events`
+/// recursively. Events are sorted by their [`MainlineSortKey`].
+///
+/// Events that can't be fetched are not part of the result.
+///
+/// ## Arguments
+///
+/// * `to_sort` - The events to sort.
+///
+/// * `resolved_power_level` - The `m.room.power_levels` event at the start of the mainline, usually
+///   the one of the resolved state.
+///
+/// * `fetch_event` - Function to fetch an event by its ID.
+pub fn mainline_sort<E: Event>(
+    to_sort: &[E::Id],
+    resolved_power_level: Option<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<Vec<E::Id>> {
+    mainline_sort_inner(to_sort, resolved_power_level, fetch_event, usize::MAX, &mut NoCache, None)
+}
+
 /// Returns the sorted `to_sort` list of `EventId`s based on a mainline sort using the depth of
 /// `resolved_power_level`, the server timestamp, and the eventId.
 ///
@@ -464,10 +1001,19 @@ fn iterative_auth_check<E: Event + Clone>(
 /// power_level event. If there have been two power events the after the most recent are depth 0,
 /// the events before (with the first power level as a parent) will be marked as depth 1. depth 1 is
 /// "older" than depth 0.
-fn mainline_sort<E: Event>(
+///
+/// `max_depth` is the maximum number of power level events to follow when walking back the power
+/// level history.
+///
+/// If `mainline_out` is set, the mainline that was used for the sort is written to it, from the
+/// oldest to the newest event.
+fn mainline_sort_inner<E: Event>(
     to_sort: &[E::Id],
     resolved_power_level: Option<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    max_depth: usize,
+    cache: &mut dyn StateResolutionCache<E::Id>,
+    mainline_out: Option<&mut Vec<E::Id>>,
 ) -> Result<Vec<E::Id>> {
     debug!("mainline sort of events");
 
@@ -476,9 +1022,85 @@ fn mainline_sort<E: Event>(
         return Ok(vec![]);
     }
 
+    let mainline = get_mainline(resolved_power_level.clone(), &fetch_event, max_depth)?;
+    let mainline_map = mainline_to_map(&mainline);
+    if let Some(mainline_out) = mainline_out {
+        *mainline_out = mainline;
+    }
+
+    let mut order_map = HashMap::new();
+    for ev_id in to_sort.iter() {
+        if let Some(event) = fetch_event(ev_id.borrow()) {
+            let origin_server_ts = event.origin_server_ts();
+            let cached_depth = resolved_power_level
+                .as_ref()
+                .and_then(|pl| cache.mainline_depth(pl.borrow(), ev_id.borrow()));
+            let depth = match cached_depth {
+                Some(depth) => Ok(depth),
+                None => get_mainline_depth(Some(event), &mainline_map, &fetch_event, max_depth),
+            };
+
+            match depth {
+                Ok(depth) => {
+                    if let Some(pl) = &resolved_power_level {
+                        cache.insert_mainline_depth(pl.clone(), ev_id.clone(), depth);
+                    }
+
+                    order_map.insert(
+                        ev_id,
+                        MainlineSortKey {
+                            mainline_depth: depth,
+                            origin_server_ts,
+                            event_id: ev_id,
+                        },
+                    );
+                }
+                Err(e @ Error::LimitExceeded { .. }) => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        // TODO: if these functions are ever made async here
+        // is a good place to yield every once in a while so other
+        // tasks can make progress
+    }
+
+    // Sort the event_ids by their depth, timestamp and EventId
+    // unwrap is OK order map and sort_event_ids are from to_sort (the same Vec)
+    let mut sort_event_ids = order_map.keys().map(|&k| k.clone()).collect::<Vec<_>>();
+    sort_event_ids.sort_by_key(|sort_id| order_map.get(sort_id).unwrap());
+
+    Ok(sort_event_ids)
+}
+
+/// Get the position of the events of the mainline of `resolved_power_level`, starting at `0` for
+/// the oldest event.
+fn get_mainline_map<E: Event>(
+    resolved_power_level: Option<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    max_depth: usize,
+) -> Result<HashMap<E::Id, usize>> {
+    Ok(mainline_to_map(&get_mainline(resolved_power_level, fetch_event, max_depth)?))
+}
+
+/// Get the position of the events of the given mainline, sorted from the oldest to the newest
+/// event.
+fn mainline_to_map<Id: Clone + Eq + Hash>(mainline: &[Id]) -> HashMap<Id, usize> {
+    mainline.iter().enumerate().map(|(idx, eid)| (eid.clone(), idx)).collect()
+}
+
+/// Get the mainline of `resolved_power_level`, from the oldest to the newest event.
+fn get_mainline<E: Event>(
+    resolved_power_level: Option<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    max_depth: usize,
+) -> Result<Vec<E::Id>> {
     let mut mainline = vec![];
     let mut pl = resolved_power_level;
     while let Some(p) = pl {
+        if mainline.len() >= max_depth {
+            return Err(recursion_limit_exceeded(max_depth));
+        }
         mainline.push(p.clone());
 
         let event = fetch_event(p.borrow())
@@ -497,35 +1119,8 @@ fn mainline_sort<E: Event>(
         // tasks can make progress
     }
 
-    let mainline_map = mainline
-        .iter()
-        .rev()
-        .enumerate()
-        .map(|(idx, eid)| ((*eid).clone(), idx))
-        .collect::<HashMap<_, _>>();
-
-    let mut order_map = HashMap::new();
-    for ev_id in to_sort.iter() {
-        if let Some(event) = fetch_event(ev_id.borrow()) {
-            if let Ok(depth) = get_mainline_depth(Some(event), &mainline_map, &fetch_event) {
-                order_map.insert(
-                    ev_id,
-                    (depth, fetch_event(ev_id.borrow()).map(|ev| ev.origin_server_ts()), ev_id),
-                );
-            }
-        }
-
-        // TODO: if these functions are ever made async here
-        // is a good place to yield every once in a while so other
-        // tasks can make progress
-    }
-
-    // Sort the event_ids by their depth, timestamp and EventId
-    // unwrap is OK order map and sort_event_ids are from to_sort (the same Vec)
-    let mut sort_event_ids = order_map.keys().map(|&k| k.clone()).collect::<Vec<_>>();
-    sort_event_ids.sort_by_key(|sort_id| order_map.get(sort_id).unwrap());
-
-    Ok(sort_event_ids)
+    mainline.reverse();
+    Ok(mainline)
 }
 
 /// Get the mainline depth from the `mainline_map` or finds a power_level event that has an
@@ -534,8 +1129,15 @@ fn get_mainline_depth<E: Event>(
     mut event: Option<E>,
     mainline_map: &HashMap<E::Id, usize>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    max_depth: usize,
 ) -> Result<usize> {
+    let mut depth = 0;
     while let Some(sort_ev) = event {
+        if depth >= max_depth {
+            return Err(recursion_limit_exceeded(max_depth));
+        }
+        depth += 1;
+
         debug!("mainline event_id {}", sort_ev.event_id());
         let id = sort_ev.event_id();
         if let Some(depth) = mainline_map.get(id.borrow()) {
@@ -556,6 +1158,10 @@ fn get_mainline_depth<E: Event>(
     Ok(0)
 }
 
+fn recursion_limit_exceeded(max: usize) -> Error {
+    Error::LimitExceeded { kind: ResolverLimit::RecursionDepth, max }
+}
+
 fn add_event_and_auth_chain_to_graph<E: Event>(
     graph: &mut HashMap<E::Id, HashSet<E::Id>>,
     event_id: E::Id,
@@ -598,8 +1204,14 @@ fn is_power_event(event: impl Event) -> bool {
         | TimelineEventType::RoomJoinRules
         | TimelineEventType::RoomCreate => event.state_key() == Some(""),
         TimelineEventType::RoomMember => {
-            if let Ok(content) = from_json_str::<RoomMemberEventContent>(event.content().get()) {
-                if [MembershipState::Leave, MembershipState::Ban].contains(&content.membership) {
+            let membership = match event.member_content() {
+                Some(content) => Some(content.membership.clone()),
+                None => from_json_str::<RoomMemberEventContent>(event.content().get())
+                    .ok()
+                    .map(|content| content.membership),
+            };
+            if let Some(membership) = membership {
+                if [MembershipState::Leave, MembershipState::Ban].contains(&membership) {
                     return Some(event.sender().as_str()) != event.state_key();
                 }
             }
@@ -612,17 +1224,17 @@ fn is_power_event(event: impl Event) -> bool {
 
 /// Convenience trait for adding event type plus state key to state maps.
 trait EventTypeExt {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String);
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey;
 }
 
 impl EventTypeExt for StateEventType {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String) {
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey {
         (self, state_key.into())
     }
 }
 
 impl EventTypeExt for TimelineEventType {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String) {
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey {
         (self.to_string().into(), state_key.into())
     }
 }
@@ -631,7 +1243,7 @@ impl<T> EventTypeExt for &T
 where
     T: EventTypeExt + Clone,
 {
-    fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String) {
+    fn with_state_key(self, state_key: impl Into<StateKey>) -> TypeStateKey {
         self.to_owned().with_state_key(state_key)
     }
 }
@@ -643,15 +1255,19 @@ mod tests {
         sync::Arc,
     };
 
+    use assert_matches::assert_matches;
     use js_int::{int, uint};
     use maplit::{hashmap, hashset};
     use rand::seq::SliceRandom;
     use ruma_common::{
         events::{
-            room::join_rules::{JoinRule, RoomJoinRulesEventContent},
+            room::{
+                join_rules::{JoinRule, RoomJoinRulesEventContent},
+                member::MembershipState,
+            },
             StateEventType, TimelineEventType,
         },
-        MilliSecondsSinceUnixEpoch, OwnedEventId, RoomVersionId,
+        EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomVersionId,
     };
     use serde_json::{json, value::to_raw_value as to_raw_json_value};
     use tracing::debug;
@@ -661,9 +1277,11 @@ mod tests {
         room_version::RoomVersion,
         test_utils::{
             alice, bob, charlie, do_check, ella, event_id, member_content_ban, member_content_join,
-            room_id, to_init_pdu_event, to_pdu_event, zara, PduEvent, TestStore, INITIAL_EVENTS,
+            member_event_builder, room_id, to_init_pdu_event, to_pdu_event, zara, Dag, PduBuilder,
+            PduEvent, TestRoom, TestStore, INITIAL_EVENTS,
         },
-        Event, EventTypeExt, StateMap,
+        AuthDecision, CandidateReport, Error, Event, EventTypeExt, InMemoryStateResolutionCache,
+        NoCache, ResolverConfig, ResolverLimit, StateKeyInterner, StateMap,
     };
 
     fn test_event_sort() {
@@ -695,6 +1313,8 @@ mod tests {
             &sorted_power_events,
             HashMap::new(), // unconflicted events
             |id| events.get(id).map(Arc::clone),
+            &mut StateKeyInterner::new(),
+            None,
         )
         .expect("iterative auth check failed on resolved events");
 
@@ -704,7 +1324,7 @@ mod tests {
         events_to_sort.shuffle(&mut rand::thread_rng());
 
         let power_level =
-            resolved_power.get(&(StateEventType::RoomPowerLevels, "".to_owned())).cloned();
+            resolved_power.get(&(StateEventType::RoomPowerLevels, "".into())).cloned();
 
         let sorted_event_ids =
             crate::mainline_sort(&events_to_sort, power_level, |id| events.get(id).map(Arc::clone))
@@ -734,6 +1354,118 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iterative_auth_check_ignores_rejected_events() {
+        for rejected in [false, true] {
+            let mut room = TestRoom::bootstrap(alice(), &[bob(), charlie()]);
+
+            let mut power_levels =
+                PduBuilder::new("PB", alice(), TimelineEventType::RoomPowerLevels)
+                    .state_key("")
+                    .serialized_content(&json!({ "users": { alice(): 100, bob(): 100 } }));
+            if rejected {
+                power_levels = power_levels.rejected();
+            }
+            room.add_event(power_levels);
+
+            let ban =
+                room.add_event(member_event_builder("BAN", bob(), charlie(), MembershipState::Ban));
+            let ban_key = ban.event_type().with_state_key(ban.state_key().unwrap());
+
+            let mut unconflicted_state = room.state.clone();
+            unconflicted_state.remove(&ban_key);
+
+            let resolved = crate::iterative_auth_check(
+                &RoomVersion::V6,
+                &[ban.event_id().clone()],
+                unconflicted_state,
+                |id| room.store.0.get(id).map(Arc::clone),
+                &mut StateKeyInterner::new(),
+                None,
+            )
+            .unwrap();
+
+            // Bob can only ban Charlie if the power levels event that raised his power level
+            // was not rejected.
+            assert_eq!(resolved.get(&ban_key) == Some(ban.event_id()), !rejected);
+        }
+    }
+
+    #[test]
+    fn resolve_excludes_rejected_events() {
+        for rejected in [false, true] {
+            let mut room = TestRoom::bootstrap(alice(), &[bob(), charlie()]);
+            let state_before_fork = room.state.clone();
+
+            // A fork where Bob's power level was raised by an event that was rejected, and where
+            // he uses it to ban Charlie.
+            let mut power_levels =
+                PduBuilder::new("PB", alice(), TimelineEventType::RoomPowerLevels)
+                    .state_key("")
+                    .serialized_content(&json!({ "users": { alice(): 100, bob(): 100 } }));
+            if rejected {
+                power_levels = power_levels.rejected();
+            }
+            let power_levels = room.add_event(power_levels);
+            let ban =
+                room.add_event(member_event_builder("BAN", bob(), charlie(), MembershipState::Ban));
+
+            let state_sets = [state_before_fork, room.state.clone()];
+            let auth_chain_sets = state_sets
+                .iter()
+                .map(|state| {
+                    room.store.auth_event_ids(room_id(), state.values().cloned().collect()).unwrap()
+                })
+                .collect();
+
+            let resolved = crate::resolve(&RoomVersionId::V6, &state_sets, auth_chain_sets, |id| {
+                room.store.0.get(id).map(Arc::clone)
+            })
+            .unwrap();
+
+            let power_levels_key = StateEventType::RoomPowerLevels.with_state_key("");
+            let charlie_key = StateEventType::RoomMember.with_state_key(charlie().as_str());
+
+            // The rejected event must not be able to reset the state of the room.
+            if rejected {
+                assert_eq!(resolved[&power_levels_key], event_id("IPOWER"));
+                assert_eq!(resolved[&charlie_key], event_id("IM_charlie"));
+            } else {
+                assert_eq!(&resolved[&power_levels_key], power_levels.event_id());
+                assert_eq!(&resolved[&charlie_key], ban.event_id());
+            }
+        }
+    }
+
+    #[test]
+    fn sort_keys() {
+        let events = INITIAL_EVENTS();
+        let fetch_event = |id: &EventId| events.get(id).map(Arc::clone);
+
+        let key = crate::power_sort_key(&event_id("IJR"), fetch_event).unwrap();
+        assert_eq!(key.sender_power_level, int!(100));
+        assert_eq!(key.event_id, event_id("IJR"));
+        // The power levels are not in the auth events of the power levels event.
+        let key = crate::power_sort_key(&event_id("IPOWER"), fetch_event).unwrap();
+        assert_eq!(key.sender_power_level, int!(0));
+
+        let power_level = Some(event_id("IPOWER"));
+        let mut events_to_sort = events.keys().cloned().collect::<Vec<_>>();
+        events_to_sort.sort();
+
+        let mut keys = events_to_sort
+            .iter()
+            .map(|id| crate::mainline_sort_key(id, power_level.clone(), fetch_event).unwrap())
+            .collect::<Vec<_>>();
+        keys.sort();
+        let sorted_by_key = keys.into_iter().map(|key| key.event_id).collect::<Vec<_>>();
+
+        assert_eq!(
+            crate::mainline_sort(&events_to_sort, power_level, fetch_event).unwrap(),
+            sorted_by_key
+        );
+    }
+
     #[test]
     fn ban_vs_power_level() {
         let _ =
@@ -778,7 +1510,7 @@ mod tests {
         let expected_state_ids =
             vec!["PA", "MA", "MB"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -839,7 +1571,7 @@ mod tests {
 
         let expected_state_ids = vec!["PA2", "T2"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -886,7 +1618,7 @@ mod tests {
         let expected_state_ids =
             vec!["T1", "MB", "PA"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -918,7 +1650,7 @@ mod tests {
 
         let expected_state_ids = vec![event_id("JR")];
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -959,7 +1691,7 @@ mod tests {
 
         let expected_state_ids = vec!["PC"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -1036,7 +1768,7 @@ mod tests {
 
         let expected_state_ids = vec!["T4", "PA2"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(events, edges, expected_state_ids);
+        do_check(events, Dag::new(alice(), &[bob(), charlie()], edges), expected_state_ids);
     }
 
     #[test]
@@ -1069,6 +1801,212 @@ mod tests {
         assert_eq!(expected, resolved);
     }
 
+    #[test]
+    fn resolve_with_limits() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets: Vec<_> = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+        let resolve = |config: &ResolverConfig| {
+            crate::resolve_with_config(
+                &RoomVersionId::V2,
+                &state_sets,
+                auth_chain_sets.clone(),
+                |id| ev_map.get(id).map(Arc::clone),
+                config,
+            )
+        };
+
+        assert_eq!(resolve(&ResolverConfig::new()).unwrap(), expected);
+
+        let mut config = ResolverConfig::new();
+        config.max_auth_chain_length = 1;
+        assert_matches!(
+            resolve(&config),
+            Err(Error::LimitExceeded { kind: ResolverLimit::AuthChainLength, max: 1 })
+        );
+
+        let mut config = ResolverConfig::new();
+        config.max_conflicted_events = 1;
+        assert_matches!(
+            resolve(&config),
+            Err(Error::LimitExceeded { kind: ResolverLimit::ConflictedEvents, max: 1 })
+        );
+
+        let mut config = ResolverConfig::new();
+        config.max_recursion_depth = 0;
+        assert_matches!(
+            resolve(&config),
+            Err(Error::LimitExceeded { kind: ResolverLimit::RecursionDepth, max: 0 })
+        );
+    }
+
+    #[test]
+    fn resolve_streaming() {
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets: Vec<_> = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+        let resolved = crate::resolve_streaming(
+            &RoomVersionId::V2,
+            state_sets.iter().cloned(),
+            auth_chain_sets.clone(),
+            |id| ev_map.get(id).map(Arc::clone),
+            &ResolverConfig::new(),
+            &mut InMemoryStateResolutionCache::new(),
+        )
+        .unwrap();
+        assert_eq!(resolved, expected);
+
+        // Without caching the sort keys.
+        let resolved = crate::resolve_streaming(
+            &RoomVersionId::V2,
+            state_sets,
+            auth_chain_sets,
+            |id| ev_map.get(id).map(Arc::clone),
+            &ResolverConfig::new(),
+            &mut NoCache,
+        )
+        .unwrap();
+        assert_eq!(resolved, expected);
+    }
+
+    #[test]
+    fn separate_streaming() {
+        let state_map = |ids: &[(&str, &str)]| -> StateMap<OwnedEventId> {
+            ids.iter()
+                .map(|&(state_key, id)| {
+                    (StateEventType::RoomMember.with_state_key(state_key), event_id(id))
+                })
+                .collect()
+        };
+        let state_sets = [
+            state_map(&[("a", "A"), ("b", "B1"), ("c", "C")]),
+            state_map(&[("a", "A"), ("b", "B2"), ("d", "D")]),
+            state_map(&[("a", "A"), ("b", "B1"), ("c", "C")]),
+        ];
+
+        let (clean, mut conflicting) = super::separate(state_sets.iter());
+        for ids in conflicting.values_mut() {
+            ids.sort();
+            ids.dedup();
+        }
+        assert_eq!(clean.len(), 1);
+        assert_eq!(conflicting.len(), 3);
+
+        assert_eq!(super::separate_streaming(state_sets), (clean, conflicting));
+    }
+
+    #[test]
+    fn resolve_custom_room_version() {
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets: Vec<_> = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+        let custom_version = RoomVersionId::try_from("org.example.custom").unwrap();
+        let resolve = || {
+            crate::resolve(&custom_version, &state_sets, auth_chain_sets.clone(), |id| {
+                ev_map.get(id).map(Arc::clone)
+            })
+        };
+
+        assert_matches!(resolve(), Err(Error::Unsupported(_)));
+
+        RoomVersion::register_custom(custom_version.clone(), RoomVersion::V2).unwrap();
+        assert_eq!(resolve().unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_with_report() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let (resolved, report) = crate::resolve_with_report(
+            &RoomVersionId::V2,
+            &state_sets,
+            state_sets
+                .iter()
+                .map(|map| {
+                    store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap()
+                })
+                .collect(),
+            |id| ev_map.get(id).map(Arc::clone),
+            &ResolverConfig::new(),
+        )
+        .unwrap();
+
+        assert_eq!(resolved, expected);
+        assert!(!report.conflicts.is_empty());
+        assert!(report.conflicted_events.contains(&event_id("IMC")));
+        assert!(report.mainline_order.contains(&event_id("IMC")));
+        assert_eq!(
+            report.auth_checks.len(),
+            report.power_order.len() + report.mainline_order.len()
+        );
+        assert_eq!(
+            report.mainline.last(),
+            resolved.get(&StateEventType::RoomPowerLevels.with_state_key(""))
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["conflicts"].as_array().unwrap().len(), report.conflicts.len());
+        assert!(json["conflicts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|conflict| conflict["type"] == "m.room.member"
+                && conflict["state_key"] == charlie().as_str()
+                && conflict["resolved"] == "$IMC:foo"));
+
+        for (key, conflict) in &report.conflicts {
+            assert_eq!(conflict.resolved.as_ref(), resolved.get(key));
+
+            for candidate in &conflict.candidates {
+                if candidate.auth != AuthDecision::NotChecked {
+                    assert!(candidate.power_order.is_some() || candidate.mainline_order.is_some());
+                }
+            }
+        }
+
+        let charlie = report
+            .conflicts
+            .get(&(StateEventType::RoomMember, charlie().as_str().into()))
+            .expect("charlie's membership is in the auth chain difference");
+        assert_eq!(charlie.resolved.as_ref(), Some(&event_id("IMC")));
+        assert_matches!(
+            charlie.candidates.as_slice(),
+            [CandidateReport {
+                power_order: None,
+                mainline_order: Some(_),
+                auth: AuthDecision::Allowed,
+                ..
+            }]
+        );
+    }
+
     #[test]
     fn test_lexicographical_sort() {
         let _ =
@@ -1109,7 +2047,11 @@ mod tests {
 
         let expected_state_ids = vec!["PA", "MB"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(&ban.values().cloned().collect::<Vec<_>>(), edges, expected_state_ids);
+        do_check(
+            &ban.values().cloned().collect::<Vec<_>>(),
+            Dag::new(alice(), &[bob(), charlie()], edges),
+            expected_state_ids,
+        );
     }
 
     #[test]
@@ -1202,7 +2144,11 @@ mod tests {
 
         let expected_state_ids = vec!["JR"].into_iter().map(event_id).collect::<Vec<_>>();
 
-        do_check(&join_rule.values().cloned().collect::<Vec<_>>(), edges, expected_state_ids);
+        do_check(
+            &join_rule.values().cloned().collect::<Vec<_>>(),
+            Dag::new(alice(), &[bob(), charlie()], edges),
+            expected_state_ids,
+        );
     }
 
     #[allow(non_snake_case)]
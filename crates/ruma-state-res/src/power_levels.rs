@@ -2,124 +2,41 @@ use std::collections::BTreeMap;
 
 use js_int::Int;
 use ruma_common::{
-    events::{room::power_levels::RoomPowerLevelsEventContent, TimelineEventType},
-    power_levels::{default_power_level, NotificationPowerLevels},
+    events::room::power_levels::RoomPowerLevelsEventContent,
+    power_levels::{default_power_level, PowerLevelParsing},
     serde::{btreemap_deserialize_v1_powerlevel_values, deserialize_v1_powerlevel},
     OwnedUserId,
 };
 use serde::Deserialize;
-use serde_json::{from_str as from_json_str, Error};
+use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue, Error};
 use tracing::error;
 
 use crate::RoomVersion;
 
-#[derive(Deserialize)]
-struct IntRoomPowerLevelsEventContent {
-    #[serde(default = "default_power_level")]
-    pub ban: Int,
-
-    #[serde(default)]
-    pub events: BTreeMap<TimelineEventType, Int>,
-
-    #[serde(default)]
-    pub events_default: Int,
-
-    #[serde(default)]
-    pub invite: Int,
-
-    #[serde(default = "default_power_level")]
-    pub kick: Int,
-
-    #[serde(default = "default_power_level")]
-    pub redact: Int,
-
-    #[serde(default = "default_power_level")]
-    pub state_default: Int,
-
-    #[serde(default)]
-    pub users: BTreeMap<OwnedUserId, Int>,
-
-    #[serde(default)]
-    pub users_default: Int,
-
-    #[serde(default)]
-    pub notifications: IntNotificationPowerLevels,
-}
-
-impl From<IntRoomPowerLevelsEventContent> for RoomPowerLevelsEventContent {
-    fn from(int_pl: IntRoomPowerLevelsEventContent) -> Self {
-        let IntRoomPowerLevelsEventContent {
-            ban,
-            events,
-            events_default,
-            invite,
-            kick,
-            redact,
-            state_default,
-            users,
-            users_default,
-            notifications,
-        } = int_pl;
-
-        let mut pl = Self::new();
-        pl.ban = ban;
-        pl.events = events;
-        pl.events_default = events_default;
-        pl.invite = invite;
-        pl.kick = kick;
-        pl.redact = redact;
-        pl.state_default = state_default;
-        pl.users = users;
-        pl.users_default = users_default;
-        pl.notifications = notifications.into();
-
-        pl
-    }
-}
-
-#[derive(Deserialize)]
-struct IntNotificationPowerLevels {
-    #[serde(default = "default_power_level")]
-    pub room: Int,
-}
-
-impl Default for IntNotificationPowerLevels {
-    fn default() -> Self {
-        Self { room: default_power_level() }
-    }
-}
-
-impl From<IntNotificationPowerLevels> for NotificationPowerLevels {
-    fn from(int_notif: IntNotificationPowerLevels) -> Self {
-        let mut notif = Self::new();
-        notif.room = int_notif.room;
-
-        notif
-    }
-}
-
 pub(crate) fn deserialize_power_levels(
-    content: &str,
+    content: &RawJsonValue,
     room_version: &RoomVersion,
 ) -> Option<RoomPowerLevelsEventContent> {
-    if room_version.integer_power_levels {
-        match from_json_str::<IntRoomPowerLevelsEventContent>(content) {
-            Ok(content) => Some(content.into()),
-            Err(_) => {
+    let parsing = if room_version.integer_power_levels {
+        PowerLevelParsing::Strict
+    } else {
+        PowerLevelParsing::Lenient
+    };
+
+    match RoomPowerLevelsEventContent::deserialize_with_parsing(content, parsing) {
+        Ok(content) => Some(content),
+        Err(_) => match parsing {
+            PowerLevelParsing::Strict => {
                 error!("m.room.power_levels event is not valid with integer values");
                 None
             }
-        }
-    } else {
-        match from_json_str(content) {
-            Ok(content) => Some(content),
-            Err(_) => {
+            PowerLevelParsing::Lenient => {
                 error!(
                     "m.room.power_levels event is not valid with integer or string integer values"
                 );
                 None
             }
-        }
+        },
     }
 }
 
@@ -0,0 +1,181 @@
+use std::fmt;
+
+use ruma_common::{CanonicalJsonObject, EventId, RoomVersionId};
+use ruma_signatures::PublicKeyMap;
+use tracing::warn;
+
+/// Verifies the signatures of events during their authorization.
+///
+/// Pass an implementation to [`auth_check_with_verifier`](crate::auth_check_with_verifier) to
+/// reject events that are not signed by the servers that must sign them.
+///
+/// This is implemented for closures that take the ID of the event to verify, and for
+/// [`PublicKeyVerifier`].
+pub trait SignatureVerifier {
+    /// Whether the event with the given ID is signed by all the servers that must sign it.
+    fn verify_event(&self, event_id: &EventId) -> bool;
+}
+
+impl<F> SignatureVerifier for F
+where
+    F: Fn(&EventId) -> bool,
+{
+    fn verify_event(&self, event_id: &EventId) -> bool {
+        self(event_id)
+    }
+}
+
+/// A [`SignatureVerifier`] that verifies the signatures of events with
+/// [`ruma_signatures::verify_event`].
+///
+/// This checks the signatures of the servers required by the room version, including the server
+/// of the sender and, for restricted joins, the server of the user that authorised the join.
+///
+/// Events whose content hash doesn't match but whose signatures are valid pass the verification,
+/// since they must be redacted rather than rejected.
+pub struct PublicKeyVerifier<F> {
+    public_key_map: PublicKeyMap,
+    room_version: RoomVersionId,
+    fetch_pdu: F,
+}
+
+impl<F> PublicKeyVerifier<F>
+where
+    F: Fn(&EventId) -> Option<CanonicalJsonObject>,
+{
+    /// Creates a new `PublicKeyVerifier`.
+    ///
+    /// `public_key_map` must contain the public keys of the servers that signed the events, and
+    /// `fetch_pdu` returns the full PDU of an event, as received over federation.
+    pub fn new(public_key_map: PublicKeyMap, room_version: RoomVersionId, fetch_pdu: F) -> Self {
+        Self { public_key_map, room_version, fetch_pdu }
+    }
+}
+
+impl<F> SignatureVerifier for PublicKeyVerifier<F>
+where
+    F: Fn(&EventId) -> Option<CanonicalJsonObject>,
+{
+    fn verify_event(&self, event_id: &EventId) -> bool {
+        let pdu = match (self.fetch_pdu)(event_id) {
+            Some(pdu) => pdu,
+            None => {
+                warn!("failed to find the PDU of {event_id} to verify its signatures");
+                return false;
+            }
+        };
+
+        match ruma_signatures::verify_event(&self.public_key_map, &pdu, &self.room_version) {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("failed to verify the signatures of {event_id}: {e}");
+                false
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for PublicKeyVerifier<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicKeyVerifier")
+            .field("public_key_map", &self.public_key_map)
+            .field("room_version", &self.room_version)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_common::{
+        event_id,
+        events::{StateEventType, TimelineEventType},
+        serde::Base64,
+        CanonicalJsonObject, EventId, RoomVersionId,
+    };
+    use ruma_signatures::{hash_and_sign_event, Ed25519KeyPair};
+    use serde_json::{
+        from_value as from_json_value, json, value::to_raw_value as to_raw_json_value,
+    };
+
+    use super::{PublicKeyVerifier, SignatureVerifier};
+    use crate::{
+        auth_check_with_verifier,
+        test_utils::{alice, to_pdu_event, PduEvent, INITIAL_EVENTS},
+        AuthResult, Event, EventTypeExt, RejectionReason, RoomVersion, StateMap,
+    };
+
+    #[test]
+    fn public_key_verifier() {
+        let key_pair =
+            Ed25519KeyPair::from_der(&Ed25519KeyPair::generate().unwrap(), "1".to_owned()).unwrap();
+        let mut pdu: CanonicalJsonObject = from_json_value(json!({
+            "auth_events": [],
+            "content": { "body": "Hello" },
+            "depth": 3,
+            "origin_server_ts": 1_000_000,
+            "prev_events": [],
+            "room_id": "!room:foo",
+            "sender": "@alice:foo",
+            "type": "m.room.message",
+        }))
+        .unwrap();
+        hash_and_sign_event("foo", &key_pair, &mut pdu, &RoomVersionId::V6).unwrap();
+
+        let public_key_map = BTreeMap::from([(
+            "foo".to_owned(),
+            BTreeMap::from([(
+                "ed25519:1".to_owned(),
+                Base64::new(key_pair.public_key().to_owned()),
+            )]),
+        )]);
+        let verifier =
+            PublicKeyVerifier::new(public_key_map, RoomVersionId::V6, |event_id: &EventId| {
+                (event_id == "$event:foo").then(|| pdu.clone())
+            });
+
+        assert!(verifier.verify_event(event_id!("$event:foo")));
+        assert!(!verifier.verify_event(event_id!("$unknown:foo")));
+    }
+
+    #[test]
+    fn reject_invalid_signature() {
+        let events = INITIAL_EVENTS();
+        let state: StateMap<_> = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), ev.clone()))
+            .collect();
+        let topic = to_pdu_event(
+            "TOPIC",
+            alice(),
+            TimelineEventType::RoomTopic,
+            Some(""),
+            to_raw_json_value(&json!({ "topic": "Hello" })).unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IMC"],
+        );
+        let fetch_state =
+            |ty: &StateEventType, key: &str| state.get(&ty.with_state_key(key)).cloned();
+
+        let result = auth_check_with_verifier(
+            &RoomVersion::V6,
+            &topic,
+            None::<PduEvent>,
+            fetch_state,
+            &|_: &EventId| true,
+        )
+        .unwrap();
+        assert_eq!(result, AuthResult::Allowed);
+
+        let result = auth_check_with_verifier(
+            &RoomVersion::V6,
+            &topic,
+            None::<PduEvent>,
+            fetch_state,
+            &|_: &EventId| false,
+        )
+        .unwrap();
+        assert_eq!(result, AuthResult::Rejected(RejectionReason::InvalidSignature));
+    }
+}
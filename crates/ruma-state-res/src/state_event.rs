@@ -5,7 +5,16 @@ use std::{
     sync::Arc,
 };
 
-use ruma_common::{events::TimelineEventType, EventId, MilliSecondsSinceUnixEpoch, RoomId, UserId};
+use ruma_common::{
+    events::{
+        room::{
+            join_rules::RoomJoinRulesEventContent, member::RoomMemberEventContent,
+            power_levels::RoomPowerLevelsEventContent,
+        },
+        TimelineEventType,
+    },
+    EventId, MilliSecondsSinceUnixEpoch, RoomId, UserId,
+};
 use serde_json::value::RawValue as RawJsonValue;
 
 /// Abstraction of a PDU so users can have their own PDU types.
@@ -43,6 +52,50 @@ pub trait Event {
 
     /// If this event is a redaction event this is the event it redacts.
     fn redacts(&self) -> Option<&Self::Id>;
+
+    /// The already deserialized content of this event, if it is an `m.room.power_levels` event.
+    ///
+    /// The authorization rules and state resolution use it instead of deserializing
+    /// [`Event::content()`] again when it is available. The content must have been deserialized
+    /// according to the rules of the room version of the event.
+    ///
+    /// The default implementation returns `None`.
+    fn power_levels_content(&self) -> Option<&RoomPowerLevelsEventContent> {
+        None
+    }
+
+    /// The already deserialized content of this event, if it is an `m.room.member` event.
+    ///
+    /// The authorization rules and state resolution use it instead of deserializing
+    /// [`Event::content()`] again when it is available.
+    ///
+    /// The default implementation returns `None`.
+    fn member_content(&self) -> Option<&RoomMemberEventContent> {
+        None
+    }
+
+    /// The already deserialized content of this event, if it is an `m.room.join_rules` event.
+    ///
+    /// The authorization rules use it instead of deserializing [`Event::content()`] again when it
+    /// is available.
+    ///
+    /// The default implementation returns `None`.
+    fn join_rules_content(&self) -> Option<&RoomJoinRulesEventContent> {
+        None
+    }
+
+    /// Whether this event was rejected by the authorization rules when it was received.
+    ///
+    /// Rejected events are excluded from the full conflicted set of [`resolve`](crate::resolve),
+    /// and are ignored when they are used as the `auth_events` of another event, or as the state
+    /// against which an event is authorized, during its iterative authorization checks.
+    ///
+    /// Events that were only soft-failed must not be reported as rejected.
+    ///
+    /// The default implementation returns `false`.
+    fn rejected(&self) -> bool {
+        false
+    }
 }
 
 impl<T: Event> Event for &T {
@@ -87,6 +140,22 @@ impl<T: Event> Event for &T {
     fn redacts(&self) -> Option<&Self::Id> {
         (*self).redacts()
     }
+
+    fn power_levels_content(&self) -> Option<&RoomPowerLevelsEventContent> {
+        (*self).power_levels_content()
+    }
+
+    fn member_content(&self) -> Option<&RoomMemberEventContent> {
+        (*self).member_content()
+    }
+
+    fn join_rules_content(&self) -> Option<&RoomJoinRulesEventContent> {
+        (*self).join_rules_content()
+    }
+
+    fn rejected(&self) -> bool {
+        (*self).rejected()
+    }
 }
 
 impl<T: Event> Event for Arc<T> {
@@ -131,4 +200,20 @@ impl<T: Event> Event for Arc<T> {
     fn redacts(&self) -> Option<&Self::Id> {
         (**self).redacts()
     }
+
+    fn power_levels_content(&self) -> Option<&RoomPowerLevelsEventContent> {
+        (**self).power_levels_content()
+    }
+
+    fn member_content(&self) -> Option<&RoomMemberEventContent> {
+        (**self).member_content()
+    }
+
+    fn join_rules_content(&self) -> Option<&RoomJoinRulesEventContent> {
+        (**self).join_rules_content()
+    }
+
+    fn rejected(&self) -> bool {
+        (**self).rejected()
+    }
 }
@@ -0,0 +1,101 @@
+use std::{collections::HashSet, sync::Arc};
+
+use ruma_common::events::StateEventType;
+
+/// The state key of a state event.
+///
+/// State keys are reference-counted, so the same key can be used in several [`StateMap`]s without
+/// allocating it again.
+///
+/// [`StateMap`]: crate::StateMap
+pub type StateKey = Arc<str>;
+
+/// The event type and state key of a state event, the key of a [`StateMap`].
+///
+/// [`StateMap`]: crate::StateMap
+pub type TypeStateKey = (StateEventType, StateKey);
+
+/// Deduplicates state keys.
+///
+/// Interning a state key that was already interned returns the same allocation. This reduces the
+/// memory used by the state of rooms with many members, where the same state keys appear in a lot
+/// of state maps.
+#[derive(Clone, Debug, Default)]
+pub struct StateKeyInterner {
+    keys: HashSet<StateKey>,
+}
+
+impl StateKeyInterner {
+    /// Creates an empty `StateKeyInterner`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the interned allocation of the given state key, interning it if necessary.
+    pub fn intern(&mut self, state_key: &str) -> StateKey {
+        if let Some(key) = self.keys.get(state_key) {
+            return key.clone();
+        }
+
+        let key = StateKey::from(state_key);
+        self.keys.insert(key.clone());
+        key
+    }
+
+    /// Get the interned allocation of the given state key, if it was interned.
+    pub fn get(&self, state_key: &str) -> Option<StateKey> {
+        self.keys.get(state_key).cloned()
+    }
+
+    /// Get the key of a [`StateMap`] for the given event type and state key, interning the state
+    /// key if necessary.
+    ///
+    /// [`StateMap`]: crate::StateMap
+    pub fn type_state_key(&mut self, event_type: StateEventType, state_key: &str) -> TypeStateKey {
+        (event_type, self.intern(state_key))
+    }
+
+    /// The number of interned state keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no state key was interned.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl Extend<StateKey> for StateKeyInterner {
+    fn extend<T: IntoIterator<Item = StateKey>>(&mut self, iter: T) {
+        self.keys.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ruma_common::events::StateEventType;
+
+    use super::{StateKey, StateKeyInterner};
+
+    #[test]
+    fn intern() {
+        let mut interner = StateKeyInterner::new();
+        assert!(interner.get("@alice:foo").is_none());
+
+        let alice = interner.intern("@alice:foo");
+        assert!(Arc::ptr_eq(&alice, &interner.intern("@alice:foo")));
+        assert!(Arc::ptr_eq(&alice, &interner.get("@alice:foo").unwrap()));
+
+        let (event_type, key) = interner.type_state_key(StateEventType::RoomMember, "@alice:foo");
+        assert_eq!(event_type, StateEventType::RoomMember);
+        assert!(Arc::ptr_eq(&alice, &key));
+
+        let bob = StateKey::from("@bob:foo");
+        interner.extend([bob.clone()]);
+        assert!(Arc::ptr_eq(&bob, &interner.intern("@bob:foo")));
+        assert_eq!(interner.len(), 2);
+    }
+}
@@ -1,13 +1,45 @@
+//! Utilities to write state resolution tests.
+//!
+//! A test graph is built with the [`dag!`](crate::dag) macro, which generates the initial events of
+//! the room from its creator and members, see [`Dag::new`]. The `$START:foo` and `$END:foo` dummy
+//! events can be used to mark the beginning and the end of the events of a test, and the resolved
+//! state can be checked with [`do_check`]:
+//!
+//! ```
+//! use js_int::int;
+//! use ruma_common::events::room::member::MembershipState;
+//! use ruma_state_res::{
+//!     dag,
+//!     test_utils::{alice, bob, do_check, event_id, init_member_event, init_power_levels_event},
+//! };
+//!
+//! let events = &[
+//!     init_power_levels_event("PA", alice(), &[(alice(), int!(100)), (bob(), int!(50))]),
+//!     init_member_event("MB", alice(), bob(), MembershipState::Ban),
+//!     init_power_levels_event("PB", bob(), &[(alice(), int!(100)), (bob(), int!(50))]),
+//! ];
+//!
+//! let dag = dag! {
+//!     creator: alice();
+//!     members: [bob()];
+//!     START -> PA -> MB -> END;
+//!     PB -> PA;
+//! };
+//!
+//! do_check(events, dag, vec![event_id("PA"), event_id("MB")]);
+//! ```
+
 use std::{
     borrow::Borrow,
     collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write,
     sync::{
         atomic::{AtomicU64, Ordering::SeqCst},
         Arc,
     },
 };
 
-use js_int::{int, uint};
+use js_int::{int, uint, Int, UInt};
 use ruma_common::{
     event_id,
     events::{
@@ -18,29 +50,122 @@ use ruma_common::{
         },
         TimelineEventType,
     },
-    room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomId, RoomVersionId,
-    UserId,
+    room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedUserId, RoomId,
+    RoomVersionId, UserId,
 };
+use serde::Serialize;
 use serde_json::{
-    json,
+    from_str as from_json_str, json,
     value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
 };
 use tracing::info;
 
-use crate::{auth_types_for_event, Error, Event, EventTypeExt, Result, StateMap};
+use crate::{
+    auth_types_for_event, auth_types_for_pdu, Error, Event, EventTypeExt, Result, RoomVersion,
+    StateMap,
+};
 
 pub use event::PduEvent;
 
 static SERVER_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
 
-pub fn do_check(
-    events: &[Arc<PduEvent>],
-    edges: Vec<Vec<OwnedEventId>>,
-    expected_state_ids: Vec<OwnedEventId>,
-) {
+/// Builds a [`Dag`], to be used with [`do_check`].
+///
+/// The graph starts with the `creator` and `members` of the room, used to generate its initial
+/// events with [`Dag::new`]. Then every chain is a list of event IDs separated by `->`, from the
+/// oldest to the most recent event, and chains are separated by `;`. The event IDs are expanded
+/// with [`event_id`].
+///
+/// ```
+/// use ruma_state_res::{
+///     dag,
+///     test_utils::{alice, bob, event_id},
+/// };
+///
+/// let dag = dag! {
+///     creator: alice();
+///     members: [bob()];
+///     START -> T1 -> END;
+///     START -> T2 -> END;
+/// };
+///
+/// assert_eq!(dag.edges[0], [event_id("END"), event_id("T1"), event_id("START")]);
+/// assert!(dag.initial_events.contains_key(&event_id("IM_bob")));
+/// ```
+#[macro_export]
+macro_rules! dag {
+    (
+        creator: $creator:expr;
+        members: [ $( $member:expr ),* $(,)? ];
+        $( $first:ident $( -> $rest:ident )* );* $(;)?
+    ) => {
+        $crate::test_utils::Dag::new($creator, &[ $( $member ),* ], ::std::vec![ $( {
+            let mut chain = ::std::vec![
+                $crate::test_utils::event_id(::std::stringify!($first))
+                $( , $crate::test_utils::event_id(::std::stringify!($rest)) )*
+            ];
+            // The edges point from an event to its previous events.
+            chain.reverse();
+            chain
+        } ),* ])
+    };
+}
+
+/// A graph of events built with [`dag!`](crate::dag).
+#[allow(clippy::exhaustive_structs)]
+pub struct Dag {
+    /// The events the graph starts with.
+    pub initial_events: HashMap<OwnedEventId, Arc<PduEvent>>,
+
+    /// The edges between the initial events, from the most recent to the oldest.
+    pub initial_edges: Vec<OwnedEventId>,
+
+    /// The chains of events of the test, from the most recent to the oldest.
+    pub edges: Vec<Vec<OwnedEventId>>,
+}
+
+impl Dag {
+    /// Creates a graph with the given edges, starting with the initial events of a room created
+    /// with [`TestRoom::bootstrap`].
+    ///
+    /// The initial events are followed by the `$START:foo` dummy event, and the `$END:foo` dummy
+    /// event is added without edges, so both can be used in `edges`.
+    pub fn new(creator: &UserId, members: &[&UserId], edges: Vec<Vec<OwnedEventId>>) -> Self {
+        let mut room = TestRoom::bootstrap(creator, members);
+        room.add_event(dummy_event_builder("START", creator));
+        room.insert(dummy_event_builder("END", creator).build());
+
+        let mut initial_edges = vec![
+            "START".to_owned(),
+            "IJR".to_owned(),
+            "IPOWER".to_owned(),
+            format!("IM_{}", creator.localpart()),
+            "CREATE".to_owned(),
+        ];
+        initial_edges.splice(1..1, members.iter().rev().map(|m| format!("IM_{}", m.localpart())));
+
+        Self {
+            initial_events: room.store.0,
+            initial_edges: initial_edges.iter().map(|id| event_id(id)).collect(),
+            edges,
+        }
+    }
+}
+
+/// Resolves the state of the given graph made of the given events, and checks that the state at
+/// `$END:foo` contains the expected events.
+///
+/// The `auth_events` and `prev_events` of the events are computed from the edges of the graph.
+///
+/// Only the state that changed since `$START:foo` is compared.
+///
+/// # Panics
+///
+/// Panics if the resolution fails or if the state doesn't match.
+pub fn do_check(events: &[Arc<PduEvent>], dag: Dag, expected_state_ids: Vec<OwnedEventId>) {
     // To activate logging use `RUST_LOG=debug cargo t`
 
-    let init_events = INITIAL_EVENTS();
+    let Dag { initial_events: init_events, initial_edges, edges } = dag;
 
     let mut store = TestStore(
         init_events
@@ -62,7 +187,7 @@ pub fn do_check(
         fake_event_map.insert(ev.event_id().to_owned(), ev.clone());
     }
 
-    for pair in INITIAL_EDGES().windows(2) {
+    for pair in initial_edges.windows(2) {
         if let [a, b] = &pair {
             graph.entry(a.to_owned()).or_insert_with(HashSet::new).insert(b.clone());
         }
@@ -134,13 +259,7 @@ pub fn do_check(
         let key = fake_event.state_key().unwrap();
         state_after.insert(ty.with_state_key(key), event_id.to_owned());
 
-        let auth_types = auth_types_for_event(
-            fake_event.event_type(),
-            fake_event.sender(),
-            fake_event.state_key(),
-            fake_event.content(),
-        )
-        .unwrap();
+        let auth_types = auth_types_for_pdu(&fake_event, &RoomVersion::V6);
 
         let mut auth_events = vec![];
         for key in auth_types {
@@ -197,7 +316,7 @@ pub fn do_check(
                 // Filter out the dummy messages events.
                 // These act as points in time where there should be a known state to
                 // test against.
-                && **k != ("m.room.message".into(), "dummy".to_owned())
+                && **k != ("m.room.message".into(), "dummy".into())
         })
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect::<StateMap<OwnedEventId>>();
@@ -205,10 +324,12 @@ pub fn do_check(
     assert_eq!(expected_state, end_state);
 }
 
+/// An in-memory store of events.
 #[allow(clippy::exhaustive_structs)]
 pub struct TestStore<E: Event>(pub HashMap<OwnedEventId, Arc<E>>);
 
 impl<E: Event> TestStore<E> {
+    /// Get the event with the given ID.
     pub fn get_event(&self, _: &RoomId, event_id: &EventId) -> Result<Arc<E>> {
         self.0
             .get(event_id)
@@ -245,6 +366,9 @@ impl<E: Event> TestStore<E> {
 // A StateStore implementation for testing
 #[allow(clippy::type_complexity)]
 impl TestStore<PduEvent> {
+    /// Add a room with a fork of two member events to the store.
+    ///
+    /// Returns the state at the two member events and the expected resolved state.
     pub fn set_up(
         &mut self,
     ) -> (StateMap<OwnedEventId>, StateMap<OwnedEventId>, StateMap<OwnedEventId>) {
@@ -331,6 +455,9 @@ impl TestStore<PduEvent> {
     }
 }
 
+/// Converts the given short ID to an event ID, e.g. `PA` to `$PA:foo`.
+///
+/// IDs that contain a `$` are used as-is.
 pub fn event_id(id: &str) -> OwnedEventId {
     if id.contains('$') {
         return id.try_into().unwrap();
@@ -339,38 +466,49 @@ pub fn event_id(id: &str) -> OwnedEventId {
     format!("${id}:foo").try_into().unwrap()
 }
 
+/// The `@alice:foo` user, the creator of the room.
 pub fn alice() -> &'static UserId {
     user_id!("@alice:foo")
 }
 
+/// The `@bob:foo` user.
 pub fn bob() -> &'static UserId {
     user_id!("@bob:foo")
 }
 
+/// The `@charlie:foo` user.
 pub fn charlie() -> &'static UserId {
     user_id!("@charlie:foo")
 }
 
+/// The `@ella:foo` user.
 pub fn ella() -> &'static UserId {
     user_id!("@ella:foo")
 }
 
+/// The `@zara:foo` user.
 pub fn zara() -> &'static UserId {
     user_id!("@zara:foo")
 }
 
+/// The `!test:foo` room.
 pub fn room_id() -> &'static RoomId {
     room_id!("!test:foo")
 }
 
+/// The content of a member event with a `ban` membership.
 pub fn member_content_ban() -> Box<RawJsonValue> {
     to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Ban)).unwrap()
 }
 
+/// The content of a member event with a `join` membership.
 pub fn member_content_join() -> Box<RawJsonValue> {
     to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Join)).unwrap()
 }
 
+/// Creates an event without `auth_events` and `prev_events`.
+///
+/// They are computed by [`do_check`].
 pub fn to_init_pdu_event(
     id: &str,
     sender: &UserId,
@@ -378,30 +516,52 @@ pub fn to_init_pdu_event(
     state_key: Option<&str>,
     content: Box<RawJsonValue>,
 ) -> Arc<PduEvent> {
-    let ts = SERVER_TIMESTAMP.fetch_add(1, SeqCst);
-    let id = if id.contains('$') { id.to_owned() } else { format!("${id}:foo") };
-
-    let state_key = state_key.map(ToOwned::to_owned);
-    Arc::new(PduEvent {
-        event_id: id.try_into().unwrap(),
-        rest: Pdu::RoomV3Pdu(RoomV3Pdu {
-            room_id: room_id().to_owned(),
-            sender: sender.to_owned(),
-            origin_server_ts: MilliSecondsSinceUnixEpoch(ts.try_into().unwrap()),
-            state_key,
-            kind: ev_type,
-            content,
-            redacts: None,
-            unsigned: BTreeMap::new(),
-            auth_events: vec![],
-            prev_events: vec![],
-            depth: uint!(0),
-            hashes: EventHash::new("".to_owned()),
-            signatures: BTreeMap::new(),
-        }),
-    })
+    let mut builder = PduBuilder::new(id, sender, ev_type).content(content);
+    if let Some(state_key) = state_key {
+        builder = builder.state_key(state_key);
+    }
+    builder.build()
+}
+
+/// Creates a member event for `target` without `auth_events` and `prev_events`.
+///
+/// They are computed by [`do_check`].
+pub fn init_member_event(
+    id: &str,
+    sender: &UserId,
+    target: &UserId,
+    membership: MembershipState,
+) -> Arc<PduEvent> {
+    to_init_pdu_event(
+        id,
+        sender,
+        TimelineEventType::RoomMember,
+        Some(target.as_str()),
+        to_raw_json_value(&RoomMemberEventContent::new(membership)).unwrap(),
+    )
+}
+
+/// Creates a power levels event with the given users' power levels, without `auth_events` and
+/// `prev_events`.
+///
+/// They are computed by [`do_check`].
+pub fn init_power_levels_event(
+    id: &str,
+    sender: &UserId,
+    users: &[(&UserId, Int)],
+) -> Arc<PduEvent> {
+    let users: BTreeMap<_, _> = users.iter().copied().collect();
+
+    to_init_pdu_event(
+        id,
+        sender,
+        TimelineEventType::RoomPowerLevels,
+        Some(""),
+        to_raw_json_value(&json!({ "users": users })).unwrap(),
+    )
 }
 
+/// Creates an event with the given `auth_events` and `prev_events`.
 pub fn to_pdu_event<S>(
     id: &str,
     sender: &UserId,
@@ -414,35 +574,343 @@ pub fn to_pdu_event<S>(
 where
     S: AsRef<str>,
 {
-    let ts = SERVER_TIMESTAMP.fetch_add(1, SeqCst);
-    let id = if id.contains('$') { id.to_owned() } else { format!("${id}:foo") };
-    let auth_events = auth_events.iter().map(AsRef::as_ref).map(event_id).collect::<Vec<_>>();
-    let prev_events = prev_events.iter().map(AsRef::as_ref).map(event_id).collect::<Vec<_>>();
-
-    let state_key = state_key.map(ToOwned::to_owned);
-    Arc::new(PduEvent {
-        event_id: id.try_into().unwrap(),
-        rest: Pdu::RoomV3Pdu(RoomV3Pdu {
-            room_id: room_id().to_owned(),
+    let mut builder = PduBuilder::new(id, sender, ev_type)
+        .content(content)
+        .auth_events(auth_events)
+        .prev_events(prev_events);
+    if let Some(state_key) = state_key {
+        builder = builder.state_key(state_key);
+    }
+    builder.build()
+}
+
+/// A builder for a [`PduEvent`] in the [`room_id`] room.
+///
+/// Unless they are set, the `origin_server_ts` of the events is incremented for every built event,
+/// the `content` is empty and the `auth_events` and `prev_events` are empty.
+///
+/// ```
+/// use ruma_common::events::TimelineEventType;
+/// use ruma_state_res::{
+///     test_utils::{alice, event_id, member_content_join, PduBuilder},
+///     Event,
+/// };
+///
+/// let event = PduBuilder::new("IMA", alice(), TimelineEventType::RoomMember)
+///     .state_key(alice().as_str())
+///     .content(member_content_join())
+///     .auth_events(&["CREATE"])
+///     .prev_events(&["CREATE"])
+///     .build();
+///
+/// assert_eq!(*event.event_id(), event_id("IMA"));
+/// assert_eq!(event.prev_events().collect::<Vec<_>>(), [&event_id("CREATE")]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PduBuilder {
+    event_id: OwnedEventId,
+    sender: OwnedUserId,
+    kind: TimelineEventType,
+    state_key: Option<String>,
+    content: Box<RawJsonValue>,
+    redacts: Option<OwnedEventId>,
+    origin_server_ts: Option<MilliSecondsSinceUnixEpoch>,
+    auth_events: Vec<OwnedEventId>,
+    prev_events: Vec<OwnedEventId>,
+    depth: UInt,
+    rejected: bool,
+}
+
+impl PduBuilder {
+    /// Creates a new `PduBuilder` for an event with the given short ID, sender and type.
+    ///
+    /// The ID is expanded with [`event_id`].
+    pub fn new(id: &str, sender: &UserId, ev_type: TimelineEventType) -> Self {
+        Self {
+            event_id: event_id(id),
             sender: sender.to_owned(),
-            origin_server_ts: MilliSecondsSinceUnixEpoch(ts.try_into().unwrap()),
-            state_key,
             kind: ev_type,
-            content,
+            state_key: None,
+            content: to_raw_json_value(&json!({})).unwrap(),
             redacts: None,
-            unsigned: BTreeMap::new(),
-            auth_events,
-            prev_events,
+            origin_server_ts: None,
+            auth_events: Vec::new(),
+            prev_events: Vec::new(),
             depth: uint!(0),
-            hashes: EventHash::new("".to_owned()),
-            signatures: BTreeMap::new(),
-        }),
-    })
+            rejected: false,
+        }
+    }
+
+    /// Sets the state key of the event, making it a state event.
+    pub fn state_key(mut self, state_key: impl Into<String>) -> Self {
+        self.state_key = Some(state_key.into());
+        self
+    }
+
+    /// Sets the content of the event.
+    pub fn content(mut self, content: Box<RawJsonValue>) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Sets the content of the event by serializing the given value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value fails to serialize.
+    pub fn serialized_content(self, content: &impl Serialize) -> Self {
+        self.content(to_raw_json_value(content).unwrap())
+    }
+
+    /// Sets the event redacted by this event, with a short ID expanded with [`event_id`].
+    pub fn redacts(mut self, id: &str) -> Self {
+        self.redacts = Some(event_id(id));
+        self
+    }
+
+    /// Sets the `origin_server_ts` of the event.
+    pub fn origin_server_ts(mut self, ts: MilliSecondsSinceUnixEpoch) -> Self {
+        self.origin_server_ts = Some(ts);
+        self
+    }
+
+    /// Sets the `auth_events` of the event, with short IDs expanded with [`event_id`].
+    pub fn auth_events<S: AsRef<str>>(mut self, ids: &[S]) -> Self {
+        self.auth_events = ids.iter().map(AsRef::as_ref).map(event_id).collect();
+        self
+    }
+
+    /// Sets the `prev_events` of the event, with short IDs expanded with [`event_id`].
+    pub fn prev_events<S: AsRef<str>>(mut self, ids: &[S]) -> Self {
+        self.prev_events = ids.iter().map(AsRef::as_ref).map(event_id).collect();
+        self
+    }
+
+    /// Sets the `depth` of the event.
+    pub fn depth(mut self, depth: UInt) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Marks the event as rejected.
+    pub fn rejected(mut self) -> Self {
+        self.rejected = true;
+        self
+    }
+
+    /// Builds the event.
+    pub fn build(self) -> Arc<PduEvent> {
+        let origin_server_ts = self.origin_server_ts.unwrap_or_else(|| {
+            MilliSecondsSinceUnixEpoch(SERVER_TIMESTAMP.fetch_add(1, SeqCst).try_into().unwrap())
+        });
+
+        Arc::new(PduEvent {
+            event_id: self.event_id,
+            rest: Pdu::RoomV3Pdu(RoomV3Pdu {
+                room_id: room_id().to_owned(),
+                sender: self.sender,
+                origin_server_ts,
+                state_key: self.state_key,
+                kind: self.kind,
+                content: self.content,
+                redacts: self.redacts,
+                unsigned: BTreeMap::new(),
+                auth_events: self.auth_events,
+                prev_events: self.prev_events,
+                depth: self.depth,
+                hashes: EventHash::new("".to_owned()),
+                signatures: BTreeMap::new(),
+            }),
+            rejected: self.rejected,
+        })
+    }
+}
+
+/// A room created with [`TestRoom::bootstrap`].
+///
+/// Events added with [`TestRoom::add_event`] are appended to a single chain, with `auth_events`
+/// selected from the current state of the room.
+#[allow(clippy::exhaustive_structs)]
+pub struct TestRoom {
+    /// The events of the room.
+    pub store: TestStore<PduEvent>,
+
+    /// The current state of the room.
+    pub state: StateMap<OwnedEventId>,
+
+    /// The most recent event of the room.
+    pub latest_event: OwnedEventId,
+}
+
+impl TestRoom {
+    /// Creates a public room with the given creator, who has power level 100, and joined members.
+    ///
+    /// The events are `$CREATE:foo`, `$IPOWER:foo`, `$IJR:foo`, and one `$IM_{localpart}:foo`
+    /// member event for the creator and each member.
+    ///
+    /// ```
+    /// use ruma_common::events::room::member::MembershipState;
+    /// use ruma_state_res::test_utils::{
+    ///     alice, bob, charlie, event_id, member_event_builder, TestRoom,
+    /// };
+    ///
+    /// let mut room = TestRoom::bootstrap(alice(), &[bob()]);
+    /// room.add_event(member_event_builder("IM_charlie", charlie(), charlie(), MembershipState::Join));
+    ///
+    /// assert_eq!(room.latest_event, event_id("IM_charlie"));
+    /// assert_eq!(room.state.len(), 6);
+    /// ```
+    pub fn bootstrap(creator: &UserId, members: &[&UserId]) -> Self {
+        let create_event = PduBuilder::new("CREATE", creator, TimelineEventType::RoomCreate)
+            .state_key("")
+            .serialized_content(&json!({ "creator": creator }))
+            .build();
+
+        let mut room = Self {
+            store: TestStore(HashMap::new()),
+            state: StateMap::new(),
+            latest_event: create_event.event_id().to_owned(),
+        };
+        room.insert(create_event);
+
+        room.add_event(member_event_builder(
+            &format!("IM_{}", creator.localpart()),
+            creator,
+            creator,
+            MembershipState::Join,
+        ));
+        room.add_event(
+            PduBuilder::new("IPOWER", creator, TimelineEventType::RoomPowerLevels)
+                .state_key("")
+                .serialized_content(&json!({ "users": { creator: 100 } })),
+        );
+        room.add_event(
+            PduBuilder::new("IJR", creator, TimelineEventType::RoomJoinRules)
+                .state_key("")
+                .serialized_content(&RoomJoinRulesEventContent::new(JoinRule::Public)),
+        );
+
+        for member in members {
+            room.add_event(member_event_builder(
+                &format!("IM_{}", member.localpart()),
+                member,
+                member,
+                MembershipState::Join,
+            ));
+        }
+
+        room
+    }
+
+    /// Adds the event built by the given builder to the room.
+    ///
+    /// The `prev_events` of the event are set to the latest event of the room, and its
+    /// `auth_events` are selected from the current state of the room.
+    ///
+    /// If the event is a state event, the state of the room is updated with it, without checking
+    /// that it is authorized.
+    pub fn add_event(&mut self, builder: PduBuilder) -> Arc<PduEvent> {
+        let member_content = (builder.kind == TimelineEventType::RoomMember)
+            .then(|| from_json_str(builder.content.get()).ok())
+            .flatten();
+        let auth_events = auth_types_for_event(
+            &builder.kind,
+            &builder.sender,
+            builder.state_key.as_deref(),
+            member_content.as_ref(),
+            &RoomVersion::V6,
+        )
+        .into_iter()
+        .filter_map(|key| self.state.get(&key).map(|id| id.as_str().to_owned()))
+        .collect::<Vec<_>>();
+
+        let event =
+            builder.auth_events(&auth_events).prev_events(&[self.latest_event.as_str()]).build();
+
+        self.latest_event = event.event_id().to_owned();
+        self.insert(event.clone());
+
+        event
+    }
+
+    fn insert(&mut self, event: Arc<PduEvent>) {
+        if let Some(state_key) = event.state_key() {
+            self.state
+                .insert(event.event_type().with_state_key(state_key), event.event_id().to_owned());
+        }
+        self.store.0.insert(event.event_id().to_owned(), event);
+    }
+}
+
+/// Creates a [`PduBuilder`] for a member event for `target`.
+pub fn member_event_builder(
+    id: &str,
+    sender: &UserId,
+    target: &UserId,
+    membership: MembershipState,
+) -> PduBuilder {
+    PduBuilder::new(id, sender, TimelineEventType::RoomMember)
+        .state_key(target.as_str())
+        .serialized_content(&RoomMemberEventContent::new(membership))
+}
+
+/// Creates a [`PduBuilder`] for a dummy message event, used to mark a point in a graph.
+fn dummy_event_builder(id: &str, sender: &UserId) -> PduBuilder {
+    PduBuilder::new(id, sender, TimelineEventType::RoomMessage)
+        .state_key("dummy")
+        .serialized_content(&json!({}))
+}
+
+/// Renders the graph of the given events in the [Graphviz] DOT format.
+///
+/// The nodes are labelled with the event ID, the type and the state key of the events. Edges to
+/// `prev_events` are solid and edges to `auth_events` are dashed. Events are sorted by ID so the
+/// output is deterministic.
+///
+/// ```
+/// use ruma_state_res::test_utils::{alice, to_dot, TestRoom};
+///
+/// let room = TestRoom::bootstrap(alice(), &[]);
+/// let dot = to_dot(room.store.0.values());
+///
+/// assert!(dot.starts_with("digraph {"));
+/// assert!(dot.contains(r#""$IPOWER:foo" -> "$IM_alice:foo";"#));
+/// ```
+///
+/// [Graphviz]: https://graphviz.org/
+pub fn to_dot<E: Event>(events: impl IntoIterator<Item = E>) -> String {
+    let mut events = events.into_iter().collect::<Vec<_>>();
+    events.sort_by(|a, b| a.event_id().cmp(b.event_id()));
+
+    let mut dot = "digraph {\n".to_owned();
+
+    for event in &events {
+        let event_id = event.event_id();
+        let mut label = format!("{event_id}\\n{}", event.event_type());
+        if let Some(state_key) = event.state_key() {
+            write!(label, "\\n{}", state_key.replace('"', "\\\"")).unwrap();
+        }
+        writeln!(dot, "    \"{event_id}\" [label=\"{label}\"];").unwrap();
+    }
+
+    for event in &events {
+        let event_id = event.event_id();
+        for prev_event in event.prev_events() {
+            writeln!(dot, "    \"{event_id}\" -> \"{prev_event}\";").unwrap();
+        }
+        for auth_event in event.auth_events() {
+            writeln!(dot, "    \"{event_id}\" -> \"{auth_event}\" [style=dashed];").unwrap();
+        }
+    }
+
+    dot.push('}');
+    dot
 }
 
-// all graphs start with these input events
+/// The initial events of the unit tests of this crate.
+#[cfg(test)]
 #[allow(non_snake_case)]
-pub fn INITIAL_EVENTS() -> HashMap<OwnedEventId, Arc<PduEvent>> {
+pub(crate) fn INITIAL_EVENTS() -> HashMap<OwnedEventId, Arc<PduEvent>> {
     vec![
         to_pdu_event::<&EventId>(
             "CREATE",
@@ -522,9 +990,10 @@ pub fn INITIAL_EVENTS() -> HashMap<OwnedEventId, Arc<PduEvent>> {
     .collect()
 }
 
-// all graphs start with these input events
+/// The `m.room.create` event of [`INITIAL_EVENTS`].
+#[cfg(test)]
 #[allow(non_snake_case)]
-pub fn INITIAL_EVENTS_CREATE_ROOM() -> HashMap<OwnedEventId, Arc<PduEvent>> {
+pub(crate) fn INITIAL_EVENTS_CREATE_ROOM() -> HashMap<OwnedEventId, Arc<PduEvent>> {
     vec![to_pdu_event::<&EventId>(
         "CREATE",
         alice(),
@@ -539,14 +1008,7 @@ pub fn INITIAL_EVENTS_CREATE_ROOM() -> HashMap<OwnedEventId, Arc<PduEvent>> {
     .collect()
 }
 
-#[allow(non_snake_case)]
-pub fn INITIAL_EDGES() -> Vec<OwnedEventId> {
-    vec!["START", "IMC", "IMB", "IJR", "IPOWER", "IMA", "CREATE"]
-        .into_iter()
-        .map(event_id)
-        .collect::<Vec<_>>()
-}
-
+/// A PDU implementing [`Event`](crate::Event).
 pub mod event {
     use ruma_common::{
         events::{pdu::Pdu, TimelineEventType},
@@ -644,13 +1106,25 @@ pub mod event {
                 _ => unreachable!("new PDU version"),
             }
         }
+
+        fn rejected(&self) -> bool {
+            self.rejected
+        }
     }
 
+    /// A PDU with its event ID.
     #[derive(Clone, Debug, Deserialize, Serialize)]
     #[allow(clippy::exhaustive_structs)]
     pub struct PduEvent {
+        /// The ID of the event.
         pub event_id: OwnedEventId,
+
+        /// The PDU.
         #[serde(flatten)]
         pub rest: Pdu,
+
+        /// Whether the event was rejected.
+        #[serde(skip)]
+        pub rejected: bool,
     }
 }
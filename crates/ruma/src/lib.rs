@@ -63,10 +63,24 @@
 //! * `events`
 //! * `signatures`
 //!
+//! # Event features
+//!
+//! The `events` feature enables all the event types. To reduce compile times, it is possible to
+//! enable only the event types that are needed with these features instead:
+//!
+//! * `events-core` -- The `events` module with the account data, ephemeral, presence, key
+//!   verification and `m.room.*` events. It is required by all the other event features.
+//! * `events-e2ee` -- The `m.dummy`, `m.forwarded_room_key`, `m.room_key`, `m.room_key_request`,
+//!   `m.secret.*` and `m.secret_storage.*` events.
+//! * `events-policy` -- The `m.policy.rule.*` events.
+//! * `events-space` -- The `m.space.*` events.
+//! * `events-voip` -- The `m.call.*` events.
+//!
 //! # `ruma-client` features
 //!
-//! The `client` feature activates [`ruma::client`][client], and `client-ext-client-api` activates
-//! `ruma-client`s `client-api` feature. All other `client-*` features activate the same feature
+//! The `client` feature activates [`ruma::client`][client], `client-ext-client-api` activates
+//! `ruma-client`s `client-api` feature and `client-ext-appservice-api` activates its
+//! `appservice-api` feature. All other `client-*` features activate the same feature
 //! without the `client-` prefix on `ruma-client`. See the crate's documentation for the effect of
 //! these features.
 //!
@@ -79,7 +93,7 @@
 #[cfg(feature = "client")]
 #[doc(inline)]
 pub use ruma_client as client;
-#[cfg(feature = "events")]
+#[cfg(feature = "events-core")]
 #[doc(inline)]
 pub use ruma_common::events;
 #[cfg(feature = "signatures")]